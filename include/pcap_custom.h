@@ -0,0 +1,47 @@
+/*
+ * pcap_custom.h - 自定义 PCAP 格式解析器 C 接口
+ *
+ * 构建: cargo rustc --lib --release --features ffi --crate-type cdylib
+ */
+
+#ifndef PCAP_CUSTOM_H
+#define PCAP_CUSTOM_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct PcapCustomReader PcapCustomReader;
+
+typedef struct PcapCustomPacket {
+    uint32_t timestamp_seconds;
+    uint32_t timestamp_nanoseconds;
+    uint32_t packet_length;
+    uint32_t checksum;
+    uint64_t offset;
+    /* 在下一次 pcap_custom_next_packet 或 pcap_custom_close 前有效 */
+    const uint8_t *payload;
+    uint32_t payload_length;
+    /* 1: CRC32 校验通过, 0: 不匹配 */
+    int checksum_valid;
+} PcapCustomPacket;
+
+/* 打开文件，失败返回 NULL（错误信息见 pcap_custom_last_error） */
+PcapCustomReader *pcap_custom_open(const char *path);
+
+/* 读取下一个数据包: 1 成功, 0 已读完, -1 参数错误 */
+int pcap_custom_next_packet(PcapCustomReader *reader, PcapCustomPacket *packet);
+
+/* 关闭句柄 */
+void pcap_custom_close(PcapCustomReader *reader);
+
+/* 当前线程最近一次错误信息，无错误时返回 NULL */
+const char *pcap_custom_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PCAP_CUSTOM_H */
@@ -0,0 +1,4 @@
+//! 配置模块
+
+pub mod settings;
+pub mod template;
@@ -0,0 +1,388 @@
+//! 配置项与分层加载：内置默认值 ← 配置文件 ← 环境变量，
+//! 命令行参数由调用方最后覆盖
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::input::keyboard::{
+    DEFAULT_MAX_STEP, DEFAULT_REPEAT_WINDOW,
+};
+use crate::core::input::keymap::{self, Keymap, Motion};
+use crate::core::pcap::filter::PacketFilter;
+use crate::core::protocol::decoder::LinkType;
+use crate::core::source::search::SearchPattern;
+use crate::core::viewer::clipboard::ClipboardBackend;
+use crate::core::viewer::columns::ColumnLayout;
+use crate::core::viewer::heatmap::ColorMode;
+use crate::core::viewer::timestamp::TimeFormat;
+
+/// 环境变量名前缀（后接大写的键名，如 `PCAP_VIEWER_THEME`）
+pub const ENV_PREFIX: &str = "PCAP_VIEWER_";
+
+/// 每行显示字节数的上限
+pub const MAX_BYTES_PER_LINE: usize = 64;
+
+/// 可由配置文件与环境变量设置的键（按键绑定除外）
+const KEYS: &[&str] = &[
+    "theme",
+    "bytes_per_line",
+    "color_mode",
+    "linktype",
+    "repeat_accel",
+    "repeat_window_ms",
+    "columns",
+    "dual_columns",
+    "clipboard",
+    "time_format",
+];
+
+/// 显示主题
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Theme {
+    /// 彩色
+    #[default]
+    Color,
+    /// 单色
+    Mono,
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "color" => Ok(Self::Color),
+            "mono" => Ok(Self::Mono),
+            other => Err(format!(
+                "unknown theme `{}` (expected color or mono)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Color => "color",
+            Self::Mono => "mono",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 查看器配置
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// 显示主题
+    pub theme: Theme,
+    /// 每行显示的字节数
+    pub bytes_per_line: usize,
+    /// 十六进制区着色方式
+    pub color_mode: ColorMode,
+    /// 查看器默认的链路层类型
+    pub linktype: LinkType,
+    /// 按住方向键滚动时加速的最大步长
+    pub repeat_accel: usize,
+    /// 同一按键两次按下的间隔小于该值时视为按住不放
+    pub repeat_window: Duration,
+    /// 十六进制视图的列布局
+    pub columns: ColumnLayout,
+    /// 终端足够宽时并排显示两栏十六进制数据
+    pub dual_columns: bool,
+    /// 复制到剪贴板的方式
+    pub clipboard: ClipboardBackend,
+    /// 时间戳的输出格式（解析信息、详情面板与报告）
+    pub time_format: TimeFormat,
+    /// 移动按键绑定
+    pub keymap: Keymap,
+    /// 命名的查找模式（`[searches]`，名称 → 查找模式）
+    pub searches: BTreeMap<String, String>,
+    /// 命名的数据包过滤（`[filters]`，以 `@名称` 引用）
+    pub filters: BTreeMap<String, PacketFilter>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            bytes_per_line: 16,
+            color_mode: ColorMode::default(),
+            linktype: LinkType::Ethernet,
+            repeat_accel: DEFAULT_MAX_STEP,
+            repeat_window: DEFAULT_REPEAT_WINDOW,
+            columns: ColumnLayout::default(),
+            dual_columns: false,
+            clipboard: ClipboardBackend::default(),
+            time_format: TimeFormat::default(),
+            keymap: Keymap::default(),
+            searches: BTreeMap::new(),
+            filters: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// 默认配置文件路径（如 `~/.config/pcap-viewer/config.toml`）
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| {
+            dir.join("pcap-viewer").join("config.toml")
+        })
+    }
+
+    /// 加载配置。指定 `path` 时该文件必须存在；
+    /// 否则读取默认路径下的配置文件，不存在时使用内置默认值
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut config = Self::default();
+
+        let file = match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => Self::default_path()
+                .filter(|path| path.exists()),
+        };
+        if let Some(file) = file {
+            let error = |message: String| {
+                PcapViewerError::Config(format!(
+                    "{}: {}",
+                    file.display(),
+                    message
+                ))
+            };
+            let text = fs::read_to_string(&file)
+                .map_err(|e| error(e.to_string()))?;
+            config.apply_toml(&text).map_err(error)?;
+        }
+
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    /// 应用配置文件内容
+    fn apply_toml(
+        &mut self,
+        text: &str,
+    ) -> std::result::Result<(), String> {
+        let table: toml::Table = toml::from_str(text)
+            .map_err(|e| e.message().to_string())?;
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("keys", toml::Value::Table(keys)) => {
+                    self.apply_keys(keys)?
+                }
+                (
+                    "searches",
+                    toml::Value::Table(searches),
+                ) => self.apply_searches(searches)?,
+                (
+                    "filters",
+                    toml::Value::Table(filters),
+                ) => self.apply_filters(filters)?,
+                (_, toml::Value::String(value)) => {
+                    self.set(&key, &value)?
+                }
+                (_, toml::Value::Integer(value)) => {
+                    self.set(&key, &value.to_string())?
+                }
+                (_, toml::Value::Boolean(value)) => {
+                    self.set(&key, &value.to_string())?
+                }
+                _ => {
+                    return Err(format!(
+                        "invalid value for `{}`",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 应用 `[keys]` 中的按键绑定
+    fn apply_keys(
+        &mut self,
+        keys: toml::Table,
+    ) -> std::result::Result<(), String> {
+        for (name, value) in keys {
+            let motion: Motion = name.parse()?;
+            let invalid = || {
+                format!(
+                    "`keys.{}` must be a list of key names",
+                    name
+                )
+            };
+            let bindings = value
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(|key| {
+                    key.as_str()
+                        .ok_or_else(invalid)
+                        .and_then(keymap::parse_key)
+                })
+                .collect::<std::result::Result<Vec<_>, _>>(
+                )?;
+            self.keymap.bind(motion, &bindings);
+        }
+        Ok(())
+    }
+
+    /// 应用 `[searches]` 中的命名查找
+    fn apply_searches(
+        &mut self,
+        searches: toml::Table,
+    ) -> std::result::Result<(), String> {
+        for (name, value) in searches {
+            let pattern =
+                value.as_str().ok_or_else(|| {
+                    format!(
+                        "`searches.{}` must be a string",
+                        name
+                    )
+                })?;
+            pattern.parse::<SearchPattern>().map_err(
+                |e| format!("`searches.{}`: {}", name, e),
+            )?;
+            self.searches.insert(name, pattern.to_string());
+        }
+        Ok(())
+    }
+
+    /// 应用 `[filters]` 中的命名过滤
+    fn apply_filters(
+        &mut self,
+        filters: toml::Table,
+    ) -> std::result::Result<(), String> {
+        for (name, value) in filters {
+            let filter = value
+                .as_str()
+                .ok_or_else(|| {
+                    format!(
+                        "`filters.{}` must be a string",
+                        name
+                    )
+                })?
+                .parse::<PacketFilter>()
+                .map_err(|e| {
+                    format!("`filters.{}`: {}", name, e)
+                })?;
+            self.filters.insert(name, filter);
+        }
+        Ok(())
+    }
+
+    /// 应用环境变量中的设置
+    fn apply_env(&mut self) -> Result<()> {
+        for key in KEYS {
+            let name = format!(
+                "{}{}",
+                ENV_PREFIX,
+                key.to_ascii_uppercase()
+            );
+            if let Ok(value) = env::var(&name) {
+                self.set(key, &value).map_err(|e| {
+                    PcapViewerError::Config(format!(
+                        "{}: {}",
+                        name, e
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 设置一项配置
+    fn set(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> std::result::Result<(), String> {
+        match key {
+            "theme" => self.theme = value.parse()?,
+            "bytes_per_line" => {
+                self.bytes_per_line = parse_number(
+                    key,
+                    value,
+                    1,
+                    MAX_BYTES_PER_LINE,
+                )?
+            }
+            "color_mode" => {
+                self.color_mode = value.parse()?
+            }
+            "linktype" => self.linktype = value.parse()?,
+            "columns" => self.columns = value.parse()?,
+            "dual_columns" => {
+                self.dual_columns = parse_bool(key, value)?
+            }
+            "clipboard" => {
+                self.clipboard = value.parse()?
+            }
+            "time_format" => {
+                self.time_format = value.parse()?
+            }
+            "repeat_accel" => {
+                self.repeat_accel =
+                    parse_number(key, value, 1, 64)?
+            }
+            "repeat_window_ms" => {
+                let millis =
+                    parse_number(key, value, 0, 10_000)?;
+                self.repeat_window =
+                    Duration::from_millis(millis as u64)
+            }
+            _ => {
+                return Err(format!(
+                    "unknown key `{}`",
+                    key
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 解析开关配置项（true / false）
+fn parse_bool(
+    key: &str,
+    value: &str,
+) -> std::result::Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!(
+            "invalid value `{}` for `{}` (expected true or false)",
+            value, key
+        )),
+    }
+}
+
+/// 解析取值范围为 `min..=max` 的整数配置项
+fn parse_number(
+    key: &str,
+    value: &str,
+    min: usize,
+    max: usize,
+) -> std::result::Result<usize, String> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|number| (min..=max).contains(number))
+        .ok_or_else(|| {
+            format!(
+                "invalid value `{}` for `{}` (expected {}-{})",
+                value, key, min, max
+            )
+        })
+}
@@ -0,0 +1,68 @@
+//! 带说明的默认配置文件（由 `config init` 写出）
+
+/// 默认配置文件内容，各项取值与内置默认值一致
+pub const DEFAULT_CONFIG: &str = r#"# pcap-viewer 配置文件
+#
+# 优先级：命令行参数 > 环境变量 > 本文件。
+# 环境变量名为 PCAP_VIEWER_ 加大写的键名，如 PCAP_VIEWER_THEME=mono
+
+# 显示主题：color（彩色）或 mono（单色）
+theme = "color"
+
+# 每行显示的字节数（1-64）
+bytes_per_line = 16
+
+# 十六进制区着色方式：structure（按文件结构）、bytes（按字节值）或 entropy（按局部熵）
+color_mode = "structure"
+
+# 查看器默认的链路层类型：ethernet、raw 或 none（--linktype 优先）
+linktype = "ethernet"
+
+# 按住方向键逐行滚动时加速的最大步长，1 表示不加速（--repeat-accel 优先）
+repeat_accel = 4
+
+# 同一按键两次按下的间隔小于该值（毫秒）时视为按住不放
+repeat_window_ms = 80
+
+# 十六进制视图的列及其顺序，逗号分隔：packet（包序号与时间）、offset（偏移）、
+# hex（十六进制）、ascii（字符）、info（解析信息）、delta（时间差 Δt）。
+# 列名后加 :宽度 固定列宽，如 "packet,offset,hex,ascii,info:60"
+columns = "offset,hex,info"
+
+# 终端足够宽（至少 170 列且能容纳两栏）时并排显示两栏，
+# 数据先从左到右、再向下排列（查看器中按 W 切换）
+dual_columns = false
+
+# 复制到剪贴板的方式：osc52（终端转义序列，SSH 中同样有效）、
+# tmux（经 tmux 透传的 OSC 52，需 set -g allow-passthrough on），
+# 或 command:<程序>（从标准输入读取内容，如 "command:wl-copy"、"command:pbcopy"）
+clipboard = "osc52"
+
+# 时间戳格式（--time-format 优先）：iso、epoch（Unix 秒）、epoch-ns（Unix 纳秒）、
+# relative（相对首个数据包的秒数），或 strftime 模式，如 "%H:%M:%S%.6f"
+time_format = "iso"
+
+# 移动按键绑定：列出的按键替换该操作的默认按键。
+# 按键写法：单个字符，或 Up / Down / Left / Right / Home / End / PageUp / PageDown，
+# 可加 Ctrl- 前缀（如 "Ctrl-d"）
+[keys]
+line_up = ["Up", "k"]
+line_down = ["Down", "j"]
+half_page_up = ["Ctrl-u"]
+half_page_down = ["Ctrl-d"]
+page_up = ["Left", "h", "Ctrl-b"]
+page_down = ["Right", "l", "Ctrl-f"]
+first = ["Home"]
+last = ["End", "G"]
+
+# 命名的查找：在查找框（/）中输入 @名称 执行，或使用 :search 名称。
+# 模式写法与查找框相同：十六进制字节、"文本" 或 re:正则
+# [searches]
+# sync = "EB 90"
+# session = '"SESSION-ID"'
+
+# 命名的数据包过滤：查看器中 :filter @名称，export 子命令中 --filter @名称
+# [filters]
+# bad = "crc == bad"
+# big = "len > 1400"
+"#;
@@ -1,16 +1,182 @@
 //! 错误类型定义
 
+use std::fmt;
+use std::ops::Range;
+
 use thiserror::Error;
 
+/// 十六进制片段最多包含的字节数
+const SNIPPET_LEN: usize = 16;
+
 /// PCAP 查看器错误类型
 #[derive(Error, Debug)]
 pub enum PcapViewerError {
     #[error("Invalid file format: {0}")]
     InvalidFormat(String),
 
+    #[error("Invalid file format: {0}")]
+    Parse(Box<ParseError>),
+
+    #[cfg(feature = "scripting")]
+    #[error("Script error: {0}")]
+    Script(String),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Config error: {0}")]
+    Config(String),
+
+    #[error("Invalid arguments: {0}")]
+    Usage(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
 
+impl From<ParseError> for PcapViewerError {
+    fn from(error: ParseError) -> Self {
+        PcapViewerError::Parse(Box::new(error))
+    }
+}
+
+/// 文件内容的解析错误：出错字段的位置、期望值与实际值，
+/// 以及出错位置附近的原始字节
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseError {
+    /// 错误说明
+    pub message: String,
+    /// 出错字段的文件偏移范围
+    pub field: Range<usize>,
+    /// 期望值
+    pub expected: Option<String>,
+    /// 实际值
+    pub found: Option<String>,
+    /// 片段在文件中的起始偏移
+    pub snippet_offset: usize,
+    /// 出错位置附近的原始字节（最多 16 字节）
+    pub snippet: Vec<u8>,
+}
+
+impl ParseError {
+    /// 位于 `field`（文件偏移范围）的错误
+    pub fn new(
+        message: impl Into<String>,
+        field: Range<usize>,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            field,
+            ..Self::default()
+        }
+    }
+
+    /// 附上期望值与实际值
+    pub fn expected(
+        mut self,
+        expected: impl fmt::Display,
+        found: impl fmt::Display,
+    ) -> Self {
+        self.expected = Some(expected.to_string());
+        self.found = Some(found.to_string());
+        self
+    }
+
+    /// 附上从文件偏移 `offset` 开始的原始字节
+    /// （超过 16 字节时截取包含出错字段的部分）
+    pub fn snippet(
+        mut self,
+        offset: usize,
+        bytes: &[u8],
+    ) -> Self {
+        let skip = self
+            .field
+            .end
+            .saturating_sub(offset + SNIPPET_LEN)
+            .min(self.field.start.saturating_sub(offset))
+            .min(bytes.len());
+        self.snippet_offset = offset + skip;
+        self.snippet = bytes[skip..]
+            [..(bytes.len() - skip).min(SNIPPET_LEN)]
+            .to_vec();
+        self
+    }
+
+    /// 取出错误中的解析错误（其他错误返回 None）
+    pub fn of(error: &anyhow::Error) -> Option<&Self> {
+        match error.downcast_ref::<PcapViewerError>() {
+            Some(PcapViewerError::Parse(error)) => {
+                Some(error)
+            }
+            _ => None,
+        }
+    }
+
+    /// 十六进制片段，及其下方用 `^` 标出出错字段的一行
+    /// （字段超出片段时指向片段末尾）。没有片段时为空
+    pub fn snippet_lines(&self) -> Vec<String> {
+        if self.snippet.is_empty() {
+            return Vec::new();
+        }
+        let prefix =
+            format!("{:08X}: ", self.snippet_offset);
+        let hex = self
+            .snippet
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // 每个字节占 3 列（最后一个字节后没有空格）
+        let len = self.snippet.len();
+        let start = self
+            .field
+            .start
+            .saturating_sub(self.snippet_offset)
+            .min(len);
+        let end = self
+            .field
+            .end
+            .saturating_sub(self.snippet_offset)
+            .clamp(start, len);
+        let marker = if end > start {
+            "^".repeat((end - start) * 3 - 1)
+        } else {
+            "^".to_string()
+        };
+        vec![
+            format!("{}{}", prefix, hex),
+            format!(
+                "{}{}",
+                " ".repeat(prefix.len() + start * 3),
+                marker
+            ),
+        ]
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} at offset 0x{:08X}",
+            self.message, self.field.start
+        )?;
+        if let (Some(expected), Some(found)) =
+            (&self.expected, &self.found)
+        {
+            write!(
+                f,
+                " (expected {}, found {})",
+                expected, found
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// 应用程序通用结果类型
 pub type Result<T> = anyhow::Result<T>;
@@ -0,0 +1,547 @@
+//! 英文字符串表（键为源代码中的中文文字）
+
+pub(super) const CATALOG: &[(&str, &str)] = &[
+    // 通用
+    ("错误", "Error:"),
+    ("文件不存在: {}", "file not found: {}"),
+    ("等待写入端: {}", "Waiting for a writer: {}"),
+    ("按任意键关闭", "Press any key to close"),
+    // 批量处理的结果表
+    ("错误: {}", "Error: {}"),
+    (", {} 个文件出错", ", {} files failed"),
+    ("总计:", "Total:"),
+    ("格式", "Format"),
+    ("版本", "Version"),
+    ("数据包", "Packets"),
+    ("字节", "Bytes"),
+    ("问题", "Problems"),
+    ("结果", "Result"),
+    ("通过", "PASS"),
+    ("失败", "FAIL"),
+    ("负载字节", "Payload bytes"),
+    ("时长 (秒)", "Duration (s)"),
+    (
+        "{} 个文件, {} 个数据包, {} 字节{}",
+        "{} files, {} packets, {} bytes{}",
+    ),
+    (
+        "{} 个文件, 通过 {} 个, 失败 {} 个{}",
+        "{} files, {} passed, {} failed{}",
+    ),
+    (
+        "{} 个文件, {} 个数据包, {} 字节负载{}",
+        "{} files, {} packets, {} payload bytes{}",
+    ),
+    // stats 子命令
+    ("文件:", "File:"),
+    ("抽样:", "Sampling:"),
+    (
+        "{} 个数据包, {} 字节负载 (链路层: {})",
+        "{} packets, {} payload bytes (link type: {})",
+    ),
+    (
+        "{} ({} / {} 个数据包，结果为近似值)",
+        "{} ({} / {} packets, results are approximate)",
+    ),
+    ("负载长度分布", "Payload length distribution"),
+    ("(分桶 {} 字节)", "({}-byte buckets)"),
+    ("长度 (字节)", "Length (bytes)"),
+    ("占比", "Share"),
+    ("(无数据包)", "(no packets)"),
+    ("时间戳异常", "Timestamp anomalies"),
+    ("(间隔阈值 {} 秒)", "(gap threshold {} s)"),
+    ("未发现时间戳异常", "No timestamp anomalies found"),
+    ("{} 处间隔, {} 处时间倒退", "{} gaps, {} backward timestamps"),
+    ("序号", "No."),
+    ("偏移", "Offset"),
+    ("类型", "Type"),
+    ("时间差 (秒)", "Delta (s)"),
+    ("间隔", "gap"),
+    ("倒退", "backward"),
+    ("到达间隔与抖动", "Inter-arrival time and jitter"),
+    (
+        "数据包不足两个，无法计算到达间隔",
+        "Fewer than two packets, no inter-arrival times to compute",
+    ),
+    (
+        "{} 个间隔, 平均 {} 秒, 标准差 {} 秒, 最小 {} 秒",
+        "{} intervals, mean {} s, std dev {} s, min {} s",
+    ),
+    ("分位数", "Quantile"),
+    ("间隔 (秒)", "Interval (s)"),
+    ("抖动 (秒)", "Jitter (s)"),
+    ("最大", "Max"),
+    ("协议层级统计", "Protocol hierarchy"),
+    ("协议", "Protocol"),
+    // report 子命令
+    ("pcap-viewer {} 报告", "pcap-viewer {} report"),
+    ("格式: {}{}", "Format: {}{}"),
+    ("（无法确定，也可能是 {}）", " (ambiguous, may also be {})"),
+    ("魔数: 0x{}", "Magic: 0x{}"),
+    ("版本: {}.{}", "Version: {}.{}"),
+    ("时区偏移: {}", "Timezone offset: {}"),
+    ("时间精度: {}", "Timestamp accuracy: {}"),
+    ("无效: {}", "Invalid: {}"),
+    ("校验结果", "Validation"),
+    ("文件头无效", "Invalid file header"),
+    ("校验和失败", "Checksum failures"),
+    ("时间倒退", "Backward timestamps"),
+    ("跳过的字节段", "Skipped byte ranges"),
+    ("末尾截断", "Truncated tail"),
+    ("结论: {}", "Verdict: {}"),
+    ("未发现问题", "No problems found"),
+    ("发现问题，详见下文", "Problems found, see below"),
+    ("统计", "Statistics"),
+    ("负载字节: {}", "Payload bytes: {}"),
+    (
+        "负载长度: 最小 {} / 平均 {} / 最大 {}",
+        "Payload length: min {} / mean {} / max {}",
+    ),
+    ("时间范围: {} .. {}", "Time range: {} .. {}"),
+    ("持续: {} 秒", "Duration: {} s"),
+    ("(链路层: {})", "(link type: {})"),
+    ("最大的 {} 个数据包", "Largest {} packets"),
+    ("包头 0x{} 计算 ({}) 0x{}", "header 0x{} computed ({}) 0x{}"),
+    ("全部通过", "All passed"),
+    // info / validate 子命令
+    ("格式:", "Format:"),
+    ("版本:", "Version:"),
+    ("时区偏移:", "Timezone offset:"),
+    ("时间精度:", "Timestamp accuracy:"),
+    ("数据包:", "Packets:"),
+    ("{} 个", "{}"),
+    ("前 {} 个", "first {}"),
+    ("后 {} 个", "last {}"),
+    ("警告", "Warning"),
+    ("提示", "Note"),
+    (
+        "版本号无法区分格式，也可能是 {}",
+        "the version number is ambiguous, this may also be {}",
+    ),
+    (
+        "{} 个数据包的负载摘要已写入 {}",
+        "payload digests of {} packets written to {}",
+    ),
+    ("{} 个数据包，未发现问题", "{} packets, no problems found"),
+    ("{} 个数据包，发现 {} 个问题", "{} packets, {} problems found"),
+    // 其他子命令
+    ("已导出:", "Exported:"),
+    (
+        "{} / {} 个数据包，{} 字节 → {}",
+        "{} / {} packets, {} bytes → {}",
+    ),
+    ("({} 字节) → {}", "({} bytes) → {}"),
+    ("正在重放:", "Replaying:"),
+    ("{} → {} (速度 {}x)", "{} → {} (speed {}x)"),
+    ("已发送 {} 个数据包 ({} 字节)", "Sent {} packets ({} bytes)"),
+    (
+        "{} 中还没有 .{} 文件，等待录制开始 (Ctrl-C 退出)",
+        "{}: no .{} files yet, waiting for a recording to start (Ctrl-C to quit)",
+    ),
+    ("已截断:", "Truncated:"),
+    (
+        "{} 个数据包 (截断 {} 个)，{} → {} 字节 → {}",
+        "{} packets ({} truncated), {} → {} bytes → {}",
+    ),
+    ("已平移:", "Shifted:"),
+    ("{} 个数据包 (偏移 {} 秒) → {}", "{} packets (offset {} s) → {}"),
+    ("已排序:", "Sorted:"),
+    (
+        "{} 个数据包 (乱序 {} 个, 临时分段 {} 个) → {}",
+        "{} packets ({} out of order, {} temporary runs) → {}",
+    ),
+    ("正在提供:", "Serving:"),
+    ("正在抓包:", "Capturing:"),
+    ("已写入 {} 个数据包到 {}", "Wrote {} packets to {}"),
+    ("{} (滚动 {})", "{} (ring {})"),
+    ("已写入默认配置:", "Wrote default config:"),
+    ("加载中 {}: {}", "Loading {}: {}"),
+    ("{} 个数据包 · 剩余 {}", "{} packets · {} left"),
+    ("下载中 {}: {}", "Downloading {}: {}"),
+    ("性能统计:", "Performance:"),
+    ("阶段", "Stage"),
+    ("次数", "Count"),
+    ("总计", "Total"),
+    ("平均", "Mean"),
+    // 状态栏
+    ("{} 字节", "{} bytes"),
+    ("{} / {} 字节", "{} / {} bytes"),
+    ("包 {}/{}", "Packet {}/{}"),
+    ("包 -/{}", "Packet -/{}"),
+    ("索引中 {}% · 剩余 {}", "Indexing {}% · {} left"),
+    ("查找 {} {}% (ESC 取消)", "Searching {} {}% (ESC to cancel)"),
+    ("等待加载 {} (ESC 取消)", "Waiting for {} to load (ESC to cancel)"),
+    ("等待索引 #{} (ESC 取消)", "Waiting for #{} to be indexed (ESC to cancel)"),
+    ("过滤 {}", "Filter {}"),
+    ("跟随中", "Following"),
+    ("缓冲 {}/{}", "Buffer {}/{}"),
+    ("编辑 HEX", "Edit HEX"),
+    ("编辑 ASCII", "Edit ASCII"),
+    (
+        "着色: 字节值 · 00 空白 控制 ASCII 高位 FF",
+        "Colors: byte value · 00 space control ASCII high FF",
+    ),
+    (
+        "着色: 局部熵 · 低→高 蓝 青 黄 红",
+        "Colors: local entropy · low→high blue cyan yellow red",
+    ),
+    ("与基线不同 {} 字节", "{} bytes differ from baseline"),
+    ("已修改 {} 字节 / {} 处", "{} bytes modified / {} ranges"),
+    ("已选择 {} 字节", "{} bytes selected"),
+    ("计数: {}", "Count: {}"),
+    ("文件头", "File header"),
+    ("包 #{} 头", "Packet #{} header"),
+    ("{}: {} 字节 {}/{}", "{}: {} byte {}/{}"),
+    // 状态消息
+    ("已录制 {} 个数据包到 {}", "Recorded {} packets to {}"),
+    ("已显示{}", "{} shown"),
+    ("已隐藏{}", "{} hidden"),
+    ("数据包时间差 Δt", "Packet time delta Δt"),
+    ("切换到 {}", "Switched to {}"),
+    ("只打开了一个文件", "Only one file is open"),
+    ("已切换到新文件 {}", "Switched to new file {}"),
+    ("没有新的录制文件", "No new recording file"),
+    ("录制已滚动到新文件 {} (按 w 打开)", "Recording rolled over to {} (press w to open)"),
+    ("录制失败，已停止: {}", "Recording failed and stopped: {}"),
+    ("读取输入流失败: {}", "Failed to read the input stream: {}"),
+    ("解析输入流失败: {}", "Failed to parse the input stream: {}"),
+    ("输入流已结束", "Input stream ended"),
+    ("输入流已结束，共丢弃 {} 个最早的数据包", "Input stream ended, {} oldest packets dropped"),
+    ("缓冲区已满 ({} 个数据包)，丢弃了最早的 {} 个，共丢弃 {} 个", "Buffer full ({} packets), dropped the oldest {}, {} dropped in total"),
+    ("索引完成: {} 个数据包", "Indexing done: {} packets"),
+    ("索引失败: {}", "Indexing failed: {}"),
+    ("读取文件失败: {}", "Failed to read the file: {}"),
+    ("比较失败: {}", "Comparison failed: {}"),
+    ("第 {} 个数据包尚未索引，索引到后跳转 (ESC 取消)", "Packet {} is not indexed yet, jumping once it is (ESC to cancel)"),
+    ("已取消等待中的跳转", "Pending jump cancelled"),
+    ("有 {} 字节未保存的修改，再次按 q 或 Ctrl-C 放弃修改并退出", "{} bytes of unsaved changes, press q or Ctrl-C again to discard them and quit"),
+    ("跟随模式下不能编辑", "Editing is not available while following"),
+    ("文件仍在加载中，加载完成后才能编辑", "The file is still loading, editing is available once it finishes"),
+    ("保存失败: {}", "Save failed: {}"),
+    ("已保存到 {}", "Saved to {}"),
+    ("已保存到 {}（原文件备份: {}）", "Saved to {} (original backed up to {})"),
+    ("结构解析失败，保留原结构: {}", "Failed to parse the structure, keeping the previous one: {}"),
+    ("没有可撤销的修改", "Nothing to undo"),
+    ("没有可重做的修改", "Nothing to redo"),
+    ("时间平移失败: {}", "Time shift failed: {}"),
+    ("已平移 {} 个数据包时间戳 (Ctrl-S 保存)", "Shifted {} packet timestamps (Ctrl-S to save)"),
+    ("已跳转到 {}", "Jumped to {}"),
+    ("已跳转到第 {} 个数据包", "Jumped to packet {}"),
+    ("{}: 数据包 #{} (0x{})，长度 {} 字节", "{}: packet #{} (0x{}), {} bytes long"),
+    ("跳转目标超出文件范围", "Jump target is beyond the end of the file"),
+    ("无效的跳转目标: {}", "Invalid jump target: {}"),
+    ("无效的包序号: {}", "Invalid packet number: {}"),
+    ("无效的范围: {}", "Invalid range: {}"),
+    ("已是最早的跳转位置", "Already at the oldest jump position"),
+    ("已是最新的跳转位置", "Already at the newest jump position"),
+    ("当前位置没有数据包", "No packet at the current position"),
+    ("视口处没有数据包", "No packet in the viewport"),
+    ("包 #{} 不是 TCP 数据包 ({})", "Packet #{} is not a TCP packet ({})"),
+    ("找到 {}: 0x{} ({} 字节){}", "Found {}: 0x{} ({} bytes){}"),
+    ("，已从文件开头继续", ", wrapped to the start of the file"),
+    ("未找到 {}", "{} not found"),
+    ("已取消查找", "Search cancelled"),
+    ("还没有查找过，请给出模式", "No previous search, give a pattern"),
+    ("查找 @{}", "Search @{}"),
+    ("没有名为 {} 的查找", "No search named {}"),
+    ("没有名为 {} 的查找（:search 列出）", "No search named {} (:search lists them)"),
+    ("没有命名的查找（:search save <名称> [模式]）", "No named searches (:search save <name> [pattern])"),
+    ("已保存查找 @{}={}（:workspace 保存到工作区）", "Saved search @{}={} (:workspace saves it to the workspace)"),
+    ("已删除查找 @{}", "Deleted search @{}"),
+    ("过滤 {}: {} / {} 个数据包匹配", "Filter {}: {} / {} packets match"),
+    ("没有匹配 {} 的数据包", "No packets match {}"),
+    ("已清除数据包过滤", "Packet filter cleared"),
+    ("高亮模式: {}", "Highlight patterns: {}"),
+    ("没有高亮模式", "No highlight patterns"),
+    ("已高亮 {} ({})，共 {} 个模式", "Highlighted {} ({}), {} patterns in total"),
+    ("已移除高亮 {}", "Removed highlight {}"),
+    ("已清除所有高亮模式", "Cleared all highlight patterns"),
+    ("没有该高亮模式: {}", "No such highlight pattern: {}"),
+    ("无效的十六进制模式: {}", "Invalid hex pattern: {}"),
+    ("未知颜色: {} (可用: red green yellow blue magenta cyan white)", "Unknown color: {} (available: red green yellow blue magenta cyan white)"),
+    ("已选择 0x{}..0x{} ({} 字节)", "Selected 0x{}..0x{} ({} bytes)"),
+    ("已取消选择", "Selection cleared"),
+    ("选中范围 0x{}..0x{}", "Selection 0x{}..0x{}"),
+    ("没有选中的字节（:select <范围>）", "No bytes selected (:select <range>)"),
+    ("光标: 0x{}，按 e 从此处开始编辑", "Cursor: 0x{}, press e to start editing here"),
+    ("已导出 {} 字节 → {}", "Exported {} bytes → {}"),
+    ("已导出 {} 个数据包 → {}", "Exported {} packets → {}"),
+    ("导出失败: {}", "Export failed: {}"),
+    ("没有可导出的数据包", "No packets to export"),
+    ("没有选区或过滤条件（:select / :filter），也可指定包序号，如 export packets 10-20,35 <文件>", "No selection or filter (:select / :filter), or give packet numbers, e.g. export packets 10-20,35 <file>"),
+    ("已复制第 {} 个数据包负载 ({} 字节, {}) 到剪贴板", "Copied the payload of packet {} ({} bytes, {}) to the clipboard"),
+    ("复制失败: {}", "Copy failed: {}"),
+    ("书签 0x{}", "Bookmark 0x{}"),
+    ("已添加书签 0x{}，共 {} 个 (' / ` 跳转)", "Bookmarked 0x{}, {} in total (' / ` to jump)"),
+    ("已删除书签 0x{}", "Removed bookmark 0x{}"),
+    ("没有书签 (按 b 添加)", "No bookmarks (press b to add one)"),
+    ("已注释 0x{}，共 {} 条 → {}", "Annotated 0x{}, {} notes → {}"),
+    ("已删除 0x{} 的注释", "Removed the note at 0x{}"),
+    ("注释保存失败: {}", "Failed to save notes: {}"),
+    ("注释加载失败: {}", "Failed to load notes: {}"),
+    ("远程数据源不支持注释", "Notes are not supported for remote sources"),
+    ("查看状态加载失败: {}", "Failed to load the view state: {}"),
+    ("已恢复上次位置 0x{} (使用 --fresh 从头打开)", "Restored the last position 0x{} (use --fresh to start from the top)"),
+    ("已保存工作区 {}", "Saved workspace {}"),
+    ("已打开工作区 {} ({} 个文件)", "Opened workspace {} ({} files)"),
+    ("工作区保存失败: {}", "Failed to save the workspace: {}"),
+    ("未指定比较文件 (使用 --diff 或 --baseline <文件>)", "No file to compare (use --diff or --baseline <file>)"),
+    ("与 {} 完全相同", "Identical to {}"),
+    ("与 {} 比较: {} 字节不同 (d / D 跳转)", "Compared with {}: {} bytes differ (d / D to jump)"),
+    ("基线 {}: {} 字节不同 (d / D 跳转)", "Baseline {}: {} bytes differ (d / D to jump)"),
+    ("差异 0x{}: {} → {}", "Difference at 0x{}: {} → {}"),
+    ("没有更多差异", "No more differences"),
+    ("时间异常: {} 处间隔, {} 处时间倒退 ([ / ] 跳转)", "Timing: {} gaps, {} backward timestamps ([ / ] to jump)"),
+    ("已关闭时间异常标记", "Timing markers off"),
+    ("数据包 #{} (0x{}): {} {} 秒", "Packet #{} (0x{}): {} {} s"),
+    ("没有更多时间异常", "No more timing issues"),
+    ("问题 {}/{} (0x{}): {}", "Issue {}/{} (0x{}): {}"),
+    ("数据包 #{} ", "Packet #{} "),
+    ("文件头魔数或版本号无效", "Invalid file header magic or version"),
+    ("CRC 校验失败", "CRC check failed"),
+    ("文件末尾数据不完整 (数据包被截断)", "Incomplete data at the end of the file (truncated packet)"),
+    ("无法解析的字节 (已跳过)", "Unparsable bytes (skipped)"),
+    ("违反校验规则", "Validation rule violated"),
+    ("已关闭双栏显示", "Dual columns off"),
+    ("已开启双栏显示", "Dual columns on"),
+    ("已开启双栏显示（终端宽度不足 {} 列或并排比较中，暂按单栏显示）", "Dual columns on (shown as one column while the terminal is narrower than {} columns or comparing side by side)"),
+    ("已折叠 {} 行负载", "Folded {} payload lines"),
+    ("已展开负载", "Payload unfolded"),
+    ("已折叠 {} 个数据包的负载", "Folded the payloads of {} packets"),
+    ("已展开 {} 处折叠", "Unfolded {} folds"),
+    ("负载不足一整行，无法折叠", "The payload is shorter than a line and cannot be folded"),
+    ("行宽未超出终端宽度", "Lines fit in the terminal width"),
+    ("偏移列：文件偏移", "Offset column: file offsets"),
+    ("偏移列：相对于数据包负载起始", "Offset column: relative to the payload start"),
+    ("列: {}", "Columns: {}"),
+    ("列布局: {}", "Column layout: {}"),
+    ("主题: 单色", "Theme: monochrome"),
+    ("主题: 彩色", "Theme: color"),
+    ("脚本错误: {}", "Script error: {}"),
+    ("未知命令: {}（可用: {}）", "Unknown command: {} (available: {})"),
+    ("用法: goto <偏移|范围|#包序号|#起-止>", "Usage: goto <offset|range|#packet|#first-last>"),
+    ("用法: packet <包序号>", "Usage: packet <packet number>"),
+    ("用法: jump <max|min|len~长度|条件>", "Usage: jump <max|min|len~length|condition>"),
+    ("用法: search [名称] | save <名称> [模式] | delete <名称>", "Usage: search [name] | save <name> [pattern] | delete <name>"),
+    ("用法: export <selection|page|packet N|范围|packets [序号]> <文件>", "Usage: export <selection|page|packet N|range|packets [numbers]> <file>"),
+    ("用法: export packets [序号] <文件>", "Usage: export packets [numbers] <file>"),
+    ("用法: copy <c|rust|python|base64>", "Usage: copy <c|rust|python|base64>"),
+    ("用法: theme <mono|color>", "Usage: theme <mono|color>"),
+    ("用法: workspace <文件.pvw>", "Usage: workspace <file.pvw>"),
+    // 十六进制区域
+    (" {} ({} 字节)", " {} ({} bytes)"),
+    (" ↔ {} ({} 字节)", " ↔ {} ({} bytes)"),
+    ("文件头无效，按原始数据显示: {}", "Invalid file header, showing raw data: {}"),
+    ("[文件头]", "[file header]"),
+    ("[包 {} 头{}]", "[packet {} header{}]"),
+    ("[包 {} 负载]", "[packet {} payload]"),
+    ("，CRC 错误", ", CRC error"),
+    ("，不匹配过滤", ", filtered out"),
+    ("[未解析数据]", "[unparsed data]"),
+    (" [书签]", " [bookmark]"),
+    (" [注释 {}: {}]", " [note {}: {}]"),
+    ("… {} 字节 …", "… {} bytes …"),
+    ("… {} 字节负载，熵 {} …", "… {} payload bytes, entropy {} …"),
+    // 解析详情与吞吐量面板
+    ("── 解析详情: 包 #{} {} (偏移 0x{}, 负载 {} 字节) ──", "── Dissection: packet #{} {} (offset 0x{}, {} payload bytes) ──"),
+    ("── 解析详情: 无数据包 ──", "── Dissection: no packet ──"),
+    ("(无字段)", "(no fields)"),
+    ("── 吞吐量: 每格 {} 秒 | 峰值 {} 包/秒, {} 字节/秒 ──", "── Throughput: {} s per cell | peak {} pkt/s, {} B/s ──"),
+    ("── 吞吐量: 无数据包 ──", "── Throughput: no packets ──"),
+    ("包/秒", "pkt/s"),
+    ("字节/秒", "B/s"),
+    // 统计视图
+    ("{} {} 个数据包, {} 字节负载", "{} {} packets, {} payload bytes"),
+    ("负载长度:", "Payload length:"),
+    ("{} 最小 {} / 平均 {} / 最大 {} 字节", "{} min {} / mean {} / max {} bytes"),
+    (" 统计 │ 分桶 {} 字节 │ 第 {} 行 / 共 {} 行", " Statistics │ bucket {} bytes │ line {} / {}"),
+    ("↑↓ ←→ Home/End | +/- 调整分桶 | ESC/s 返回", "↑↓ ←→ Home/End | +/- bucket size | ESC/s back"),
+    // 字节频率视图
+    ("数据包 #{} 负载", "Payload of packet #{}"),
+    ("来源:", "Source:"),
+    ("{} {} ({} 字节)", "{} {} ({} bytes)"),
+    ("熵:", "Entropy:"),
+    ("{} {} 位/字节，{} 种字节值 / 256 → {}", "{} {} bits/byte, {} distinct values / 256 → {}"),
+    ("无数据", "no data"),
+    ("高熵，可能是压缩或加密数据", "high entropy, likely compressed or encrypted"),
+    ("低熵，结构化或文本数据", "low entropy, structured or text data"),
+    ("中等熵", "medium entropy"),
+    ("频率网格 (行为高 4 位，列为低 4 位)", "Frequency grid (rows: high nibble, columns: low nibble)"),
+    ("各字节值出现次数", "Count per byte value"),
+    (" 字节频率 │ {} │ 第 {} 行 / 共 {} 行", " Byte frequency │ {} │ line {} / {}"),
+    ("↑↓ ←→ Home/End | ESC/F 返回", "↑↓ ←→ Home/End | ESC/F back"),
+    // TCP 流视图
+    ("(该会话没有负载数据)", "(no payload in this session)"),
+    (" TCP 流: {} ⇄ {} │ {} 个数据包 │ 第 {} 行 / 共 {} 行", " TCP stream: {} ⇄ {} │ {} packets │ line {} / {}"),
+    ("■ 客户端→服务端 {} 字节", "■ client→server {} bytes"),
+    ("■ 服务端→客户端 {} 字节", "■ server→client {} bytes"),
+    ("⚠ 此前缺失 {} 字节", "⚠ {} bytes missing before this"),
+    ("| ↑↓ ←→ Home/End | ESC/f 返回", "| ↑↓ ←→ Home/End | ESC/f back"),
+    // 底部按键提示
+    (
+        "编辑: 方向键 移动光标 | 0-9A-F/字符 改写 | Tab 切换HEX/ASCII | Ctrl-Z/Y 撤销/重做 | Ctrl-S 保存 | Ctrl-A 另存为 | ESC 结束编辑",
+        "Edit: arrows move cursor | 0-9A-F/chars overwrite | Tab HEX/ASCII | Ctrl-Z/Y undo/redo | Ctrl-S save | Ctrl-A save as | ESC stop editing",
+    ),
+    (" d/D 下/上一处差异 |", " d/D next/prev difference |"),
+    (" n/N/F1-F9 切换文件 |", " n/N/F1-F9 switch file |"),
+    (
+        "? 全部按键 |{} ↑↓ 滚动 | ←→ 翻页 | g 跳转 | : 命令 | e 编辑 | s 统计 | ESC/q 退出",
+        "? all keys |{} ↑↓ scroll | ←→ page | g go to | : command | e edit | s stats | ESC/q quit",
+    ),
+    // 输入框
+    ("注释 {} (留空删除):", "Note at {} (empty to delete):"),
+    ("另存为:", "Save as:"),
+    (
+        "跳转到 (0x偏移 / 偏移 / 0x起-0x止 / #包序号 / #起-止):",
+        "Go to (0xoffset / offset / 0xstart-0xend / #packet / #first-last):",
+    ),
+    (
+        "时间平移 (如 -3600s，或 @2024-01-01T00:00:00Z 对齐首包):",
+        "Time shift (e.g. -3600s, or @2024-01-01T00:00:00Z to align the first packet):",
+    ),
+    (
+        "查找 (DE AD BE EF / \"文本\" / re:正则):",
+        "Search (DE AD BE EF / \"text\" / re:regex):",
+    ),
+    (
+        "复制负载为 (c / rust / python / base64):",
+        "Copy payload as (c / rust / python / base64):",
+    ),
+    // 终端过小时的摘要
+    ("文件: {}", "File: {}"),
+    ("大小: {} 字节", "Size: {} bytes"),
+    ("数据包: {}", "Packets: {}"),
+    ("行: {}/{}", "Line: {}/{}"),
+    ("偏移: {}", "Offset: {}"),
+    ("终端过小 ({}x{})", "Terminal too small ({}x{})"),
+    ("需要 {}x{}", "Needs {}x{}"),
+    ("q 退出", "q quit"),
+    // 按键帮助
+    ("按键帮助", "Keys"),
+    ("导航", "Navigation"),
+    ("查找", "Search"),
+    ("显示", "Display"),
+    ("文件", "File"),
+    ("上移一行", "Line up"),
+    ("下移一行", "Line down"),
+    ("上移半页", "Half page up"),
+    ("下移半页", "Half page down"),
+    ("上一页", "Previous page"),
+    ("下一页", "Next page"),
+    ("首页", "First page"),
+    ("末页（带计数时跳到第 N 行）", "Last page (line N with a count)"),
+    ("数字", "Digits"),
+    (
+        "计数前缀，重复随后的移动，如 25↓、10]",
+        "Count prefix repeating the next motion, e.g. 25↓, 10]",
+    ),
+    ("跳转到偏移或 #包序号", "Go to offset or #packet"),
+    ("跳转历史后退/前进", "Jump history back/forward"),
+    ("滚轮", "Wheel"),
+    (
+        "滚动（Shift 翻页），单击定位光标",
+        "Scroll (Shift pages), click places the cursor",
+    ),
+    ("下/上一处时间异常", "Next/previous timing issue"),
+    (
+        "下一个问题（格式、校验、时间）",
+        "Next problem (format, checksum, timing)",
+    ),
+    ("下/上一个书签", "Next/previous bookmark"),
+    (
+        "下/上一处差异（--diff/--baseline）",
+        "Next/previous difference (--diff/--baseline)",
+    ),
+    (
+        "查找 DE AD、\"文本\" 或 re:正则（回车找下一个）",
+        "Search DE AD, \"text\" or re:regex (Enter finds next)",
+    ),
+    (
+        "暗显不匹配的数据包，如 len>100 或 @名称",
+        "Dim non-matching packets, e.g. len>100 or @name",
+    ),
+    (
+        "跳到最大/最小包，或 len~1500、len>60000",
+        "Jump to largest/smallest packet, or len~1500, len>60000",
+    ),
+    (
+        "查找历史；@名称 执行命名的查找（Tab 补全）",
+        "Search history; @name runs a named search (Tab completes)",
+    ),
+    (
+        "列出/执行命名的查找，save/delete <名称>",
+        "List/run named searches, save/delete <name>",
+    ),
+    ("切换着色模式", "Cycle color mode"),
+    ("颜色图例", "Color legend"),
+    ("标记时间间隔与时间倒退", "Mark timing gaps and backward timestamps"),
+    ("时间差 Δt 列", "Time delta Δt column"),
+    ("ASCII 列", "ASCII column"),
+    ("宽终端并排显示两栏", "Two side-by-side panes on wide terminals"),
+    ("折叠/展开当前数据包的负载", "Fold/unfold the current packet's payload"),
+    (
+        "偏移列：文件偏移/相对负载起始的偏移",
+        "Offset column: file offset/relative to payload start",
+    ),
+    (
+        "折叠所有（或匹配条件的）负载 / 全部展开",
+        "Fold all (or matching) payloads / unfold all",
+    ),
+    ("左右滚动超出终端宽度的行", "Scroll lines wider than the terminal"),
+    (
+        "列布局，如 packet,offset,hex,ascii,info:60",
+        "Column layout, e.g. packet,offset,hex,ascii,info:60",
+    ),
+    ("吞吐量面板", "Throughput panel"),
+    ("小地图", "Minimap"),
+    ("包序号栏", "Packet number gutter"),
+    ("统计视图", "Statistics view"),
+    (
+        "字节频率与熵（当前数据包或选中范围）",
+        "Byte frequency and entropy (current packet or selection)",
+    ),
+    ("跟踪 TCP 流", "Follow TCP stream"),
+    ("刷新屏幕", "Redraw screen"),
+    ("单色/彩色", "Mono/color"),
+    ("编辑模式（Ctrl-S 保存）", "Edit mode (Ctrl-S saves)"),
+    ("撤销/重做", "Undo/redo"),
+    ("时间平移", "Time shift"),
+    ("注释", "Annotate"),
+    ("切换书签", "Toggle bookmark"),
+    ("切换文件", "Switch file"),
+    (
+        "打开监视目录中的新文件（watch --ask）",
+        "Open the new file in the watched directory (watch --ask)",
+    ),
+    ("导出选中或匹配的数据包", "Export selected or matching packets"),
+    (
+        "复制负载为 C / Rust / Python / base64",
+        "Copy payload as C / Rust / Python / base64",
+    ),
+    ("命令行（Tab 补全命令名）", "Command line (Tab completes commands)"),
+    ("退出", "Quit"),
+    // 图例
+    ("标记图例", "Marker legend"),
+    ("数据包头", "Packet header"),
+    ("负载", "Payload"),
+    ("文件头中无效的字段", "Invalid file header field"),
+    ("查找匹配与选中范围", "Search match and selection"),
+    ("已修改的字节", "Modified byte"),
+    ("与基线不同（--baseline）", "Differs from baseline (--baseline)"),
+    ("不匹配过滤条件的数据包", "Packet not matching the filter"),
+    ("有注释的字节", "Annotated byte"),
+    ("[文件头] [包 N 头]", "[file header] [packet N header]"),
+    ("区域开始处的标记", "Marker where a region starts"),
+    ("[包 N 负载]", "[packet N payload]"),
+    ("数据包负载开始", "Start of a packet payload"),
+    ("编辑光标", "Edit cursor"),
+    ("高亮模式匹配的字节", "Byte matching a highlight pattern"),
+    (
+        "单色主题下不显示颜色（:theme color 切换）",
+        "Colors are off in the mono theme (:theme color)",
+    ),
+    (
+        "当前按字节值或熵着色，结构颜色暂不显示（c 切换）",
+        "Coloring by byte value or entropy; structure colors hidden (c cycles)",
+    ),
+];
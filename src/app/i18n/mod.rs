@@ -0,0 +1,143 @@
+//! 界面文字的本地化：源代码中的中文文字即为键，其他语言从字符串表中查找，
+//! 表中没有的文字按原文显示
+//!
+//! 带参数的文字以 `{}` 作占位符，由 [`tr!`](crate::tr) 依次填入：
+//!
+//! ```text
+//! tr!("包 {}/{}", index + 1, total)
+//! ```
+
+mod en;
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    /// 中文（源文字）
+    #[default]
+    Zh,
+    /// 英文
+    En,
+}
+
+impl Lang {
+    /// 由区域设置推断语言（依次取 LC_ALL、LC_MESSAGES、LANG 中第一个非空值）；
+    /// 未设置或为 C / POSIX 时为 None，其他非中文区域使用英文
+    pub fn from_locale() -> Option<Self> {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())?;
+        match locale.as_str() {
+            "C" | "POSIX" => None,
+            _ => Some(locale.parse().unwrap_or(Self::En)),
+        }
+    }
+
+    /// 字符串表（中文为源文字，没有表）
+    fn catalog(
+        self,
+    ) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Zh => &[],
+            Self::En => en::CATALOG,
+        }
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    /// 接受语言代码或区域名，如 `en`、`zh_CN.UTF-8`
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let code = s
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default();
+        match code.to_ascii_lowercase().as_str() {
+            "zh" => Ok(Self::Zh),
+            "en" => Ok(Self::En),
+            _ => Err(format!(
+                "unknown language `{}` (expected zh or en)",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let code = match self {
+            Self::Zh => "zh",
+            Self::En => "en",
+        };
+        f.write_str(code)
+    }
+}
+
+/// 当前界面语言（0 中文，1 英文）
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// 设置界面语言
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+/// 当前界面语言
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+/// 当前语言下的文字（字符串表中没有时返回原文）
+pub fn text(source: &str) -> &str {
+    lang()
+        .catalog()
+        .iter()
+        .find(|(key, _)| *key == source)
+        .map_or(source, |(_, translated)| translated)
+}
+
+/// 将参数依次填入文字中的 `{}` 占位符
+pub fn fill(
+    template: &str,
+    args: &[&dyn fmt::Display],
+) -> String {
+    let mut output = String::new();
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        output.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            output.push_str(&arg.to_string());
+        }
+        output.push_str(part);
+    }
+    output
+}
+
+/// 本地化文字：`tr!("跟随中")`，带参数时 `tr!("过滤 {}", filter)`
+#[macro_export]
+macro_rules! tr {
+    ($source:literal) => {
+        $crate::app::i18n::text($source)
+    };
+    ($source:literal, $($arg:expr),+ $(,)?) => {
+        $crate::app::i18n::fill(
+            $crate::app::i18n::text($source),
+            &[$(&$arg as &dyn ::std::fmt::Display),+],
+        )
+    };
+}
@@ -1,3 +1,4 @@
 //! 日志模块
 
+pub mod perf;
 pub mod setup;
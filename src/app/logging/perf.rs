@@ -0,0 +1,125 @@
+//! 性能统计（--perf）：按 span 名称汇总耗时，退出时输出
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::core::viewer::text::{
+    pad_left_to_width, pad_to_width,
+};
+use crate::tr;
+
+/// 同名 span 的耗时汇总
+#[derive(Debug, Default, Clone, Copy)]
+struct Timing {
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+/// 各 span 的耗时汇总（与记录它的日志层共享）
+#[derive(Debug, Clone, Default)]
+pub struct PerfSummary {
+    timings: Arc<Mutex<BTreeMap<&'static str, Timing>>>,
+}
+
+/// 记录每个 span 从创建到关闭耗时的日志层
+pub struct PerfLayer {
+    summary: PerfSummary,
+}
+
+impl PerfLayer {
+    /// 创建日志层及其汇总结果
+    pub fn new() -> (Self, PerfSummary) {
+        let summary = PerfSummary::default();
+        (
+            Self {
+                summary: summary.clone(),
+            },
+            summary,
+        )
+    }
+}
+
+impl<S> Layer<S> for PerfLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        _attrs: &Attributes<'_>,
+        id: &Id,
+        ctx: Context<'_, S>,
+    ) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Instant::now());
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(started) =
+            span.extensions().get::<Instant>().copied()
+        else {
+            return;
+        };
+        let elapsed = started.elapsed();
+
+        let mut timings = self
+            .summary
+            .timings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let timing =
+            timings.entry(span.name()).or_default();
+        timing.count += 1;
+        timing.total += elapsed;
+        timing.max = timing.max.max(elapsed);
+    }
+}
+
+impl fmt::Display for PerfSummary {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let timings = self
+            .timings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        writeln!(f, "{}", tr!("性能统计:"))?;
+        writeln!(
+            f,
+            "  {}{}{}{}{}",
+            pad_to_width(tr!("阶段"), 16),
+            pad_left_to_width(tr!("次数"), 8),
+            pad_left_to_width(tr!("总计"), 12),
+            pad_left_to_width(tr!("平均"), 12),
+            pad_left_to_width(tr!("最大"), 12)
+        )?;
+        for (name, timing) in timings.iter() {
+            let average = timing
+                .total
+                .checked_div(timing.count as u32)
+                .unwrap_or_default();
+            writeln!(
+                f,
+                "  {:<16}{:>8}{:>12}{:>12}{:>12}",
+                name,
+                timing.count,
+                format!("{:.1?}", timing.total),
+                format!("{:.1?}", average),
+                format!("{:.1?}", timing.max)
+            )?;
+        }
+        Ok(())
+    }
+}
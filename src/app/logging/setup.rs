@@ -1,16 +1,143 @@
 //! 日志系统初始化
 
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use tracing::Level;
+use tracing_subscriber::filter::{EnvFilter, Targets};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{
-    layer::SubscriberExt, util::SubscriberInitExt,
+    layer::SubscriberExt, util::SubscriberInitExt, Layer,
 };
 
-/// 初始化日志系统
-pub fn init_logging() {
+use crate::app::error::types::Result;
+use crate::app::logging::perf::{PerfLayer, PerfSummary};
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// 便于阅读的文本
+    #[default]
+    Text,
+    /// 每行一个 JSON 对象，便于日志系统采集
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format `{}` (expected text or json)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 日志选项
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// 详细程度（-v 次数）
+    pub verbose: u8,
+    /// 只输出错误
+    pub quiet: bool,
+    /// 日志文件（省略时输出到 stderr）
+    pub log_file: Option<PathBuf>,
+    /// 输出格式
+    pub format: LogFormat,
+    /// 汇总各 span 的耗时
+    pub perf: bool,
+}
+
+impl LogOptions {
+    /// 日志过滤条件：指定了 -v / -q 时按参数，
+    /// 否则使用 RUST_LOG，都没有时只输出警告与错误
+    fn filter(&self) -> EnvFilter {
+        let level = match (self.quiet, self.verbose) {
+            (true, _) => "error",
+            (false, 0) => {
+                if let Ok(filter) =
+                    EnvFilter::try_from_default_env()
+                {
+                    return filter;
+                }
+                "warn"
+            }
+            (false, 1) => "info",
+            (false, 2) => "debug",
+            (false, _) => "trace",
+        };
+        EnvFilter::new(format!("pcap_viewer={}", level))
+    }
+}
+
+/// 初始化日志系统。开启 `perf` 时另外汇总各 span 的耗时，
+/// 返回的汇总结果在退出时输出
+pub fn init_logging(
+    options: &LogOptions,
+) -> Result<Option<PerfSummary>> {
+    // 查看器运行时终端处于原始模式，写入 stderr 的日志会打乱画面，
+    // 需要保留日志时应写入文件
+    let writer = match &options.log_file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+            BoxMakeWriter::new(Mutex::new(file))
+        }
+        None => BoxMakeWriter::new(io::stderr),
+    };
+
+    // span 关闭时输出耗时（span 均为 trace 级别，默认不输出）
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(options.log_file.is_none())
+        .with_span_events(FmtSpan::CLOSE);
+    let fmt_layer = match options.format {
+        LogFormat::Text => fmt_layer.boxed(),
+        LogFormat::Json => fmt_layer.json().boxed(),
+    }
+    .with_filter(options.filter());
+
+    let (perf_layer, summary) = if options.perf {
+        let (layer, summary) = PerfLayer::new();
+        let layer = layer.with_filter(
+            Targets::new()
+                .with_target("pcap_viewer", Level::TRACE),
+        );
+        (Some(layer), Some(summary))
+    } else {
+        (None, None)
+    };
+
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "pcap_viewer=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
+        .with(fmt_layer)
+        .with(perf_layer)
         .init();
+    Ok(summary)
 }
@@ -1,4 +1,8 @@
 //! 应用程序核心模块
 
+#[cfg(feature = "cli")]
+pub mod config;
 pub mod error;
+pub mod i18n;
+#[cfg(feature = "cli")]
 pub mod logging;
@@ -1,24 +1,1021 @@
 //! 命令行参数定义
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+use clap_complete::Shell;
+use std::io::{self, IsTerminal};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
+use crate::cli::commands::export::ExportFormat;
+use pcap_viewer::app::config::settings::{
+    Config, Theme, MAX_BYTES_PER_LINE,
+};
+use pcap_viewer::app::i18n::Lang;
+use pcap_viewer::app::logging::setup::{
+    LogFormat, LogOptions,
+};
+use pcap_viewer::core::pcap::builder::{
+    ParseMode, ParserOptions,
+};
+use pcap_viewer::core::pcap::checksum::ChecksumAlgorithm;
+use pcap_viewer::core::pcap::filter::FilterSpec;
+#[cfg(feature = "hash")]
+use pcap_viewer::core::pcap::hash::HashAlgorithm;
+use pcap_viewer::core::pcap::parser::PacketLimit;
+use pcap_viewer::core::pcap::ring;
+use pcap_viewer::core::pcap::rollover::RingSpec;
+use pcap_viewer::core::pcap::sample::Sampling;
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::timeshift;
+use pcap_viewer::core::protocol::decoder::LinkType;
+use pcap_viewer::core::range::{OffsetRange, PacketList};
+use pcap_viewer::core::source::watch::WatchDir;
+use pcap_viewer::core::viewer::palette::ColorChoice;
+use pcap_viewer::core::viewer::timestamp::TimeFormat;
+
 /// PCAP 文件查看器 - 支持自定义PCAP格式的十六进制查看工具
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
+pub struct Cli {
+    /// 子命令（省略时直接打开查看器）
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// 查看器参数
+    #[command(flatten)]
+    pub view: Option<CliArgs>,
+
+    /// 退出时输出解析、索引、绘制与按键处理的耗时统计
+    #[arg(long, global = true)]
+    pub perf: bool,
+
+    /// 输出更详细的日志（-v 信息，-vv 调试，-vvv 跟踪）
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        conflicts_with = "quiet"
+    )]
+    pub verbose: u8,
+
+    /// 只输出机器可读的结果与错误：不输出进度、提示与摘要，日志只保留错误
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// 将日志写入文件而不是 stderr（查看器运行时 stderr 会打乱画面）
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// 何时输出颜色（auto / always / never），auto 时遵循 NO_COLOR 与 CLICOLOR_FORCE
+    #[arg(
+        long,
+        global = true,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto
+    )]
+    pub color: ColorChoice,
+
+    /// 配置文件路径（默认 ~/.config/pcap-viewer/config.toml）
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 界面语言（zh / en），默认按 LC_ALL、LANG 等区域设置
+    #[arg(long, global = true, value_name = "LANG")]
+    pub lang: Option<Lang>,
+
+    /// 日志格式（text / json，json 为每行一个对象，便于日志系统采集）
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        default_value_t = LogFormat::Text
+    )]
+    pub log_format: LogFormat,
+}
+
+impl Cli {
+    /// 日志选项
+    pub fn log_options(&self) -> LogOptions {
+        LogOptions {
+            verbose: self.verbose,
+            quiet: self.quiet,
+            log_file: self.log_file.clone(),
+            format: self.log_format,
+            perf: self.perf,
+        }
+    }
+}
+
+/// 子命令
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// 以十六进制查看器打开文件（默认）
+    View(Box<CliArgs>),
+
+    /// 输出统计信息（协议层级等）
+    Stats(StatsArgs),
+
+    /// 输出文件格式与文件头信息
+    Info(InfoArgs),
+
+    /// 生成纯文本报告（文件头、校验结果、统计与异常），便于附在问题报告中
+    Report(ReportArgs),
+
+    /// 按校验规则检查文件（校验和、时间单调、负载长度），发现问题时退出码为 4
+    Validate(ValidateArgs),
+
+    /// 从网卡抓包并写入自定义格式文件
+    #[cfg(feature = "capture")]
+    Capture(CaptureArgs),
+
+    /// 接收 UDP（含组播）数据报并实时查看
+    Listen(ListenArgs),
+
+    /// 监视目录，以跟随模式打开最新的抓包文件，录制滚动到新文件时自动切换
+    Watch(WatchArgs),
+
+    /// 按原始时间间隔通过 UDP 重放数据包
+    Replay(ReplayArgs),
+
+    /// 平移所有数据包的时间戳
+    Timeshift(TimeshiftArgs),
+
+    /// 将选中的数据包写入新文件，或将着色的十六进制视图导出为 HTML
+    Export(ExportArgs),
+
+    /// 截断每个数据包的负载以缩小文件
+    Truncate(TruncateArgs),
+
+    /// 按时间戳排序数据包（支持大于内存的文件）
+    Sort(SortArgs),
+
+    /// 通过 HTTP/JSON 接口提供远程查看
+    #[cfg(feature = "remote")]
+    Serve(ServeArgs),
+
+    /// 管理配置文件
+    Config(ConfigArgs),
+
+    /// 输出 shell 补全脚本或 man 手册页（供打包时生成）
+    Completions(CompletionsArgs),
+}
+
+/// 查看器参数
+#[derive(Args, Debug, Clone)]
 pub struct CliArgs {
-    /// PCAP 文件路径
+    /// PCAP 文件或工作区文件（.pvw）路径（启用 remote 特性时也可为 http(s):// 或 s3:// URL）
     pub file_path: PathBuf,
+
+    /// 同时打开的其他文件（n/N 或 F1-F9 切换）
+    #[arg(value_name = "MORE_FILES")]
+    pub more_files: Vec<PathBuf>,
+
+    /// 数据包负载的链路层类型（ethernet / raw / none，默认取配置文件）
+    #[arg(long)]
+    pub linktype: Option<LinkType>,
+
+    /// 每行显示的字节数（1-64，默认取配置文件）
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32)
+            .range(1..=MAX_BYTES_PER_LINE as i64)
+    )]
+    pub bytes_per_line: Option<u32>,
+
+    /// 严格模式：遇到任何损坏的结构（含 CRC 不匹配）立即报错退出
+    #[arg(long, conflicts_with = "lenient")]
+    pub strict: bool,
+
+    /// 宽松模式：遇到损坏的数据包时向后搜索下一个有效的数据包继续解析
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// 校验和算法（crc32 / crc32c / xor / none；auto 时任一算法一致即视为通过）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Auto
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 数据包头布局描述文件（TOML，用于带序号等变体格式）
+    #[arg(long, value_name = "PATH")]
+    pub spec: Option<PathBuf>,
+
+    /// 按 UTC 显示时间，忽略文件头中的时区偏移
+    #[arg(long)]
+    pub utc: bool,
+
+    /// 时间戳格式（iso / epoch / epoch-ns / relative 或 strftime 模式），默认取配置文件
+    #[arg(long, value_name = "FORMAT")]
+    pub time_format: Option<TimeFormat>,
+
+    /// 持续监视文件，自动加载新追加的数据包。
+    /// 命名管道或字符设备按顺序读取，只保留最近 --ring-size 个数据包
+    #[arg(long)]
+    pub follow: bool,
+
+    /// 跟随命名管道或字符设备时保留的数据包数量，超出时丢弃最早的数据包
+    #[arg(
+        long,
+        value_name = "PACKETS",
+        default_value_t = ring::DEFAULT_CAPACITY as u32,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub ring_size: u32,
+
+    /// 跟随时把读到的数据包同时录制到文件（定期同步到磁盘，退出时完成写入）
+    #[arg(
+        long = "record",
+        value_name = "FILE",
+        requires = "follow"
+    )]
+    pub record_to: Option<PathBuf>,
+
+    /// 滚动录制（如 10x100M：每个文件写到 100 MB 后换新文件，只保留最近 10 个）
+    #[arg(
+        long,
+        value_name = "FILESxSIZE",
+        requires = "record_to"
+    )]
+    pub ring: Option<RingSpec>,
+
+    /// 在数据包头的解析信息中显示与前一个数据包的时间差 Δt（T 切换）
+    #[arg(long)]
+    pub delta: bool,
+
+    /// 无障碍模式：不使用颜色与框线字符，区域开始处插入文字标记（如 [包 12 头]），
+    /// 字节状态以符号表示，便于屏幕阅读器朗读
+    #[arg(long)]
+    pub accessible: bool,
+
+    /// 内联绘制，保留终端滚动缓冲：启动时不清除原有输出，退出后最后一屏仍留在终端中
+    #[arg(long)]
+    pub no_alt_screen: bool,
+
+    /// 时间异常标记中视为间隔的阈值（如 500ms、2s）
+    #[arg(
+        long,
+        value_name = "THRESHOLD",
+        default_value = "1s",
+        value_parser = parse_time_offset
+    )]
+    pub gap_threshold: i128,
+
+    /// 与另一个文件并排比较，不同的字节高亮显示（d / D 跳转）
+    #[arg(long, value_name = "FILE")]
+    pub diff: Option<PathBuf>,
+
+    /// 以另一个文件为基线，在主视图中标出与基线不同的字节
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "diff"
+    )]
+    pub baseline: Option<PathBuf>,
+
+    /// 忽略上次保存的查看状态（位置、高亮、书签），从文件开头打开
+    #[arg(long)]
+    pub fresh: bool,
+
+    /// 转储时（输出不是终端或指定 --paginate）最多输出的行数，默认输出整个文件
+    #[arg(long, value_name = "N")]
+    pub lines: Option<usize>,
+
+    /// 只输出前 N 个数据包（不解析整个文件）
+    #[arg(long, value_name = "N", conflicts_with = "tail")]
+    pub head: Option<usize>,
+
+    /// 只输出后 N 个数据包（从文件末尾向前查找）
+    #[arg(long, value_name = "N")]
+    pub tail: Option<usize>,
+
+    /// 只输出抽样的数据包（every:N 或 random:P）
+    #[arg(long, value_name = "SPEC")]
+    pub sample: Option<Sampling>,
+
+    /// 只输出该字节范围（如 0x1000-0x2000、0x1000-）
+    #[arg(long, value_name = "RANGE")]
+    pub range: Option<OffsetRange>,
+
+    /// 只输出指定序号的数据包（从 1 开始，如 5-10,42,100-）
+    #[arg(
+        long,
+        value_name = "LIST",
+        conflicts_with_all = ["tail", "sample"]
+    )]
+    pub packets: Option<PacketList>,
+
+    /// 输出为终端时也不进入交互模式，输出十六进制转储，
+    /// 超过一屏时交给分页器（$PAGER，默认 less -R）
+    #[arg(long, conflicts_with = "no_pager")]
+    pub paginate: bool,
+
+    /// 转储输出直接写到终端，不使用分页器
+    #[arg(long)]
+    pub no_pager: bool,
+
+    /// 按住方向键逐行滚动时加速的最大步长（1 表示不加速，默认取配置文件）
+    #[arg(long, value_name = "MAX")]
+    pub repeat_accel: Option<usize>,
+
+    /// 自定义解析脚本（Rhai），需定义 dissect(payload) 函数
+    #[cfg(feature = "scripting")]
+    #[arg(long, value_name = "SCRIPT")]
+    pub script: Option<PathBuf>,
+
+    /// 配置文件与环境变量中的设置（命令行参数优先）
+    #[arg(skip)]
+    pub config: Config,
+
+    /// watch 子命令监视的目录（出现新文件时切换）
+    #[arg(skip)]
+    pub watch: Option<WatchDir>,
+
+    /// 按 --spec 加载的数据包头布局
+    #[arg(skip)]
+    pub record: RecordSpec,
 }
 
 impl CliArgs {
-    /// 获取每行显示的字节数（固定为16）
+    /// 获取每行显示的字节数（-b 优先，否则取配置文件）
     pub fn bytes_per_line(&self) -> usize {
-        16
+        self.bytes_per_line
+            .map_or(self.config.bytes_per_line, |bytes| {
+                bytes as usize
+            })
     }
 
-    /// 是否禁用颜色（固定为false，即启用颜色）
+    /// 是否禁用颜色（单色主题或无障碍模式）
     pub fn no_color(&self) -> bool {
-        false
+        self.config.theme == Theme::Mono || self.accessible
+    }
+
+    /// 时间戳格式（--time-format 优先，否则取配置文件）
+    pub fn time_format(&self) -> TimeFormat {
+        self.time_format.clone().unwrap_or_else(|| {
+            self.config.time_format.clone()
+        })
+    }
+
+    /// 数据包负载的链路层类型
+    pub fn linktype(&self) -> LinkType {
+        self.linktype.unwrap_or(self.config.linktype)
+    }
+
+    /// 按住方向键滚动时加速的最大步长
+    pub fn repeat_accel(&self) -> usize {
+        self.repeat_accel
+            .unwrap_or(self.config.repeat_accel)
+    }
+
+    /// 按 --strict / --lenient / --checksum / --spec 选择的解析器选项
+    pub fn parser_options(&self) -> ParserOptions {
+        ParserOptions {
+            checksum: self.checksum,
+            record: self.record.clone(),
+            sample: self.sample,
+            ..ParserOptions::for_mode(parse_mode(
+                self.strict,
+                self.lenient,
+            ))
+        }
+    }
+
+    /// 是否输出十六进制转储而不进入交互模式：输出被重定向、指定了 --paginate，
+    /// 或使用了 --head / --tail / --sample / --range / --packets（转储专用）
+    pub fn dump_output(&self) -> bool {
+        !io::stdout().is_terminal()
+            || self.paginate
+            || self.packet_limit().is_some()
+            || self.sample.is_some()
+            || self.range.is_some()
+            || self.packets.is_some()
+    }
+
+    /// 转储时的数据包数量限制
+    pub fn packet_limit(&self) -> Option<PacketLimit> {
+        PacketLimitArgs {
+            head: self.head,
+            tail: self.tail,
+        }
+        .limit()
+    }
+}
+
+/// 只处理开头或末尾若干个数据包的参数（不解析整个文件）
+#[derive(Args, Debug, Clone, Default)]
+pub struct PacketLimitArgs {
+    /// 只处理前 N 个数据包
+    #[arg(long, value_name = "N", conflicts_with = "tail")]
+    pub head: Option<usize>,
+
+    /// 只处理后 N 个数据包（从文件末尾向前查找）
+    #[arg(long, value_name = "N")]
+    pub tail: Option<usize>,
+}
+
+impl PacketLimitArgs {
+    /// 指定的数据包数量限制
+    pub fn limit(&self) -> Option<PacketLimit> {
+        self.head
+            .map(PacketLimit::Head)
+            .or(self.tail.map(PacketLimit::Tail))
+    }
+}
+
+/// 文件与数据包负载摘要参数
+#[cfg(feature = "hash")]
+#[derive(Args, Debug, Clone, Default)]
+pub struct HashArgs {
+    /// 计算整个文件的摘要（sha256 / blake3，如 --hash=blake3，默认 sha256）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "sha256"
+    )]
+    pub hash: Option<HashAlgorithm>,
+
+    /// 同时计算每个数据包负载的摘要，与偏移、长度、时间戳一起写入 CSV 文件
+    #[arg(long, value_name = "PATH", requires = "hash")]
+    pub packet_hashes: Option<PathBuf>,
+}
+
+/// 批量处理多个文件的参数
+#[derive(Args, Debug, Clone, Default)]
+pub struct BatchArgs {
+    /// 同时处理的文件数（默认为 CPU 核数）
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub jobs: Option<u32>,
+}
+
+impl BatchArgs {
+    /// 并行处理的文件数
+    pub fn jobs(&self) -> usize {
+        self.jobs.map_or_else(
+            || {
+                std::thread::available_parallelism()
+                    .map_or(1, |n| n.get())
+            },
+            |jobs| jobs as usize,
+        )
+    }
+}
+
+/// config 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+/// config 子命令的操作
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// 写出带说明的默认配置文件（位置由 --config 指定）
+    Init {
+        /// 覆盖已有的配置文件
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// completions 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct CompletionsArgs {
+    /// 目标 shell（bash / zsh / fish / powershell / elvish）
+    #[arg(
+        required_unless_present = "man",
+        conflicts_with = "man"
+    )]
+    pub shell: Option<Shell>,
+
+    /// 输出 roff 格式的 man 手册页（如 > pcap-viewer.1）
+    #[arg(long)]
+    pub man: bool,
+}
+
+/// info 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct InfoArgs {
+    /// PCAP 文件路径或通配符（如 captures/*.pcap），多个文件时并行处理并输出汇总表
+    #[arg(required = true, value_name = "FILE_PATH")]
+    pub files: Vec<PathBuf>,
+
+    /// 多个文件时的并行处理
+    #[command(flatten)]
+    pub batch: BatchArgs,
+
+    /// 列出开头或末尾的若干个数据包
+    #[command(flatten)]
+    pub limit: PacketLimitArgs,
+
+    /// 文件与数据包负载摘要
+    #[cfg(feature = "hash")]
+    #[command(flatten)]
+    pub hash: HashArgs,
+
+    /// 数据包列表的时间戳格式（iso / epoch / epoch-ns / relative 或 strftime 模式），默认取配置文件
+    #[arg(long, value_name = "FORMAT")]
+    pub time_format: Option<TimeFormat>,
+
+    /// 配置（提供默认的时间戳格式）
+    #[arg(skip)]
+    pub config: Config,
+}
+
+/// report 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct ReportArgs {
+    /// PCAP 文件路径
+    pub file_path: PathBuf,
+
+    /// 报告输出文件（省略时输出到标准输出）
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// 列出负载最大的数据包个数
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub top: usize,
+
+    /// 报告时间间隔的阈值（如 500ms、2s）
+    #[arg(
+        long,
+        value_name = "THRESHOLD",
+        default_value = "1s",
+        value_parser = parse_time_offset
+    )]
+    pub gap_threshold: i128,
+
+    /// 数据包负载的链路层类型（ethernet / raw / none）
+    #[arg(long, default_value_t = LinkType::Ethernet)]
+    pub linktype: LinkType,
+
+    /// 校验和算法（crc32 / crc32c / xor / none；auto 时任一算法一致即视为通过）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Auto
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 数据包头布局描述文件（TOML，用于带序号等变体格式）
+    #[arg(long, value_name = "PATH")]
+    pub spec: Option<PathBuf>,
+
+    /// 时间戳格式（iso / epoch / epoch-ns / relative 或 strftime 模式），默认取配置文件
+    #[arg(long, value_name = "FORMAT")]
+    pub time_format: Option<TimeFormat>,
+
+    /// 配置（提供默认的时间戳格式）
+    #[arg(skip)]
+    pub config: Config,
+}
+
+/// validate 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct ValidateArgs {
+    /// PCAP 文件路径或通配符（如 captures/*.pcap），多个文件时并行处理并输出汇总表
+    #[arg(required = true, value_name = "FILE_PATH")]
+    pub files: Vec<PathBuf>,
+
+    /// 多个文件时的并行处理
+    #[command(flatten)]
+    pub batch: BatchArgs,
+
+    /// 校验和算法（crc32 / crc32c / xor / none；auto 时任一算法一致即视为通过）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Auto
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 数据包头布局描述文件（TOML，用于带序号等变体格式）
+    #[arg(long, value_name = "PATH")]
+    pub spec: Option<PathBuf>,
+
+    /// 负载长度下限（字节），更短的数据包视为问题
+    #[arg(long, value_name = "BYTES")]
+    pub min_len: Option<u32>,
+
+    /// 负载长度上限（字节），更长的数据包视为问题
+    #[arg(long, value_name = "BYTES")]
+    pub max_len: Option<u32>,
+
+    /// 文件与数据包负载摘要
+    #[cfg(feature = "hash")]
+    #[command(flatten)]
+    pub hash: HashArgs,
+}
+
+/// stats 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct StatsArgs {
+    /// PCAP 文件路径或通配符（如 captures/*.pcap），多个文件时并行处理并输出汇总表
+    #[arg(required = true, value_name = "FILE_PATH")]
+    pub files: Vec<PathBuf>,
+
+    /// 多个文件时的并行处理
+    #[command(flatten)]
+    pub batch: BatchArgs,
+
+    /// 数据包负载的链路层类型（ethernet / raw / none）
+    #[arg(long, default_value_t = LinkType::Ethernet)]
+    pub linktype: LinkType,
+
+    /// 严格模式：遇到任何损坏的结构（含 CRC 不匹配）立即报错退出
+    #[arg(long, conflicts_with = "lenient")]
+    pub strict: bool,
+
+    /// 宽松模式：遇到损坏的数据包时向后搜索下一个有效的数据包继续解析
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// 校验和算法（crc32 / crc32c / xor / none；auto 时任一算法一致即视为通过）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Auto
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 数据包头布局描述文件（TOML，用于带序号等变体格式）
+    #[arg(long, value_name = "PATH")]
+    pub spec: Option<PathBuf>,
+
+    /// 报告超过阈值的时间间隔与时间倒退（如 --gaps=500ms，默认 1s）
+    #[arg(
+        long,
+        value_name = "THRESHOLD",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "1s",
+        value_parser = parse_time_offset
+    )]
+    pub gaps: Option<i128>,
+
+    /// 输出负载长度分布直方图，可指定分桶大小（如 --histogram=128，默认 64 字节）
+    #[arg(
+        long,
+        value_name = "BUCKET",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "64",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub histogram: Option<u32>,
+
+    /// 输出到达间隔与抖动的分位数（抖动为间隔与中位间隔之差）
+    #[arg(long)]
+    pub jitter: bool,
+
+    /// 只统计抽样的数据包（every:N 每 N 个取 1 个，random:P 按概率 P 随机抽取）
+    #[arg(
+        long,
+        value_name = "SPEC",
+        conflicts_with_all = ["gaps", "jitter"]
+    )]
+    pub sample: Option<Sampling>,
+}
+
+impl StatsArgs {
+    /// 按 --strict / --lenient 选择的解析模式
+    pub fn parse_mode(&self) -> ParseMode {
+        parse_mode(self.strict, self.lenient)
+    }
+}
+
+/// 解析模式（默认尽量解析，遇到损坏的数据包时停止）
+fn parse_mode(strict: bool, lenient: bool) -> ParseMode {
+    if strict {
+        ParseMode::Strict
+    } else if lenient {
+        ParseMode::Lenient
+    } else {
+        ParseMode::BestEffort
+    }
+}
+
+/// capture 子命令参数
+#[cfg(feature = "capture")]
+#[derive(Args, Debug, Clone)]
+pub struct CaptureArgs {
+    /// 网卡名称（如 eth0）
+    #[arg(short, long)]
+    pub interface: String,
+
+    /// 输出文件路径
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// 抓取指定数量的数据包后停止
+    #[arg(short, long)]
+    pub count: Option<usize>,
+
+    /// 滚动录制（如 10x100M：每个文件写到 100 MB 后换新文件，只保留最近 10 个）
+    #[arg(long, value_name = "FILESxSIZE")]
+    pub ring: Option<RingSpec>,
+
+    /// 抓包的同时在查看器中实时显示
+    #[arg(long)]
+    pub view: bool,
+
+    /// 写入的校验和算法（crc32 / crc32c / xor / none）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Crc32
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 实时查看器使用的配置
+    #[arg(skip)]
+    pub config: Config,
+}
+
+/// listen 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct ListenArgs {
+    /// 监听地址（组播地址时自动加入组，如 239.0.0.1:5000）
+    #[arg(long, value_name = "ADDR")]
+    pub udp: SocketAddr,
+
+    /// 同时录制到文件（默认仅写入临时文件）
+    #[arg(short, long, visible_alias = "record")]
+    pub output: Option<PathBuf>,
+
+    /// 接收指定数量的数据包后停止录制
+    #[arg(short, long)]
+    pub count: Option<usize>,
+
+    /// 滚动录制（如 10x100M：每个文件写到 100 MB 后换新文件，只保留最近 10 个）
+    #[arg(
+        long,
+        value_name = "FILESxSIZE",
+        requires = "output"
+    )]
+    pub ring: Option<RingSpec>,
+
+    /// 数据报内容的链路层类型（ethernet / raw / none）
+    #[arg(long, default_value_t = LinkType::None)]
+    pub linktype: LinkType,
+
+    /// 写入的校验和算法（crc32 / crc32c / xor / none）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Crc32
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 实时查看器使用的配置
+    #[arg(skip)]
+    pub config: Config,
+}
+
+/// watch 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct WatchArgs {
+    /// 要监视的目录
+    pub dir: PathBuf,
+
+    /// 抓包文件扩展名
+    #[arg(
+        long,
+        value_name = "EXT",
+        default_value = "pcap"
+    )]
+    pub ext: String,
+
+    /// 出现新文件时在状态栏提示（按 w 打开），而不是自动切换
+    #[arg(long)]
+    pub ask: bool,
+
+    /// 数据包负载的链路层类型（ethernet / raw / none），用于解码协议
+    #[arg(long)]
+    pub linktype: Option<LinkType>,
+
+    /// 校验和算法（auto 时按文件内容检测）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Auto
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 查看器使用的配置
+    #[arg(skip)]
+    pub config: Config,
+}
+
+/// replay 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct ReplayArgs {
+    /// PCAP 文件路径
+    #[arg(short, long)]
+    pub file: PathBuf,
+
+    /// 目标地址（如 127.0.0.1:6000）
+    #[arg(long, value_name = "ADDR")]
+    pub udp: SocketAddr,
+
+    /// 重放速度倍率（2.0 表示两倍速）
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+    pub speed: f64,
+}
+
+/// 解析重放速度倍率（必须为正数）
+fn parse_speed(
+    value: &str,
+) -> std::result::Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed > 0.0 && speed.is_finite() => {
+            Ok(speed)
+        }
+        _ => Err(format!(
+            "invalid speed `{}` (expected a positive number)",
+            value
+        )),
     }
 }
+
+/// serve 子命令参数
+#[cfg(feature = "remote")]
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+    /// PCAP 文件路径
+    pub file_path: PathBuf,
+
+    /// 监听地址（默认只接受本机连接，与他人共享时显式指定如 0.0.0.0:8080）
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub bind: SocketAddr,
+
+    /// 数据包负载的链路层类型（ethernet / raw / none）
+    #[arg(long, default_value_t = LinkType::Ethernet)]
+    pub linktype: LinkType,
+}
+
+/// timeshift 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct TimeshiftArgs {
+    /// 输入文件路径
+    #[arg(short, long)]
+    pub file: PathBuf,
+
+    /// 时间偏移（如 -3600s、1h30m、250ms）
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        conflicts_with = "rebase",
+        required_unless_present = "rebase",
+        value_parser = parse_time_offset
+    )]
+    pub offset: Option<i128>,
+
+    /// 将第一个数据包对齐到指定时间（如 2024-01-01T00:00:00Z）
+    #[arg(long, value_name = "TIME", value_parser = parse_time_point)]
+    pub rebase: Option<i128>,
+
+    /// 输出文件路径
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+/// export 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    /// 输入文件路径
+    #[arg(short, long)]
+    pub file: PathBuf,
+
+    /// 输出文件路径
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// 导出格式（pcap：选中的数据包；html：着色的十六进制视图）
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value_t = ExportFormat::Pcap
+    )]
+    pub format: ExportFormat,
+
+    /// 字节范围（如 0x1000-0x2000、0x1000-），pcap 导出与范围重叠的数据包，
+    /// html 导出该范围（默认整个文件）
+    #[arg(long, value_name = "RANGE")]
+    pub range: Option<OffsetRange>,
+
+    /// 只导出满足条件的数据包（如 len>100、crc==bad，或配置文件中的 @名称）
+    #[arg(long, value_name = "EXPR")]
+    pub filter: Option<FilterSpec>,
+
+    /// 只导出指定序号的数据包（从 1 开始，如 5-10,42,100-）
+    #[arg(long, value_name = "LIST")]
+    pub packets: Option<PacketList>,
+
+    /// 只导出开头或末尾的若干个数据包
+    #[command(flatten)]
+    pub limit: PacketLimitArgs,
+
+    /// 只导出抽样的数据包（every:N 或 random:P）
+    #[arg(
+        long,
+        value_name = "SPEC",
+        conflicts_with = "packets"
+    )]
+    pub sample: Option<Sampling>,
+
+    /// 配置（提供 --filter @名称 引用的命名过滤）
+    #[arg(skip)]
+    pub config: Config,
+}
+
+/// truncate 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct TruncateArgs {
+    /// 输入文件路径
+    #[arg(short, long)]
+    pub file: PathBuf,
+
+    /// 每个数据包保留的最大负载字节数
+    #[arg(short, long)]
+    pub snaplen: usize,
+
+    /// 输出文件路径
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// 重新计算时使用的校验和算法（auto 时沿用按输入文件检测到的算法）
+    #[arg(
+        long,
+        value_name = "ALGORITHM",
+        default_value_t = ChecksumAlgorithm::Auto
+    )]
+    pub checksum: ChecksumAlgorithm,
+
+    /// 数据包头布局描述文件（TOML，用于带序号等变体格式，输出沿用该布局）
+    #[arg(long, value_name = "PATH")]
+    pub spec: Option<PathBuf>,
+}
+
+/// sort 子命令参数
+#[derive(Args, Debug, Clone)]
+pub struct SortArgs {
+    /// 输入文件路径
+    #[arg(short, long)]
+    pub file: PathBuf,
+
+    /// 输出文件路径
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// 内存中每个排序分段的大小上限（MiB），超出时使用临时文件归并
+    #[arg(long, default_value_t = 256)]
+    pub memory: usize,
+
+    /// 临时文件目录（默认为系统临时目录）
+    #[arg(long)]
+    pub temp_dir: Option<PathBuf>,
+
+    /// 数据包头布局描述文件（TOML，用于带序号等变体格式，输出沿用该布局）
+    #[arg(long, value_name = "PATH")]
+    pub spec: Option<PathBuf>,
+}
+
+/// 解析时间偏移（纳秒）
+fn parse_time_offset(
+    value: &str,
+) -> std::result::Result<i128, String> {
+    timeshift::parse_offset(value)
+        .map_err(|_| {
+            format!(
+                "invalid time offset `{}` (e.g. -3600s, 1h30m, 250ms)",
+                value
+            )
+        })
+}
+
+/// 解析目标时间（Unix 纳秒）
+fn parse_time_point(
+    value: &str,
+) -> std::result::Result<i128, String> {
+    timeshift::parse_time(value)
+        .map_err(|_| {
+            format!(
+                "invalid time `{}` (expected RFC 3339 or YYYY-MM-DD HH:MM:SS)",
+                value
+            )
+        })
+}
@@ -0,0 +1,190 @@
+//! 批量处理多个文件：展开通配符，按 --jobs 并行处理，按输入顺序输出结果表
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use colored::*;
+
+use crate::cli::exit;
+
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::viewer::text::styled_display_width;
+use pcap_viewer::tr;
+
+/// 表格列的对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// 展开参数中的通配符（`*`、`?`、`[...]`），按字母顺序排列匹配的文件；
+/// 不含通配符或本身就是已存在的路径时原样保留
+pub fn expand(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let pattern = path.to_string_lossy();
+        if path.exists()
+            || !pattern.contains(['*', '?', '['])
+        {
+            files.push(path.clone());
+            continue;
+        }
+        let matches: Vec<PathBuf> = glob::glob(&pattern)
+            .map_err(|e| {
+                PcapViewerError::Usage(format!(
+                    "invalid glob `{}`: {}",
+                    pattern, e
+                ))
+            })?
+            .filter_map(|entry| entry.ok())
+            .filter(|path| path.is_file())
+            .collect();
+        if matches.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no files match `{}`", pattern),
+            )
+            .into());
+        }
+        files.extend(matches);
+    }
+    Ok(files)
+}
+
+/// 用至多 `jobs` 个线程处理所有文件，结果按输入顺序返回
+pub fn process<T: Send>(
+    files: &[PathBuf],
+    jobs: usize,
+    task: impl Fn(&Path) -> Result<T> + Sync,
+) -> Vec<Result<T>> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, files.len().max(1)) {
+            let sender = sender.clone();
+            let (next, task) = (&next, &task);
+            scope.spawn(move || loop {
+                let index =
+                    next.fetch_add(1, Ordering::Relaxed);
+                let Some(file) = files.get(index) else {
+                    break;
+                };
+                let _ = sender.send((index, task(file)));
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<_> =
+        receiver.into_iter().collect();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// 多个文件时不支持的参数
+pub fn single_file_only(
+    option: &str,
+    given: bool,
+) -> Result<()> {
+    if !given {
+        return Ok(());
+    }
+    Err(PcapViewerError::Usage(format!(
+        "{} only applies to a single file",
+        option
+    ))
+    .into())
+}
+
+/// 处理失败的文件在表中显示的错误信息
+pub fn error_cell(error: &anyhow::Error) -> String {
+    tr!("错误: {}", format!("{:#}", error))
+        .bright_red()
+        .to_string()
+}
+
+/// 汇总行末尾的失败文件数（没有失败时为空）
+pub fn failure_note<T>(results: &[Result<T>]) -> String {
+    match results.iter().filter(|r| r.is_err()).count() {
+        0 => String::new(),
+        failed => tr!(", {} 个文件出错", failed),
+    }
+}
+
+/// 有文件处理失败时以第一个失败文件对应的退出码结束进程（错误已在表中列出）
+pub fn exit_on_failure<T>(results: &[Result<T>]) {
+    if let Some(error) =
+        results.iter().find_map(|r| r.as_ref().err())
+    {
+        exit::exit(exit::code_for(error));
+    }
+}
+
+/// 输出表格：标题行加粗，各列按显示宽度对齐（单元格可带颜色）。
+/// 单元格少于列数的行（如错误信息）最后一格跨越其余各列，不参与列宽计算
+pub fn print_table(
+    columns: &[(&str, Align)],
+    rows: &[Vec<String>],
+) {
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, (title, _))| {
+            rows.iter()
+                .filter(|row| {
+                    row.len() == columns.len()
+                        || index + 1 < row.len()
+                })
+                .filter_map(|row| row.get(index))
+                .map(|cell| styled_display_width(cell))
+                .chain([styled_display_width(title)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row =
+        |cells: &mut dyn Iterator<Item = String>| {
+            cells
+                .zip(columns.iter().zip(&widths))
+                .map(|(cell, ((_, align), &width))| {
+                    let padding =
+                        " ".repeat(width.saturating_sub(
+                            styled_display_width(&cell),
+                        ));
+                    match align {
+                        Align::Left => {
+                            format!("{}{}", cell, padding)
+                        }
+                        Align::Right => {
+                            format!("{}{}", padding, cell)
+                        }
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+    println!(
+        "{}",
+        format_row(&mut columns.iter().map(
+            |(title, _)| {
+                title.bright_white().bold().to_string()
+            }
+        ))
+    );
+    for row in rows {
+        println!(
+            "{}",
+            format_row(&mut row.iter().cloned())
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! capture 子命令：从网卡抓包写入自定义格式文件
+
+use colored::*;
+use pnet_datalink::{self, Channel, DataLinkReceiver};
+use std::io::ErrorKind;
+use std::sync::atomic::AtomicBool;
+
+use crate::cli::args::CaptureArgs;
+use crate::cli::commands::live::{self, READ_TIMEOUT};
+use crate::cli::quiet;
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::parser::PcapFileHeader;
+use pcap_viewer::core::pcap::rollover::RollingWriter;
+use pcap_viewer::core::protocol::decoder::LinkType;
+use pcap_viewer::tr;
+
+/// 运行 capture 子命令
+pub fn run(args: CaptureArgs) -> Result<()> {
+    let mut rx = open_interface(&args.interface)?;
+    let mut writer = RollingWriter::create(
+        &args.output,
+        PcapFileHeader::default().to_bytes().to_vec(),
+        args.ring,
+    )?
+    .with_checksum(args.checksum);
+    // 立即写出文件头，便于查看器打开
+    writer.flush()?;
+
+    let next_frame = move || match rx.next() {
+        Ok(frame) => Ok(Some(frame.to_vec())),
+        Err(e) if e.kind() == ErrorKind::TimedOut => {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    };
+
+    let count = if args.view {
+        live::record_and_view(
+            writer,
+            LinkType::Ethernet,
+            args.count,
+            args.config.clone(),
+            next_frame,
+        )?
+    } else {
+        if !quiet() {
+            println!(
+                "{} {} → {}",
+                tr!("正在抓包:").bright_white().bold(),
+                args.interface,
+                live::describe_output(
+                    &args.output,
+                    args.ring
+                )
+            );
+        }
+        let stop = AtomicBool::new(false);
+        live::record(writer, args.count, &stop, next_frame)?
+    };
+
+    if !quiet() {
+        println!(
+            "{}",
+            tr!(
+                "已写入 {} 个数据包到 {}",
+                count,
+                live::describe_output(
+                    &args.output,
+                    args.ring
+                )
+            )
+        );
+    }
+    Ok(())
+}
+
+/// 打开网卡的数据链路层通道
+fn open_interface(
+    name: &str,
+) -> Result<Box<dyn DataLinkReceiver>> {
+    let interfaces = pnet_datalink::interfaces();
+    let Some(interface) =
+        interfaces.iter().find(|iface| iface.name == name)
+    else {
+        let available: Vec<&str> = interfaces
+            .iter()
+            .map(|iface| iface.name.as_str())
+            .collect();
+        return Err(PcapViewerError::Network(format!(
+            "Interface not found: {} (available: {})",
+            name,
+            available.join(", ")
+        ))
+        .into());
+    };
+
+    let config = pnet_datalink::Config {
+        read_timeout: Some(READ_TIMEOUT),
+        ..Default::default()
+    };
+    match pnet_datalink::channel(interface, config) {
+        Ok(Channel::Ethernet(_, rx)) => Ok(rx),
+        Ok(_) => Err(PcapViewerError::Network(format!(
+            "Unsupported channel type on {}",
+            name
+        ))
+        .into()),
+        Err(e) => Err(PcapViewerError::Network(format!(
+            "Failed to open {}: {}",
+            name, e
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,33 @@
+//! completions 子命令：输出 shell 补全脚本或 man 手册页
+
+use clap::CommandFactory;
+use std::io::{self, Write};
+
+use crate::cli::args::{Cli, CompletionsArgs};
+use pcap_viewer::app::error::types::Result;
+
+/// 运行 completions 子命令，结果写到标准输出，
+/// 下游提前关闭管道（如 | head）时静默结束
+pub fn run(args: CompletionsArgs) -> Result<()> {
+    let mut command = Cli::command();
+    let mut out = Vec::new();
+    match args.shell {
+        Some(shell) => {
+            let name = command.get_name().to_string();
+            clap_complete::generate(
+                shell,
+                &mut command,
+                name,
+                &mut out,
+            );
+        }
+        None => clap_mangen::Man::new(command)
+            .render(&mut out)?,
+    }
+    match io::stdout().lock().write_all(&out) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+            Err(e.into())
+        }
+        _ => Ok(()),
+    }
+}
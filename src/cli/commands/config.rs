@@ -0,0 +1,57 @@
+//! config 子命令：管理配置文件
+
+use colored::*;
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::{ConfigArgs, ConfigCommand};
+use crate::cli::quiet;
+use pcap_viewer::app::config::settings::Config;
+use pcap_viewer::app::config::template::DEFAULT_CONFIG;
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::tr;
+
+/// 运行 config 子命令，`path` 为 --config 指定的配置文件
+pub fn run(
+    args: ConfigArgs,
+    path: Option<PathBuf>,
+) -> Result<()> {
+    let path = path.or_else(Config::default_path).ok_or_else(
+        || {
+            PcapViewerError::Config(
+                "cannot determine the config directory, use --config"
+                    .to_string(),
+            )
+        },
+    )?;
+
+    match args.command {
+        ConfigCommand::Init { force } => init(&path, force),
+    }
+}
+
+/// 写出带说明的默认配置文件，已存在时需指定 --force 才覆盖
+fn init(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        return Err(PcapViewerError::Config(format!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        ))
+        .into());
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, DEFAULT_CONFIG)?;
+
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("已写入默认配置:").bright_white().bold(),
+            path.display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,243 @@
+//! export 子命令：将选中的数据包写入新文件，或将十六进制视图导出为 HTML
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use colored::*;
+
+use crate::cli::args::ExportArgs;
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::builder::ParserOptions;
+use pcap_viewer::core::pcap::filter::PacketFilter;
+use pcap_viewer::core::pcap::parser::PcapParser;
+use pcap_viewer::core::pcap::writer::PcapWriter;
+use pcap_viewer::core::viewer::html;
+use pcap_viewer::tr;
+
+/// HTML 导出每行的字节数
+const HTML_BYTES_PER_LINE: usize = 16;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// 只含选中数据包的新文件
+    #[default]
+    Pcap,
+    /// 按区域着色的十六进制视图
+    Html,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pcap" => Ok(Self::Pcap),
+            "html" => Ok(Self::Html),
+            other => Err(format!(
+                "unknown export format `{}` (expected pcap or html)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Pcap => "pcap",
+            Self::Html => "html",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 运行 export 子命令
+pub fn run(args: ExportArgs) -> Result<()> {
+    ensure_file_exists(&args.file);
+
+    let file_data = std::fs::read(&args.file)?;
+
+    match args.format {
+        ExportFormat::Pcap => {
+            // --tail 时不知道数据包在整个文件中的序号
+            if args.packets.is_some()
+                && args.limit.tail.is_some()
+            {
+                return Err(PcapViewerError::Usage(
+                    "--packets cannot be combined with --tail"
+                        .to_string(),
+                )
+                .into());
+            }
+            let filter = args
+                .filter
+                .as_ref()
+                .map(|spec| {
+                    spec.resolve(&args.config.filters)
+                })
+                .transpose()?;
+            // 校验校验和，供 crc 过滤条件使用
+            let builder = PcapParser::builder()
+                .verify_crc(true)
+                .sample(args.sample);
+            let parser = match args.limit.limit() {
+                Some(limit) => {
+                    PcapParser::from_bytes_limited(
+                        &file_data,
+                        builder.options().clone(),
+                        limit,
+                    )?
+                }
+                None => builder.parse_bytes(&file_data)?,
+            };
+            export_packets(
+                &args, filter, &parser, &file_data,
+            )
+        }
+        ExportFormat::Html => {
+            if args.filter.is_some()
+                || args.packets.is_some()
+                || args.limit.limit().is_some()
+                || args.sample.is_some()
+            {
+                return Err(PcapViewerError::Usage(
+                    "--filter, --packets, --head, --tail and --sample only apply to --format pcap"
+                        .to_string(),
+                )
+                .into());
+            }
+            // 文件头无效时按原始数据导出（不着色）
+            let parser = PcapParser::from_bytes_or_raw(
+                &file_data,
+                ParserOptions::default(),
+            )?;
+            export_html(&args, &parser, &file_data)
+        }
+    }
+}
+
+/// 将选中的数据包写入新文件
+fn export_packets(
+    args: &ExportArgs,
+    filter: Option<PacketFilter>,
+    parser: &PcapParser,
+    file_data: &[u8],
+) -> Result<()> {
+    // 沿用原文件头（时区偏移、时间精度等字段保持不变）
+    let file_header =
+        parser.file_header().cloned().unwrap_or_default();
+    let mut writer = PcapWriter::create_with_header(
+        &args.output,
+        &file_header,
+    )?;
+    let range = resolve_range(args, file_data)?;
+    for (index, packet) in
+        parser.packets().iter().enumerate()
+    {
+        let end = packet.offset
+            + parser.header_size()
+            + packet.header.packet_length as usize;
+        let selected = range.start < end
+            && packet.offset < range.end
+            && args
+                .packets
+                .as_ref()
+                .is_none_or(|list| list.contains(index))
+            && filter.is_none_or(|filter| {
+                filter.matches(packet)
+            });
+        if !selected {
+            continue;
+        }
+        if let Some(packet) =
+            parser.packet_ref(file_data, index)
+        {
+            writer.copy_packet(&packet)?;
+        }
+    }
+    let packets_written = writer.packets_written();
+    let bytes_written = writer.bytes_written();
+    writer.finish()?;
+
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("已导出:").bright_white().bold(),
+            tr!(
+                "{} / {} 个数据包，{} 字节 → {}",
+                packets_written,
+                parser.packets().len(),
+                bytes_written,
+                args.output.display()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// 将字节范围（默认整个文件）渲染为 HTML
+fn export_html(
+    args: &ExportArgs,
+    parser: &PcapParser,
+    file_data: &[u8],
+) -> Result<()> {
+    let range = resolve_range(args, file_data)?;
+
+    let title = format!(
+        "{} 0x{:08X}..0x{:08X}",
+        args.file.display(),
+        range.start,
+        range.end
+    );
+    let page = html::render(
+        parser,
+        file_data,
+        range.clone(),
+        HTML_BYTES_PER_LINE,
+        &title,
+    );
+    std::fs::write(&args.output, page)?;
+
+    if !quiet() {
+        println!(
+            "{} 0x{:08X}..0x{:08X} {}",
+            tr!("已导出:").bright_white().bold(),
+            range.start,
+            range.end,
+            tr!(
+                "({} 字节) → {}",
+                range.len(),
+                args.output.display()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// 按文件长度解析 --range（默认整个文件）
+fn resolve_range(
+    args: &ExportArgs,
+    file_data: &[u8],
+) -> Result<Range<usize>> {
+    let Some(range) = args.range else {
+        return Ok(0..file_data.len());
+    };
+    range.resolve(file_data.len()).ok_or_else(|| {
+        PcapViewerError::Usage(format!(
+            "Range {} is outside the file ({} bytes)",
+            range,
+            file_data.len()
+        ))
+        .into()
+    })
+}
@@ -0,0 +1,312 @@
+//! info 子命令：输出文件格式与文件头信息
+
+use colored::*;
+use std::path::Path;
+
+#[cfg(feature = "hash")]
+use crate::cli::args::HashArgs;
+use crate::cli::args::InfoArgs;
+use crate::cli::batch::{self, Align};
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::builder::ParserOptions;
+use pcap_viewer::core::pcap::format;
+use pcap_viewer::core::pcap::parser::{
+    PacketLimit, PcapParser,
+};
+use pcap_viewer::core::viewer::timestamp::TimestampStyle;
+use pcap_viewer::tr;
+
+/// 多个文件时每个文件的汇总
+struct Summary {
+    variant: String,
+    version: String,
+    packets: usize,
+    size: usize,
+    digest: Option<String>,
+}
+
+/// 运行 info 子命令：单个文件输出详细信息，多个文件输出汇总表
+pub fn run(args: InfoArgs) -> Result<()> {
+    let files = batch::expand(&args.files)?;
+    if let [file_path] = files.as_slice() {
+        return run_file(&args, file_path);
+    }
+    batch::single_file_only(
+        "--head/--tail",
+        args.limit.limit().is_some(),
+    )?;
+    #[cfg(feature = "hash")]
+    batch::single_file_only(
+        "--packet-hashes",
+        args.hash.packet_hashes.is_some(),
+    )?;
+
+    let results =
+        batch::process(&files, args.batch.jobs(), |path| {
+            summarize(&args, path)
+        });
+    #[cfg_attr(not(feature = "hash"), allow(unused_mut))]
+    let mut columns = vec![
+        (tr!("文件"), Align::Left),
+        (tr!("格式"), Align::Left),
+        (tr!("版本"), Align::Left),
+        (tr!("数据包"), Align::Right),
+        (tr!("字节"), Align::Right),
+    ];
+    #[cfg(feature = "hash")]
+    let hash_title = args.hash.hash.map(|a| a.to_string());
+    #[cfg(feature = "hash")]
+    if let Some(title) = &hash_title {
+        columns.push((title, Align::Left));
+    }
+    let rows: Vec<Vec<String>> = files
+        .iter()
+        .zip(&results)
+        .map(|(path, result)| {
+            let file = path.display().to_string();
+            match result {
+                Ok(summary) => [
+                    file,
+                    summary.variant.clone(),
+                    summary.version.clone(),
+                    summary.packets.to_string(),
+                    summary.size.to_string(),
+                ]
+                .into_iter()
+                .chain(summary.digest.clone())
+                .collect(),
+                Err(e) => vec![file, batch::error_cell(e)],
+            }
+        })
+        .collect();
+    batch::print_table(&columns, &rows);
+
+    if !quiet() {
+        let summaries: Vec<&Summary> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .collect();
+        println!(
+            "{} {}",
+            tr!("总计:").bright_white().bold(),
+            tr!(
+                "{} 个文件, {} 个数据包, {} 字节{}",
+                files.len(),
+                summaries
+                    .iter()
+                    .map(|summary| summary.packets)
+                    .sum::<usize>(),
+                summaries
+                    .iter()
+                    .map(|summary| summary.size)
+                    .sum::<usize>(),
+                batch::failure_note(&results)
+            )
+        );
+    }
+    batch::exit_on_failure(&results);
+    Ok(())
+}
+
+/// 一个文件的汇总
+fn summarize(
+    args: &InfoArgs,
+    path: &Path,
+) -> Result<Summary> {
+    let file_data = std::fs::read(path)?;
+    let detection = format::detect(&file_data);
+    let parser = PcapParser::from_bytes(&file_data)?;
+    let version = parser.file_header().map_or_else(
+        || "-".to_string(),
+        |header| {
+            format!(
+                "{}.{}",
+                header.major_version, header.minor_version
+            )
+        },
+    );
+    #[cfg(feature = "hash")]
+    let digest = args
+        .hash
+        .hash
+        .map(|algorithm| algorithm.digest(&file_data));
+    #[cfg(not(feature = "hash"))]
+    let digest = {
+        let _ = args;
+        None
+    };
+    Ok(Summary {
+        variant: detection.variant.to_string(),
+        version,
+        packets: parser.packets().len(),
+        size: file_data.len(),
+        digest,
+    })
+}
+
+/// 输出单个文件的格式与文件头信息
+fn run_file(
+    args: &InfoArgs,
+    file_path: &Path,
+) -> Result<()> {
+    ensure_file_exists(file_path);
+
+    let file_data = std::fs::read(file_path)?;
+    let detection = format::detect(&file_data);
+
+    println!(
+        "{} {}",
+        tr!("文件:").bright_white().bold(),
+        file_path.display()
+    );
+    println!(
+        "{} {}",
+        tr!("格式:").bright_white().bold(),
+        detection.variant
+    );
+    if let Some(alternative) = detection.alternative {
+        eprintln!(
+            "{} {}",
+            tr!("警告").yellow().bold(),
+            tr!(
+                "版本号无法区分格式，也可能是 {}",
+                alternative
+            )
+        );
+    }
+
+    // 其他格式或文件头无效时在此报错
+    let limit = args.limit.limit();
+    let parser = match limit {
+        Some(limit) => PcapParser::from_bytes_limited(
+            &file_data,
+            ParserOptions::default(),
+            limit,
+        )?,
+        None => PcapParser::from_bytes(&file_data)?,
+    };
+    if let Some(header) = parser.file_header() {
+        println!(
+            "{} {}.{}",
+            tr!("版本:").bright_white().bold(),
+            header.major_version,
+            header.minor_version
+        );
+        println!(
+            "{} {}",
+            tr!("时区偏移:").bright_white().bold(),
+            header.timezone_offset
+        );
+        println!(
+            "{} {}",
+            tr!("时间精度:").bright_white().bold(),
+            header.timestamp_accuracy
+        );
+    }
+    #[cfg(feature = "hash")]
+    print_hashes(&args.hash, &file_data, &parser)?;
+    let Some(limit) = limit else {
+        println!(
+            "{} {}",
+            tr!("数据包:").bright_white().bold(),
+            tr!("{} 个", parser.packets().len())
+        );
+        return Ok(());
+    };
+
+    // 只列出开头或末尾的数据包（未解析整个文件，总数未知）
+    let count = parser.packets().len();
+    println!(
+        "{} {}",
+        tr!("数据包:").bright_white().bold(),
+        match limit {
+            PacketLimit::Head(_) => tr!("前 {} 个", count),
+            PacketLimit::Tail(_) => tr!("后 {} 个", count),
+        }
+    );
+    let style = parser
+        .file_header()
+        .map_or_else(TimestampStyle::default, |header| {
+            TimestampStyle::from_header(header, false)
+        })
+        .with_format(
+            args.time_format.clone().unwrap_or_else(|| {
+                args.config.time_format.clone()
+            }),
+        )
+        .with_origin(
+            parser.packets().first().map_or(0, |packet| {
+                packet.header.timestamp_nanos()
+            }),
+        );
+    for packet in parser.packets() {
+        let time = style
+            .format(
+                packet.header.timestamp_seconds,
+                packet.header.timestamp_nanoseconds,
+            )
+            .unwrap_or_else(|| "INVALID".to_string());
+        println!(
+            "  0x{:08X} {}  {}",
+            packet.offset,
+            tr!(
+                "{} 字节",
+                format!(
+                    "{:>8}",
+                    packet.header.packet_length
+                )
+            ),
+            time
+        );
+    }
+    Ok(())
+}
+
+/// 输出整个文件的摘要，并按需将每个数据包负载的摘要写入 CSV 文件
+#[cfg(feature = "hash")]
+pub fn print_hashes(
+    args: &HashArgs,
+    data: &[u8],
+    parser: &PcapParser,
+) -> Result<()> {
+    let Some(algorithm) = args.hash else {
+        return Ok(());
+    };
+    println!(
+        "{} {}",
+        format!("{}:", algorithm).bright_white().bold(),
+        algorithm.digest(data)
+    );
+
+    let Some(path) = &args.packet_hashes else {
+        return Ok(());
+    };
+    let mut csv = format!(
+        "packet,offset,length,timestamp,{}\n",
+        algorithm
+    );
+    for packet in parser.packet_refs(data) {
+        let header = packet.header();
+        csv.push_str(&format!(
+            "{},{},{},{}.{:09},{}\n",
+            packet.index() + 1,
+            packet.offset(),
+            header.packet_length,
+            header.timestamp_seconds,
+            header.timestamp_nanoseconds,
+            algorithm.digest(packet.payload())
+        ));
+    }
+    std::fs::write(path, csv)?;
+    println!(
+        "{} {}",
+        tr!("提示").yellow().bold(),
+        tr!(
+            "{} 个数据包的负载摘要已写入 {}",
+            parser.packets().len(),
+            path.display()
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! listen 子命令：接收 UDP（含组播）数据报并实时查看
+
+use std::io::ErrorKind;
+use std::net::{
+    IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket,
+};
+use std::path::PathBuf;
+
+use crate::cli::args::ListenArgs;
+use crate::cli::commands::live::{self, READ_TIMEOUT};
+use crate::cli::quiet;
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::parser::PcapFileHeader;
+use pcap_viewer::core::pcap::rollover::RollingWriter;
+use pcap_viewer::tr;
+
+/// UDP 数据报最大长度
+const MAX_DATAGRAM_SIZE: usize = 65536;
+
+/// 运行 listen 子命令
+pub fn run(args: ListenArgs) -> Result<()> {
+    let socket = bind_socket(args.udp)?;
+    socket.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    // 未指定输出文件时录制到临时文件，退出后删除
+    let (file_path, keep_file) = match &args.output {
+        Some(path) => (path.clone(), true),
+        None => (temp_capture_path(), false),
+    };
+    let mut writer = RollingWriter::create(
+        &file_path,
+        PcapFileHeader::default().to_bytes().to_vec(),
+        args.ring,
+    )?
+    .with_checksum(args.checksum);
+    writer.flush()?;
+
+    let mut buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+    let next_frame = move || match socket
+        .recv_from(&mut buffer)
+    {
+        Ok((len, _)) => Ok(Some(buffer[..len].to_vec())),
+        Err(e)
+            if matches!(
+                e.kind(),
+                ErrorKind::WouldBlock | ErrorKind::TimedOut
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    };
+
+    let result = live::record_and_view(
+        writer,
+        args.linktype,
+        args.count,
+        args.config.clone(),
+        next_frame,
+    );
+
+    if keep_file {
+        if let Ok(count) = &result {
+            if !quiet() {
+                println!(
+                    "{}",
+                    tr!(
+                        "已写入 {} 个数据包到 {}",
+                        count,
+                        live::describe_output(
+                            &file_path, args.ring
+                        )
+                    )
+                );
+            }
+        }
+    } else {
+        let _ = std::fs::remove_file(&file_path);
+    }
+
+    result.map(|_| ())
+}
+
+/// 绑定 UDP 套接字，组播地址时加入对应组
+fn bind_socket(addr: SocketAddr) -> Result<UdpSocket> {
+    let bind_result = match addr.ip() {
+        IpAddr::V4(group) if group.is_multicast() => {
+            UdpSocket::bind((
+                Ipv4Addr::UNSPECIFIED,
+                addr.port(),
+            ))
+            .and_then(|socket| {
+                socket.join_multicast_v4(
+                    &group,
+                    &Ipv4Addr::UNSPECIFIED,
+                )?;
+                Ok(socket)
+            })
+        }
+        IpAddr::V6(group) if group.is_multicast() => {
+            UdpSocket::bind((
+                Ipv6Addr::UNSPECIFIED,
+                addr.port(),
+            ))
+            .and_then(|socket| {
+                socket.join_multicast_v6(&group, 0)?;
+                Ok(socket)
+            })
+        }
+        _ => UdpSocket::bind(addr),
+    };
+
+    bind_result.map_err(|e| {
+        PcapViewerError::Network(format!(
+            "Failed to listen on {}: {}",
+            addr, e
+        ))
+        .into()
+    })
+}
+
+/// 临时录制文件路径
+fn temp_capture_path() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "pcap-viewer-listen-{}.pcap",
+        std::process::id()
+    ))
+}
@@ -0,0 +1,174 @@
+//! 实时数据源：后台录制到文件，前台以跟随模式查看
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{
+    Duration, Instant, SystemTime, UNIX_EPOCH,
+};
+
+use crate::cli::args::CliArgs;
+use crate::cli::run_viewer;
+use pcap_viewer::app::config::settings::Config;
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::checksum::ChecksumAlgorithm;
+use pcap_viewer::core::pcap::ring;
+use pcap_viewer::core::pcap::rollover::{
+    RingSpec, RollingWriter,
+};
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::timing;
+use pcap_viewer::core::protocol::decoder::LinkType;
+use pcap_viewer::core::source::watch::WatchDir;
+use pcap_viewer::tr;
+
+/// 读取超时，用于定期检查停止标志
+pub const READ_TIMEOUT: Duration =
+    Duration::from_millis(200);
+
+/// 录制文件同步到磁盘的间隔
+pub const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 录制循环，返回写入的数据包数量
+///
+/// `next_frame` 超时未收到数据时返回 `Ok(None)`。
+pub fn record<F>(
+    mut writer: RollingWriter,
+    limit: Option<usize>,
+    stop: &AtomicBool,
+    mut next_frame: F,
+) -> Result<usize>
+where
+    F: FnMut() -> Result<Option<Vec<u8>>>,
+{
+    let mut last_sync = Instant::now();
+    while !stop.load(Ordering::Relaxed)
+        && limit.is_none_or(|limit| {
+            writer.packets_written() < limit
+        })
+    {
+        let Some(frame) = next_frame()? else {
+            continue;
+        };
+
+        // 以接收时刻作为时间戳
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        writer.write_packet(
+            now.as_secs() as u32,
+            now.subsec_nanos(),
+            &frame,
+        )?;
+        // 逐包刷新，保证查看器与中断时的数据完整，并定期同步到磁盘
+        writer.flush()?;
+        if last_sync.elapsed() >= SYNC_INTERVAL {
+            writer.sync()?;
+            last_sync = Instant::now();
+        }
+    }
+
+    let count = writer.packets_written();
+    writer.sync()?;
+    writer.finish()?;
+    Ok(count)
+}
+
+/// 录制目标的描述：文件路径，滚动录制时附带滚动方式
+pub fn describe_output(
+    path: &Path,
+    ring: Option<RingSpec>,
+) -> String {
+    match ring {
+        Some(ring) => {
+            tr!("{} (滚动 {})", path.display(), ring)
+        }
+        None => path.display().to_string(),
+    }
+}
+
+/// 后台录制到文件，同时以跟随模式打开查看器，返回写入的数据包数量。
+/// 滚动录制时查看器监视所在目录，切换到新写入的文件
+pub fn record_and_view<F>(
+    writer: RollingWriter,
+    linktype: LinkType,
+    limit: Option<usize>,
+    config: Config,
+    next_frame: F,
+) -> Result<usize>
+where
+    F: FnMut() -> Result<Option<Vec<u8>>> + Send + 'static,
+{
+    let file_path = writer.current_path().to_path_buf();
+    let watch = writer.ring().map(|_| WatchDir {
+        dir: match file_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => {
+                dir.to_path_buf()
+            }
+            _ => PathBuf::from("."),
+        },
+        extension: file_path
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        ask: false,
+    });
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            record(writer, limit, &stop, next_frame)
+        })
+    };
+
+    let viewer_result = run_viewer(CliArgs {
+        file_path,
+        more_files: Vec::new(),
+        linktype: Some(linktype),
+        bytes_per_line: None,
+        strict: false,
+        lenient: false,
+        checksum: ChecksumAlgorithm::Auto,
+        spec: None,
+        utc: false,
+        time_format: None,
+        follow: true,
+        ring_size: ring::DEFAULT_CAPACITY as u32,
+        record_to: None,
+        ring: None,
+        delta: false,
+        accessible: false,
+        no_alt_screen: false,
+        gap_threshold: timing::DEFAULT_GAP_THRESHOLD,
+        diff: None,
+        baseline: None,
+        fresh: true,
+        lines: None,
+        head: None,
+        tail: None,
+        sample: None,
+        range: None,
+        packets: None,
+        paginate: false,
+        no_pager: false,
+        watch,
+        repeat_accel: None,
+        #[cfg(feature = "scripting")]
+        script: None,
+        config,
+        record: RecordSpec::default(),
+    });
+
+    stop.store(true, Ordering::Relaxed);
+    let count = handle.join().map_err(|_| {
+        PcapViewerError::Network(
+            "recording thread panicked".to_string(),
+        )
+    })??;
+    viewer_result?;
+
+    Ok(count)
+}
@@ -0,0 +1,20 @@
+//! 子命令实现
+
+#[cfg(feature = "capture")]
+pub mod capture;
+pub mod completions;
+pub mod config;
+pub mod export;
+pub mod info;
+pub mod listen;
+pub mod live;
+pub mod replay;
+pub mod report;
+#[cfg(feature = "remote")]
+pub mod serve;
+pub mod sort;
+pub mod stats;
+pub mod timeshift;
+pub mod truncate;
+pub mod validate;
+pub mod watch;
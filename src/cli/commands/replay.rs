@@ -0,0 +1,90 @@
+//! replay 子命令：按原始时间间隔通过 UDP 重放数据包负载
+
+use colored::*;
+use std::net::{SocketAddr, UdpSocket};
+use std::thread;
+use std::time::Duration;
+
+use crate::cli::args::ReplayArgs;
+use crate::cli::ensure_file_exists;
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::parser::{
+    DataPacketHeader, PcapParser,
+};
+use pcap_viewer::tr;
+
+/// 运行 replay 子命令
+pub fn run(args: ReplayArgs) -> Result<()> {
+    ensure_file_exists(&args.file);
+
+    let parser = PcapParser::new(&args.file)?;
+    let file_data = std::fs::read(&args.file)?;
+
+    let bind_addr: SocketAddr = if args.udp.is_ipv4() {
+        "0.0.0.0:0".parse()?
+    } else {
+        "[::]:0".parse()?
+    };
+    let socket = UdpSocket::bind(bind_addr)?;
+    socket.connect(args.udp).map_err(|e| {
+        PcapViewerError::Network(format!(
+            "Failed to connect to {}: {}",
+            args.udp, e
+        ))
+    })?;
+
+    println!(
+        "{} {}",
+        tr!("正在重放:").bright_white().bold(),
+        tr!(
+            "{} → {} (速度 {}x)",
+            args.file.display(),
+            args.udp,
+            args.speed
+        )
+    );
+
+    let mut previous: Option<DataPacketHeader> = None;
+    let mut packets_sent = 0usize;
+    let mut bytes_sent = 0usize;
+    for packet in parser.packet_refs(&file_data) {
+        let header = *packet.header();
+        if let Some(previous) = previous {
+            let delay = timestamp_delta(&previous, &header);
+            if !delay.is_zero() {
+                thread::sleep(delay.div_f64(args.speed));
+            }
+        }
+        previous = Some(header);
+
+        bytes_sent += socket.send(packet.payload())?;
+        packets_sent += 1;
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "已发送 {} 个数据包 ({} 字节)",
+            packets_sent,
+            bytes_sent
+        )
+    );
+    Ok(())
+}
+
+/// 两个数据包之间的时间间隔（时间戳倒退时为零）
+fn timestamp_delta(
+    previous: &DataPacketHeader,
+    current: &DataPacketHeader,
+) -> Duration {
+    let to_duration = |header: &DataPacketHeader| {
+        Duration::new(
+            header.timestamp_seconds as u64,
+            header.timestamp_nanoseconds,
+        )
+    };
+    to_duration(current)
+        .saturating_sub(to_duration(previous))
+}
@@ -0,0 +1,455 @@
+//! report 子命令：生成可直接附在问题报告中的纯文本摘要
+//! （文件头、校验结果、统计、最大的数据包、时间间隔与校验和失败）
+
+use std::fmt::Write;
+
+use crate::cli::args::ReportArgs;
+use crate::cli::commands::stats::{
+    format_seconds, percentage,
+};
+use crate::cli::ensure_file_exists;
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::builder::{
+    ParseMode, ParserOptions,
+};
+use pcap_viewer::core::pcap::diagnostics::{
+    self, DiagnosticKind,
+};
+use pcap_viewer::core::pcap::format;
+use pcap_viewer::core::pcap::parser::{
+    PcapFileHeader, PcapParser,
+};
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::timing::{
+    self, TimingIssueKind,
+};
+use pcap_viewer::core::protocol::decoder;
+use pcap_viewer::core::protocol::hierarchy::{
+    ProtocolHierarchy, ProtocolNode,
+};
+use pcap_viewer::core::viewer::text::{
+    display_width, pad_left_to_width, pad_to_width,
+};
+use pcap_viewer::core::viewer::timestamp::TimestampStyle;
+use pcap_viewer::tr;
+
+/// 运行 report 子命令
+pub fn run(args: ReportArgs) -> Result<()> {
+    ensure_file_exists(&args.file_path);
+
+    let record = match &args.spec {
+        Some(path) => RecordSpec::load(path)?,
+        None => RecordSpec::default(),
+    };
+    let file_data = std::fs::read(&args.file_path)?;
+    // 宽松模式并校验校验和，尽量覆盖损坏的文件
+    let options = ParserOptions {
+        checksum: args.checksum,
+        record,
+        verify_crc: true,
+        ..ParserOptions::for_mode(ParseMode::Lenient)
+    };
+    let parser =
+        PcapParser::from_bytes_or_raw(&file_data, options)?;
+
+    let report = generate(&args, &parser, &file_data);
+    match &args.output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+/// 生成报告全文
+fn generate(
+    args: &ReportArgs,
+    parser: &PcapParser,
+    data: &[u8],
+) -> String {
+    let mut out = String::new();
+    let style = parser
+        .file_header()
+        .map_or_else(TimestampStyle::default, |header| {
+            TimestampStyle::from_header(header, false)
+        })
+        .with_format(
+            args.time_format.clone().unwrap_or_else(|| {
+                args.config.time_format.clone()
+            }),
+        )
+        .with_origin(
+            parser.packets().first().map_or(0, |packet| {
+                packet.header.timestamp_nanos()
+            }),
+        );
+    let time = |seconds, nanoseconds| {
+        style.format(seconds, nanoseconds).unwrap_or_else(
+            || {
+                format!(
+                    "INVALID({},{})",
+                    seconds, nanoseconds
+                )
+            },
+        )
+    };
+
+    let _ = writeln!(
+        out,
+        "{}",
+        tr!(
+            "pcap-viewer {} 报告",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+    let _ = writeln!(
+        out,
+        "{}",
+        tr!("文件: {}", args.file_path.display())
+    );
+    let _ = writeln!(
+        out,
+        "{}",
+        tr!("大小: {} 字节", data.len())
+    );
+    let detection = format::detect(data);
+    let _ = writeln!(
+        out,
+        "{}",
+        tr!(
+            "格式: {}{}",
+            detection.variant,
+            detection
+                .alternative
+                .map(|alternative| {
+                    tr!(
+                        "（无法确定，也可能是 {}）",
+                        alternative
+                    )
+                })
+                .unwrap_or_default()
+        )
+    );
+
+    // 文件头
+    section(&mut out, tr!("文件头"));
+    match PcapFileHeader::read(
+        data,
+        parser.options().endianness,
+    ) {
+        Ok(header) => {
+            let _ = writeln!(
+                out,
+                "{}",
+                tr!(
+                    "魔数: 0x{}",
+                    format!("{:08X}", header.magic_number)
+                )
+            );
+            let _ = writeln!(
+                out,
+                "{}",
+                tr!(
+                    "版本: {}.{}",
+                    header.major_version,
+                    header.minor_version
+                )
+            );
+            let _ = writeln!(
+                out,
+                "{}",
+                tr!("时区偏移: {}", header.timezone_offset)
+            );
+            let _ = writeln!(
+                out,
+                "{}",
+                tr!(
+                    "时间精度: {}",
+                    header.timestamp_accuracy
+                )
+            );
+        }
+        Err(e) => {
+            let _ = writeln!(out, "{}", tr!("无效: {}", e));
+        }
+    }
+
+    // 校验结果
+    section(&mut out, tr!("校验结果"));
+    let found = diagnostics::diagnose(data, parser);
+    let count = |kind| {
+        found
+            .iter()
+            .filter(|diagnostic| diagnostic.kind == kind)
+            .count()
+    };
+    let counts = [
+        (tr!("文件头无效"), DiagnosticKind::InvalidHeader),
+        (tr!("校验和失败"), DiagnosticKind::BadChecksum),
+        (
+            tr!("时间倒退"),
+            DiagnosticKind::BackwardsTimestamp,
+        ),
+        (tr!("跳过的字节段"), DiagnosticKind::Skipped),
+        (tr!("末尾截断"), DiagnosticKind::Truncated),
+    ];
+    // 标签列至少 16 列宽，译文较长时按最长的标签对齐
+    let label_width = counts
+        .iter()
+        .map(|(label, _)| display_width(label) + 1)
+        .fold(16, usize::max);
+    for (label, kind) in counts {
+        let _ = writeln!(
+            out,
+            "{} {}",
+            pad_to_width(
+                &format!("{}:", label),
+                label_width
+            ),
+            count(kind)
+        );
+    }
+    let _ = writeln!(
+        out,
+        "{}",
+        tr!(
+            "结论: {}",
+            if found.is_empty() {
+                tr!("未发现问题")
+            } else {
+                tr!("发现问题，详见下文")
+            }
+        )
+    );
+
+    // 统计
+    section(&mut out, tr!("统计"));
+    let packets = parser.packets();
+    let lengths = packets
+        .iter()
+        .map(|packet| packet.header.packet_length as u64);
+    let total: u64 = lengths.clone().sum();
+    let _ = writeln!(
+        out,
+        "{}",
+        tr!("数据包: {}", packets.len())
+    );
+    let _ = writeln!(out, "{}", tr!("负载字节: {}", total));
+    if let (Some(min), Some(max)) =
+        (lengths.clone().min(), lengths.max())
+    {
+        let _ = writeln!(
+            out,
+            "{}",
+            tr!(
+                "负载长度: 最小 {} / 平均 {} / 最大 {}",
+                min,
+                format!(
+                    "{:.1}",
+                    total as f64 / packets.len() as f64
+                ),
+                max
+            )
+        );
+    }
+    // 时间戳可能乱序，取最早与最晚的时间戳而不是首尾数据包
+    let earliest = packets.iter().min_by_key(|packet| {
+        packet.header.timestamp_nanos()
+    });
+    let latest = packets.iter().max_by_key(|packet| {
+        packet.header.timestamp_nanos()
+    });
+    if let (Some(first), Some(last)) = (earliest, latest) {
+        let _ = writeln!(
+            out,
+            "{}",
+            tr!(
+                "时间范围: {} .. {}",
+                time(
+                    first.header.timestamp_seconds,
+                    first.header.timestamp_nanoseconds
+                ),
+                time(
+                    last.header.timestamp_seconds,
+                    last.header.timestamp_nanoseconds
+                )
+            )
+        );
+        let _ = writeln!(
+            out,
+            "{}",
+            tr!(
+                "持续: {} 秒",
+                format_seconds(
+                    last.header.timestamp_nanos()
+                        - first.header.timestamp_nanos()
+                )
+            )
+        );
+    }
+
+    let mut hierarchy = ProtocolHierarchy::new();
+    for packet in parser.packet_refs(data) {
+        let decoded = decoder::decode(
+            args.linktype,
+            packet.payload(),
+        );
+        hierarchy.add(
+            &decoded.layers,
+            packet.header().packet_length as usize,
+        );
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "{} {} {} {}",
+        pad_to_width(tr!("协议"), 28),
+        pad_left_to_width(tr!("数据包"), 10),
+        pad_left_to_width(tr!("占比"), 8),
+        tr!("(链路层: {})", args.linktype)
+    );
+    let total_packets = hierarchy.total_packets();
+    for node in hierarchy.finish() {
+        write_node(&mut out, &node, 0, total_packets);
+    }
+
+    // 最大的数据包
+    section(&mut out, &tr!("最大的 {} 个数据包", args.top));
+    let mut largest: Vec<usize> =
+        (0..packets.len()).collect();
+    largest.sort_by_key(|&index| {
+        std::cmp::Reverse(
+            packets[index].header.packet_length,
+        )
+    });
+    for &index in largest.iter().take(args.top) {
+        let packet = &packets[index];
+        let _ = writeln!(
+            out,
+            "{:>10} 0x{:08X} {}  {}",
+            format!("#{}", index + 1),
+            packet.offset,
+            tr!(
+                "{} 字节",
+                format!(
+                    "{:>8}",
+                    packet.header.packet_length
+                )
+            ),
+            time(
+                packet.header.timestamp_seconds,
+                packet.header.timestamp_nanoseconds
+            )
+        );
+    }
+    if packets.is_empty() {
+        let _ = writeln!(out, "{}", tr!("(无数据包)"));
+    }
+
+    // 时间戳间隔与倒退
+    section(
+        &mut out,
+        &format!(
+            "{} {}",
+            tr!("时间戳异常"),
+            tr!(
+                "(间隔阈值 {} 秒)",
+                format_seconds(args.gap_threshold)
+            )
+        ),
+    );
+    let issues =
+        timing::find_issues(packets, args.gap_threshold);
+    let (gap, backwards) = (tr!("间隔"), tr!("倒退"));
+    let kind_width =
+        display_width(gap).max(display_width(backwards));
+    for issue in &issues {
+        let kind = match issue.kind {
+            TimingIssueKind::Gap => gap,
+            TimingIssueKind::Backwards => backwards,
+        };
+        let _ = writeln!(
+            out,
+            "{:>10} 0x{:08X} {} {:>22}",
+            format!("#{}", issue.index + 1),
+            issue.offset,
+            pad_to_width(kind, kind_width),
+            format_seconds(issue.delta_nanos)
+        );
+    }
+    if issues.is_empty() {
+        let _ =
+            writeln!(out, "{}", tr!("未发现时间戳异常"));
+    }
+
+    // 校验和失败
+    section(&mut out, tr!("校验和失败"));
+    let checksum = parser.options().checksum.resolve(
+        parser.packet_refs(data).map(|packet| {
+            (packet.payload(), packet.header().checksum)
+        }),
+    );
+    let mut failures = 0;
+    for diagnostic in found
+        .iter()
+        .filter(|d| d.kind == DiagnosticKind::BadChecksum)
+    {
+        let Some(packet) =
+            diagnostic.packet.and_then(|index| {
+                parser.packet_ref(data, index)
+            })
+        else {
+            continue;
+        };
+        failures += 1;
+        let _ = writeln!(
+            out,
+            "{:>10} 0x{:08X} {}",
+            format!("#{}", packet.index() + 1),
+            packet.offset(),
+            tr!(
+                "包头 0x{} 计算 ({}) 0x{}",
+                format!("{:08X}", packet.header().checksum),
+                checksum,
+                format!(
+                    "{:08X}",
+                    checksum.compute(packet.payload())
+                )
+            )
+        );
+    }
+    if failures == 0 {
+        let _ = writeln!(out, "{}", tr!("全部通过"));
+    }
+    out
+}
+
+/// 写入章节标题
+fn section(out: &mut String, title: &str) {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", title);
+    let _ = writeln!(out, "{}", "-".repeat(40));
+}
+
+/// 递归写入协议层级节点
+fn write_node(
+    out: &mut String,
+    node: &ProtocolNode,
+    depth: usize,
+    total_packets: usize,
+) {
+    let name =
+        format!("{}{}", "  ".repeat(depth), node.name);
+    let _ = writeln!(
+        out,
+        "{:<28} {:>10} {:>7.1}%",
+        name,
+        node.packets,
+        percentage(
+            node.packets as u64,
+            total_packets as u64
+        )
+    );
+    for child in &node.children {
+        write_node(out, child, depth + 1, total_packets);
+    }
+}
@@ -0,0 +1,309 @@
+//! serve 子命令：通过 HTTP/JSON 接口提供远程查看
+//!
+//! 接口：
+//! - `GET /`：原始文件内容（供 `pcap-viewer view http://host:port/` 使用）
+//! - `GET /api/info`：文件头与数据包总数
+//! - `GET /api/packets?start=0&limit=100`：数据包列表
+//! - `GET /api/packets/<index>`：数据包详情（协议层与负载）
+//! - `GET /api/hex?offset=0&length=256`：十六进制页
+
+use colored::*;
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::cli::args::ServeArgs;
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::parser::PcapParser;
+use pcap_viewer::core::protocol::decoder::{
+    self, LinkType,
+};
+use pcap_viewer::tr;
+
+/// 数据包列表单次返回的最大数量
+const MAX_PACKET_LIMIT: usize = 1000;
+
+/// 十六进制页单次返回的最大字节数
+const MAX_HEX_LENGTH: usize = 64 * 1024;
+
+/// 十六进制页每行字节数
+const HEX_BYTES_PER_LINE: usize = 16;
+
+/// 已解析的文件
+struct Capture {
+    file_name: String,
+    file_data: Vec<u8>,
+    parser: PcapParser,
+    linktype: LinkType,
+}
+
+/// 运行 serve 子命令
+pub fn run(args: ServeArgs) -> Result<()> {
+    ensure_file_exists(&args.file_path);
+
+    let file_data = std::fs::read(&args.file_path)?;
+    let capture = Capture {
+        file_name: args
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        parser: PcapParser::builder()
+            .verify_crc(true)
+            .parse_bytes(&file_data)?,
+        file_data,
+        linktype: args.linktype,
+    };
+
+    let server = Server::http(args.bind).map_err(|e| {
+        PcapViewerError::Network(format!(
+            "Failed to listen on {}: {}",
+            args.bind, e
+        ))
+    })?;
+
+    if !quiet() {
+        println!(
+            "{} {} → http://{}/",
+            tr!("正在提供:").bright_white().bold(),
+            args.file_path.display(),
+            args.bind
+        );
+    }
+
+    for request in server.incoming_requests() {
+        if let Err(e) = handle_request(&capture, request) {
+            tracing::warn!(
+                "failed to handle request: {}",
+                e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// 处理单个请求
+fn handle_request(
+    capture: &Capture,
+    request: Request,
+) -> Result<()> {
+    let url = request.url().to_string();
+    let (path, query) =
+        url.split_once('?').unwrap_or((&url, ""));
+
+    if path == "/" {
+        return serve_file(capture, request);
+    }
+
+    let body = match path {
+        "/api/info" => Some(info(capture)),
+        "/api/packets" => Some(packet_list(capture, query)),
+        "/api/hex" => Some(hex_page(capture, query)),
+        _ => path
+            .strip_prefix("/api/packets/")
+            .and_then(|index| index.parse().ok())
+            .and_then(|index| {
+                packet_detail(capture, index)
+            }),
+    };
+
+    let response = match body {
+        Some(body) => {
+            Response::from_string(body.to_string())
+                .with_status_code(200)
+        }
+        None => Response::from_string(
+            json!({ "error": "not found" }).to_string(),
+        )
+        .with_status_code(404),
+    };
+    request.respond(response.with_header(
+        static_header("Content-Type", "application/json"),
+    ))?;
+    Ok(())
+}
+
+/// 返回原始文件内容（支持单段 Range 请求）
+fn serve_file(
+    capture: &Capture,
+    request: Request,
+) -> Result<()> {
+    let file_size = capture.file_data.len();
+    let range = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Range"))
+        .and_then(|header| {
+            parse_range(header.value.as_str(), file_size)
+        });
+
+    let (status, body, content_range) = match range {
+        Some((start, end)) => (
+            206,
+            capture.file_data[start..=end].to_vec(),
+            Some(format!(
+                "bytes {}-{}/{}",
+                start, end, file_size
+            )),
+        ),
+        None => (200, capture.file_data.clone(), None),
+    };
+
+    let mut response = Response::from_data(body)
+        .with_status_code(status)
+        .with_header(static_header(
+            "Content-Type",
+            "application/octet-stream",
+        ))
+        .with_header(static_header(
+            "Accept-Ranges",
+            "bytes",
+        ));
+    if let Some(content_range) = content_range {
+        response = response.with_header(
+            Header::from_bytes(
+                "Content-Range",
+                content_range,
+            )
+            .expect("content range header is valid"),
+        );
+    }
+    request.respond(response)?;
+    Ok(())
+}
+
+/// 解析 `bytes=start-end` 形式的 Range 头，返回闭区间
+fn parse_range(
+    value: &str,
+    file_size: usize,
+) -> Option<(usize, usize)> {
+    let (start, end) =
+        value.strip_prefix("bytes=")?.split_once('-')?;
+    let start: usize = start.trim().parse().ok()?;
+    let last = file_size.checked_sub(1)?;
+    let end = match end.trim() {
+        "" => last,
+        end => end.parse::<usize>().ok()?.min(last),
+    };
+    (start <= end && start < file_size)
+        .then_some((start, end))
+}
+
+/// 构造固定内容的响应头
+fn static_header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name, value)
+        .expect("static header is valid")
+}
+
+/// 文件信息
+fn info(capture: &Capture) -> Value {
+    json!({
+        "file_name": capture.file_name,
+        "file_size": capture.file_data.len(),
+        "file_header": capture.parser.file_header(),
+        "packet_count": capture.parser.packets().len(),
+    })
+}
+
+/// 数据包列表
+fn packet_list(capture: &Capture, query: &str) -> Value {
+    let packets = capture.parser.packets();
+    let start = query_param(query, "start").unwrap_or(0);
+    let limit = query_param(query, "limit")
+        .unwrap_or(100)
+        .min(MAX_PACKET_LIMIT);
+
+    let items: Vec<Value> = packets
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(limit)
+        .map(|(index, packet)| {
+            json!({ "index": index, "packet": packet })
+        })
+        .collect();
+
+    json!({ "total": packets.len(), "packets": items })
+}
+
+/// 数据包详情
+fn packet_detail(
+    capture: &Capture,
+    index: usize,
+) -> Option<Value> {
+    let packet = capture
+        .parser
+        .packet_ref(&capture.file_data, index)?;
+    let decoded =
+        decoder::decode(capture.linktype, packet.payload());
+    let layers: Vec<&str> = decoded
+        .layers
+        .iter()
+        .map(|layer| layer.name())
+        .collect();
+
+    Some(json!({
+        "index": index,
+        "offset": packet.offset(),
+        "header": packet.header(),
+        "checksum_valid": packet.checksum_valid(),
+        "layers": layers,
+        "payload": to_hex(packet.payload()),
+    }))
+}
+
+/// 十六进制页
+fn hex_page(capture: &Capture, query: &str) -> Value {
+    let file_size = capture.file_data.len();
+    let offset = query_param(query, "offset")
+        .unwrap_or(0)
+        .min(file_size);
+    let length = query_param(query, "length")
+        .unwrap_or(256)
+        .min(MAX_HEX_LENGTH);
+    let end = offset.saturating_add(length).min(file_size);
+
+    let lines: Vec<Value> = capture.file_data[offset..end]
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            json!({
+                "offset": offset + i * HEX_BYTES_PER_LINE,
+                "hex": to_hex(chunk),
+                "ascii": ascii,
+            })
+        })
+        .collect();
+
+    json!({ "offset": offset, "file_size": file_size, "lines": lines })
+}
+
+/// 读取查询参数
+fn query_param(query: &str, name: &str) -> Option<usize> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// 字节序列转为空格分隔的十六进制字符串
+fn to_hex(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
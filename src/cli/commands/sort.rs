@@ -0,0 +1,62 @@
+//! sort 子命令：按时间戳排序数据包
+
+use std::fs::File;
+use std::io::BufReader;
+
+use colored::*;
+
+use crate::cli::args::SortArgs;
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::builder::ParserOptions;
+use pcap_viewer::core::pcap::reader::PcapReader;
+use pcap_viewer::core::pcap::sort::ExternalSorter;
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::writer::PcapWriter;
+use pcap_viewer::tr;
+
+/// 运行 sort 子命令
+pub fn run(args: SortArgs) -> Result<()> {
+    ensure_file_exists(&args.file);
+
+    let record = match &args.spec {
+        Some(path) => RecordSpec::load(path)?,
+        None => RecordSpec::default(),
+    };
+    let reader = PcapReader::with_options(
+        BufReader::new(File::open(&args.file)?),
+        ParserOptions {
+            record,
+            ..ParserOptions::default()
+        },
+    )?;
+    // 输出沿用源文件的文件头（字节序与格式变体）
+    let mut writer = PcapWriter::create_with_raw_header(
+        &args.output,
+        reader.file_header_bytes(),
+    )?;
+
+    let mut sorter = ExternalSorter::new().chunk_bytes(
+        args.memory.saturating_mul(1024 * 1024),
+    );
+    if let Some(temp_dir) = &args.temp_dir {
+        sorter = sorter.temp_dir(temp_dir);
+    }
+    let summary = sorter.sort(reader, &mut writer)?;
+    writer.finish()?;
+
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("已排序:").bright_white().bold(),
+            tr!(
+                "{} 个数据包 (乱序 {} 个, 临时分段 {} 个) → {}",
+                summary.packets,
+                summary.out_of_order,
+                summary.runs,
+                args.output.display()
+            )
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,444 @@
+//! stats 子命令：输出文件统计信息
+
+use colored::*;
+use std::path::Path;
+
+use crate::cli::args::StatsArgs;
+use crate::cli::batch::{self, Align};
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::histogram::SizeHistogram;
+use pcap_viewer::core::pcap::parser::PcapParser;
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::timing::{
+    self, TimingIssueKind,
+};
+use pcap_viewer::core::protocol::decoder;
+use pcap_viewer::core::protocol::hierarchy::{
+    ProtocolHierarchy, ProtocolNode,
+};
+use pcap_viewer::core::viewer::text::{
+    horizontal_bar, pad_left_to_width, pad_to_width,
+};
+use pcap_viewer::tr;
+
+/// 直方图条形的最大宽度
+const HISTOGRAM_BAR_WIDTH: usize = 40;
+
+/// 多个文件时每个文件的统计
+struct Summary {
+    packets: usize,
+    bytes: u64,
+    duration: i128,
+}
+
+/// 运行 stats 子命令：单个文件输出完整统计，多个文件输出汇总表
+pub fn run(args: StatsArgs) -> Result<()> {
+    let files = batch::expand(&args.files)?;
+    if let [file_path] = files.as_slice() {
+        return run_file(&args, file_path);
+    }
+    batch::single_file_only(
+        "--histogram/--gaps/--jitter",
+        args.histogram.is_some()
+            || args.gaps.is_some()
+            || args.jitter,
+    )?;
+
+    let results =
+        batch::process(&files, args.batch.jobs(), |path| {
+            let parser = open(&args, path)?;
+            let duration = match (
+                parser.packets().first(),
+                parser.packets().last(),
+            ) {
+                (Some(first), Some(last)) => {
+                    last.header.timestamp_nanos()
+                        - first.header.timestamp_nanos()
+                }
+                _ => 0,
+            };
+            Ok(Summary {
+                packets: parser.packets().len(),
+                bytes: parser
+                    .packets()
+                    .iter()
+                    .map(|packet| {
+                        packet.header.packet_length as u64
+                    })
+                    .sum(),
+                duration,
+            })
+        });
+
+    let columns = [
+        (tr!("文件"), Align::Left),
+        (tr!("数据包"), Align::Right),
+        (tr!("负载字节"), Align::Right),
+        (tr!("时长 (秒)"), Align::Right),
+    ];
+    let rows: Vec<Vec<String>> = files
+        .iter()
+        .zip(&results)
+        .map(|(path, result)| {
+            let file = path.display().to_string();
+            match result {
+                Ok(summary) => vec![
+                    file,
+                    summary.packets.to_string(),
+                    summary.bytes.to_string(),
+                    format_seconds(summary.duration),
+                ],
+                Err(e) => vec![file, batch::error_cell(e)],
+            }
+        })
+        .collect();
+    batch::print_table(&columns, &rows);
+
+    if !quiet() {
+        let summaries: Vec<&Summary> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .collect();
+        println!(
+            "{} {}",
+            tr!("总计:").bright_white().bold(),
+            tr!(
+                "{} 个文件, {} 个数据包, {} 字节负载{}",
+                files.len(),
+                summaries
+                    .iter()
+                    .map(|summary| summary.packets)
+                    .sum::<usize>(),
+                summaries
+                    .iter()
+                    .map(|summary| summary.bytes)
+                    .sum::<u64>(),
+                batch::failure_note(&results)
+            )
+        );
+    }
+    batch::exit_on_failure(&results);
+    Ok(())
+}
+
+/// 按参数解析一个文件
+fn open(
+    args: &StatsArgs,
+    file_path: &Path,
+) -> Result<PcapParser> {
+    let record = match &args.spec {
+        Some(path) => RecordSpec::load(path)?,
+        None => RecordSpec::default(),
+    };
+    PcapParser::builder()
+        .mode(args.parse_mode())
+        .checksum(args.checksum)
+        .record(record)
+        .sample(args.sample)
+        .open(file_path)
+}
+
+/// 输出单个文件的完整统计
+fn run_file(
+    args: &StatsArgs,
+    file_path: &Path,
+) -> Result<()> {
+    ensure_file_exists(file_path);
+
+    let parser = open(args, file_path)?;
+    let file_data = std::fs::read(file_path)?;
+
+    // 逐个解码数据包负载并累计协议栈
+    let mut hierarchy = ProtocolHierarchy::new();
+    for packet in parser.packet_refs(&file_data) {
+        let decoded = decoder::decode(
+            args.linktype,
+            packet.payload(),
+        );
+        hierarchy.add(
+            &decoded.layers,
+            packet.header().packet_length as usize,
+        );
+    }
+
+    println!(
+        "{} {}",
+        tr!("文件:").bright_white().bold(),
+        file_path.display()
+    );
+    println!(
+        "{} {}",
+        tr!("总计:").bright_white().bold(),
+        tr!(
+            "{} 个数据包, {} 字节负载 (链路层: {})",
+            hierarchy.total_packets(),
+            hierarchy.total_bytes(),
+            args.linktype
+        )
+    );
+    if let Some(sample) = args.sample {
+        println!(
+            "{} {}",
+            tr!("抽样:").bright_white().bold(),
+            tr!(
+                "{} ({} / {} 个数据包，结果为近似值)",
+                sample,
+                parser.packets().len(),
+                parser.packets_seen()
+            )
+        );
+    }
+    println!();
+
+    print_hierarchy(&hierarchy);
+
+    if let Some(bucket_size) = args.histogram {
+        println!();
+        let histogram = SizeHistogram::compute(
+            parser.packets(),
+            bucket_size,
+        );
+        for line in
+            histogram_lines(&histogram, HISTOGRAM_BAR_WIDTH)
+        {
+            println!("{}", line);
+        }
+    }
+
+    if let Some(threshold) = args.gaps {
+        println!();
+        print_timing_issues(&parser, threshold);
+    }
+
+    if args.jitter {
+        println!();
+        print_interval_stats(&parser);
+    }
+    Ok(())
+}
+
+/// 生成负载长度直方图的各行（标题、表头与每个分桶一行）
+pub fn histogram_lines(
+    histogram: &SizeHistogram,
+    bar_width: usize,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "{} {}",
+            tr!("负载长度分布").bright_white().bold(),
+            tr!("(分桶 {} 字节)", histogram.bucket_size())
+        ),
+        format!(
+            "{} {} {}",
+            pad_left_to_width(tr!("长度 (字节)"), 15),
+            pad_left_to_width(tr!("数据包"), 10),
+            pad_left_to_width(tr!("占比"), 8)
+        ),
+        "-".repeat(35 + bar_width),
+    ];
+
+    let max_count = histogram.max_count();
+    for (range, count) in histogram.buckets() {
+        lines.push(format!(
+            "{:>15} {:>10} {:>7.1}% {}",
+            format!("{}-{}", range.start, range.end - 1),
+            count,
+            percentage(count, histogram.total()),
+            horizontal_bar(count, max_count, bar_width)
+                .bright_cyan()
+        ));
+    }
+    if histogram.total() == 0 {
+        lines.push(tr!("(无数据包)").to_string());
+    }
+    lines
+}
+
+/// 输出时间戳异常列表
+fn print_timing_issues(
+    parser: &PcapParser,
+    threshold: i128,
+) {
+    let issues =
+        timing::find_issues(parser.packets(), threshold);
+    let backwards = issues
+        .iter()
+        .filter(|issue| {
+            issue.kind == TimingIssueKind::Backwards
+        })
+        .count();
+
+    println!(
+        "{} {}",
+        tr!("时间戳异常").bright_white().bold(),
+        tr!("(间隔阈值 {} 秒)", format_seconds(threshold))
+    );
+    if issues.is_empty() {
+        println!("{}", tr!("未发现时间戳异常"));
+        return;
+    }
+    println!(
+        "{}",
+        tr!(
+            "{} 处间隔, {} 处时间倒退",
+            issues.len() - backwards,
+            backwards
+        )
+    );
+    println!(
+        "{} {} {} {}",
+        pad_left_to_width(tr!("序号"), 10),
+        pad_left_to_width(tr!("偏移"), 12),
+        pad_to_width(tr!("类型"), 8),
+        pad_left_to_width(tr!("时间差 (秒)"), 22)
+    );
+    println!("{}", "-".repeat(72));
+
+    for issue in &issues {
+        let (kind, delta) = match issue.kind {
+            TimingIssueKind::Gap => (
+                tr!("间隔"),
+                format_seconds(issue.delta_nanos)
+                    .bright_yellow(),
+            ),
+            TimingIssueKind::Backwards => (
+                tr!("倒退"),
+                format_seconds(issue.delta_nanos)
+                    .bright_red(),
+            ),
+        };
+        println!(
+            "{:>10} {:>12} {} {:>22}",
+            format!("#{}", issue.index + 1),
+            format!("0x{:08X}", issue.offset),
+            pad_to_width(kind, 8),
+            delta
+        );
+    }
+}
+
+/// 输出到达间隔与抖动的分位数
+fn print_interval_stats(parser: &PcapParser) {
+    println!(
+        "{}",
+        tr!("到达间隔与抖动").bright_white().bold()
+    );
+    let Some(stats) =
+        timing::interval_stats(parser.packets())
+    else {
+        println!(
+            "{}",
+            tr!("数据包不足两个，无法计算到达间隔")
+        );
+        return;
+    };
+    println!(
+        "{}",
+        tr!(
+            "{} 个间隔, 平均 {} 秒, 标准差 {} 秒, 最小 {} 秒",
+            stats.count,
+            format_seconds(stats.mean.round() as i128),
+            format!("{:.9}", stats.stddev / 1e9),
+            format_seconds(stats.min)
+        )
+    );
+    println!(
+        "{} {} {}",
+        pad_to_width(tr!("分位数"), 8),
+        pad_left_to_width(tr!("间隔 (秒)"), 22),
+        pad_left_to_width(tr!("抖动 (秒)"), 22)
+    );
+    println!("{}", "-".repeat(54));
+
+    let rows = [
+        ("P50", stats.interval.p50, stats.jitter.p50),
+        ("P90", stats.interval.p90, stats.jitter.p90),
+        ("P99", stats.interval.p99, stats.jitter.p99),
+        (tr!("最大"), stats.interval.max, stats.jitter.max),
+    ];
+    for (label, interval, jitter) in rows {
+        println!(
+            "{} {:>22} {:>22}",
+            pad_to_width(label, 8),
+            format_seconds(interval),
+            format_seconds(jitter).bright_yellow()
+        );
+    }
+}
+
+/// 将纳秒格式化为带符号的秒
+pub fn format_seconds(nanos: i128) -> String {
+    let sign = if nanos < 0 { "-" } else { "+" };
+    let nanos = nanos.unsigned_abs();
+    format!(
+        "{}{}.{:09}",
+        sign,
+        nanos / 1_000_000_000,
+        nanos % 1_000_000_000
+    )
+}
+
+/// 输出协议层级统计表
+fn print_hierarchy(hierarchy: &ProtocolHierarchy) {
+    let total_packets = hierarchy.total_packets();
+    let total_bytes = hierarchy.total_bytes();
+
+    println!(
+        "{}",
+        tr!("协议层级统计").bright_white().bold()
+    );
+    println!(
+        "{} {} {} {} {}",
+        pad_to_width(tr!("协议"), 28),
+        pad_left_to_width(tr!("数据包"), 10),
+        pad_left_to_width(tr!("占比"), 8),
+        pad_left_to_width(tr!("字节"), 12),
+        pad_left_to_width(tr!("占比"), 8)
+    );
+    println!("{}", "-".repeat(72));
+
+    for node in hierarchy.clone().finish() {
+        print_node(&node, 0, total_packets, total_bytes);
+    }
+}
+
+/// 递归输出层级节点
+fn print_node(
+    node: &ProtocolNode,
+    depth: usize,
+    total_packets: usize,
+    total_bytes: u64,
+) {
+    let name =
+        format!("{}{}", "  ".repeat(depth), node.name);
+    println!(
+        "{:<28} {:>10} {:>7.1}% {:>12} {:>7.1}%",
+        name,
+        node.packets,
+        percentage(
+            node.packets as u64,
+            total_packets as u64
+        ),
+        node.bytes,
+        percentage(node.bytes, total_bytes)
+    );
+
+    for child in &node.children {
+        print_node(
+            child,
+            depth + 1,
+            total_packets,
+            total_bytes,
+        );
+    }
+}
+
+/// 计算百分比（总数为 0 时返回 0）
+pub fn percentage(value: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        value as f64 * 100.0 / total as f64
+    }
+}
@@ -0,0 +1,70 @@
+//! timeshift 子命令：平移数据包时间戳
+
+use colored::*;
+
+use crate::cli::args::TimeshiftArgs;
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::parser::{
+    PcapParser, FILE_HEADER_SIZE,
+};
+use pcap_viewer::core::pcap::timeshift::{self, TimeShift};
+use pcap_viewer::core::pcap::writer::PcapWriter;
+use pcap_viewer::tr;
+
+/// 运行 timeshift 子命令
+pub fn run(args: TimeshiftArgs) -> Result<()> {
+    ensure_file_exists(&args.file);
+
+    let shift = match (args.offset, args.rebase) {
+        (Some(offset), _) => TimeShift::Offset(offset),
+        (None, Some(time)) => TimeShift::Rebase(time),
+        // clap 保证二者必有其一
+        (None, None) => unreachable!(),
+    };
+
+    let file_data = std::fs::read(&args.file)?;
+    let parser = PcapParser::from_bytes(&file_data)?;
+    let offset = shift.offset_nanos(
+        parser
+            .packets()
+            .first()
+            .map(|packet| &packet.header),
+    );
+
+    let file_header =
+        parser.file_header().cloned().unwrap_or_default();
+    let mut writer = PcapWriter::create_with_header(
+        &args.output,
+        &file_header,
+    )?;
+    let mut end_offset =
+        file_data.len().min(FILE_HEADER_SIZE);
+    for packet in parser.packet_refs(&file_data) {
+        let header = timeshift::shift_header(
+            packet.header(),
+            offset,
+        )?;
+        writer
+            .write_raw_packet(&header, packet.payload())?;
+        end_offset = packet.end_offset();
+    }
+    // 保留最后一个数据包之后无法解析的数据
+    writer.write_raw_bytes(&file_data[end_offset..])?;
+    let packets_written = writer.packets_written();
+    writer.finish()?;
+
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("已平移:").bright_white().bold(),
+            tr!(
+                "{} 个数据包 (偏移 {} 秒) → {}",
+                packets_written,
+                format!("{:+.9}", offset as f64 / 1e9),
+                args.output.display()
+            )
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! truncate 子命令：截断数据包负载
+
+use colored::*;
+
+use crate::cli::args::TruncateArgs;
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::builder::ParserOptions;
+use pcap_viewer::core::pcap::parser::{
+    PcapParser, FILE_HEADER_SIZE,
+};
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::writer::PcapWriter;
+use pcap_viewer::tr;
+
+/// 运行 truncate 子命令
+pub fn run(args: TruncateArgs) -> Result<()> {
+    ensure_file_exists(&args.file);
+
+    let record = match &args.spec {
+        Some(path) => RecordSpec::load(path)?,
+        None => RecordSpec::default(),
+    };
+    let file_data = std::fs::read(&args.file)?;
+    let parser = PcapParser::from_bytes_with_options(
+        &file_data,
+        ParserOptions {
+            record,
+            ..ParserOptions::default()
+        },
+    )?;
+
+    let checksum = args.checksum.resolve(
+        parser.packet_refs(&file_data).map(|packet| {
+            (packet.payload(), packet.header().checksum)
+        }),
+    );
+    // 输出沿用源文件的文件头、字节序与数据包头布局
+    let mut writer = PcapWriter::create_with_raw_header(
+        &args.output,
+        &file_data[..FILE_HEADER_SIZE],
+    )?
+    .with_checksum(checksum);
+    let mut truncated = 0;
+    for packet in parser.packet_refs(&file_data) {
+        let payload = packet.payload();
+        let kept =
+            &payload[..payload.len().min(args.snaplen)];
+        if kept.len() == payload.len() {
+            writer.copy_packet(&packet)?;
+            continue;
+        }
+        truncated += 1;
+        // 重新计算长度；原校验和已不一致的保留原值，不因截断而“修复”
+        writer.write_packet_as(
+            packet.header_bytes(),
+            &parser.options().record,
+            parser.endianness(),
+            kept,
+            checksum
+                .matches(payload, packet.header().checksum),
+        )?;
+    }
+    let packets_written = writer.packets_written();
+    let bytes_written = writer.bytes_written();
+    writer.finish()?;
+
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("已截断:").bright_white().bold(),
+            tr!(
+                "{} 个数据包 (截断 {} 个)，{} → {} 字节 → {}",
+                packets_written,
+                truncated,
+                file_data.len(),
+                bytes_written,
+                args.output.display()
+            )
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! validate 子命令：按校验规则检查文件，列出所有问题，发现问题时退出码为 4
+
+use colored::*;
+use std::path::Path;
+
+use crate::cli::args::ValidateArgs;
+use crate::cli::batch::{self, Align};
+#[cfg(feature = "hash")]
+use crate::cli::commands::info::print_hashes;
+use crate::cli::exit;
+use crate::cli::hex_viewer::diagnostic_label;
+use crate::cli::{ensure_file_exists, quiet};
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::builder::{
+    ParseMode, ParserOptions,
+};
+use pcap_viewer::core::pcap::diagnostics::{
+    self, Diagnostic,
+};
+use pcap_viewer::core::pcap::parser::PcapParser;
+use pcap_viewer::core::pcap::rules::{
+    LengthBoundsRule, RuleSet,
+};
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::tr;
+
+/// 多个文件时每个文件的校验结果
+struct Outcome {
+    packets: usize,
+    problems: usize,
+    digest: Option<String>,
+}
+
+/// 运行 validate 子命令：单个文件列出所有问题，多个文件输出汇总表
+pub fn run(args: ValidateArgs) -> Result<()> {
+    let files = batch::expand(&args.files)?;
+    if let [file_path] = files.as_slice() {
+        return run_file(&args, file_path);
+    }
+    #[cfg(feature = "hash")]
+    batch::single_file_only(
+        "--packet-hashes",
+        args.hash.packet_hashes.is_some(),
+    )?;
+
+    let results =
+        batch::process(&files, args.batch.jobs(), |path| {
+            let (file_data, parser, found) =
+                check(&args, path)?;
+            #[cfg(feature = "hash")]
+            let digest = args.hash.hash.map(|algorithm| {
+                algorithm.digest(&file_data)
+            });
+            #[cfg(not(feature = "hash"))]
+            let digest = {
+                let _ = file_data;
+                None
+            };
+            Ok(Outcome {
+                packets: parser.packets().len(),
+                problems: found.len(),
+                digest,
+            })
+        });
+
+    #[cfg_attr(not(feature = "hash"), allow(unused_mut))]
+    let mut columns = vec![
+        (tr!("文件"), Align::Left),
+        (tr!("数据包"), Align::Right),
+        (tr!("问题"), Align::Right),
+        (tr!("结果"), Align::Left),
+    ];
+    #[cfg(feature = "hash")]
+    let hash_title = args.hash.hash.map(|a| a.to_string());
+    #[cfg(feature = "hash")]
+    if let Some(title) = &hash_title {
+        columns.push((title, Align::Left));
+    }
+    let rows: Vec<Vec<String>> = files
+        .iter()
+        .zip(&results)
+        .map(|(path, result)| {
+            let file = path.display().to_string();
+            match result {
+                Ok(outcome) => [
+                    file,
+                    outcome.packets.to_string(),
+                    outcome.problems.to_string(),
+                    if outcome.problems == 0 {
+                        tr!("通过")
+                            .bright_green()
+                            .to_string()
+                    } else {
+                        tr!("失败").bright_red().to_string()
+                    },
+                ]
+                .into_iter()
+                .chain(outcome.digest.clone())
+                .collect(),
+                Err(e) => vec![file, batch::error_cell(e)],
+            }
+        })
+        .collect();
+    batch::print_table(&columns, &rows);
+
+    let failed = results
+        .iter()
+        .filter_map(|result| result.as_ref().ok())
+        .filter(|outcome| outcome.problems > 0)
+        .count();
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("总计:").bright_white().bold(),
+            tr!(
+                "{} 个文件, 通过 {} 个, 失败 {} 个{}",
+                files.len(),
+                results
+                    .iter()
+                    .filter(|r| r.is_ok())
+                    .count()
+                    - failed,
+                failed,
+                batch::failure_note(&results)
+            )
+        );
+    }
+    batch::exit_on_failure(&results);
+    if failed > 0 {
+        exit::exit(exit::VALIDATION);
+    }
+    Ok(())
+}
+
+/// 按校验规则检查一个文件，返回(文件内容, 解析结果, 发现的问题)
+fn check(
+    args: &ValidateArgs,
+    file_path: &Path,
+) -> Result<(Vec<u8>, PcapParser, Vec<Diagnostic>)> {
+    let record = match &args.spec {
+        Some(path) => RecordSpec::load(path)?,
+        None => RecordSpec::default(),
+    };
+    let file_data = std::fs::read(file_path)?;
+    // 宽松模式并校验校验和，尽量找出所有问题
+    let options = ParserOptions {
+        checksum: args.checksum,
+        record,
+        verify_crc: true,
+        ..ParserOptions::for_mode(ParseMode::Lenient)
+    };
+    let parser =
+        PcapParser::from_bytes_or_raw(&file_data, options)?;
+
+    let mut rules = RuleSet::builtin();
+    if args.min_len.is_some() || args.max_len.is_some() {
+        rules.register(LengthBoundsRule {
+            min: args.min_len.unwrap_or(0),
+            max: args.max_len.unwrap_or(u32::MAX),
+        });
+    }
+    let found = diagnostics::diagnose_with(
+        &file_data, &parser, &rules,
+    );
+    Ok((file_data, parser, found))
+}
+
+/// 检查单个文件并列出所有问题
+fn run_file(
+    args: &ValidateArgs,
+    file_path: &Path,
+) -> Result<()> {
+    ensure_file_exists(file_path);
+
+    let (file_data, parser, found) =
+        check(args, file_path)?;
+
+    #[cfg(feature = "hash")]
+    print_hashes(&args.hash, &file_data, &parser)?;
+    #[cfg(not(feature = "hash"))]
+    let _ = file_data;
+
+    for diagnostic in &found {
+        println!(
+            "0x{:08X} {}",
+            diagnostic.offset,
+            diagnostic_label(diagnostic)
+        );
+    }
+    if found.is_empty() {
+        if !quiet() {
+            println!(
+                "{} {}",
+                tr!("通过").bright_green().bold(),
+                tr!(
+                    "{} 个数据包，未发现问题",
+                    parser.packets().len()
+                )
+            );
+        }
+        return Ok(());
+    }
+    if !quiet() {
+        println!(
+            "{} {}",
+            tr!("失败").bright_red().bold(),
+            tr!(
+                "{} 个数据包，发现 {} 个问题",
+                parser.packets().len(),
+                found.len()
+            )
+        );
+    }
+    exit::exit(exit::VALIDATION);
+}
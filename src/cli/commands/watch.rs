@@ -0,0 +1,101 @@
+//! watch 子命令：监视录制目录，以跟随模式打开最新的抓包文件，
+//! 录制程序滚动到新文件时切换过去
+
+use std::thread;
+use std::time::Duration;
+
+use colored::*;
+
+use crate::cli::args::{CliArgs, WatchArgs};
+use crate::cli::{quiet, run_viewer};
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::ring;
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::pcap::timing;
+use pcap_viewer::core::source::watch::WatchDir;
+use pcap_viewer::tr;
+
+/// 目录中还没有抓包文件时检查的间隔
+const WAIT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 运行 watch 子命令
+pub fn run(args: WatchArgs) -> Result<()> {
+    if !args.dir.is_dir() {
+        return Err(PcapViewerError::Usage(format!(
+            "Not a directory: {}",
+            args.dir.display()
+        ))
+        .into());
+    }
+    let watch = WatchDir {
+        dir: args.dir.clone(),
+        extension: args
+            .ext
+            .trim_start_matches('.')
+            .to_string(),
+        ask: args.ask,
+    };
+
+    // 等待录制程序写出第一个文件
+    let mut waiting = false;
+    let file_path = loop {
+        if let Some(path) = watch.newest()? {
+            break path;
+        }
+        if !waiting {
+            if !quiet() {
+                eprintln!(
+                    "{} {}",
+                    tr!("提示").yellow().bold(),
+                    tr!(
+                        "{} 中还没有 .{} 文件，等待录制开始 (Ctrl-C 退出)",
+                        args.dir.display(),
+                        watch.extension
+                    )
+                );
+            }
+            waiting = true;
+        }
+        thread::sleep(WAIT_INTERVAL);
+    };
+
+    run_viewer(CliArgs {
+        file_path,
+        more_files: Vec::new(),
+        linktype: args.linktype,
+        bytes_per_line: None,
+        strict: false,
+        lenient: false,
+        checksum: args.checksum,
+        spec: None,
+        utc: false,
+        time_format: None,
+        follow: true,
+        ring_size: ring::DEFAULT_CAPACITY as u32,
+        record_to: None,
+        ring: None,
+        delta: false,
+        accessible: false,
+        no_alt_screen: false,
+        gap_threshold: timing::DEFAULT_GAP_THRESHOLD,
+        diff: None,
+        baseline: None,
+        fresh: true,
+        lines: None,
+        head: None,
+        tail: None,
+        sample: None,
+        range: None,
+        packets: None,
+        paginate: false,
+        no_pager: false,
+        repeat_accel: None,
+        #[cfg(feature = "scripting")]
+        script: None,
+        config: args.config,
+        watch: Some(watch),
+        record: RecordSpec::default(),
+    })
+}
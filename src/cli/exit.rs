@@ -0,0 +1,46 @@
+//! 进程退出码约定，供脚本按结果分支：
+//! 0 成功，1 用法错误（及其他失败），2 文件不存在，3 解析错误，4 校验未通过
+
+use std::io;
+
+use pcap_viewer::app::error::types::PcapViewerError;
+
+/// 成功
+pub const SUCCESS: u8 = 0;
+/// 命令行用法错误（配置错误等其他失败同样使用）
+pub const USAGE: u8 = 1;
+/// 文件不存在
+pub const NOT_FOUND: u8 = 2;
+/// 文件格式或内容解析错误
+pub const PARSE: u8 = 3;
+/// validate 发现问题
+pub const VALIDATION: u8 = 4;
+
+/// 错误对应的退出码
+pub fn code_for(error: &anyhow::Error) -> u8 {
+    for cause in error.chain() {
+        match cause.downcast_ref::<PcapViewerError>() {
+            Some(
+                PcapViewerError::InvalidFormat(_)
+                | PcapViewerError::Parse(_),
+            ) => return PARSE,
+            Some(PcapViewerError::Io(e))
+                if e.kind() == io::ErrorKind::NotFound =>
+            {
+                return NOT_FOUND;
+            }
+            _ => {}
+        }
+        if cause.downcast_ref::<io::Error>().is_some_and(
+            |e| e.kind() == io::ErrorKind::NotFound,
+        ) {
+            return NOT_FOUND;
+        }
+    }
+    USAGE
+}
+
+/// 以指定退出码结束进程
+pub fn exit(code: u8) -> ! {
+    std::process::exit(code.into())
+}
@@ -0,0 +1,183 @@
+//! 字节频率视图：当前数据包负载或选中范围的字节值分布与熵
+
+use colored::*;
+
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::viewer::frequency::ByteFrequency;
+use pcap_viewer::core::viewer::layout::Region;
+use pcap_viewer::core::viewer::pagination::PaginationState;
+use pcap_viewer::core::viewer::text::horizontal_bar;
+use pcap_viewer::tr;
+
+/// 条形的最大宽度
+const BAR_WIDTH: usize = 40;
+
+/// 由低到高的方块字符（频率网格）
+const BLOCKS: [char; 8] =
+    ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 高于此熵（位/字节）时认为是压缩或加密数据
+const HIGH_ENTROPY: f64 = 7.5;
+
+/// 低于此熵（位/字节）时认为是结构化或文本数据
+const LOW_ENTROPY: f64 = 5.0;
+
+/// 字节频率视图（打开时的快照）
+pub struct FrequencyView {
+    source: String,
+    lines: Vec<String>,
+    pagination: PaginationState,
+}
+
+impl FrequencyView {
+    /// 统计数据并创建视图，`source` 描述数据来源
+    pub fn new(
+        data: &[u8],
+        source: String,
+        lines_per_page: usize,
+    ) -> Self {
+        let frequency = ByteFrequency::compute(data);
+        let lines = frequency_lines(&frequency, &source);
+        let mut pagination =
+            PaginationState::new(lines_per_page, 0);
+        pagination.update_total_lines(lines.len());
+        Self {
+            source,
+            lines,
+            pagination,
+        }
+    }
+
+    /// 获取分页状态
+    pub fn pagination_mut(
+        &mut self,
+    ) -> &mut PaginationState {
+        &mut self.pagination
+    }
+
+    /// 绘制视图（内容页 + 底部信息）
+    pub fn render(
+        &self,
+        content: &mut Region<'_>,
+        footer: &mut Region<'_>,
+    ) -> Result<()> {
+        let start_line =
+            self.pagination.display_start_line();
+        let end_line = (start_line
+            + self.pagination.lines_per_page())
+        .min(self.lines.len());
+
+        for line in &self.lines[start_line..end_line] {
+            content.push(line.as_str());
+        }
+
+        footer.push_bar(&tr!(
+            " 字节频率 │ {} │ 第 {} 行 / 共 {} 行",
+            self.source,
+            start_line + 1,
+            self.lines.len()
+        ));
+        footer.push(
+            tr!("↑↓ ←→ Home/End | ESC/F 返回")
+                .bright_black()
+                .to_string(),
+        );
+
+        Ok(())
+    }
+}
+
+/// 生成视图内容：熵、16×16 频率网格与每个字节值一行的条形图
+fn frequency_lines(
+    frequency: &ByteFrequency,
+    source: &str,
+) -> Vec<String> {
+    let entropy = frequency.entropy();
+    let verdict = if frequency.total() == 0 {
+        tr!("无数据").normal()
+    } else if entropy >= HIGH_ENTROPY {
+        tr!("高熵，可能是压缩或加密数据").bright_red()
+    } else if entropy < LOW_ENTROPY {
+        tr!("低熵，结构化或文本数据").bright_green()
+    } else {
+        tr!("中等熵").bright_yellow()
+    };
+    let mut lines = vec![
+        tr!(
+            "{} {} ({} 字节)",
+            tr!("来源:").bright_white().bold(),
+            source,
+            frequency.total()
+        ),
+        tr!(
+            "{} {} 位/字节，{} 种字节值 / 256 → {}",
+            tr!("熵:").bright_white().bold(),
+            format!("{:.3}", entropy),
+            frequency.distinct(),
+            verdict
+        ),
+        String::new(),
+        format!(
+            "{}",
+            tr!("频率网格 (行为高 4 位，列为低 4 位)")
+                .bright_white()
+                .bold()
+        ),
+        format!(
+            "      {}",
+            (0..16)
+                .map(|low| format!("{:X}", low))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+    ];
+
+    let max_count = frequency.max_count();
+    for high in 0..16u8 {
+        let cells: Vec<String> = (0..16u8)
+            .map(|low| {
+                let count =
+                    frequency.count(high << 4 | low);
+                if count == 0 {
+                    "·".bright_black().to_string()
+                } else {
+                    let level = ((count * 7)
+                        / max_count.max(1))
+                        as usize;
+                    BLOCKS[level]
+                        .to_string()
+                        .bright_cyan()
+                        .to_string()
+                }
+            })
+            .collect();
+        lines.push(format!(
+            "  {:X}0  {}",
+            high,
+            cells.join(" ")
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{}",
+        tr!("各字节值出现次数").bright_white().bold()
+    ));
+    for byte in 0..=255u8 {
+        let count = frequency.count(byte);
+        lines.push(format!(
+            "0x{:02X} {:>10} {:>6.2}% {}",
+            byte,
+            count,
+            if frequency.total() == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0
+                    / frequency.total() as f64
+            },
+            horizontal_bar(count, max_count, BAR_WIDTH)
+                .bright_cyan()
+        ));
+    }
+    lines
+}
@@ -1,48 +1,292 @@
 //! 十六进制查看器
 
-use chrono::DateTime;
 use colored::*;
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
-use std::io::{self, Write};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyModifiers,
+    MouseButton, MouseEvent, MouseEventKind,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind, IsTerminal};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use crate::app::error::types::Result;
 use crate::cli::args::CliArgs;
-use crate::core::input::keyboard::KeyboardHandler;
-use crate::core::pcap::parser::{
-    DataPacket, PcapFileHeader, PcapParser,
+use crate::cli::frequency_view::FrequencyView;
+use crate::cli::loading;
+use crate::cli::recording::Recording;
+use crate::cli::stats_view::StatsView;
+use crate::cli::stream_view::StreamView;
+use crate::cli::{pager, quiet};
+use pcap_viewer::app::error::types::{ParseError, Result};
+use pcap_viewer::app::i18n::text;
+use pcap_viewer::core::dissect::field::Dissector;
+use pcap_viewer::core::input::count::{
+    CountInput, CountPrefix,
+};
+use pcap_viewer::core::input::history::History;
+use pcap_viewer::core::input::keyboard::KeyboardHandler;
+use pcap_viewer::core::input::keymap::Motion;
+use pcap_viewer::core::input::line_editor::LineEditor;
+use pcap_viewer::core::pcap::diagnostics::{
+    self, Diagnostic, DiagnosticKind,
+};
+use pcap_viewer::core::pcap::filter::{
+    FilterSpec, PacketFilter,
+};
+use pcap_viewer::core::pcap::format;
+use pcap_viewer::core::pcap::histogram::DEFAULT_BUCKET_SIZE;
+use pcap_viewer::core::pcap::indexer::BackgroundIndexer;
+use pcap_viewer::core::pcap::locate::PacketTarget;
+use pcap_viewer::core::pcap::packet_ref::PacketRef;
+use pcap_viewer::core::pcap::parser::{
+    DataPacket, PacketLimit, PcapFileHeader, PcapParser,
+    FILE_HEADER_SIZE,
+};
+use pcap_viewer::core::pcap::ring::PacketStream;
+use pcap_viewer::core::pcap::spec::RecordField;
+use pcap_viewer::core::pcap::timeshift::{self, TimeShift};
+use pcap_viewer::core::pcap::timing::{
+    self, TimingIssueKind,
+};
+use pcap_viewer::core::pcap::writer::PcapWriter;
+use pcap_viewer::core::protocol::decoder;
+use pcap_viewer::core::protocol::tcp_stream::{
+    StreamKey, StreamReassembler,
+};
+use pcap_viewer::core::range::{
+    parse_offset, OffsetRange, PacketList,
+};
+use pcap_viewer::core::source;
+use pcap_viewer::core::source::search::{
+    Search, SearchPattern, SearchStep,
+};
+use pcap_viewer::core::source::DataSource;
+use pcap_viewer::core::viewer::annotation::Annotations;
+use pcap_viewer::core::viewer::bookmark::Bookmarks;
+use pcap_viewer::core::viewer::columns::{
+    Column, ColumnLayout, ColumnSpec,
+};
+use pcap_viewer::core::viewer::diff;
+use pcap_viewer::core::viewer::edit::{
+    EditState, InputMode,
+};
+use pcap_viewer::core::viewer::file_data::FileData;
+use pcap_viewer::core::viewer::fold::{Folds, Row};
+use pcap_viewer::core::viewer::frequency::ByteFrequency;
+use pcap_viewer::core::viewer::heatmap::{
+    self, ByteCategory, ColorMode,
+};
+use pcap_viewer::core::viewer::highlight::{
+    parse_hex_pattern, HighlightColor, HighlightPattern,
+    Highlighter,
+};
+use pcap_viewer::core::viewer::jump_list::JumpList;
+use pcap_viewer::core::viewer::layout::{
+    self, Constraint, Frame, Rect, Region,
+};
+use pcap_viewer::core::viewer::line_cache::LineCache;
+use pcap_viewer::core::viewer::literal::CopyFormat;
+use pcap_viewer::core::viewer::minimap::{
+    Minimap, MinimapMark,
+};
+use pcap_viewer::core::viewer::pagination::PaginationState;
+use pcap_viewer::core::viewer::palette::{Palette, Shade};
+use pcap_viewer::core::viewer::session::{
+    SavedHighlight, StateStore, ViewerState,
+};
+use pcap_viewer::core::viewer::sparkline::{
+    sparkline, Throughput,
 };
-use crate::core::viewer::pagination::PaginationState;
-use crate::core::viewer::terminal::TerminalManager;
+use pcap_viewer::core::viewer::terminal::TerminalManager;
+use pcap_viewer::core::viewer::text::{
+    display_width, fit_styled_to_width, pad_to_width,
+    skip_styled_width, styled_display_width,
+    truncate_styled_to_width, truncate_to_width,
+    wrap_items,
+};
+use pcap_viewer::core::viewer::timestamp::TimestampStyle;
+use pcap_viewer::core::viewer::workspace::{
+    Workspace, WorkspaceFile, WORKSPACE_EXTENSION,
+};
+use pcap_viewer::tr;
 
 /// 十六进制查看器
 pub struct HexViewer {
     parser: PcapParser,
     args: CliArgs,
-    file_data: Vec<u8>,
+    file_data: FileData,
     // 模块化组件
     terminal_manager: TerminalManager,
     keyboard_handler: KeyboardHandler,
+    count_prefix: CountPrefix, // 正在输入的计数前缀
     pagination: PaginationState,
+    dissector: Option<Box<dyn Dissector>>, // 自定义负载解析器
+    stream_view: Option<StreamView>, // TCP 流跟踪视图（打开时替代十六进制视图）
+    stats_view: Option<StatsView>, // 统计视图（打开时替代十六进制视图）
+    frequency_view: Option<FrequencyView>, // 字节频率视图（打开时替代十六进制视图）
+    // 编辑状态
+    edit: EditState,
+    editing: bool,          // 是否处于编辑模式
+    prompt: Option<Prompt>, // 底部输入框（打开时接管按键）
+    show_help: bool, // 是否显示按键帮助（任意键关闭）
+    show_legend: bool, // 是否显示颜色图例（L 打开，任意键关闭）
+    backed_up: HashSet<PathBuf>, // 本次会话中已备份过的文件
+    confirm_quit: bool, // 有未保存修改时，再次按退出键才真正退出
+    jump_list: JumpList, // 跳转历史（Ctrl-O 后退 / Tab 前进）
+    show_timing: bool,   // 是否标记时间间隔与时间倒退
+    show_throughput: bool, // 是否在顶部显示吞吐量面板
+    show_minimap: bool,  // 是否在右侧显示小地图（滚动条列）
+    columns: ColumnLayout, // 十六进制视图的列布局（:columns、#、A 与 T 切换）
+    dual_columns: bool, // 终端足够宽时并排显示两栏（W 切换）
+    h_scroll: usize, // 横向滚动的列数（行宽超出终端宽度时 Shift+←/→）
+    relative_offsets: bool, // 偏移列显示相对于数据包负载起始的偏移（O 切换）
+    color_mode: ColorMode,  // 十六进制区着色方式
+    palette: Palette,       // 按终端色深选择的区域着色
+    highlighter: Highlighter, // 用户自定义的字节模式高亮
+    annotations: Annotations, // 偏移注释
+    notes_path: Option<PathBuf>, // 注释旁路文件（远程数据源时为 None）
+    bookmarks: Bookmarks,        // 书签
+    state_store: Option<StateStore>, // 查看状态存储（无法确定数据目录时为 None）
+    workspace_path: Option<PathBuf>, // 最近打开或保存的工作区文件
+    sessions: Vec<FileSession>, // 所有打开的文件（当前文件的状态位于上面各字段中）
+    active_file: usize, // 当前文件在 sessions 中的序号
+    diff: Option<DiffTarget>, // 并排比较的文件（--diff）
+    baseline: Option<DiffTarget>, // 比较基线（--baseline）
+    baseline_differences: usize, // 与基线不同的字节数
+    crc_errors: BTreeSet<usize>, // CRC 校验失败的数据包序号
+    packet_filter: Option<PacketFilter>, // 数据包过滤条件，不匹配的数据包暗色显示
+    selection: Option<Range<usize>>, // 选中的字节范围（:select）
+    indexer: Option<BackgroundIndexer>, // 后台索引任务（大文件加载完成前）
+    stream: Option<PacketStream>, // 跟随的命名管道或字符设备
+    recording: Option<Recording>, // 跟随时同时录制到的文件（--record）
+    pending_jump: Option<PendingJump>, // 目标尚未加载、加载到后再执行的跳转
+    search: Option<Search>, // 进行中的查找（逐块进行，ESC 取消）
+    folds: Folds, // 折叠的数据包负载（z 切换，:fold / :unfold）
+    last_search: Option<SearchPattern>, // 上一次查找的模式（再次打开查找框时预填）
+    search_history: History, // 查找框的输入历史（↑/↓ 翻阅）
+    saved_searches: BTreeMap<String, String>, // 命名的查找（配置文件、工作区与 :search save）
+    watch_pending: Option<PathBuf>, // watch --ask 时尚未打开的新文件
+    last_watch_check: Instant, // 上次检查监视目录的时间
+    line_cache: RefCell<LineCache<LineKey>>, // 已渲染行的缓存，滚动时复用
     // 状态管理
+    status_message: Option<String>, // 临时提示信息，下次按键时清除
     last_display_start_line: usize, // 上次显示的起始行，用于检测是否需要重绘
+    last_terminal_size: (usize, usize), // 上次的终端尺寸，用于检测宽度变化
+}
+
+/// 单个打开文件的状态，切换文件时与查看器中的对应字段交换
+#[derive(Default)]
+struct FileSession {
+    file_path: PathBuf,
+    parser: PcapParser,
+    file_data: FileData,
+    edit: EditState,
+    jump_list: JumpList,
+    crc_errors: BTreeSet<usize>,
+    highlighter: Highlighter,
+    annotations: Annotations,
+    notes_path: Option<PathBuf>,
+    bookmarks: Bookmarks,
+    selection: Option<Range<usize>>,
+    indexer: Option<BackgroundIndexer>,
+    stream: Option<PacketStream>,
+    recording: Option<Recording>,
+    pending_jump: Option<PendingJump>,
+    folds: Folds,
+    offset: usize, // 切换离开时视口起始处的字节偏移
+}
+
+/// 主界面各区域
+struct ScreenLayout {
+    banner: Rect,     // 文件头无效时的警告
+    throughput: Rect, // 吞吐量面板
+    ruler: Rect,      // 列标尺
+    page: Rect,       // 十六进制数据区
+    detail: Rect,     // 解析详情面板
+    footer: Rect,     // 提示信息、状态栏与帮助信息
+}
+
+/// 并排比较或作为基线的文件
+struct DiffTarget {
+    file_path: PathBuf,
+    data: FileData,
 }
 
+/// 行宽超出终端宽度时可左右滚动，终端不窄于该宽度即显示完整布局
+const MIN_SCROLL_WIDTH: usize = 40;
+
+/// Shift+←/→ 每次横向滚动的列数
+const H_SCROLL_STEP: usize = 8;
+
+/// 完整布局所需的最小终端高度（底部 2 行 + 列标尺 1 行 + 至少 4 行数据）
+const MIN_LAYOUT_HEIGHT: usize = 7;
+
+/// 底部占用的行数（状态栏、提示信息或按键帮助）
+const FOOTER_LINES: usize = 2;
+
+/// 解析详情面板占用的行数（标题 1 行 + 字段 3 行）
+const DETAIL_PANE_LINES: usize = 4;
+
+/// 左侧包序号栏宽度（序号 7 列 + 时间 12 列 + 2 个空格）
+const GUTTER_WIDTH: usize = 21;
+
+/// 并排显示两栏所需的最小终端宽度
+const DUAL_MIN_WIDTH: usize = 170;
+
+/// 顶部列标尺占用的行数
+const RULER_LINES: usize = 1;
+
+/// 吞吐量面板占用的行数（标题 1 行 + 折线 2 行 + 视口标记 1 行）
+const THROUGHPUT_PANEL_LINES: usize = 4;
+
+/// 吞吐量面板左侧标签宽度
+const THROUGHPUT_LABEL_WIDTH: usize = 8;
+
+/// 跟随模式下检查文件增长的间隔
+const FOLLOW_POLL_INTERVAL: Duration =
+    Duration::from_millis(200);
+
+/// 查找时每次主循环最多连续处理的时长（之后检查按键并更新进度）
+const SEARCH_STEP_BUDGET: Duration =
+    Duration::from_millis(50);
+
+/// watch 模式下检查目录中新文件的间隔
+const WATCH_POLL_INTERVAL: Duration =
+    Duration::from_secs(1);
+
+/// 渲染行缓存的容量（行数）
+const LINE_CACHE_CAPACITY: usize = 1024;
+
+/// 分页读取时在可见范围前后多取的字节数（供局部熵与跨行的高亮匹配使用）
+const FETCH_MARGIN: usize = 4096;
+
+/// 后台索引进行中时取回新数据的间隔
+const INDEX_POLL_INTERVAL: Duration =
+    Duration::from_millis(200);
+
 impl HexViewer {
-    /// 创建新的十六进制查看器
+    /// 创建新的十六进制查看器（文件内容已读入内存或按需分页读取，
+    /// 指定 `indexer` 时其余数据包由其在后台继续索引）
     pub fn new(
         parser: PcapParser,
+        file_data: FileData,
+        indexer: Option<BackgroundIndexer>,
         args: CliArgs,
     ) -> Result<Self> {
-        // 读取整个文件到内存
-        let file_data = std::fs::read(&args.file_path)?;
-
         // 创建组件
-        let terminal_manager = TerminalManager::new();
-        let keyboard_handler = KeyboardHandler::default();
+        let terminal_manager = TerminalManager::new()
+            .with_inline(args.no_alt_screen);
+        let keyboard_handler = KeyboardHandler::new(
+            args.repeat_accel(),
+            args.config.repeat_window,
+        );
 
         // 计算分页信息
-        let lines_per_page =
-            terminal_manager.calculate_display_lines(6); // 减去帮助信息占用的行数
+        let lines_per_page = terminal_manager
+            .calculate_display_lines(FOOTER_LINES); // 减去底部占用的行数
         let total_lines =
             file_data.len().div_ceil(args.bytes_per_line());
         let pagination = PaginationState::new(
@@ -50,41 +294,651 @@ impl HexViewer {
             total_lines,
         );
 
-        Ok(Self {
+        let color_mode = args.config.color_mode;
+        let dual_columns = args.config.dual_columns;
+        let show_minimap = !args.accessible; // 小地图由色块组成
+        let mut columns = args.config.columns.clone();
+        if args.delta && !columns.contains(Column::Delta) {
+            columns.toggle(Column::Delta);
+        }
+        let saved_searches = args.config.searches.clone();
+        let mut viewer = Self {
             parser,
             args,
             file_data,
             terminal_manager,
             keyboard_handler,
+            count_prefix: CountPrefix::new(),
             pagination,
+            dissector: None,
+            stream_view: None,
+            stats_view: None,
+            frequency_view: None,
+            edit: EditState::new(),
+            editing: false,
+            prompt: None,
+            show_help: false,
+            show_legend: false,
+            jump_list: JumpList::new(),
+            show_timing: false,
+            show_throughput: false,
+            show_minimap,
+            columns,
+            dual_columns,
+            h_scroll: 0,
+            relative_offsets: false,
+            color_mode,
+            palette: Palette::default(),
+            highlighter: Highlighter::new(),
+            annotations: Annotations::new(),
+            notes_path: None,
+            bookmarks: Bookmarks::new(),
+            state_store: StateStore::open_default(),
+            workspace_path: None,
+            sessions: vec![FileSession::default()],
+            active_file: 0,
+            diff: None,
+            baseline: None,
+            baseline_differences: 0,
+            crc_errors: BTreeSet::new(),
+            packet_filter: None,
+            selection: None,
+            indexer,
+            stream: None,
+            recording: None,
+            pending_jump: None,
+            search: None,
+            folds: Folds::new(),
+            last_search: None,
+            search_history: History::default(),
+            saved_searches,
+            watch_pending: None,
+            last_watch_check: Instant::now(),
+            line_cache: RefCell::new(LineCache::new(
+                LINE_CACHE_CAPACITY,
+            )),
+            backed_up: HashSet::new(),
+            confirm_quit: false,
+            status_message: None,
             last_display_start_line: usize::MAX, // 初始值设为最大值，确保第一次显示
+            last_terminal_size: (0, 0),
+        };
+        viewer.refresh_crc_errors();
+        viewer.load_annotations();
+        if !viewer.args.fresh {
+            viewer.restore_state();
+        }
+        Ok(viewer)
+    }
+
+    /// 设置自定义负载解析器，解析结果显示在详情面板中
+    #[cfg(feature = "scripting")]
+    pub fn set_dissector(
+        &mut self,
+        dissector: Box<dyn Dissector>,
+    ) {
+        self.dissector = Some(dissector);
+        // 详情面板占用额外行数，下次刷新时重新计算分页
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 设置并排比较的文件，与当前文件逐字节比较
+    pub fn set_diff(
+        &mut self,
+        file_path: PathBuf,
+        data: FileData,
+    ) -> Result<()> {
+        let count = diff::count_differences(
+            &self.file_data,
+            &data,
+        )?;
+        self.status_message = Some(if count == 0 {
+            tr!("与 {} 完全相同", file_path.display())
+        } else {
+            tr!(
+                "与 {} 比较: {} 字节不同 (d / D 跳转)",
+                file_path.display(),
+                count
+            )
+        });
+        self.diff = Some(DiffTarget { file_path, data });
+        self.update_total_lines();
+        Ok(())
+    }
+
+    /// 跟随命名管道或字符设备，新数据包由 `stream` 提供
+    pub fn set_stream(&mut self, stream: PacketStream) {
+        self.stream = Some(stream);
+    }
+
+    /// 把已读到与之后跟随读到的数据包同时录制到文件
+    pub fn start_recording(
+        &mut self,
+        path: &Path,
+    ) -> Result<()> {
+        let total = self.packets_seen();
+        self.recording = Some(Recording::create(
+            path,
+            self.args.ring,
+            &self.parser,
+            self.file_data.load()?,
+            total,
+        )?);
+        Ok(())
+    }
+
+    /// 数据源累计读到的数据包数（环形缓冲区中已丢弃的也计入）
+    fn packets_seen(&self) -> usize {
+        match &self.stream {
+            Some(stream) => stream.ring().received(),
+            None => self.parser.packets().len(),
+        }
+    }
+
+    /// 把新读到的数据包追加到录制文件，写入失败时停止录制并在状态栏提示
+    fn record_new_packets(&mut self) {
+        let total = self.packets_seen();
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+        let written =
+            self.file_data.load().and_then(|data| {
+                recording.write_new(
+                    &self.parser,
+                    data,
+                    total,
+                )
+            });
+        if let Err(e) = written {
+            self.status_message =
+                Some(tr!("录制失败，已停止: {}", e));
+            self.recording = None;
+        }
+    }
+
+    /// 退出时完成所有文件的录制，返回录制目标的描述与写入的数据包数量
+    fn finish_recordings(
+        &mut self,
+    ) -> Result<Vec<(String, usize)>> {
+        self.recording
+            .take()
+            .into_iter()
+            .chain(self.sessions.iter_mut().filter_map(
+                |session| session.recording.take(),
+            ))
+            .map(Recording::finish)
+            .collect()
+    }
+
+    /// 设置比较基线，主视图中与基线不同的字节以红色下划线标出
+    pub fn set_baseline(
+        &mut self,
+        file_path: PathBuf,
+        data: FileData,
+    ) -> Result<()> {
+        self.baseline_differences =
+            diff::count_differences(
+                &self.file_data,
+                &data,
+            )?;
+        self.status_message = Some(tr!(
+            "基线 {}: {} 字节不同 (d / D 跳转)",
+            file_path.display(),
+            self.baseline_differences
+        ));
+        self.baseline =
+            Some(DiffTarget { file_path, data });
+        Ok(())
+    }
+
+    /// 重新统计与基线不同的字节数
+    fn refresh_baseline_differences(&mut self) {
+        let count = self.baseline.as_ref().map_or(
+            Ok(0),
+            |target| {
+                diff::count_differences(
+                    &self.file_data,
+                    &target.data,
+                )
+            },
+        );
+        match count {
+            Ok(count) => self.baseline_differences = count,
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("比较失败: {}", e))
+            }
+        }
+    }
+
+    /// 指定偏移处是否与基线不同
+    fn differs_from_baseline(&self, offset: usize) -> bool {
+        self.baseline.as_ref().is_some_and(|target| {
+            self.differs_from(target, offset)
         })
     }
 
+    /// 指定偏移处是否与比较文件不同（只有一边有数据时也算不同；
+    /// 只比较已取回的内容）
+    fn differs_from(
+        &self,
+        target: &DiffTarget,
+        offset: usize,
+    ) -> bool {
+        self.file_data.byte(offset)
+            != target.data.byte(offset)
+    }
+
+    /// 跳转到视口首行之后（或之前）的下一处差异
+    fn jump_to_difference(&mut self, forward: bool) {
+        let Some(target) =
+            self.diff.as_ref().or(self.baseline.as_ref())
+        else {
+            self.status_message = Some(
+                tr!(
+                    "未指定比较文件 (使用 --diff 或 --baseline <文件>)"
+                ).to_string(),
+            );
+            return;
+        };
+
+        let bytes_per_line = self.args.bytes_per_line();
+        let line_start = self.viewport_offset();
+        let found = if forward {
+            diff::next_difference(
+                &self.file_data,
+                &target.data,
+                line_start + bytes_per_line,
+            )
+        } else {
+            diff::prev_difference(
+                &self.file_data,
+                &target.data,
+                line_start,
+            )
+        };
+
+        match found {
+            Ok(Some(offset)) => {
+                let byte = |data: &FileData| {
+                    let mut byte = [0];
+                    match data
+                        .read_at(offset as u64, &mut byte)
+                    {
+                        Ok(1) => format!("{:02X}", byte[0]),
+                        _ => "--".to_string(),
+                    }
+                };
+                self.status_message = Some(tr!(
+                    "差异 0x{}: {} → {}",
+                    format!("{:08X}", offset),
+                    byte(&self.file_data),
+                    byte(&target.data)
+                ));
+                self.record_jump();
+                self.scroll_to_offset(offset);
+            }
+            Ok(None) => {
+                self.status_message =
+                    Some(tr!("没有更多差异").to_string())
+            }
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("比较失败: {}", e))
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 显示内容的字节数（并排比较时取两个文件中较长者）
+    fn content_len(&self) -> usize {
+        let diff_len = self
+            .diff
+            .as_ref()
+            .map_or(0, |target| target.data.len());
+        self.file_data.len().max(diff_len)
+    }
+
+    /// 文件完整长度（后台加载中为数据源总长度，而非已读取的部分）
+    fn full_len(&self) -> usize {
+        self.indexer
+            .as_ref()
+            .map_or(self.file_data.len(), |indexer| {
+                indexer.progress().total_bytes
+            })
+    }
+
+    /// 按内容长度更新分页总行数
+    fn update_total_lines(&mut self) {
+        self.pagination.update_total_lines(
+            self.folds.total_rows(
+                self.content_len()
+                    .div_ceil(self.args.bytes_per_line()),
+            ),
+        );
+    }
+
+    /// 追加打开一个文件（不切换到该文件）
+    pub fn add_file(
+        &mut self,
+        file_path: PathBuf,
+        parser: PcapParser,
+        file_data: FileData,
+        indexer: Option<BackgroundIndexer>,
+    ) {
+        self.sessions.push(FileSession {
+            file_path,
+            parser,
+            file_data,
+            indexer,
+            ..FileSession::default()
+        });
+
+        // 在新文件上执行与 new() 相同的初始化
+        let active = self.active_file;
+        self.switch_file(self.sessions.len() - 1);
+        self.refresh_crc_errors();
+        self.load_annotations();
+        if !self.args.fresh {
+            self.restore_state();
+        }
+        self.switch_file(active);
+    }
+
+    /// 切换到指定序号的文件
+    fn switch_file(&mut self, index: usize) {
+        if index == self.active_file
+            || index >= self.sessions.len()
+        {
+            return;
+        }
+
+        let mut session =
+            std::mem::take(&mut self.sessions[index]);
+        self.swap_session(&mut session);
+        self.sessions[self.active_file] = session;
+        self.active_file = index;
+        self.invalidate_lines();
+
+        // 其他视图基于前一个文件，切换后关闭
+        self.editing = false;
+        self.stream_view = None;
+        self.stats_view = None;
+        self.frequency_view = None;
+        self.search = None;
+        self.confirm_quit = false;
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 交换当前文件状态与 `session`，并定位到其保存的视口位置
+    fn swap_session(&mut self, session: &mut FileSession) {
+        use std::mem::swap;
+
+        let offset = self.viewport_offset();
+        swap(
+            &mut self.args.file_path,
+            &mut session.file_path,
+        );
+        swap(&mut self.parser, &mut session.parser);
+        swap(&mut self.file_data, &mut session.file_data);
+        swap(&mut self.edit, &mut session.edit);
+        swap(&mut self.jump_list, &mut session.jump_list);
+        swap(&mut self.crc_errors, &mut session.crc_errors);
+        swap(
+            &mut self.highlighter,
+            &mut session.highlighter,
+        );
+        swap(
+            &mut self.annotations,
+            &mut session.annotations,
+        );
+        swap(&mut self.notes_path, &mut session.notes_path);
+        swap(&mut self.bookmarks, &mut session.bookmarks);
+        swap(&mut self.selection, &mut session.selection);
+        swap(&mut self.indexer, &mut session.indexer);
+        swap(&mut self.stream, &mut session.stream);
+        swap(&mut self.recording, &mut session.recording);
+        swap(
+            &mut self.pending_jump,
+            &mut session.pending_jump,
+        );
+        swap(&mut self.folds, &mut session.folds);
+
+        self.update_total_lines();
+        self.refresh_baseline_differences();
+        self.pagination.go_to_first_page();
+        self.scroll_to_offset(session.offset);
+        session.offset = offset;
+    }
+
+    /// 切换到下一个（或上一个）文件，到头时循环
+    fn cycle_file(&mut self, forward: bool) {
+        let count = self.sessions.len();
+        if count < 2 {
+            self.status_message =
+                Some(tr!("只打开了一个文件").to_string());
+            return;
+        }
+        let index = if forward {
+            (self.active_file + 1) % count
+        } else {
+            (self.active_file + count - 1) % count
+        };
+        self.switch_file(index);
+        self.status_message =
+            Some(tr!("切换到 {}", self.file_label()));
+    }
+
+    /// 当前文件标签，如 `[2/3] b.pcap`
+    fn file_label(&self) -> String {
+        let name = self
+            .args
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| {
+                self.args.file_path.to_string_lossy()
+            });
+        format!(
+            "[{}/{}] {}",
+            self.active_file + 1,
+            self.sessions.len(),
+            name
+        )
+    }
+
+    /// 所有打开文件中未保存修改的字节数
+    fn unsaved_bytes(&self) -> usize {
+        self.edit.modified_count()
+            + self
+                .sessions
+                .iter()
+                .map(|session| {
+                    session.edit.modified_count()
+                })
+                .sum::<usize>()
+    }
+
     /// 运行查看器
     pub fn run(&mut self) -> Result<()> {
         if self.args.no_color() {
             colored::control::set_override(false);
         }
 
+        // 输出被重定向或指定 --paginate 等转储参数时不进入交互模式，
+        // 直接输出十六进制转储
+        if self.args.dump_output() {
+            return self.dump(self.args.lines);
+        }
+
         // 进入交互模式
-        self.interactive_mode()?;
+        let result = self.interactive_mode();
+
+        // 无论是否出错都完成录制，保证录制文件完整
+        for (target, count) in self.finish_recordings()? {
+            if !quiet() {
+                println!(
+                    "{}",
+                    tr!(
+                        "已录制 {} 个数据包到 {}",
+                        count,
+                        target
+                    )
+                );
+            }
+        }
+        result?;
+
+        // 退出时保存每个文件的查看状态，下次打开同一文件时恢复
+        self.save_state();
+        for index in 0..self.sessions.len() {
+            if index != self.active_file {
+                self.switch_file(index);
+                self.save_state();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 按行输出整个文件（或前 limit 行）的十六进制转储，输出为终端时超过一屏交给分页器，
+    /// 下游提前关闭管道（如 | head）时静默结束
+    fn dump(&self, limit: Option<usize>) -> Result<()> {
+        let bytes_per_line = self.args.bytes_per_line();
+        // 各范围覆盖的行，相邻范围落在同一行时只输出一次
+        let mut last_line = None;
+        let lines = self
+            .dump_ranges()
+            .into_iter()
+            .flat_map(|range| {
+                let first = range.start / bytes_per_line;
+                let end =
+                    range.end.div_ceil(bytes_per_line);
+                (first..end).map(move |line| {
+                    let offset = line * bytes_per_line;
+                    offset
+                        ..(offset + bytes_per_line)
+                            .min(range.end)
+                })
+            })
+            .filter(|line| {
+                let index = line.start / bytes_per_line;
+                if last_line
+                    .is_some_and(|last| index <= last)
+                {
+                    return false;
+                }
+                last_line = Some(index);
+                true
+            })
+            .take(limit.unwrap_or(usize::MAX));
+
+        if let Some((warning, snippet)) = self
+            .header_warning()
+            .split_first()
+            .filter(|_| !quiet())
+        {
+            eprintln!(
+                "{} {}",
+                tr!("警告").yellow().bold(),
+                warning
+            );
+            for line in snippet {
+                eprintln!("  {}", line);
+            }
+        }
 
+        let lines = lines.map(|line| {
+            self.format_line(line).map_err(io::Error::other)
+        });
+        pager::write_lines(
+            lines,
+            io::stdout().is_terminal()
+                && !self.args.no_pager,
+        )?;
         Ok(())
     }
 
+    /// 转储的字节范围：--head 到最后一个解析出的数据包结束为止，
+    /// --tail 从第一个解析出的数据包所在行开始，
+    /// --sample / --packets 为文件头与每个选中的数据包，
+    /// 指定 --range 时只保留与其重叠的部分
+    fn dump_ranges(&self) -> Vec<Range<usize>> {
+        let content_len = self.content_len();
+        let ranges = self.dump_packet_ranges();
+        let Some(range) = self.args.range else {
+            return ranges;
+        };
+        let clip = range.clamp(content_len);
+        ranges
+            .into_iter()
+            .map(|range| {
+                range.start.max(clip.start)
+                    ..range.end.min(clip.end)
+            })
+            .filter(|range| range.start < range.end)
+            .collect()
+    }
+
+    /// 按 --head / --tail / --sample / --packets 选出的字节范围
+    fn dump_packet_ranges(&self) -> Vec<Range<usize>> {
+        let content_len = self.content_len();
+        let packets = self.parser.packets();
+        let packet_end = |packet: &DataPacket| {
+            packet.offset
+                + self.parser.header_size()
+                + packet.header.packet_length as usize
+        };
+        if self.args.sample.is_some()
+            || self.args.packets.is_some()
+        {
+            let list = self.args.packets.as_ref();
+            return std::iter::once(
+                0..FILE_HEADER_SIZE.min(content_len),
+            )
+            .chain(
+                packets
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| {
+                        list.is_none_or(|list| {
+                            list.contains(*index)
+                        })
+                    })
+                    .map(|(_, packet)| {
+                        packet.offset..packet_end(packet)
+                    }),
+            )
+            .collect();
+        }
+        let range = match self.args.packet_limit() {
+            Some(PacketLimit::Head(_)) => {
+                let end = packets
+                    .last()
+                    .map_or(FILE_HEADER_SIZE, packet_end);
+                0..end.min(content_len)
+            }
+            Some(PacketLimit::Tail(_)) => {
+                let start = packets
+                    .first()
+                    .map_or(content_len, |packet| {
+                        packet.offset
+                    });
+                start..content_len
+            }
+            None => 0..content_len,
+        };
+        vec![range]
+    }
+
     /// 交互模式
     fn interactive_mode(&mut self) -> Result<()> {
         // 启用原始模式
         self.terminal_manager.enter_raw_mode()?;
 
         // 初始显示
-        self.terminal_manager.clear_screen()?;
         let _ = self.update_terminal_size()?; // 忽略返回值，初始化时总是需要显示
-        self.display_current_page()?;
-        self.display_help()?;
-        // 确保初始显示内容已刷新到终端
-        io::stdout().flush()?;
+        self.redraw()?;
 
         loop {
             // 更新终端尺寸
@@ -97,188 +951,4990 @@ impl HexViewer {
                     != self.last_display_start_line;
 
             if needs_redraw {
-                // 只有在需要时才重绘
-                self.terminal_manager.clear_screen()?;
-                self.display_current_page()?;
-                self.display_help()?;
-                // 确保所有输出都已刷新到终端
-                io::stdout().flush()?;
-                self.last_display_start_line =
-                    self.pagination.display_start_line();
+                if event::poll(Duration::ZERO)? {
+                    // 还有待处理的输入（如按住按键）时先处理完再重绘
+                    self.last_display_start_line =
+                        usize::MAX;
+                } else {
+                    // 只有在需要时才重绘（只输出与上一帧不同的行）
+                    self.redraw()?;
+                    self.last_display_start_line = self
+                        .pagination
+                        .display_start_line();
+                }
+            }
+
+            // 查找进行中时逐块查找，块之间处理按键（ESC 取消）
+            if self.search.is_some()
+                && !event::poll(Duration::ZERO)?
+            {
+                self.continue_search()?;
+                continue;
+            }
+
+            // 后台索引进行中时定时取回新数据
+            if self.indexer.is_some()
+                && !event::poll(INDEX_POLL_INTERVAL)?
+            {
+                self.receive_indexed();
+                continue;
+            }
+
+            // 跟随模式下定时检查文件增长
+            if self.args.follow
+                && !event::poll(FOLLOW_POLL_INTERVAL)?
+            {
+                if self.reload_if_grown()?
+                    | self.check_watch_dir()?
+                {
+                    self.last_display_start_line =
+                        usize::MAX; // 强制重绘
+                }
+                continue;
             }
 
             // 等待用户输入
-            match event::read()? {
-                Event::Key(KeyEvent {
-                    code,
-                    modifiers,
-                    ..
-                }) => {
-                    // 使用键盘处理器进行防抖
+            let event = event::read()?;
+            let _span =
+                tracing::trace_span!("event").entered();
+            match event {
+                Event::Key(
+                    key_event @ KeyEvent {
+                        code,
+                        modifiers,
+                        ..
+                    },
+                ) => {
+                    // 忽略按键松开事件，并记录按住不放的重复次数
                     if !self
                         .keyboard_handler
-                        .should_process_key(&code)
+                        .accept(&key_event)
+                    {
+                        continue;
+                    }
+
+                    // 除单纯移动视口外的按键都可能改变行内容
+                    if !self
+                        .is_view_only_key(code, modifiers)
+                    {
+                        self.invalidate_lines();
+                    }
+
+                    // 清除上一次的提示信息
+                    if self.status_message.take().is_some()
+                    {
+                        self.last_display_start_line =
+                            usize::MAX;
+                    }
+
+                    // Ctrl-C 在任何模式下都视为退出
+                    if code == KeyCode::Char('c')
+                        && modifiers
+                            .contains(KeyModifiers::CONTROL)
+                    {
+                        let confirm_quit = std::mem::take(
+                            &mut self.confirm_quit,
+                        );
+                        if self.request_quit(confirm_quit) {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // 按键帮助与颜色图例打开时任意键关闭
+                    if self.show_help || self.show_legend {
+                        self.show_help = false;
+                        self.show_legend = false;
+                        self.last_display_start_line =
+                            usize::MAX;
+                        continue;
+                    }
+
+                    // 输入框打开时由其处理按键
+                    if self.prompt.is_some() {
+                        self.handle_prompt_key(code);
+                        continue;
+                    }
+
+                    // 统计视图打开时由其处理按键
+                    if self.stats_view.is_some() {
+                        self.handle_stats_view_key(code);
+                        continue;
+                    }
+
+                    // 字节频率视图打开时由其处理按键
+                    if self.frequency_view.is_some() {
+                        self.handle_frequency_view_key(
+                            code,
+                        );
+                        continue;
+                    }
+
+                    // TCP 流视图打开时由其处理按键
+                    if self.stream_view.is_some() {
+                        self.handle_stream_view_key(code);
+                        continue;
+                    }
+
+                    // 编辑模式下由编辑器处理按键
+                    if self.editing {
+                        self.handle_edit_key(
+                            code, modifiers,
+                        );
+                        continue;
+                    }
+
+                    let confirm_quit = std::mem::take(
+                        &mut self.confirm_quit,
+                    );
+                    // 数字键输入计数前缀，随后的移动命令重复相应次数
+                    let count = match self
+                        .count_prefix
+                        .handle(code)
+                    {
+                        CountInput::Pending
+                        | CountInput::Cancelled => {
+                            self.last_display_start_line =
+                                usize::MAX;
+                            continue;
+                        }
+                        CountInput::Key(count) => count,
+                    };
+                    let repeat = count.unwrap_or(1);
+
+                    // Shift+←/→ 左右滚动超出终端宽度的行
+                    if modifiers
+                        .contains(KeyModifiers::SHIFT)
+                        && matches!(
+                            code,
+                            KeyCode::Left | KeyCode::Right
+                        )
+                    {
+                        self.scroll_horizontally(
+                            code == KeyCode::Right,
+                            repeat,
+                        );
+                        continue;
+                    }
+
+                    // 移动按键由绑定表统一处理
+                    if let Some(motion) = self
+                        .args
+                        .config
+                        .keymap
+                        .motion_for(code, modifiers)
                     {
+                        // 按住逐行滚动的按键时逐渐加速
+                        let count = match (motion, count) {
+                            (
+                                Motion::LineUp
+                                | Motion::LineDown,
+                                None,
+                            ) => Some(
+                                self.keyboard_handler
+                                    .step(),
+                            ),
+                            _ => count,
+                        };
+                        self.apply_motion(motion, count);
                         continue;
                     }
 
-                    match (code, modifiers) {
-                        (KeyCode::Esc, _)
-                        | (KeyCode::Char('q'), _) => {
-                            break;
-                        }
-                        (KeyCode::Up, _) => {
-                            self.pagination.scroll_up();
-                        }
-                        (KeyCode::Down, _) => {
-                            self.pagination.scroll_down();
-                        }
-                        (KeyCode::Left, _) => {
-                            self.pagination.page_up();
-                        }
-                        (KeyCode::Right, _) => {
-                            self.pagination.page_down();
-                        }
-                        (KeyCode::Home, _) => {
-                            self.pagination
-                                .go_to_first_page();
-                        }
-                        (KeyCode::End, _) => {
-                            self.pagination
-                                .go_to_last_page();
-                        }
-                        (KeyCode::Char('r'), _) => {
-                            // 刷新终端尺寸，强制重绘
-                            let _ = self
-                                .update_terminal_size()?;
-                            self.last_display_start_line =
-                                usize::MAX; // 强制重绘
-                        }
-                        _ => {}
-                    }
+                    match (code, modifiers) {
+                        // 查找进行中时 ESC 只取消查找
+                        (KeyCode::Esc, _)
+                            if self.search.is_some() =>
+                        {
+                            self.search = None;
+                            self.status_message = Some(
+                                tr!("已取消查找")
+                                    .to_string(),
+                            );
+                            self.last_display_start_line =
+                                usize::MAX;
+                        }
+                        // 有等待加载的跳转时 ESC 只取消跳转
+                        (KeyCode::Esc, _)
+                            if self
+                                .pending_jump
+                                .is_some() =>
+                        {
+                            self.pending_jump = None;
+                            self.status_message = Some(
+                                tr!("已取消等待中的跳转")
+                                    .to_string(),
+                            );
+                            self.last_display_start_line =
+                                usize::MAX;
+                        }
+                        (KeyCode::Esc, _)
+                        | (KeyCode::Char('q'), _) => {
+                            if !self
+                                .request_quit(confirm_quit)
+                            {
+                                continue;
+                            }
+                            break;
+                        }
+                        (KeyCode::Char('e'), _) => {
+                            self.enter_edit_mode();
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('n'), _) => {
+                            self.cycle_file(true);
+                        }
+                        (KeyCode::Char('w'), _) => {
+                            self.open_watch_pending()?;
+                        }
+                        (KeyCode::Char('N'), _) => {
+                            self.cycle_file(false);
+                        }
+                        (KeyCode::F(n @ 1..=9), _) => {
+                            let index = n as usize - 1;
+                            if index < self.sessions.len() {
+                                self.switch_file(index);
+                                self.status_message =
+                                    Some(tr!(
+                                        "切换到 {}",
+                                        self.file_label()
+                                    ));
+                            }
+                        }
+                        (KeyCode::Char('d'), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_difference(
+                                    true,
+                                );
+                            }
+                        }
+                        (KeyCode::Char('D'), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_difference(
+                                    false,
+                                );
+                            }
+                        }
+                        (KeyCode::Char('g'), _) => {
+                            self.open_prompt(
+                                PromptKind::Goto,
+                                "",
+                            );
+                        }
+                        (KeyCode::Char('t'), _) => {
+                            self.open_time_shift_prompt();
+                        }
+                        (KeyCode::Char('o'), modifiers)
+                            if modifiers.contains(
+                                KeyModifiers::CONTROL,
+                            ) =>
+                        {
+                            self.jump_back();
+                        }
+                        // 终端中 Ctrl-I 与 Tab 相同
+                        (KeyCode::Tab, _) => {
+                            self.jump_forward();
+                        }
+                        (KeyCode::Char('u'), _) => {
+                            self.undo();
+                        }
+                        (KeyCode::Char('r'), modifiers)
+                            if modifiers.contains(
+                                KeyModifiers::CONTROL,
+                            ) =>
+                        {
+                            self.redo();
+                        }
+                        (KeyCode::Char('a'), _) => {
+                            self.toggle_timing_overlay();
+                        }
+                        (KeyCode::Char('T'), _) => {
+                            self.toggle_column(
+                                Column::Delta,
+                                "数据包时间差 Δt",
+                            );
+                        }
+                        (KeyCode::Char('W'), _) => {
+                            self.toggle_dual_columns();
+                        }
+                        (KeyCode::Char('z'), _) => {
+                            self.toggle_fold();
+                        }
+                        (KeyCode::Char('O'), _) => {
+                            self.toggle_relative_offsets();
+                        }
+                        (KeyCode::Char('A'), _) => {
+                            self.toggle_column(
+                                Column::Ascii,
+                                "ASCII 列",
+                            );
+                        }
+                        (KeyCode::Char(']'), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_timing_issue(
+                                    true,
+                                );
+                            }
+                        }
+                        (KeyCode::Char('['), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_timing_issue(
+                                    false,
+                                );
+                            }
+                        }
+                        (KeyCode::Char('!'), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_diagnostic();
+                            }
+                        }
+                        (KeyCode::Char(';'), _) => {
+                            self.open_annotation_prompt();
+                        }
+                        (KeyCode::Char('y'), _) => {
+                            self.open_prompt(
+                                PromptKind::CopyAs,
+                                "",
+                            );
+                        }
+                        (KeyCode::Char('b'), _) => {
+                            self.toggle_bookmark();
+                        }
+                        (KeyCode::Char('\''), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_bookmark(true);
+                            }
+                        }
+                        (KeyCode::Char('`'), _) => {
+                            for _ in 0..repeat {
+                                self.jump_to_bookmark(
+                                    false,
+                                );
+                            }
+                        }
+                        (KeyCode::Char('?'), _) => {
+                            self.show_help = true;
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('L'), _) => {
+                            self.show_legend = true;
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char(':'), _) => {
+                            self.open_prompt(
+                                PromptKind::Command,
+                                "",
+                            );
+                        }
+                        (KeyCode::Char('/'), _) => {
+                            let initial = self
+                                .last_search
+                                .as_ref()
+                                .map(|pattern| {
+                                    pattern.to_string()
+                                })
+                                .unwrap_or_default();
+                            self.open_prompt(
+                                PromptKind::Search,
+                                &initial,
+                            );
+                        }
+                        (KeyCode::Char('c'), _) => {
+                            self.color_mode =
+                                self.color_mode.next();
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('#'), _) => {
+                            self.columns
+                                .toggle(Column::Packet);
+                            self.update_page_size();
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('m'), _) => {
+                            self.show_minimap =
+                                !self.show_minimap;
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('p'), _) => {
+                            self.show_throughput =
+                                !self.show_throughput;
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('s'), _) => {
+                            self.stats_view =
+                                Some(StatsView::new(
+                                    &self.parser,
+                                    DEFAULT_BUCKET_SIZE,
+                                    self.view_lines_per_page(),
+                                ));
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('F'), _) => {
+                            self.open_frequency_view();
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('f'), _) => {
+                            self.open_stream_view();
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        (KeyCode::Char('r'), _) => {
+                            // 刷新终端尺寸，强制整屏重绘
+                            let _ = self
+                                .update_terminal_size()?;
+                            self.terminal_manager
+                                .invalidate();
+                            self.last_display_start_line =
+                                usize::MAX; // 强制重绘
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Resize(_, _) => {
+                    // 终端尺寸变化时立即重新布局并整屏重绘
+                    let _ = self.update_terminal_size()?;
+                    self.terminal_manager.invalidate();
+                    self.last_display_start_line =
+                        usize::MAX;
+                }
+                Event::Mouse(mouse) => {
+                    self.handle_mouse(mouse);
+                }
+                _ => {
+                    // 忽略其他事件
+                    continue;
+                }
+            }
+        }
+
+        // 恢复终端（由 TerminalManager 的 Drop trait 自动处理）
+
+        Ok(())
+    }
+
+    /// 处理鼠标事件：滚轮滚动（按住 Shift 翻页），左键单击定位光标
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.prompt.is_some() {
+            return;
+        }
+
+        let page =
+            mouse.modifiers.contains(KeyModifiers::SHIFT);
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                self.scroll_by_wheel(true, page)
+            }
+            MouseEventKind::ScrollDown => {
+                self.scroll_by_wheel(false, page)
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.invalidate_lines();
+                self.click_at(
+                    mouse.column as usize,
+                    mouse.row as usize,
+                )
+            }
+            _ => return,
+        }
+        self.last_display_start_line = usize::MAX; // 强制重绘
+    }
+
+    /// 滚轮滚动当前视图，编辑模式下移动光标
+    fn scroll_by_wheel(&mut self, up: bool, page: bool) {
+        if self.editing {
+            let code = match (up, page) {
+                (true, false) => KeyCode::Up,
+                (false, false) => KeyCode::Down,
+                (true, true) => KeyCode::PageUp,
+                (false, true) => KeyCode::PageDown,
+            };
+            self.invalidate_lines();
+            self.handle_edit_key(code, KeyModifiers::NONE);
+            return;
+        }
+
+        let pagination = if let Some(view) =
+            self.stats_view.as_mut()
+        {
+            view.pagination_mut()
+        } else if let Some(view) = self.stream_view.as_mut()
+        {
+            view.pagination_mut()
+        } else if let Some(view) =
+            self.frequency_view.as_mut()
+        {
+            view.pagination_mut()
+        } else {
+            &mut self.pagination
+        };
+        match (up, page) {
+            (true, false) => pagination.scroll_up(),
+            (false, false) => pagination.scroll_down(),
+            (true, true) => pagination.page_up(),
+            (false, true) => pagination.page_down(),
+        }
+    }
+
+    /// 单击十六进制区域时把光标移到对应字节
+    fn click_at(&mut self, column: usize, row: usize) {
+        if self.stats_view.is_some()
+            || self.stream_view.is_some()
+            || self.frequency_view.is_some()
+            || self.terminal_manager.is_smaller_than(
+                self.min_screen_width(),
+                MIN_LAYOUT_HEIGHT,
+            )
+        {
+            return;
+        }
+
+        let (width, height) =
+            self.terminal_manager.get_size();
+        let page = self
+            .screen_layout(Rect::new(0, height, width))
+            .page;
+        let column = column
+            + self
+                .horizontal_scroll(self.content_width())
+                .0;
+        // 并排两栏时右栏从分隔列之后开始
+        let (column, pane) = match self.dual_column_width()
+        {
+            Some(half) if column == half => return,
+            Some(half) if column > half => {
+                (column - half - 1, 1)
+            }
+            _ => (column, 0),
+        };
+        // 十六进制列中每个字节占 3 列；排在前面的列宽度不定时无法定位
+        let Some(hex_start) =
+            self.columns.start_of(Column::Hex, |spec| {
+                self.column_width(spec)
+            })
+        else {
+            return;
+        };
+        if row < page.top
+            || row >= page.bottom()
+            || column < hex_start
+        {
+            return;
+        }
+        let bytes_per_line = self.args.bytes_per_line();
+        let index = (column - hex_start) / 3;
+        if index >= bytes_per_line {
+            return;
+        }
+
+        let row = self.pagination.display_start_line()
+            + (row - page.top) * self.lines_per_row()
+            + pane;
+        let Row::Line(line) = self.folds.row(row) else {
+            return;
+        };
+        let offset = line * bytes_per_line + index;
+        if offset >= self.file_data.len() {
+            return;
+        }
+        self.edit.set_cursor(offset, self.file_data.len());
+        if !self.editing {
+            self.status_message = Some(tr!(
+                "光标: 0x{}，按 e 从此处开始编辑",
+                format!("{:08X}", offset)
+            ));
+        }
+    }
+
+    /// 执行移动操作（按计数重复），跳到首页或末页前记录跳转位置；
+    /// 带计数的 G 跳到指定行
+    fn apply_motion(
+        &mut self,
+        motion: Motion,
+        count: Option<usize>,
+    ) {
+        // 移动视口即放弃等待中的跳转
+        self.pending_jump = None;
+        let step: fn(&mut PaginationState) = match motion {
+            Motion::LineUp => PaginationState::scroll_up,
+            Motion::LineDown => {
+                PaginationState::scroll_down
+            }
+            Motion::HalfPageUp => {
+                PaginationState::half_page_up
+            }
+            Motion::HalfPageDown => {
+                PaginationState::half_page_down
+            }
+            Motion::PageUp => PaginationState::page_up,
+            Motion::PageDown => PaginationState::page_down,
+            Motion::First => {
+                self.record_jump();
+                self.pagination.go_to_first_page();
+                return;
+            }
+            Motion::Last => {
+                self.record_jump();
+                match count {
+                    Some(line) => self.scroll_to_offset(
+                        line.saturating_sub(1)
+                            * self.args.bytes_per_line(),
+                    ),
+                    // 后台加载中时等到文件末尾加载后再跳转
+                    None if self.indexer.is_some() => self
+                        .scroll_to_offset(
+                            self.full_len()
+                                .saturating_sub(1),
+                        ),
+                    None => {
+                        self.pagination.go_to_last_page()
+                    }
+                }
+                return;
+            }
+        };
+        for _ in 0..count.unwrap_or(1) {
+            step(&mut self.pagination);
+        }
+    }
+
+    /// 请求退出，有未保存的修改且尚未确认时提示并返回 false
+    fn request_quit(&mut self, confirmed: bool) -> bool {
+        let unsaved = self.unsaved_bytes();
+        if unsaved > 0 && !confirmed {
+            self.status_message = Some(tr!(
+                "有 {} 字节未保存的修改，再次按 q 或 Ctrl-C 放弃修改并退出",
+                unsaved
+            ));
+            self.confirm_quit = true;
+            self.last_display_start_line = usize::MAX;
+            return false;
+        }
+        true
+    }
+
+    /// 文件增长时重新加载并解析，返回是否有新数据
+    fn reload_if_grown(&mut self) -> Result<bool> {
+        if self.stream.is_some() {
+            return Ok(self.receive_stream());
+        }
+        // 文件已被删除（如滚动录制删除了最早的文件）时保留已读取的内容
+        let file_len =
+            match std::fs::metadata(&self.args.file_path) {
+                Ok(metadata) => metadata.len() as usize,
+                Err(e)
+                    if e.kind() == ErrorKind::NotFound =>
+                {
+                    return Ok(false)
+                }
+                Err(e) => return Err(e.into()),
+            };
+        if file_len <= self.file_data.len() {
+            return Ok(false);
+        }
+
+        let file_data =
+            std::fs::read(&self.args.file_path)?;
+        let parser = PcapParser::from_bytes_or_raw(
+            &file_data,
+            self.args.parser_options(),
+        )?;
+        self.file_data = file_data.into();
+        self.set_parser(parser);
+        self.invalidate_lines();
+        self.record_new_packets();
+
+        // 停留在末尾时自动滚动到最新数据
+        let at_end = self.pagination.is_at_end();
+        self.pagination.update_total_lines(
+            self.folds.total_rows(
+                self.file_data
+                    .len()
+                    .div_ceil(self.args.bytes_per_line()),
+            ),
+        );
+        if at_end {
+            self.pagination.go_to_last_page();
+        }
+
+        Ok(true)
+    }
+
+    /// 取回流式输入的新数据包并以环形缓冲区中的内容替换数据，
+    /// 丢弃了旧数据包或输入结束时在状态栏提示，返回是否需要重绘
+    fn receive_stream(&mut self) -> bool {
+        let Some(stream) = &mut self.stream else {
+            return false;
+        };
+        let was_finished = stream.is_finished();
+        let evicted_bytes = stream.ring().evicted_bytes();
+        let evicted = stream.ring().evicted();
+        let received = match stream.receive() {
+            Ok(received) => received,
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("读取输入流失败: {}", e));
+                return true;
+            }
+        };
+        let ring = stream.ring();
+        let ended = !was_finished && stream.is_finished();
+        if ended {
+            self.status_message = Some(
+                if ring.evicted() > 0 {
+                    tr!(
+                        "输入流已结束，共丢弃 {} 个最早的数据包",
+                        ring.evicted()
+                    )
+                } else {
+                    tr!("输入流已结束").to_string()
+                },
+            );
+        }
+        if received == 0 {
+            return ended;
+        }
+
+        let dropped = ring.evicted() - evicted;
+        let shifted = ring.evicted_bytes() - evicted_bytes;
+        if dropped > 0 && !ended {
+            self.status_message = Some(tr!(
+                "缓冲区已满 ({} 个数据包)，丢弃了最早的 {} 个，共丢弃 {} 个",
+                ring.capacity(),
+                dropped,
+                ring.evicted()
+            ));
+        }
+        let file_data = ring.to_bytes();
+        let parser = match PcapParser::from_bytes_or_raw(
+            &file_data,
+            self.args.parser_options(),
+        ) {
+            Ok(parser) => parser,
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("解析输入流失败: {}", e));
+                return true;
+            }
+        };
+        self.file_data = file_data.into();
+        self.set_parser(parser);
+        self.invalidate_lines();
+        self.record_new_packets();
+
+        // 停留在末尾时自动滚动到最新数据，否则随丢弃的数据一起上移，保持显示相同的内容
+        let at_end = self.pagination.is_at_end();
+        let bytes_per_line = self.args.bytes_per_line();
+        let first_line = self
+            .pagination
+            .display_start_line()
+            .saturating_sub(shifted / bytes_per_line);
+        self.pagination.update_total_lines(
+            self.folds.total_rows(
+                self.file_data
+                    .len()
+                    .div_ceil(bytes_per_line),
+            ),
+        );
+        if at_end {
+            self.pagination.go_to_last_page();
+        } else {
+            self.pagination.go_to_line(first_line);
+        }
+        true
+    }
+
+    /// watch 模式下定时检查目录：出现尚未打开的新文件时切换过去
+    /// （--ask 时只在状态栏提示），返回是否需要重绘
+    fn check_watch_dir(&mut self) -> Result<bool> {
+        let Some(watch) = &self.args.watch else {
+            return Ok(false);
+        };
+        if self.last_watch_check.elapsed()
+            < WATCH_POLL_INTERVAL
+        {
+            return Ok(false);
+        }
+        self.last_watch_check = Instant::now();
+
+        // 目录暂时不可读（如录制程序正在轮换）时下次再试
+        let Ok(Some(newest)) = watch.newest() else {
+            return Ok(false);
+        };
+        let already_open = self.args.file_path == newest
+            || self
+                .sessions
+                .iter()
+                .any(|session| session.file_path == newest);
+        if already_open
+            || self.watch_pending.as_ref() == Some(&newest)
+        {
+            return Ok(false);
+        }
+
+        if watch.ask {
+            self.status_message = Some(tr!(
+                "录制已滚动到新文件 {} (按 w 打开)",
+                newest.display()
+            ));
+            self.watch_pending = Some(newest);
+            return Ok(true);
+        }
+        self.open_watched(newest)?;
+        Ok(true)
+    }
+
+    /// 打开 watch --ask 提示的新文件
+    fn open_watch_pending(&mut self) -> Result<()> {
+        match self.watch_pending.take() {
+            Some(path) => self.open_watched(path)?,
+            None => {
+                self.status_message = Some(
+                    tr!("没有新的录制文件").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+        Ok(())
+    }
+
+    /// 追加打开监视目录中的新文件并切换过去，停在末尾继续跟随
+    fn open_watched(
+        &mut self,
+        path: PathBuf,
+    ) -> Result<()> {
+        let file_data = std::fs::read(&path)?;
+        let parser = PcapParser::from_bytes_or_raw(
+            &file_data,
+            self.args.parser_options(),
+        )?;
+        self.add_file(path, parser, file_data.into(), None);
+        self.switch_file(self.sessions.len() - 1);
+        self.pagination.go_to_last_page();
+        self.status_message = Some(tr!(
+            "已切换到新文件 {}",
+            self.file_label()
+        ));
+        Ok(())
+    }
+
+    /// 取回后台索引的新数据，更新总行数与 CRC 校验结果
+    fn receive_indexed(&mut self) {
+        let _span =
+            tracing::trace_span!("receive_index").entered();
+        let Some(indexer) = &mut self.indexer else {
+            return;
+        };
+        let known_packets = self.parser.packets().len();
+        match indexer.receive(
+            &mut self.parser,
+            &mut self.file_data,
+            Duration::ZERO,
+        ) {
+            Ok(true) => {
+                // 新增数据包的 CRC 已由工作线程校验，主线程不必读取其负载
+                let new_errors: Vec<usize> = self
+                    .parser
+                    .packets()[known_packets..]
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, packet)| {
+                        packet.checksum_valid == Some(false)
+                    })
+                    .map(|(index, _)| known_packets + index)
+                    .collect();
+                self.crc_errors.extend(new_errors);
+                self.invalidate_lines();
+                self.update_total_lines();
+                self.resolve_pending_jump();
+            }
+            Ok(false) => {}
+            Err(e) => {
+                self.indexer = None;
+                self.resolve_pending_jump();
+                self.status_message =
+                    Some(tr!("索引失败: {}", e));
+            }
+        }
+
+        if self
+            .indexer
+            .as_ref()
+            .is_some_and(BackgroundIndexer::is_finished)
+        {
+            self.indexer = None;
+            self.refresh_baseline_differences();
+            self.invalidate_lines();
+            self.resolve_pending_jump();
+            self.status_message = Some(tr!(
+                "索引完成: {} 个数据包",
+                self.parser.packets().len()
+            ));
+        }
+        self.last_display_start_line = usize::MAX; // 刷新状态栏中的进度
+    }
+
+    /// 进入编辑模式，光标位于视口起始处（若已不可见）
+    fn enter_edit_mode(&mut self) {
+        if self.args.follow {
+            self.status_message =
+                Some(tr!("跟随模式下不能编辑").to_string());
+            return;
+        }
+        if self.indexer.is_some() {
+            // 保存时会写回整个文件，必须等全部数据读入并完成索引
+            self.status_message = Some(
+                tr!("文件仍在加载中，加载完成后才能编辑")
+                    .to_string(),
+            );
+            return;
+        }
+        if self.file_data.is_empty() {
+            return;
+        }
+        // 编辑与保存需要完整内容
+        if let Err(e) = self.file_data.load() {
+            self.status_message =
+                Some(tr!("读取文件失败: {}", e));
+            return;
+        }
+
+        // 编辑时逐行移动光标，先展开所有折叠
+        if !self.folds.is_empty() {
+            self.change_folds(Folds::clear);
+        }
+
+        let bytes_per_line = self.args.bytes_per_line();
+        let first_line =
+            self.pagination.display_start_line();
+        let cursor_line =
+            self.edit.cursor() / bytes_per_line;
+        if cursor_line < first_line
+            || cursor_line
+                >= first_line
+                    + self.pagination.lines_per_page()
+        {
+            self.edit.set_cursor(
+                first_line * bytes_per_line,
+                self.file_data.len(),
+            );
+        }
+        self.editing = true;
+    }
+
+    /// 处理编辑模式中的按键
+    fn handle_edit_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) {
+        let len = self.file_data.len();
+        let bytes_per_line =
+            self.args.bytes_per_line() as isize;
+        let page = bytes_per_line
+            * self.pagination.lines_per_page() as isize;
+        let ctrl =
+            modifiers.contains(KeyModifiers::CONTROL);
+
+        match code {
+            KeyCode::Esc => self.editing = false,
+            KeyCode::Left => self.edit.move_cursor(-1, len),
+            KeyCode::Right => self.edit.move_cursor(1, len),
+            KeyCode::Up => {
+                self.edit.move_cursor(-bytes_per_line, len)
+            }
+            KeyCode::Down => {
+                self.edit.move_cursor(bytes_per_line, len)
+            }
+            KeyCode::PageUp => {
+                self.edit.move_cursor(-page, len)
+            }
+            KeyCode::PageDown => {
+                self.edit.move_cursor(page, len)
+            }
+            KeyCode::Home => {
+                let cursor = self.edit.cursor();
+                self.edit.set_cursor(
+                    cursor
+                        - cursor % bytes_per_line as usize,
+                    len,
+                );
+            }
+            KeyCode::End => {
+                let cursor = self.edit.cursor();
+                self.edit.set_cursor(
+                    cursor
+                        - cursor % bytes_per_line as usize
+                        + bytes_per_line as usize
+                        - 1,
+                    len,
+                );
+            }
+            KeyCode::Tab => self.edit.toggle_mode(),
+            KeyCode::Char('s') if ctrl => {
+                self.save(self.args.file_path.clone());
+            }
+            KeyCode::Char('a') if ctrl => {
+                let path = self
+                    .args
+                    .file_path
+                    .to_string_lossy()
+                    .to_string();
+                self.open_prompt(PromptKind::SaveAs, &path);
+            }
+            KeyCode::Char('z') if ctrl => self.undo(),
+            KeyCode::Char('y') if ctrl => self.redo(),
+            // 十六进制输入时 `;` 不是有效字符，用于注释光标处
+            KeyCode::Char(';')
+                if self.edit.mode() == InputMode::Hex =>
+            {
+                self.open_annotation_prompt();
+            }
+            KeyCode::Char(ch) if !ctrl => {
+                if let Some(change) =
+                    self.file_data.resident_mut().and_then(
+                        |data| self.edit.input(data, ch),
+                    )
+                {
+                    self.on_byte_changed(change.offset);
+                }
+            }
+            _ => {}
+        }
+
+        // 保持光标可见
+        self.pagination.ensure_line_visible(
+            self.edit.cursor() / bytes_per_line as usize,
+        );
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 替换解析结果并重新校验 CRC
+    fn set_parser(&mut self, parser: PcapParser) {
+        self.parser = parser;
+        self.refresh_crc_errors();
+        self.refresh_baseline_differences();
+    }
+
+    /// 重新校验所有数据包的 CRC32（分页读取时使用索引线程的校验结果）
+    fn refresh_crc_errors(&mut self) {
+        self.crc_errors = match self.file_data.resident() {
+            Some(data) => self
+                .parser
+                .packet_refs(data)
+                .filter(|packet| {
+                    !self.checksum_matches(packet)
+                })
+                .map(|packet| packet.index())
+                .collect(),
+            None => self
+                .parser
+                .packets()
+                .iter()
+                .enumerate()
+                .filter(|(_, packet)| {
+                    packet.checksum_valid == Some(false)
+                })
+                .map(|(index, _)| index)
+                .collect(),
+        };
+    }
+
+    /// 数据包负载的校验和是否与包头一致（按 --checksum 选择的算法）
+    fn checksum_matches(
+        &self,
+        packet: &PacketRef<'_>,
+    ) -> bool {
+        !self.args.record.has_checksum()
+            || self.args.checksum.matches(
+                packet.payload(),
+                packet.header().checksum,
+            )
+    }
+
+    /// 字节修改后，若涉及文件头或数据包头则重新解析结构，否则只重新校验所在数据包
+    fn on_byte_changed(&mut self, offset: usize) {
+        self.refresh_baseline_differences();
+        if self.get_byte_color_type(offset)
+            == ByteColorType::PacketData
+        {
+            if let Some(packet) = self
+                .parser
+                .packet_index_at(offset)
+                .and_then(|index| {
+                    self.file_data
+                        .packet_ref(&self.parser, index)
+                })
+            {
+                let index = packet.index();
+                if self.checksum_matches(&packet) {
+                    self.crc_errors.remove(&index);
+                } else {
+                    self.crc_errors.insert(index);
+                }
+            }
+            return;
+        }
+
+        match PcapParser::from_bytes_or_raw(
+            self.file_data.resident().unwrap_or_default(),
+            self.args.parser_options(),
+        ) {
+            Ok(parser) => self.set_parser(parser),
+            Err(e) => {
+                self.status_message = Some(tr!(
+                    "结构解析失败，保留原结构: {}",
+                    e
+                ));
+            }
+        }
+    }
+
+    /// 打开底部输入框
+    fn open_prompt(
+        &mut self,
+        kind: PromptKind,
+        initial: &str,
+    ) {
+        self.prompt = Some(Prompt {
+            kind,
+            editor: LineEditor::new(initial),
+        });
+        self.search_history.reset();
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 处理输入框中的按键
+    fn handle_prompt_key(&mut self, code: KeyCode) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+
+        let editor = &mut prompt.editor;
+        match code {
+            KeyCode::Esc => self.prompt = None,
+            // 空的跳转输入框中再按 g（即 gg）跳到首页
+            KeyCode::Char('g')
+                if prompt.kind == PromptKind::Goto
+                    && editor.text().is_empty() =>
+            {
+                self.prompt = None;
+                self.apply_motion(Motion::First, None);
+            }
+            KeyCode::Enter => {
+                if let Some(prompt) = self.prompt.take() {
+                    self.submit_prompt(prompt);
+                }
+            }
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Delete => editor.delete(),
+            KeyCode::Left => editor.move_left(),
+            KeyCode::Right => editor.move_right(),
+            KeyCode::Home => editor.move_home(),
+            KeyCode::End => editor.move_end(),
+            KeyCode::Tab
+                if prompt.kind == PromptKind::Command =>
+            {
+                complete_command(editor)
+            }
+            KeyCode::Tab
+                if prompt.kind == PromptKind::Search =>
+            {
+                complete_saved_search(
+                    editor,
+                    &self.saved_searches,
+                )
+            }
+            KeyCode::Up
+                if prompt.kind == PromptKind::Search =>
+            {
+                let text = editor.text().to_string();
+                if let Some(entry) =
+                    self.search_history.older(&text)
+                {
+                    editor.set_text(entry);
+                }
+            }
+            KeyCode::Down
+                if prompt.kind == PromptKind::Search =>
+            {
+                if let Some(entry) =
+                    self.search_history.newer()
+                {
+                    editor.set_text(entry);
+                }
+            }
+            KeyCode::Char(ch) => editor.insert(ch),
+            _ => {}
+        }
+
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 提交输入框内容
+    fn submit_prompt(&mut self, prompt: Prompt) {
+        let text = prompt.editor.text().trim();
+        // 提交空注释表示删除，其余输入框忽略空输入
+        if text.is_empty()
+            && !matches!(
+                prompt.kind,
+                PromptKind::Annotate(_)
+            )
+        {
+            return;
+        }
+
+        match prompt.kind {
+            PromptKind::SaveAs => {
+                self.save(PathBuf::from(text))
+            }
+            PromptKind::Goto => {
+                if let Err(message) = self.goto(text) {
+                    self.status_message = Some(message);
+                }
+            }
+            PromptKind::TimeShift => {
+                self.status_message =
+                    Some(match self.shift_timestamps(text) {
+                        Ok(count) => tr!(
+                            "已平移 {} 个数据包时间戳 (Ctrl-S 保存)",
+                            count
+                        ),
+                        Err(e) => {
+                            tr!("时间平移失败: {}", e)
+                        }
+                    });
+            }
+            PromptKind::Command => self.run_command(text),
+            PromptKind::Search => self.start_search(text),
+            PromptKind::CopyAs => {
+                self.status_message = Some(
+                    self.copy_packet_as(text)
+                        .unwrap_or_else(|e| e),
+                );
+            }
+            PromptKind::Annotate(offset) => {
+                self.annotate(offset, text)
+            }
+        }
+    }
+
+    /// 开始查找：从选中范围（上一次匹配）之后或视口开始，到末尾后从开头继续；
+    /// `@名称` 执行命名的查找
+    fn start_search(&mut self, text: &str) {
+        self.search_history.push(text);
+        let text = match text.strip_prefix('@') {
+            Some(name) => {
+                match self.saved_searches.get(name.trim()) {
+                    Some(pattern) => pattern.clone(),
+                    None => {
+                        self.status_message = Some(tr!(
+                            "没有名为 {} 的查找（:search 列出）",
+                            name.trim()
+                        ));
+                        return;
+                    }
+                }
+            }
+            None => text.to_string(),
+        };
+        let pattern = match text.parse::<SearchPattern>() {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                self.status_message = Some(e.to_string());
+                return;
+            }
+        };
+        let origin = self
+            .selection
+            .as_ref()
+            .map_or(self.viewport_offset(), |range| {
+                range.start + 1
+            });
+        self.last_search = Some(pattern.clone());
+        self.search =
+            Some(Search::new(pattern, origin as u64));
+    }
+
+    /// 继续进行中的查找，每次最多处理 `SEARCH_STEP_BUDGET` 时长
+    fn continue_search(&mut self) -> Result<()> {
+        let Some(search) = self.search.as_mut() else {
+            return Ok(());
+        };
+        let started = Instant::now();
+        let step = loop {
+            let step = search.step(&self.file_data)?;
+            if step != SearchStep::Pending
+                || started.elapsed() >= SEARCH_STEP_BUDGET
+            {
+                break step;
+            }
+        };
+        self.last_display_start_line = usize::MAX; // 更新进度
+        let (pattern, wrapped) = (
+            search.pattern().to_string(),
+            search.wrapped(),
+        );
+        match step {
+            SearchStep::Pending => {}
+            SearchStep::NotFound => {
+                self.search = None;
+                self.status_message =
+                    Some(tr!("未找到 {}", pattern));
+            }
+            SearchStep::Found(range) => {
+                self.search = None;
+                let range = range.start as usize
+                    ..range.end as usize;
+                self.record_jump();
+                self.scroll_to_offset(range.start);
+                if self.editing {
+                    self.edit.set_cursor(
+                        range.start,
+                        self.file_data.len(),
+                    );
+                }
+                self.status_message = Some(tr!(
+                    "找到 {}: 0x{} ({} 字节){}",
+                    pattern,
+                    format!("{:08X}", range.start),
+                    range.len(),
+                    if wrapped {
+                        tr!("，已从文件开头继续")
+                    } else {
+                        ""
+                    }
+                ));
+                self.selection = Some(range);
+            }
+        }
+        Ok(())
+    }
+
+    /// 从旁路文件加载注释（远程数据源不支持注释）
+    fn load_annotations(&mut self) {
+        if source::is_remote(&self.args.file_path) {
+            return;
+        }
+        let path =
+            Annotations::sidecar_path(&self.args.file_path);
+        match Annotations::load(&path) {
+            Ok(annotations) => {
+                self.annotations = annotations;
+            }
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("注释加载失败: {}", e));
+            }
+        }
+        self.notes_path = Some(path);
+    }
+
+    /// 打开注释输入框：编辑模式下注释光标处，否则注释视口起始处
+    fn open_annotation_prompt(&mut self) {
+        if self.notes_path.is_none() {
+            self.status_message = Some(
+                tr!("远程数据源不支持注释").to_string(),
+            );
+            return;
+        }
+        if self.file_data.is_empty() {
+            return;
+        }
+
+        let offset = if self.editing {
+            self.edit.cursor()
+        } else {
+            self.viewport_offset()
+        };
+        let existing = self
+            .annotations
+            .get(offset)
+            .unwrap_or_default()
+            .to_string();
+        self.open_prompt(
+            PromptKind::Annotate(offset),
+            &existing,
+        );
+    }
+
+    /// 设置注释并立即写入旁路文件
+    fn annotate(&mut self, offset: usize, text: &str) {
+        let Some(path) = self.notes_path.clone() else {
+            return;
+        };
+        if !self.annotations.set(offset, text) {
+            return;
+        }
+
+        self.status_message =
+            Some(match self.annotations.save(&path) {
+                Ok(()) if text.is_empty() => {
+                    tr!(
+                        "已删除 0x{} 的注释",
+                        format!("{:08X}", offset)
+                    )
+                }
+                Ok(()) => tr!(
+                    "已注释 0x{}，共 {} 条 → {}",
+                    format!("{:08X}", offset),
+                    self.annotations.len(),
+                    path.display()
+                ),
+                Err(e) => tr!("注释保存失败: {}", e),
+            });
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 执行 `:` 命令
+    fn run_command(&mut self, text: &str) {
+        let mut words = text.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+
+        let message = match command {
+            "highlight" | "hl" => {
+                self.command_highlight(&args)
+            }
+            "nohighlight" | "nohl" => {
+                self.command_nohighlight(&args)
+            }
+            "workspace" | "ws" => {
+                self.command_workspace(&args)
+            }
+            "goto" => self.command_goto(&args),
+            "packet" => self.command_packet(&args),
+            "jump" => self.command_jump(&args),
+            "search" => self.command_search(&args),
+            "filter" => self.command_filter(&args),
+            "select" => self.command_select(&args),
+            "export" => self.command_export(&args),
+            "copy" => match args.as_slice() {
+                [format] => self.copy_packet_as(format),
+                _ => Err(tr!(
+                    "用法: copy <c|rust|python|base64>"
+                )
+                .to_string()),
+            },
+            "theme" => self.command_theme(&args),
+            "columns" => self.command_columns(&args),
+            "fold" => self.command_fold(&args),
+            "unfold" => {
+                let count = self.folds.len();
+                self.change_folds(Folds::clear);
+                Ok(tr!("已展开 {} 处折叠", count))
+            }
+            _ => Err(tr!(
+                "未知命令: {}（可用: {}）",
+                command,
+                COMMANDS.join(" ")
+            )),
+        };
+        self.status_message =
+            Some(message.unwrap_or_else(|e| e));
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// `:highlight <模式> [颜色]`：添加高亮模式；无参数时列出已有模式
+    fn command_highlight(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let Some(pattern) = args.first() else {
+            if self.highlighter.is_empty() {
+                return Ok(tr!("没有高亮模式").to_string());
+            }
+            let items: Vec<String> = self
+                .highlighter
+                .patterns()
+                .iter()
+                .map(|pattern| {
+                    format!(
+                        "{} ({})",
+                        format_hex_bytes(&pattern.bytes),
+                        pattern.color
+                    )
+                })
+                .collect();
+            return Ok(tr!(
+                "高亮模式: {}",
+                items.join(", ")
+            ));
+        };
+
+        let bytes =
+            parse_hex_pattern(pattern).map_err(|_| {
+                tr!("无效的十六进制模式: {}", pattern)
+            })?;
+        let color = match args.get(1) {
+            Some(name) => name.parse().map_err(|_| {
+                tr!(
+                    "未知颜色: {} (可用: red green yellow blue magenta cyan white)",
+                    name
+                )
+            })?,
+            None => HighlightColor::Red,
+        };
+
+        let text = format_hex_bytes(&bytes);
+        self.highlighter
+            .add(HighlightPattern { bytes, color });
+        Ok(tr!(
+            "已高亮 {} ({})，共 {} 个模式",
+            text,
+            color,
+            self.highlighter.patterns().len()
+        ))
+    }
+
+    /// `:goto <偏移|范围|#包序号|#起-止>`：跳转到指定位置，范围时同时选中
+    fn command_goto(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let target = args.first().ok_or_else(|| {
+            tr!("用法: goto <偏移|范围|#包序号|#起-止>")
+                .to_string()
+        })?;
+        self.goto(target)?;
+        Ok(tr!("已跳转到 {}", target))
+    }
+
+    /// `:packet <包序号>`：跳转到第 N 个数据包（从 1 开始）
+    fn command_packet(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let number = args.first().ok_or_else(|| {
+            tr!("用法: packet <包序号>").to_string()
+        })?;
+        self.goto(&format!("#{}", number))?;
+        if self.pending_jump.is_some() {
+            return Ok(tr!(
+                "第 {} 个数据包尚未索引，索引到后跳转 (ESC 取消)",
+                number
+            ));
+        }
+        Ok(tr!("已跳转到第 {} 个数据包", number))
+    }
+
+    /// `:jump <max|min|len~长度|条件>`：跳到负载最长的数据包、最短的非空数据包、
+    /// 长度最接近给定值的数据包，或视口之后下一个满足条件的数据包
+    fn command_jump(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        if args.is_empty() {
+            return Err(tr!(
+                "用法: jump <max|min|len~长度|条件>"
+            )
+            .to_string());
+        }
+        let target = args
+            .concat()
+            .parse::<PacketTarget>()
+            .map_err(|e| e.to_string())?;
+        let index = target
+            .find(
+                self.parser.packets(),
+                self.viewport_offset(),
+            )
+            .ok_or_else(|| {
+                tr!("没有匹配 {} 的数据包", target)
+            })?;
+        let packet = &self.parser.packets()[index];
+        let (offset, len) =
+            (packet.offset, packet.header.packet_length);
+        self.record_jump();
+        self.scroll_to_offset(offset);
+        Ok(tr!(
+            "{}: 数据包 #{} (0x{})，长度 {} 字节",
+            target,
+            index + 1,
+            format!("{:08X}", offset),
+            len
+        ))
+    }
+
+    /// `:filter <字段><运算符><值>` 或 `:filter @名称`：不匹配的数据包暗色显示，
+    /// 并跳到视口之后第一个匹配的数据包；无参数时清除过滤
+    fn command_filter(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        if args.is_empty() {
+            self.packet_filter = None;
+            return Ok(tr!("已清除数据包过滤").to_string());
+        }
+
+        let filter = args
+            .concat()
+            .parse::<FilterSpec>()
+            .and_then(|spec| {
+                spec.resolve(&self.args.config.filters)
+            })
+            .map_err(|e| e.to_string())?;
+        let packets = self.parser.packets();
+        let matched = packets
+            .iter()
+            .filter(|packet| filter.matches(packet))
+            .count();
+        let viewport = self.viewport_offset();
+        let first = packets
+            .iter()
+            .filter(|packet| filter.matches(packet))
+            .find(|packet| packet.offset >= viewport)
+            .or_else(|| {
+                packets
+                    .iter()
+                    .find(|packet| filter.matches(packet))
+            })
+            .map(|packet| packet.offset);
+        let total = packets.len();
+        self.packet_filter = Some(filter);
+        if let Some(offset) = first {
+            self.record_jump();
+            self.scroll_to_offset(offset);
+        }
+        Ok(tr!(
+            "过滤 {}: {} / {} 个数据包匹配",
+            filter,
+            matched,
+            total
+        ))
+    }
+
+    /// `:fold [条件|@名称]`：折叠所有（或匹配条件的）数据包的负载
+    fn command_fold(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let filter = if args.is_empty() {
+            None
+        } else {
+            Some(
+                args.concat()
+                    .parse::<FilterSpec>()
+                    .and_then(|spec| {
+                        spec.resolve(
+                            &self.args.config.filters,
+                        )
+                    })
+                    .map_err(|e| e.to_string())?,
+            )
+        };
+        // 只用到数据包的偏移，不需要读取内容
+        let ranges: Vec<_> = self
+            .parser
+            .packet_refs(&[])
+            .zip(self.parser.packets())
+            .filter(|(_, packet)| {
+                filter.is_none_or(|filter| {
+                    filter.matches(packet)
+                })
+            })
+            .map(|(packet, _)| self.payload_lines(&packet))
+            .collect();
+        let folded = self.change_folds(|folds| {
+            ranges
+                .into_iter()
+                .filter(|lines| folds.fold(lines.clone()))
+                .count()
+        });
+        Ok(tr!("已折叠 {} 个数据包的负载", folded))
+    }
+
+    /// `:select <范围>`（如 `0x1000-0x2000`、`0x10+64`）：选中字节范围；
+    /// 无参数时取消选择
+    fn command_select(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let Some(text) = args.first() else {
+            self.selection = None;
+            return Ok(tr!("已取消选择").to_string());
+        };
+        let range = self
+            .parse_range(text)
+            .ok_or_else(|| tr!("无效的范围: {}", text))?;
+        let message = tr!(
+            "已选择 0x{}..0x{} ({} 字节)",
+            format!("{:08X}", range.start),
+            format!("{:08X}", range.end),
+            range.len()
+        );
+        self.selection = Some(range);
+        Ok(message)
+    }
+
+    /// `:export <selection|page|packet N|范围> <文件>`：将字节写入文件；
+    /// `:export packets [10-20,35] <文件>`：将数据包写入新文件
+    fn command_export(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let usage = || {
+            tr!(
+                "用法: export <selection|page|packet N|范围|packets [序号]> <文件>"
+            ).to_string()
+        };
+        let (path, what) =
+            args.split_last().ok_or_else(usage)?;
+        let range = match what {
+            ["packets", list @ ..] => {
+                return self.export_packets(list, path)
+            }
+            ["selection"] => {
+                self.selection.clone().ok_or_else(|| {
+                    tr!("没有选中的字节（:select <范围>）")
+                        .to_string()
+                })?
+            }
+            ["page"] => {
+                let start = self.viewport_offset();
+                let end = start
+                    + self.pagination.lines_per_page()
+                        * self.args.bytes_per_line();
+                start..end.min(self.file_data.len())
+            }
+            ["packet", number] => {
+                let packet = number
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| {
+                        number.checked_sub(1)
+                    })
+                    .and_then(|index| {
+                        self.parser.packet_ref(&[], index)
+                    })
+                    .ok_or_else(|| {
+                        tr!("无效的包序号: {}", number)
+                    })?;
+                packet.offset()
+                    ..packet
+                        .end_offset()
+                        .min(self.file_data.len())
+            }
+            [text] => {
+                self.parse_range(text).ok_or_else(|| {
+                    tr!("无效的范围: {}", text)
+                })?
+            }
+            _ => return Err(usage()),
+        };
+
+        let mut bytes = vec![0; range.len()];
+        self.file_data
+            .read_at(range.start as u64, &mut bytes)
+            .and_then(|read| {
+                Ok(std::fs::write(path, &bytes[..read])?)
+            })
+            .map_err(|e| tr!("导出失败: {}", e))?;
+        Ok(tr!("已导出 {} 字节 → {}", range.len(), path))
+    }
+
+    /// 将数据包写入新文件（沿用原文件头）：指定包序号时导出这些数据包，
+    /// 否则导出与选区重叠的数据包，没有选区时导出匹配过滤条件的数据包
+    fn export_packets(
+        &self,
+        list: &[&str],
+        path: &str,
+    ) -> std::result::Result<String, String> {
+        let packets = self.parser.packets();
+        let indices: Vec<usize> = match list {
+            [text] => {
+                let list = text
+                    .parse::<PacketList>()
+                    .map_err(|e| e.to_string())?;
+                (0..packets.len())
+                    .filter(|&index| list.contains(index))
+                    .collect()
+            }
+            [] => {
+                if let Some(selection) = &self.selection {
+                    self.parser
+                        .packet_refs(&[])
+                        .filter(|packet| {
+                            packet.offset() < selection.end
+                                && packet.end_offset()
+                                    > selection.start
+                        })
+                        .map(|packet| packet.index())
+                        .collect()
+                } else if let Some(filter) =
+                    &self.packet_filter
+                {
+                    (0..packets.len())
+                        .filter(|&index| {
+                            filter.matches(&packets[index])
+                        })
+                        .collect()
+                } else {
+                    return Err(tr!(
+                        "没有选区或过滤条件（:select / :filter），也可指定包序号，如 export packets 10-20,35 <文件>"
+                    ).to_string());
+                }
+            }
+            _ => {
+                return Err(tr!(
+                    "用法: export packets [序号] <文件>"
+                )
+                .to_string())
+            }
+        };
+        if indices.is_empty() {
+            return Err(
+                tr!("没有可导出的数据包").to_string()
+            );
+        }
+
+        let write = || -> Result<usize> {
+            let file_header = self
+                .parser
+                .file_header()
+                .cloned()
+                .unwrap_or_default();
+            let mut writer =
+                PcapWriter::create_with_header(
+                    path,
+                    &file_header,
+                )?;
+            for &index in &indices {
+                let Some((start, bytes)) =
+                    self.file_data
+                        .read_packet(&self.parser, index)?
+                else {
+                    continue;
+                };
+                if let Some(packet) = self
+                    .parser
+                    .packet_ref_in(&bytes, start, index)
+                {
+                    writer.copy_packet(&packet)?;
+                }
+            }
+            let count = writer.packets_written();
+            writer.finish()?;
+            Ok(count)
+        };
+        let count =
+            write().map_err(|e| tr!("导出失败: {}", e))?;
+        Ok(tr!("已导出 {} 个数据包 → {}", count, path))
+    }
+
+    /// 将光标（或视口起始处）所在数据包的负载按指定格式复制到剪贴板
+    fn copy_packet_as(
+        &self,
+        format: &str,
+    ) -> std::result::Result<String, String> {
+        let format = format.parse::<CopyFormat>()?;
+        let offset = if self.editing {
+            self.edit.cursor()
+        } else {
+            self.viewport_offset()
+        };
+        let packet =
+            self.packet_at_offset(offset).ok_or_else(
+                || tr!("当前位置没有数据包").to_string(),
+            )?;
+        let number = packet.index() + 1;
+        let text = format.format(
+            packet.payload(),
+            &format!("packet_{}", number),
+        );
+        self.terminal_manager
+            .set_clipboard(
+                &text,
+                &self.args.config.clipboard,
+            )
+            .map_err(|e| tr!("复制失败: {}", e))?;
+        Ok(tr!(
+            "已复制第 {} 个数据包负载 ({} 字节, {}) 到剪贴板",
+            number,
+            packet.payload().len(),
+            format
+        ))
+    }
+
+    /// `:search [名称]`：执行命名的查找，无参数时列出；
+    /// `:search save <名称> [模式]` 保存（省略模式时保存上一次查找），
+    /// `:search delete <名称>` 删除
+    fn command_search(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        match args {
+            [] if self.saved_searches.is_empty() => Ok(
+                tr!(
+                    "没有命名的查找（:search save <名称> [模式]）"
+                ).to_string(),
+            ),
+            [] => Ok(self
+                .saved_searches
+                .iter()
+                .map(|(name, pattern)| {
+                    format!("@{}={}", name, pattern)
+                })
+                .collect::<Vec<_>>()
+                .join("  ")),
+            ["save", name, pattern @ ..] => {
+                let pattern = if pattern.is_empty() {
+                    self.last_search
+                        .as_ref()
+                        .ok_or(tr!("还没有查找过，请给出模式"))?
+                        .to_string()
+                } else {
+                    let pattern = pattern.join(" ");
+                    pattern
+                        .parse::<SearchPattern>()
+                        .map_err(|e| e.to_string())?;
+                    pattern
+                };
+                let message = tr!(
+                    "已保存查找 @{}={}（:workspace 保存到工作区）",
+                    name,
+                    pattern
+                );
+                self.saved_searches
+                    .insert(name.to_string(), pattern);
+                Ok(message)
+            }
+            ["delete", name] => self
+                .saved_searches
+                .remove(*name)
+                .map(|_| tr!("已删除查找 @{}", name))
+                .ok_or_else(|| {
+                    tr!("没有名为 {} 的查找", name)
+                }),
+            [name] => {
+                self.start_search(&format!("@{}", name));
+                Ok(self.status_message.take().unwrap_or_else(
+                    || tr!("查找 @{}", name),
+                ))
+            }
+            _ => Err(tr!(
+                "用法: search [名称] | save <名称> [模式] | delete <名称>"
+            ).to_string()),
+        }
+    }
+
+    /// `:columns [布局|reset]`：设置十六进制视图的列布局，
+    /// reset 恢复配置文件中的布局；无参数时显示当前布局
+    fn command_columns(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        match args {
+            [] => return Ok(tr!("列: {}", self.columns)),
+            ["reset"] => {
+                self.columns =
+                    self.args.config.columns.clone()
+            }
+            _ => self.columns = args.concat().parse()?,
+        }
+        self.update_page_size();
+        Ok(tr!("列布局: {}", self.columns))
+    }
+
+    /// `:theme <mono|color>`：切换单色或彩色显示
+    fn command_theme(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let message = match args.first() {
+            Some(&"mono") => {
+                colored::control::set_override(false);
+                tr!("主题: 单色")
+            }
+            Some(&"color") => {
+                colored::control::set_override(true);
+                tr!("主题: 彩色")
+            }
+            _ => {
+                return Err(tr!("用法: theme <mono|color>")
+                    .to_string())
+            }
+        };
+        self.terminal_manager.invalidate();
+        Ok(message.to_string())
+    }
+
+    /// 跳转到输入的目标（偏移或 #包序号），编辑模式下同时移动光标；
+    /// 目标为范围（如 `0x1000-0x2000`、`#5-10`）时跳转到起始处并选中该范围
+    fn goto(
+        &mut self,
+        text: &str,
+    ) -> std::result::Result<(), String> {
+        if let Some(range) = self.parse_goto_range(text) {
+            self.record_jump();
+            self.scroll_to_offset(range.start);
+            if self.editing {
+                self.edit.set_cursor(
+                    range.start,
+                    self.file_data.len(),
+                );
+            }
+            self.selection = Some(range);
+            return Ok(());
+        }
+        // 后台索引尚未到达的数据包，索引到后再跳转
+        if let Some(index) = self.unindexed_packet(text) {
+            self.record_jump();
+            self.pending_jump =
+                Some(PendingJump::Packet(index));
+            self.pagination.go_to_last_page();
+            return Ok(());
+        }
+        let offset =
+            self.parse_goto_target(text).ok_or_else(
+                || tr!("无效的跳转目标: {}", text),
+            )?;
+        self.record_jump();
+        self.scroll_to_offset(offset);
+        if self.editing {
+            self.edit
+                .set_cursor(offset, self.file_data.len());
+        }
+        Ok(())
+    }
+
+    /// 解析字节范围（`0x1000-0x2000`、`0x1000-`、`<起始>..<结束>` 或
+    /// `<起始>+<长度>`，语法见 `core::range`）
+    fn parse_range(
+        &self,
+        text: &str,
+    ) -> Option<Range<usize>> {
+        text.parse::<OffsetRange>()
+            .ok()?
+            .resolve(self.file_data.len())
+    }
+
+    /// 解析跳转范围：字节范围或 `#5-10`（第 5 到第 10 个数据包）
+    fn parse_goto_range(
+        &self,
+        text: &str,
+    ) -> Option<Range<usize>> {
+        let Some(list) = text.strip_prefix('#') else {
+            return self.parse_range(text);
+        };
+        if !list.contains('-') {
+            return None;
+        }
+        let (first, last) =
+            list.parse::<PacketList>().ok()?.first()?;
+        let packets = self.parser.packets();
+        let last = last
+            .unwrap_or(packets.len().saturating_sub(1))
+            .min(packets.len().checked_sub(1)?);
+        let start =
+            self.parser.packet_ref(&[], first)?.offset();
+        let end = self
+            .parser
+            .packet_ref(&[], last)?
+            .end_offset()
+            .min(self.file_data.len());
+        (start < end).then_some(start..end)
+    }
+
+    /// `:nohighlight [模式]`：移除指定模式；无参数时移除全部
+    fn command_nohighlight(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let Some(pattern) = args.first() else {
+            self.highlighter.clear();
+            return Ok(
+                tr!("已清除所有高亮模式").to_string()
+            );
+        };
+
+        let bytes =
+            parse_hex_pattern(pattern).map_err(|_| {
+                tr!("无效的十六进制模式: {}", pattern)
+            })?;
+        if self.highlighter.remove(&bytes) {
+            Ok(tr!(
+                "已移除高亮 {}",
+                format_hex_bytes(&bytes)
+            ))
+        } else {
+            Err(tr!(
+                "没有该高亮模式: {}",
+                format_hex_bytes(&bytes)
+            ))
+        }
+    }
+
+    /// `:workspace [路径]`：将当前文件及其状态、注释保存为工作区；
+    /// 省略路径时保存到最近打开或保存的工作区
+    fn command_workspace(
+        &mut self,
+        args: &[&str],
+    ) -> std::result::Result<String, String> {
+        let path = match args.first() {
+            Some(path) => {
+                let mut path = PathBuf::from(path);
+                if path.extension().is_none() {
+                    path.set_extension(WORKSPACE_EXTENSION);
+                }
+                path
+            }
+            None => self
+                .workspace_path
+                .clone()
+                .ok_or_else(|| {
+                    tr!("用法: workspace <文件.pvw>")
+                        .to_string()
+                })?,
+        };
+
+        // 依次切换到每个文件收集状态，再回到当前文件
+        let active = self.active_file;
+        let mut files = Vec::new();
+        for index in 0..self.sessions.len() {
+            self.switch_file(index);
+            files.push(WorkspaceFile {
+                path: self.args.file_path.clone(),
+                state: self.current_state(),
+                notes: self.annotations.clone(),
+            });
+        }
+        self.switch_file(active);
+        let workspace = Workspace {
+            active,
+            files,
+            searches: self.saved_searches.clone(),
+        };
+        workspace
+            .save(&path)
+            .map_err(|e| tr!("工作区保存失败: {}", e))?;
+        let message =
+            tr!("已保存工作区 {}", path.display());
+        self.workspace_path = Some(path);
+        Ok(message)
+    }
+
+    /// 打开时间平移输入框
+    fn open_time_shift_prompt(&mut self) {
+        if self.args.follow {
+            self.status_message =
+                Some(tr!("跟随模式下不能编辑").to_string());
+            return;
+        }
+        self.open_prompt(PromptKind::TimeShift, "");
+    }
+
+    /// 平移所有数据包时间戳：`-3600s` 为相对偏移，`@<时间>` 将首包对齐到该时间
+    ///
+    /// 整个平移作为一次可撤销的修改，返回被平移的数据包数量
+    fn shift_timestamps(
+        &mut self,
+        text: &str,
+    ) -> Result<usize> {
+        let shift = match text.strip_prefix('@') {
+            Some(time) => TimeShift::Rebase(
+                timeshift::parse_time(time.trim())?,
+            ),
+            None => TimeShift::Offset(
+                timeshift::parse_offset(text)?,
+            ),
+        };
+
+        let packets = self.parser.packets();
+        let offset = shift.offset_nanos(
+            packets.first().map(|packet| &packet.header),
+        );
+        let endianness = self.parser.endianness();
+        let record = &self.args.record;
+        let mut values =
+            Vec::with_capacity(packets.len() * 8);
+        for packet in packets {
+            let header = timeshift::shift_header(
+                &packet.header,
+                offset,
+            )?;
+            let fields = [
+                (
+                    RecordField::TimestampSeconds,
+                    header.timestamp_seconds,
+                ),
+                (
+                    RecordField::TimestampNanoseconds,
+                    header.timestamp_nanoseconds,
+                ),
+            ];
+            for (field, value) in fields {
+                if let Some((start, bytes)) = record.encode(
+                    field,
+                    value as u64,
+                    endianness,
+                ) {
+                    values.extend(
+                        bytes.into_iter().enumerate().map(
+                            |(i, byte)| {
+                                (
+                                    packet.offset
+                                        + start
+                                        + i,
+                                    byte,
+                                )
+                            },
+                        ),
+                    );
+                }
+            }
+        }
+        let count = packets.len();
+
+        let data = self.file_data.load()?;
+        self.edit.apply_batch(data, values);
+        let parser = PcapParser::from_bytes_with_options(
+            data,
+            self.args.parser_options(),
+        )?;
+        self.set_parser(parser);
+        self.last_display_start_line = usize::MAX;
+        Ok(count)
+    }
+
+    /// 解析跳转目标：`0x1F0`（十六进制偏移）、`496`（十进制偏移）、`#12`（第 12 个数据包）
+    fn parse_goto_target(
+        &self,
+        text: &str,
+    ) -> Option<usize> {
+        let offset =
+            if let Some(number) = text.strip_prefix('#') {
+                let index = number
+                    .parse::<usize>()
+                    .ok()?
+                    .checked_sub(1)?;
+                self.parser.packets().get(index)?.offset
+            } else {
+                parse_offset(text)?
+            };
+        (offset < self.full_len()).then_some(offset)
+    }
+
+    /// 跳转目标 `#<包序号>` 指向后台索引尚未到达的数据包时返回其序号
+    fn unindexed_packet(
+        &self,
+        text: &str,
+    ) -> Option<usize> {
+        self.indexer.as_ref()?;
+        let index = text
+            .strip_prefix('#')?
+            .parse::<usize>()
+            .ok()?
+            .checked_sub(1)?;
+        (index >= self.parser.packets().len())
+            .then_some(index)
+    }
+
+    /// 视口起始处的数据行（折叠时与显示行不同）
+    fn top_line(&self) -> usize {
+        self.folds.line_of_row(
+            self.pagination.display_start_line(),
+        )
+    }
+
+    /// 视口起始处的字节偏移
+    fn viewport_offset(&self) -> usize {
+        self.top_line() * self.args.bytes_per_line()
+    }
+
+    /// 视口末尾（最后一个显示行之后）的字节偏移
+    fn viewport_end(&self) -> usize {
+        self.folds.line_of_row(
+            self.pagination.display_start_line()
+                + self.pagination.lines_per_page(),
+        ) * self.args.bytes_per_line()
+    }
+
+    /// 记录当前视口位置到跳转历史
+    fn record_jump(&mut self) {
+        let offset = self.viewport_offset();
+        self.jump_list.push(offset);
+    }
+
+    /// 后退到跳转历史中的上一个位置
+    fn jump_back(&mut self) {
+        let current = self.viewport_offset();
+        match self.jump_list.back(current) {
+            Some(offset) => self.scroll_to_offset(offset),
+            None => {
+                self.status_message = Some(
+                    tr!("已是最早的跳转位置").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 前进到跳转历史中的下一个位置
+    fn jump_forward(&mut self) {
+        match self.jump_list.forward() {
+            Some(offset) => self.scroll_to_offset(offset),
+            None => {
+                self.status_message = Some(
+                    tr!("已是最新的跳转位置").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 切换视口起始处的书签
+    fn toggle_bookmark(&mut self) {
+        if self.file_data.is_empty() {
+            return;
+        }
+        let offset = self.viewport_offset();
+        let added = self.bookmarks.toggle(offset);
+        self.status_message = Some(if added {
+            tr!(
+                "已添加书签 0x{}，共 {} 个 (' / ` 跳转)",
+                format!("{:08X}", offset),
+                self.bookmarks.len()
+            )
+        } else {
+            tr!(
+                "已删除书签 0x{}",
+                format!("{:08X}", offset)
+            )
+        });
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 跳转到视口之后（或之前）的下一个书签，到头时循环
+    fn jump_to_bookmark(&mut self, forward: bool) {
+        let current = self.viewport_offset();
+        let target = if forward {
+            self.bookmarks.next_after(current)
+        } else {
+            self.bookmarks.prev_before(current)
+        };
+
+        match target {
+            Some(offset) => {
+                self.record_jump();
+                self.scroll_to_offset(offset);
+                self.status_message = Some(tr!(
+                    "书签 0x{}",
+                    format!("{:08X}", offset)
+                ));
+            }
+            None => {
+                self.status_message = Some(
+                    tr!("没有书签 (按 b 添加)").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 应用工作区中保存的状态与注释（替换各文件的高亮、书签与注释），
+    /// 打开的文件须与工作区中的文件一一对应
+    pub fn open_workspace(
+        &mut self,
+        workspace_path: PathBuf,
+        workspace: &Workspace,
+    ) {
+        for (index, file) in
+            workspace.files.iter().enumerate()
+        {
+            self.switch_file(index);
+            self.highlighter.clear();
+            self.apply_state(file.state.clone());
+            self.annotations = file.notes.clone();
+        }
+        self.switch_file(
+            workspace.active.min(self.sessions.len() - 1),
+        );
+        self.saved_searches.extend(
+            workspace.searches.iter().map(
+                |(name, pattern)| {
+                    (name.clone(), pattern.clone())
+                },
+            ),
+        );
+        self.status_message = Some(tr!(
+            "已打开工作区 {} ({} 个文件)",
+            workspace_path.display(),
+            self.sessions.len()
+        ));
+        self.workspace_path = Some(workspace_path);
+    }
+
+    /// 恢复上次关闭该文件时保存的查看状态
+    fn restore_state(&mut self) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        let state = match store.load(&self.args.file_path) {
+            Ok(Some(state)) => state,
+            Ok(None) => return,
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("查看状态加载失败: {}", e));
+                return;
+            }
+        };
+
+        let offset = state.offset;
+        if self.apply_state(state) {
+            self.status_message = Some(tr!(
+                "已恢复上次位置 0x{} (使用 --fresh 从头打开)",
+                format!("{:08X}", offset)
+            ));
+        }
+    }
+
+    /// 应用保存的查看状态，返回是否移动了视口
+    fn apply_state(&mut self, state: ViewerState) -> bool {
+        // 文件可能已被截断，超出范围的位置直接丢弃
+        let len = self.full_len();
+        for highlight in &state.highlights {
+            let (Ok(bytes), Ok(color)) = (
+                parse_hex_pattern(&highlight.pattern),
+                highlight.color.parse(),
+            ) else {
+                continue;
+            };
+            self.highlighter
+                .add(HighlightPattern { bytes, color });
+        }
+        self.bookmarks = state
+            .bookmarks
+            .into_iter()
+            .filter(|&offset| offset < len)
+            .collect();
+        if state.offset == 0 || state.offset >= len {
+            return false;
+        }
+        self.scroll_to_offset(state.offset);
+        true
+    }
+
+    /// 当前查看状态（视口位置、高亮与书签）
+    fn current_state(&self) -> ViewerState {
+        ViewerState {
+            offset: self.viewport_offset(),
+            bytes_per_line: self.args.bytes_per_line(),
+            highlights: self
+                .highlighter
+                .patterns()
+                .iter()
+                .map(|pattern| SavedHighlight {
+                    pattern: format_hex_bytes(
+                        &pattern.bytes,
+                    ),
+                    color: pattern.color.to_string(),
+                })
+                .collect(),
+            bookmarks: self.bookmarks.iter().collect(),
+        }
+    }
+
+    /// 保存当前查看状态（失败时仅记录日志，不影响退出）
+    fn save_state(&self) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        if let Err(e) = store.save(
+            &self.args.file_path,
+            self.current_state(),
+        ) {
+            tracing::warn!(
+                "Failed to save view state ({}): {}",
+                store.path().display(),
+                e
+            );
+        }
+    }
+
+    /// 切换时间异常标记，打开时汇总异常数量
+    fn toggle_timing_overlay(&mut self) {
+        self.show_timing = !self.show_timing;
+        self.status_message = Some(if self.show_timing {
+            let issues = timing::find_issues(
+                self.parser.packets(),
+                self.args.gap_threshold,
+            );
+            let backwards = issues
+                .iter()
+                .filter(|issue| {
+                    issue.kind == TimingIssueKind::Backwards
+                })
+                .count();
+            tr!(
+                "时间异常: {} 处间隔, {} 处时间倒退 ([ / ] 跳转)",
+                issues.len() - backwards,
+                backwards
+            )
+        } else {
+            tr!("已关闭时间异常标记").to_string()
+        });
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 跳转到视口之后（或之前）的下一个时间异常
+    fn jump_to_timing_issue(&mut self, forward: bool) {
+        let bytes_per_line = self.args.bytes_per_line();
+        let current_line = self.top_line();
+        let issues = timing::find_issues(
+            self.parser.packets(),
+            self.args.gap_threshold,
+        );
+        let target = if forward {
+            issues.iter().find(|issue| {
+                issue.offset / bytes_per_line > current_line
+            })
+        } else {
+            issues.iter().rev().find(|issue| {
+                issue.offset / bytes_per_line < current_line
+            })
+        };
+
+        match target {
+            Some(issue) => {
+                self.record_jump();
+                self.scroll_to_offset(issue.offset);
+                self.show_timing = true;
+                self.status_message = Some(tr!(
+                    "数据包 #{} (0x{}): {} {} 秒",
+                    issue.index + 1,
+                    format!("{:08X}", issue.offset),
+                    timing_issue_label(issue.kind),
+                    format_delta(issue.delta_nanos)
+                ));
+            }
+            None => {
+                self.status_message = Some(
+                    tr!("没有更多时间异常").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 跳转到视口首行之后的下一个问题，到末尾时回到第一个
+    fn jump_to_diagnostic(&mut self) {
+        let data = match self.file_data.load() {
+            Ok(data) => data,
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("读取文件失败: {}", e));
+                return;
+            }
+        };
+        let list =
+            diagnostics::diagnose(data, &self.parser);
+        let bytes_per_line = self.args.bytes_per_line();
+        let current_line = self.top_line();
+        let position = list
+            .iter()
+            .position(|diagnostic| {
+                diagnostic.offset / bytes_per_line
+                    > current_line
+            })
+            .or((!list.is_empty()).then_some(0));
+
+        match position {
+            Some(position) => {
+                let diagnostic = &list[position];
+                self.record_jump();
+                self.scroll_to_offset(diagnostic.offset);
+                self.status_message = Some(tr!(
+                    "问题 {}/{} (0x{}): {}",
+                    position + 1,
+                    list.len(),
+                    format!("{:08X}", diagnostic.offset),
+                    diagnostic_label(diagnostic)
+                ));
+            }
+            None => {
+                self.status_message =
+                    Some(tr!("未发现问题").to_string())
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 数据包与前一个数据包之间的时间异常标记（未开启标记时为空）
+    fn format_timing_marker(
+        &self,
+        packet: &PacketRef<'_>,
+    ) -> String {
+        if !self.show_timing {
+            return String::new();
+        }
+        let Some(previous) =
+            packet.index().checked_sub(1).and_then(
+                |index| self.parser.packets().get(index),
+            )
+        else {
+            return String::new();
+        };
+        let Some((kind, delta)) = timing::check_pair(
+            &previous.header,
+            packet.header(),
+            self.args.gap_threshold,
+        ) else {
+            return String::new();
+        };
+
+        let marker = format!(
+            " ⚠ {} {}s",
+            timing_issue_label(kind),
+            format_delta(delta)
+        );
+        match kind {
+            TimingIssueKind::Gap => {
+                marker.bright_yellow().bold().to_string()
+            }
+            TimingIssueKind::Backwards => {
+                marker.bright_red().bold().to_string()
+            }
+        }
+    }
+
+    /// 与前一个数据包的时间差 Δt（第一个数据包时为空）
+    fn format_packet_delta(
+        &self,
+        packet: &PacketRef<'_>,
+    ) -> String {
+        let Some(previous) =
+            packet.index().checked_sub(1).and_then(
+                |index| self.parser.packets().get(index),
+            )
+        else {
+            return String::new();
+        };
+        let delta = packet.header().timestamp_nanos()
+            - previous.header.timestamp_nanos();
+        let text =
+            format!(" Δt: {:+.6}s", delta as f64 / 1e9);
+        if delta < 0 {
+            text.bright_red().to_string()
+        } else {
+            text.bright_cyan().to_string()
+        }
+    }
+
+    /// 滚动使指定偏移所在行位于视口顶部
+    fn scroll_to_offset(&mut self, offset: usize) {
+        if offset >= self.file_data.len()
+            && self.indexer.is_some()
+        {
+            // 目标尚未加载：先停在已加载部分的末尾，加载到后再跳转
+            self.pending_jump =
+                Some(PendingJump::Offset(offset));
+            self.pagination.go_to_last_page();
+            return;
+        }
+        // 目标在折叠的负载中时展开
+        let line = offset / self.args.bytes_per_line();
+        if self.folds.unfold(line).is_some() {
+            self.update_total_lines();
+        }
+        self.pagination
+            .go_to_line(self.folds.row_of_line(line));
+    }
+
+    /// 执行目标已加载（或已索引）的等待中跳转；
+    /// 加载结束仍未到达目标时放弃跳转
+    fn resolve_pending_jump(&mut self) {
+        let Some(pending) = self.pending_jump else {
+            return;
+        };
+        let target = match pending {
+            PendingJump::Offset(offset) => (offset
+                < self.file_data.len())
+            .then_some(offset),
+            PendingJump::Packet(index) => self
+                .parser
+                .packets()
+                .get(index)
+                .map(|packet| packet.offset),
+        };
+        match target {
+            Some(offset) => {
+                self.pending_jump = None;
+                self.scroll_to_offset(offset);
+            }
+            None if self.indexer.is_none() => {
+                self.pending_jump = None;
+                self.pagination.go_to_last_page();
+                self.status_message = Some(
+                    tr!("跳转目标超出文件范围").to_string(),
+                );
+            }
+            // 等待期间跟随已加载部分的末尾
+            None => self.pagination.go_to_last_page(),
+        }
+    }
+
+    /// 撤销最近一次字节修改
+    fn undo(&mut self) {
+        match self
+            .file_data
+            .resident_mut()
+            .and_then(|data| self.edit.undo(data))
+        {
+            Some(offset) => {
+                self.after_history_change(offset)
+            }
+            None => {
+                self.status_message = Some(
+                    tr!("没有可撤销的修改").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 重做最近一次撤销的修改
+    fn redo(&mut self) {
+        match self
+            .file_data
+            .resident_mut()
+            .and_then(|data| self.edit.redo(data))
+        {
+            Some(offset) => {
+                self.after_history_change(offset)
+            }
+            None => {
+                self.status_message = Some(
+                    tr!("没有可重做的修改").to_string(),
+                )
+            }
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 撤销 / 重做后刷新结构并使被修改的字节可见
+    fn after_history_change(&mut self, offset: usize) {
+        self.on_byte_changed(offset);
+        self.pagination.ensure_line_visible(
+            offset / self.args.bytes_per_line(),
+        );
+    }
+
+    /// 保存到指定路径，结果显示在提示信息中
+    fn save(&mut self, path: PathBuf) {
+        self.status_message =
+            Some(match self.save_to(&path) {
+                Ok(Some(backup)) => tr!(
+                    "已保存到 {}（原文件备份: {}）",
+                    path.display(),
+                    backup.display()
+                ),
+                Ok(None) => {
+                    tr!("已保存到 {}", path.display())
+                }
+                Err(e) => tr!("保存失败: {}", e),
+            });
+    }
+
+    /// 写出当前内容，覆盖已有文件前先备份，返回本次创建的备份路径
+    fn save_to(
+        &mut self,
+        path: &Path,
+    ) -> Result<Option<PathBuf>> {
+        if source::is_remote(path) {
+            return Err(io::Error::other(
+                "cannot save to a remote source",
+            )
+            .into());
+        }
+
+        // 每个文件在本次会话中只备份一次，保证备份为最初内容
+        let mut backup = None;
+        if path.exists()
+            && self.backed_up.insert(path.to_path_buf())
+        {
+            let backup_path = with_suffix(path, ".bak");
+            std::fs::copy(path, &backup_path)?;
+            backup = Some(backup_path);
+        }
+
+        // 先写入临时文件再替换，避免写入中断时损坏原文件
+        let temp_path = with_suffix(path, ".tmp");
+        let mut writer = PcapWriter::without_header(
+            BufWriter::new(File::create(&temp_path)?),
+        );
+        writer.write_raw_bytes(self.file_data.load()?)?;
+        writer.finish()?;
+        std::fs::rename(&temp_path, path)?;
+
+        self.edit.mark_saved();
+        self.args.file_path = path.to_path_buf();
+        Ok(backup)
+    }
+
+    /// 处理 TCP 流视图中的按键
+    fn handle_stream_view_key(&mut self, code: KeyCode) {
+        let Some(stream_view) = self.stream_view.as_mut()
+        else {
+            return;
+        };
+
+        let pagination = stream_view.pagination_mut();
+        match code {
+            KeyCode::Esc
+            | KeyCode::Char('q')
+            | KeyCode::Char('f') => {
+                self.stream_view = None;
+            }
+            KeyCode::Up => pagination.scroll_up(),
+            KeyCode::Down => pagination.scroll_down(),
+            KeyCode::Left => pagination.page_up(),
+            KeyCode::Right => pagination.page_down(),
+            KeyCode::Home => pagination.go_to_first_page(),
+            KeyCode::End => pagination.go_to_last_page(),
+            _ => {}
+        }
+
+        // 流视图不影响主视图的起始行，需强制重绘
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 处理字节频率视图中的按键
+    fn handle_frequency_view_key(&mut self, code: KeyCode) {
+        let Some(frequency_view) =
+            self.frequency_view.as_mut()
+        else {
+            return;
+        };
+
+        let pagination = frequency_view.pagination_mut();
+        match code {
+            KeyCode::Esc
+            | KeyCode::Char('q')
+            | KeyCode::Char('F') => {
+                self.frequency_view = None;
+            }
+            KeyCode::Up => pagination.scroll_up(),
+            KeyCode::Down => pagination.scroll_down(),
+            KeyCode::Left => pagination.page_up(),
+            KeyCode::Right => pagination.page_down(),
+            KeyCode::Home => pagination.go_to_first_page(),
+            KeyCode::End => pagination.go_to_last_page(),
+            _ => {}
+        }
+
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 打开字节频率视图：有选中范围时统计选中的字节，否则统计当前数据包的负载
+    fn open_frequency_view(&mut self) {
+        let lines_per_page = self.view_lines_per_page();
+        if let Some(range) = self.selection.clone() {
+            let end = range.end.min(self.file_data.len());
+            let start = range.start.min(end);
+            let mut bytes = vec![0; end - start];
+            if let Err(e) = self
+                .file_data
+                .read_at(start as u64, &mut bytes)
+            {
+                self.status_message =
+                    Some(tr!("读取文件失败: {}", e));
+                return;
+            }
+            self.frequency_view = Some(FrequencyView::new(
+                &bytes,
+                tr!(
+                    "选中范围 0x{}..0x{}",
+                    format!("{:08X}", start),
+                    format!("{:08X}", end)
+                ),
+                lines_per_page,
+            ));
+            return;
+        }
+
+        let offset = if self.editing {
+            self.edit.cursor()
+        } else {
+            self.viewport_offset()
+        };
+        let Some(packet) = self.packet_at_offset(offset)
+        else {
+            self.status_message =
+                Some(tr!("当前位置没有数据包").to_string());
+            return;
+        };
+        let view = FrequencyView::new(
+            packet.payload(),
+            tr!("数据包 #{} 负载", packet.index() + 1),
+            lines_per_page,
+        );
+        self.frequency_view = Some(view);
+    }
+
+    /// 处理统计视图中的按键
+    fn handle_stats_view_key(&mut self, code: KeyCode) {
+        let Some(stats_view) = self.stats_view.as_mut()
+        else {
+            return;
+        };
+
+        match code {
+            KeyCode::Esc
+            | KeyCode::Char('q')
+            | KeyCode::Char('s') => {
+                self.stats_view = None;
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                stats_view
+                    .resize_buckets(&self.parser, true)
+            }
+            KeyCode::Char('-') => stats_view
+                .resize_buckets(&self.parser, false),
+            code => {
+                let pagination =
+                    stats_view.pagination_mut();
+                match code {
+                    KeyCode::Up => pagination.scroll_up(),
+                    KeyCode::Down => {
+                        pagination.scroll_down()
+                    }
+                    KeyCode::Left => pagination.page_up(),
+                    KeyCode::Right => {
+                        pagination.page_down()
+                    }
+                    KeyCode::Home => {
+                        pagination.go_to_first_page()
+                    }
+                    KeyCode::End => {
+                        pagination.go_to_last_page()
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // 统计视图不影响主视图的起始行，需强制重绘
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 以视口所在数据包的四元组打开 TCP 流跟踪视图
+    fn open_stream_view(&mut self) {
+        let viewport_offset = self.viewport_offset();
+        let Some(packet) =
+            self.packet_at_offset(viewport_offset)
+        else {
+            self.status_message =
+                Some(tr!("当前位置没有数据包").to_string());
+            return;
+        };
+
+        let decoded = decoder::decode(
+            self.args.linktype(),
+            packet.payload(),
+        );
+        let Some(key) = StreamKey::from_decoded(&decoded)
+        else {
+            let protocols: Vec<&str> = decoded
+                .layers
+                .iter()
+                .map(|layer| layer.name())
+                .collect();
+            self.status_message = Some(tr!(
+                "包 #{} 不是 TCP 数据包 ({})",
+                packet.index() + 1,
+                protocols.join("/")
+            ));
+            return;
+        };
+
+        // 按捕获顺序重组该会话的所有数据包
+        let data = match self.file_data.load() {
+            Ok(data) => data,
+            Err(e) => {
+                self.status_message =
+                    Some(tr!("读取文件失败: {}", e));
+                return;
+            }
+        };
+        let mut reassembler = StreamReassembler::new(key);
+        for packet in self.parser.packet_refs(data) {
+            reassembler.push(&decoder::decode(
+                self.args.linktype(),
+                packet.payload(),
+            ));
+        }
+
+        let key = *reassembler.key();
+        let packet_count = reassembler.packet_count();
+        self.stream_view = Some(StreamView::new(
+            key,
+            reassembler.finish(),
+            packet_count,
+            self.view_lines_per_page(),
+        ));
+    }
+
+    /// 更新终端尺寸
+    fn update_terminal_size(&mut self) -> Result<bool> {
+        let lines_changed = self.update_page_size();
+
+        // 宽度变化同样需要重绘（可能在完整布局与精简视图之间切换）
+        let terminal_size =
+            self.terminal_manager.get_size();
+        let size_changed = lines_changed
+            || terminal_size != self.last_terminal_size;
+        self.last_terminal_size = terminal_size;
+        if size_changed {
+            self.invalidate_lines();
+        }
+
+        Ok(size_changed)
+    }
+
+    /// 按终端尺寸与布局重新计算各视图每页的行数，返回主视图每页行数是否变化
+    fn update_page_size(&mut self) -> bool {
+        let (width, height) =
+            self.terminal_manager.get_size();
+        let new_lines_per_page = self
+            .screen_layout(Rect::new(0, height, width))
+            .page
+            .height
+            .max(1)
+            * self.lines_per_row();
+        let view_lines_per_page =
+            self.view_lines_per_page();
+        let lines_changed = new_lines_per_page
+            != self.pagination.lines_per_page();
+
+        if lines_changed {
+            // 更新分页信息
+            self.pagination
+                .update_lines_per_page(new_lines_per_page);
+            if let Some(stream_view) =
+                self.stream_view.as_mut()
+            {
+                stream_view
+                    .pagination_mut()
+                    .update_lines_per_page(
+                        view_lines_per_page,
+                    );
+            }
+            if let Some(stats_view) =
+                self.stats_view.as_mut()
+            {
+                stats_view
+                    .pagination_mut()
+                    .update_lines_per_page(
+                        view_lines_per_page,
+                    );
+            }
+            if let Some(frequency_view) =
+                self.frequency_view.as_mut()
+            {
+                frequency_view
+                    .pagination_mut()
+                    .update_lines_per_page(
+                        view_lines_per_page,
+                    );
+            }
+        }
+        lines_changed
+    }
+
+    /// 并排两栏时每栏的宽度（未开启、终端不够宽或并排比较时为 None）；
+    /// 最右一列留给小地图，两栏之间留 1 列分隔
+    fn dual_column_width(&self) -> Option<usize> {
+        if !self.dual_columns
+            || self.diff.is_some()
+            || self.args.accessible
+        {
+            return None;
+        }
+        let (width, _) = self.terminal_manager.get_size();
+        let half = width.saturating_sub(2) / 2;
+        (width >= DUAL_MIN_WIDTH
+            && half >= self.min_layout_width())
+        .then_some(half)
+    }
+
+    /// 每个屏幕行显示的数据行数（并排两栏时为 2）
+    fn lines_per_row(&self) -> usize {
+        if self.dual_column_width().is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// 数据包负载中完整占据的数据行（不含与包头或下一个数据包共用的行）
+    fn payload_lines(
+        &self,
+        packet: &PacketRef<'_>,
+    ) -> Range<usize> {
+        let bytes_per_line = self.args.bytes_per_line();
+        packet.payload_offset().div_ceil(bytes_per_line)
+            ..packet.end_offset() / bytes_per_line
+    }
+
+    /// 修改折叠，视口保持在原来的数据行
+    fn change_folds<R>(
+        &mut self,
+        change: impl FnOnce(&mut Folds) -> R,
+    ) -> R {
+        let line = self.top_line();
+        let result = change(&mut self.folds);
+        self.update_total_lines();
+        self.pagination
+            .go_to_line(self.folds.row_of_line(line));
+        self.last_display_start_line = usize::MAX; // 强制重绘
+        result
+    }
+
+    /// 折叠或展开视口处数据包的负载
+    fn toggle_fold(&mut self) {
+        let Some(lines) = self
+            .packet_at_offset(self.viewport_offset())
+            .map(|packet| self.payload_lines(&packet))
+        else {
+            self.status_message =
+                Some(tr!("视口处没有数据包").to_string());
+            return;
+        };
+        let folded = self.folds.get(lines.start).is_some();
+        self.status_message = Some(if folded {
+            self.change_folds(|folds| {
+                folds.unfold(lines.start)
+            });
+            tr!("已展开负载").to_string()
+        } else if self
+            .change_folds(|folds| folds.fold(lines.clone()))
+        {
+            tr!("已折叠 {} 行负载", lines.len())
+        } else {
+            tr!("负载不足一整行，无法折叠").to_string()
+        });
+    }
+
+    /// 开关并排两栏显示
+    fn toggle_dual_columns(&mut self) {
+        self.dual_columns = !self.dual_columns;
+        self.update_page_size();
+        self.status_message = Some(
+            match (
+                self.dual_columns,
+                self.dual_column_width(),
+            ) {
+                (false, _) => tr!("已关闭双栏显示").to_string(),
+                (true, Some(_)) => {
+                    tr!("已开启双栏显示").to_string()
+                }
+                (true, None) => tr!(
+                    "已开启双栏显示（终端宽度不足 {} 列或并排比较中，暂按单栏显示）",
+                    DUAL_MIN_WIDTH.max(
+                        self.min_layout_width() * 2 + 2
+                    )
+                ),
+            },
+        );
+        self.last_display_start_line = usize::MAX; // 强制重绘
+    }
+
+    /// 统计视图与 TCP 流视图每页的行数（只保留底部区域）
+    fn view_lines_per_page(&self) -> usize {
+        let (_, height) = self.terminal_manager.get_size();
+        height.saturating_sub(FOOTER_LINES).max(1)
+    }
+
+    /// 显示完整布局所需的最小终端宽度（更窄的行可左右滚动查看）
+    fn min_screen_width(&self) -> usize {
+        self.min_layout_width().min(MIN_SCROLL_WIDTH)
+    }
+
+    /// 数据行可用的宽度（最右一列显示小地图时除外）
+    fn content_width(&self) -> usize {
+        let (width, _) = self.terminal_manager.get_size();
+        width.saturating_sub(usize::from(self.show_minimap))
+    }
+
+    /// 当前页最宽的数据行的显示宽度（并排两栏时各栏已适配宽度，为 0）
+    fn widest_page_line(&self) -> usize {
+        if self.dual_column_width().is_some() {
+            return 0;
+        }
+        self.page_rows()
+            .filter_map(|row| self.row_text(&row).ok())
+            .map(|line| styled_display_width(&line))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// 实际的横向滚动列数（不超过最宽的行恰好显示完整处）与右侧是否还有内容
+    fn horizontal_scroll(
+        &self,
+        width: usize,
+    ) -> (usize, bool) {
+        let widest = self.widest_page_line();
+        let scroll =
+            self.h_scroll.min(widest.saturating_sub(width));
+        (scroll, widest > scroll + width)
+    }
+
+    /// 向左或向右滚动超出终端宽度的行
+    fn scroll_horizontally(
+        &mut self,
+        right: bool,
+        repeat: usize,
+    ) {
+        let width = self.content_width();
+        let max =
+            self.widest_page_line().saturating_sub(width);
+        let (scroll, _) = self.horizontal_scroll(width);
+        let step = H_SCROLL_STEP.saturating_mul(repeat);
+        self.h_scroll = if right {
+            scroll.saturating_add(step).min(max)
+        } else {
+            scroll.saturating_sub(step)
+        };
+        if max == 0 {
+            self.status_message =
+                Some(tr!("行宽未超出终端宽度").to_string());
+        }
+        self.last_display_start_line = usize::MAX;
+    }
+
+    /// 按横向滚动位置截取一行到 `width` 列
+    fn scroll_line(
+        line: &str,
+        scroll: usize,
+        width: usize,
+    ) -> String {
+        truncate_styled_to_width(
+            &skip_styled_width(line, scroll),
+            width,
+        )
+    }
+
+    /// 完整布局所需的最小终端宽度（各列宽度之和，宽度不定的列按 1 列计）
+    fn min_layout_width(&self) -> usize {
+        self.columns
+            .columns()
+            .iter()
+            .map(|spec| {
+                self.column_width(spec).unwrap_or(1)
+            })
+            .sum()
+    }
+
+    /// 列的显示宽度（解析信息与 Δt 未固定宽度时不定）
+    fn column_width(
+        &self,
+        spec: &ColumnSpec,
+    ) -> Option<usize> {
+        let bytes_per_line = self.args.bytes_per_line();
+        spec.width.or(match spec.column {
+            Column::Packet => Some(GUTTER_WIDTH),
+            Column::Offset => Some(10),
+            Column::Hex => Some(bytes_per_line * 3),
+            Column::Ascii => Some(bytes_per_line + 1),
+            Column::Info | Column::Delta => None,
+        })
+    }
+
+    /// 显示或隐藏一列
+    fn toggle_column(
+        &mut self,
+        column: Column,
+        name: &str,
+    ) {
+        let shown = self.columns.toggle(column);
+        self.update_page_size();
+        self.status_message = Some(if shown {
+            tr!("已显示{}", text(name))
+        } else {
+            tr!("已隐藏{}", text(name))
+        });
+        self.last_display_start_line = usize::MAX; // 强制重绘
+    }
+
+    /// `range` 内每个字节的高亮颜色（在已取回的内容中匹配）
+    fn highlight_colors(
+        &self,
+        range: Range<usize>,
+    ) -> Vec<Option<HighlightColor>> {
+        let (start, data) = self.file_data.window();
+        self.highlighter.colors_in(
+            data,
+            range.start.saturating_sub(start)
+                ..range.end.saturating_sub(start),
+        )
+    }
+
+    /// 无障碍模式的十六进制列：区域开始处插入文字标记，
+    /// 字节状态以其后的符号表示（见 `ACCESSIBLE_LEGEND`）
+    fn format_accessible_hex_line(
+        &self,
+        data: &[u8],
+        offset: usize,
+    ) -> String {
+        let highlights = self
+            .highlight_colors(offset..offset + data.len());
+        let mut output = String::new();
+        for (i, &byte) in data.iter().enumerate() {
+            let current_offset = offset + i;
+            if let Some(marker) =
+                self.region_marker(current_offset)
+            {
+                output.push_str(&marker);
+                output.push(' ');
+            }
+            let mark = if self.editing
+                && current_offset == self.edit.cursor()
+            {
+                '<'
+            } else if self.selection.as_ref().is_some_and(
+                |range| range.contains(&current_offset),
+            ) {
+                '+'
+            } else if self.edit.is_modified(current_offset)
+            {
+                '*'
+            } else if self
+                .differs_from_baseline(current_offset)
+            {
+                '!'
+            } else if highlights
+                .get(i)
+                .copied()
+                .flatten()
+                .is_some()
+            {
+                '~'
+            } else {
+                ' '
+            };
+            output
+                .push_str(&format!("{:02X}{}", byte, mark));
+        }
+        output
+    }
+
+    /// 从该偏移开始的区域的文字标记（无障碍模式），如 `[包 12 头]`
+    fn region_marker(
+        &self,
+        offset: usize,
+    ) -> Option<String> {
+        if offset == 0 {
+            return (self.file_data.len()
+                >= FILE_HEADER_SIZE)
+                .then(|| tr!("[文件头]").to_string());
+        }
+        if offset < FILE_HEADER_SIZE {
+            return None;
+        }
+        let Some(index) =
+            self.parser.packet_index_at(offset)
+        else {
+            // 文件头或数据包之后无法解析的数据
+            let previous =
+                self.get_byte_color_type(offset - 1);
+            return (previous != ByteColorType::Unknown)
+                .then(|| tr!("[未解析数据]").to_string());
+        };
+        let packet = self
+            .file_data
+            .packet_ref(&self.parser, index)?;
+        if offset == packet.payload_offset() {
+            return Some(tr!("[包 {} 负载]", index + 1));
+        }
+        if offset != packet.offset() {
+            return None;
+        }
+        let mut notes = String::new();
+        if self.crc_errors.contains(&index) {
+            notes.push_str(tr!("，CRC 错误"));
+        }
+        if self.is_filtered_out(offset) {
+            notes.push_str(tr!("，不匹配过滤"));
+        }
+        Some(tr!("[包 {} 头{}]", index + 1, notes))
+    }
+
+    /// 偏移列内容：绝对文件偏移，或相对于行首所在数据包负载起始的偏移
+    /// （行首在包头内时为负）；相对模式下不属于任何数据包的行留空
+    fn format_offset(&self, offset: usize) -> String {
+        if !self.relative_offsets {
+            return format!("{:08X}: ", offset);
+        }
+        let Some(packet) = self
+            .parser
+            .packet_index_at(offset)
+            .and_then(|index| {
+                self.file_data
+                    .packet_ref(&self.parser, index)
+            })
+        else {
+            return " ".repeat(10);
+        };
+        let payload = packet.payload_offset();
+        if offset >= payload {
+            format!("+{:07X}: ", offset - payload)
+        } else {
+            format!("-{:07X}: ", payload - offset)
+        }
+    }
+
+    /// 切换偏移列的绝对/相对显示
+    fn toggle_relative_offsets(&mut self) {
+        self.relative_offsets = !self.relative_offsets;
+        self.status_message = Some(
+            if self.relative_offsets {
+                tr!("偏移列：相对于数据包负载起始")
+            } else {
+                tr!("偏移列：文件偏移")
+            }
+            .to_string(),
+        );
+        self.last_display_start_line = usize::MAX; // 强制重绘
+    }
+
+    /// 包序号栏内容：行内有数据包头时显示序号与时间（时:分:秒.毫秒），否则为空白
+    fn format_gutter(&self, offset: usize) -> String {
+        let Some(packet) =
+            self.find_packet_header_in_line(offset)
+        else {
+            return " ".repeat(GUTTER_WIDTH);
+        };
+
+        let header = packet.header();
+        let time = self
+            .timestamp_style()
+            .format_time_of_day(
+                header.timestamp_seconds,
+                header.timestamp_nanoseconds,
+                3,
+            )
+            .unwrap_or_else(|| "INVALID".to_string());
+        format!(
+            "{} {} ",
+            format!(
+                "{:>7}",
+                format!("#{}", packet.index() + 1)
+            )
+            .bright_cyan(),
+            format!("{:<12}", time).bright_green()
+        )
+    }
+
+    /// 绘制一帧：先在内存中生成整屏内容，再交给终端管理器增量输出
+    fn redraw(&mut self) -> Result<()> {
+        let _span =
+            tracing::trace_span!("render").entered();
+        self.fetch_visible()?;
+        let (width, height) =
+            self.terminal_manager.get_size();
+        let mut frame = Frame::new(width, height);
+        self.display_screen(&mut frame)?;
+        self.terminal_manager.draw(&frame)
+    }
+
+    /// 分页读取时取回当前页的内容（扩展到与之重叠的完整数据包，
+    /// 比较文件取回同一范围）
+    fn fetch_visible(&mut self) -> Result<()> {
+        let bytes_per_line = self.args.bytes_per_line();
+        let lines = |row: Row| match row {
+            Row::Line(line) => line..line + 1,
+            Row::Fold(lines) => lines,
+        };
+        let mut rows = self.page_rows().map(lines);
+        let Some(first) = rows.next() else {
+            return Ok(());
+        };
+        let last = rows.last().unwrap_or(first.clone());
+
+        let packet = |offset: usize| {
+            self.parser.packet_index_at(offset).and_then(
+                |index| self.parser.packet_ref(&[], index),
+            )
+        };
+        let start = first.start * bytes_per_line;
+        let end = last.end * bytes_per_line;
+        let start = packet(start)
+            .map_or(start, |packet| packet.offset())
+            .saturating_sub(FETCH_MARGIN);
+        let end = packet(end - 1).map_or(end, |packet| {
+            packet.end_offset().max(end)
+        }) + FETCH_MARGIN;
+
+        self.file_data.fetch(start..end)?;
+        for target in self
+            .diff
+            .iter_mut()
+            .chain(self.baseline.iter_mut())
+        {
+            target.data.fetch(start..end)?;
+        }
+        Ok(())
+    }
+
+    /// 根据终端尺寸显示完整布局或精简视图
+    fn display_screen(
+        &self,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let screen = frame.area();
+        if self.terminal_manager.is_smaller_than(
+            self.min_screen_width(),
+            MIN_LAYOUT_HEIGHT,
+        ) {
+            return self.display_compact_summary(
+                &mut frame.region(screen),
+            );
+        }
+
+        // 按键帮助、颜色图例、统计视图、字节频率视图与 TCP 流视图替代十六进制视图，只保留底部区域
+        if self.show_help
+            || self.show_legend
+            || self.stats_view.is_some()
+            || self.frequency_view.is_some()
+            || self.stream_view.is_some()
+        {
+            let areas = layout::split_vertical(
+                screen,
+                &[
+                    Constraint::Fill,
+                    Constraint::Length(FOOTER_LINES),
+                ],
+            );
+            let mut regions = frame.regions(&areas);
+            let mut footer =
+                regions.pop().expect("two areas");
+            let mut content =
+                regions.pop().expect("two areas");
+            if self.show_help {
+                return self.display_help(
+                    &mut content,
+                    &mut footer,
+                );
+            }
+            if self.show_legend {
+                self.display_legend(
+                    &mut content,
+                    &mut footer,
+                );
+                return Ok(());
+            }
+            if let Some(frequency_view) =
+                &self.frequency_view
+            {
+                return frequency_view
+                    .render(&mut content, &mut footer);
+            }
+            return match (
+                &self.stats_view,
+                &self.stream_view,
+            ) {
+                (Some(stats_view), _) => stats_view
+                    .render(&mut content, &mut footer),
+                (None, Some(stream_view)) => stream_view
+                    .render(&mut content, &mut footer),
+                (None, None) => Ok(()),
+            };
+        }
+
+        let layout = self.screen_layout(screen);
+        self.display_banner(
+            &mut frame.region(layout.banner),
+        );
+        self.display_throughput_panel(
+            &mut frame.region(layout.throughput),
+        )?;
+        self.display_ruler(
+            &mut frame.region(layout.ruler),
+        )?;
+        self.display_current_page(
+            &mut frame.region(layout.page),
+        )?;
+        self.display_detail_pane(
+            &mut frame.region(layout.detail),
+        )?;
+        self.display_footer(
+            &mut frame.region(layout.footer),
+        )
+    }
+
+    /// 按键帮助：按类别列出所有按键，超出高度时分多栏显示
+    fn display_help(
+        &self,
+        content: &mut Region<'_>,
+        footer: &mut Region<'_>,
+    ) -> Result<()> {
+        let sections = self.help_sections();
+        let key_width = sections
+            .iter()
+            .flat_map(|(_, entries)| entries)
+            .map(|(keys, _)| display_width(text(keys)))
+            .max()
+            .unwrap_or(0);
+
+        // (内容, 是否为类别标题)
+        let mut lines: Vec<(String, bool)> = Vec::new();
+        for (title, entries) in &sections {
+            if !lines.is_empty() {
+                lines.push((String::new(), false));
+            }
+            lines.push((text(title).to_string(), true));
+            for (keys, description) in entries {
+                lines.push((
+                    format!(
+                        "  {}  {}",
+                        pad_to_width(text(keys), key_width),
+                        text(description)
+                    ),
+                    false,
+                ));
+            }
+        }
+
+        let rows = content.height().max(1);
+        let column_width = lines
+            .iter()
+            .map(|(line, _)| display_width(line))
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let columns: Vec<&[(String, bool)]> =
+            lines.chunks(rows).collect();
+        for row in 0..rows.min(lines.len()) {
+            let line: String = columns
+                .iter()
+                .filter_map(|column| column.get(row))
+                .map(|(text, is_title)| {
+                    let cell =
+                        pad_to_width(text, column_width);
+                    if *is_title {
+                        cell.bright_white()
+                            .bold()
+                            .to_string()
+                    } else {
+                        cell
+                    }
+                })
+                .collect();
+            content.push(truncate_styled_to_width(
+                &line,
+                content.width(),
+            ));
+        }
+
+        footer.push_bar(&format!(" {}", tr!("按键帮助")));
+        footer.push(
+            tr!("按任意键关闭").bright_black().to_string(),
+        );
+        Ok(())
+    }
+
+    /// 颜色图例：以当前主题与色深绘制各类字节的颜色样例
+    fn display_legend(
+        &self,
+        content: &mut Region<'_>,
+        footer: &mut Region<'_>,
+    ) {
+        let sample = || "A1 B2 C3 ".to_string();
+        let entries = if self.args.accessible {
+            ACCESSIBLE_LEGEND
+                .iter()
+                .map(|&(sample, description)| {
+                    (text(sample).to_string(), description)
+                })
+                .collect()
+        } else {
+            vec![
+                (
+                    self.shade(
+                        sample(),
+                        self.palette.file_header,
+                    ),
+                    "文件头",
+                ),
+                (
+                    self.shade(
+                        sample(),
+                        self.palette.packet_header,
+                    ),
+                    "数据包头",
+                ),
+                (
+                    self.shade(
+                        sample(),
+                        self.palette.packet_data,
+                    ),
+                    "负载",
+                ),
+                (
+                    self.shade(
+                        sample(),
+                        self.palette.invalid,
+                    ),
+                    "文件头中无效的字段",
+                ),
+                (
+                    sample().on_white().black().to_string(),
+                    "查找匹配与选中范围",
+                ),
+                (
+                    self.shade(
+                        sample(),
+                        self.palette.modified,
+                    ),
+                    "已修改的字节",
+                ),
+                (
+                    "A1 B2 C3"
+                        .bright_red()
+                        .bold()
+                        .underline()
+                        .to_string()
+                        + " ",
+                    "与基线不同（--baseline）",
+                ),
+                (
+                    sample().bright_black().to_string(),
+                    "不匹配过滤条件的数据包",
+                ),
+                (
+                    "A1 B2 C3".underline().to_string()
+                        + " ",
+                    "有注释的字节",
+                ),
+            ]
+        };
+
+        content.push(
+            text(if self.args.accessible {
+                "标记图例"
+            } else {
+                "颜色图例"
+            })
+            .bright_white()
+            .bold()
+            .to_string(),
+        );
+        content.push(String::new());
+        let swatch_width = entries
+            .iter()
+            .map(|(swatch, _)| styled_display_width(swatch))
+            .max()
+            .unwrap_or(0);
+        for (swatch, description) in entries {
+            content.push(truncate_styled_to_width(
+                &format!(
+                    "  {}  {}",
+                    fit_styled_to_width(
+                        &swatch,
+                        swatch_width
+                    ),
+                    text(description)
+                ),
+                content.width(),
+            ));
+        }
+
+        let note = if self.args.accessible {
+            None
+        } else if !colored::control::SHOULD_COLORIZE
+            .should_colorize()
+        {
+            Some(
+                "单色主题下不显示颜色（:theme color 切换）",
+            )
+        } else if self.color_mode != ColorMode::Structure {
+            Some("当前按字节值或熵着色，结构颜色暂不显示（c 切换）")
+        } else {
+            None
+        };
+        if let Some(note) = note {
+            content.push(String::new());
+            content.push(truncate_to_width(
+                text(note),
+                content.width(),
+            ));
+        }
+
+        footer.push_bar(&format!(" {}", tr!("颜色图例")));
+        footer.push(
+            tr!("按任意键关闭").bright_black().to_string(),
+        );
+    }
+
+    /// 按类别分组的按键说明，移动按键取自绑定表
+    fn help_sections(
+        &self,
+    ) -> Vec<(&'static str, Vec<(String, &'static str)>)>
+    {
+        let motion_keys = |motion: Motion| {
+            self.args
+                .config
+                .keymap
+                .keys_for(motion)
+                .join("/")
+        };
+        let fixed = |entries: &[(&str, &'static str)]| {
+            entries
+                .iter()
+                .map(|&(keys, description)| {
+                    (keys.to_string(), description)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut navigation = vec![
+            (motion_keys(Motion::LineUp), "上移一行"),
+            (motion_keys(Motion::LineDown), "下移一行"),
+            (motion_keys(Motion::HalfPageUp), "上移半页"),
+            (motion_keys(Motion::HalfPageDown), "下移半页"),
+            (motion_keys(Motion::PageUp), "上一页"),
+            (motion_keys(Motion::PageDown), "下一页"),
+            (
+                format!(
+                    "{}/gg",
+                    motion_keys(Motion::First)
+                ),
+                "首页",
+            ),
+            (
+                motion_keys(Motion::Last),
+                "末页（带计数时跳到第 N 行）",
+            ),
+        ];
+        navigation.extend(fixed(&[
+            (
+                "数字",
+                "计数前缀，重复随后的移动，如 25↓、10]",
+            ),
+            ("g", "跳转到偏移或 #包序号"),
+            ("Ctrl-O/Tab", "跳转历史后退/前进"),
+            ("滚轮", "滚动（Shift 翻页），单击定位光标"),
+        ]));
+
+        vec![
+            ("导航", navigation),
+            (
+                "查找",
+                fixed(&[
+                    ("]/[", "下/上一处时间异常"),
+                    ("!", "下一个问题（格式、校验、时间）"),
+                    ("'/`", "下/上一个书签"),
+                    ("d/D", "下/上一处差异（--diff/--baseline）"),
+                    ("/", "查找 DE AD、\"文本\" 或 re:正则（回车找下一个）"),
+                    (":filter", "暗显不匹配的数据包，如 len>100 或 @名称"),
+                    (":jump", "跳到最大/最小包，或 len~1500、len>60000"),
+                    ("/ ↑↓", "查找历史；@名称 执行命名的查找（Tab 补全）"),
+                    (":search", "列出/执行命名的查找，save/delete <名称>"),
+                ]),
+            ),
+            (
+                "显示",
+                fixed(&[
+                    ("c", "切换着色模式"),
+                    ("L", "颜色图例"),
+                    ("a", "标记时间间隔与时间倒退"),
+                    ("T", "时间差 Δt 列"),
+                    ("A", "ASCII 列"),
+                    ("W", "宽终端并排显示两栏"),
+                    ("z", "折叠/展开当前数据包的负载"),
+                    ("O", "偏移列：文件偏移/相对负载起始的偏移"),
+                    (":fold/:unfold", "折叠所有（或匹配条件的）负载 / 全部展开"),
+                    ("Shift-←/→", "左右滚动超出终端宽度的行"),
+                    (":columns", "列布局，如 packet,offset,hex,ascii,info:60"),
+                    ("p", "吞吐量面板"),
+                    ("m", "小地图"),
+                    ("#", "包序号栏"),
+                    ("s", "统计视图"),
+                    ("F", "字节频率与熵（当前数据包或选中范围）"),
+                    ("f", "跟踪 TCP 流"),
+                    ("r", "刷新屏幕"),
+                    (":theme", "单色/彩色"),
+                ]),
+            ),
+            (
+                "文件",
+                fixed(&[
+                    ("e", "编辑模式（Ctrl-S 保存）"),
+                    ("u/Ctrl-R", "撤销/重做"),
+                    ("t", "时间平移"),
+                    (";", "注释"),
+                    ("b", "切换书签"),
+                    ("n/N/F1-F9", "切换文件"),
+                    ("w", "打开监视目录中的新文件（watch --ask）"),
+                    (":export packets", "导出选中或匹配的数据包"),
+                    ("y", "复制负载为 C / Rust / Python / base64"),
+                    (":", "命令行（Tab 补全命令名）"),
+                    ("ESC/q/Ctrl-C", "退出"),
+                ]),
+            ),
+        ]
+    }
+
+    /// 主界面自上而下的区域划分（未显示的面板高度为 0）
+    fn screen_layout(&self, screen: Rect) -> ScreenLayout {
+        let optional = |shown: bool, lines: usize| {
+            Constraint::Length(if shown {
+                lines
+            } else {
+                0
+            })
+        };
+        let areas = layout::split_vertical(
+            screen,
+            &[
+                Constraint::Length(
+                    self.header_warning().len(),
+                ),
+                optional(
+                    self.show_throughput,
+                    THROUGHPUT_PANEL_LINES,
+                ),
+                Constraint::Length(RULER_LINES),
+                Constraint::Fill,
+                optional(
+                    self.dissector.is_some(),
+                    DETAIL_PANE_LINES,
+                ),
+                Constraint::Length(FOOTER_LINES),
+            ],
+        );
+        ScreenLayout {
+            banner: areas[0],
+            throughput: areas[1],
+            ruler: areas[2],
+            page: areas[3],
+            detail: areas[4],
+            footer: areas[5],
+        }
+    }
+
+    /// 文件头无效时的警告（此时不解析数据包，按原始数据显示）。
+    /// 第一行为错误说明（含偏移、期望值与实际值），其后为文件头的十六进制片段；
+    /// 文件头有效时为空
+    fn header_warning(&self) -> Vec<String> {
+        let Err(e) = PcapFileHeader::read(
+            self.file_data.head(),
+            self.args.parser_options().endianness,
+        ) else {
+            return Vec::new();
+        };
+        let mut lines =
+            vec![tr!("文件头无效，按原始数据显示: {}", e)];
+        if let Some(error) = ParseError::of(&e) {
+            lines.extend(error.snippet_lines());
+        }
+        if let Some(alternative) =
+            format::detect(self.file_data.head())
+                .alternative
+        {
+            lines.push(tr!(
+                "版本号无法区分格式，也可能是 {}",
+                alternative
+            ));
+        }
+        lines
+    }
+
+    /// 文件头中无效的字段（不足一个文件头时为空）
+    fn invalid_header_fields(&self) -> Vec<Range<usize>> {
+        let Some(bytes) =
+            self.file_data.head().get(..FILE_HEADER_SIZE)
+        else {
+            return Vec::new();
+        };
+        let endianness =
+            self.args.parser_options().endianness;
+        PcapFileHeader::decode(
+            bytes,
+            endianness.resolve(bytes),
+        )
+        .invalid_fields()
+    }
+
+    /// 显示文件头警告横幅（红底白字，按区域宽度补齐）
+    fn display_banner(&self, area: &mut Region<'_>) {
+        for (i, line) in
+            self.header_warning().iter().enumerate()
+        {
+            // 说明前加警告符号，片段与之对齐
+            let prefix = if i == 0 { "⚠ " } else { "  " };
+            let text = pad_to_width(
+                &truncate_to_width(
+                    &format!("{}{}", prefix, line),
+                    area.width(),
+                ),
+                area.width(),
+            );
+            area.push(
+                text.on_red()
+                    .bright_white()
+                    .bold()
+                    .to_string(),
+            );
+        }
+    }
+
+    /// 显示解析详情面板（当前视口所在数据包的自定义解析结果）
+    fn display_detail_pane(
+        &self,
+        area: &mut Region<'_>,
+    ) -> Result<()> {
+        let Some(dissector) = &self.dissector else {
+            return Ok(());
+        };
+
+        let (width, _) = self.terminal_manager.get_size();
+        let viewport_offset = self.viewport_offset();
+
+        let mut lines = Vec::new();
+        match self.packet_at_offset(viewport_offset) {
+            Some(packet) => {
+                let payload = packet.payload();
+                let (time, _) = self.format_packet_time(
+                    packet.header().timestamp_seconds,
+                    packet.header().timestamp_nanoseconds,
+                );
+
+                lines.push(
+                    tr!(
+                        "── 解析详情: 包 #{} {} (偏移 0x{}, 负载 {} 字节) ──",
+                        packet.index() + 1,
+                        time,
+                        format!("{:08X}", packet.offset()),
+                        payload.len()
+                    )
+                    .bright_white()
+                    .bold()
+                    .to_string(),
+                );
+
+                match dissector.dissect(payload) {
+                    Ok(fields) if fields.is_empty() => {
+                        lines.push(
+                            tr!("(无字段)")
+                                .bright_black()
+                                .to_string(),
+                        );
+                    }
+                    Ok(fields) => {
+                        let items: Vec<String> = fields
+                            .iter()
+                            .map(|f| {
+                                format!(
+                                    "{}={}",
+                                    f.name, f.value
+                                )
+                            })
+                            .collect();
+                        lines.extend(wrap_items(
+                            &items, " | ", width,
+                        ));
+                    }
+                    Err(e) => {
+                        lines.push(
+                            truncate_to_width(
+                                &tr!("脚本错误: {}", e),
+                                width,
+                            )
+                            .bright_red()
+                            .to_string(),
+                        );
+                    }
+                }
+            }
+            None => {
+                lines.push(
+                    tr!("── 解析详情: 无数据包 ──")
+                        .bright_white()
+                        .bold()
+                        .to_string(),
+                );
+            }
+        }
+
+        // 超出面板高度的行由区域截断
+        for line in lines {
+            area.push(line);
+        }
+
+        Ok(())
+    }
+
+    /// 显示固定在数据区上方的列标尺（字节列序号与文件名 / 大小）
+    fn display_ruler(
+        &self,
+        area: &mut Region<'_>,
+    ) -> Result<()> {
+        let (width, _) = self.terminal_manager.get_size();
+        // 编辑模式下高亮光标所在列
+        let cursor_column = self.editing.then(|| {
+            self.edit.cursor() % self.args.bytes_per_line()
+        });
+
+        let mut columns = String::new();
+        for column in 0..self.args.bytes_per_line() {
+            let label = format!("{:02X}", column);
+            if cursor_column == Some(column) {
+                columns.push_str(
+                    &label.reversed().bold().to_string(),
+                );
+            } else {
+                columns.push_str(&label);
+            }
+            columns.push(' ');
+        }
+        // 与十六进制列对齐（无法确定其位置时从行首开始）
+        let hex_start = self
+            .columns
+            .start_of(Column::Hex, |spec| {
+                self.column_width(spec)
+            })
+            .unwrap_or(0);
+        let mut ruler =
+            format!("{}{}", " ".repeat(hex_start), columns);
+        // 并排两栏时右栏使用相同的列标尺
+        if let Some(half) = self.dual_column_width() {
+            ruler = format!(
+                "{} {}",
+                fit_styled_to_width(&ruler, half),
+                ruler
+            );
+        }
+
+        let file_name = |path: &Path| {
+            path.file_name()
+                .map(|name| {
+                    name.to_string_lossy().to_string()
+                })
+                .unwrap_or_default()
+        };
+        let mut file_info = tr!(
+            " {} ({} 字节)",
+            file_name(&self.args.file_path),
+            self.file_data.len()
+        );
+        // 并排比较时右栏使用相同的列标尺
+        if let Some(target) = &self.diff {
+            ruler.push_str(&format!("| {}", columns));
+            file_info.push_str(&tr!(
+                " ↔ {} ({} 字节)",
+                file_name(&target.file_path),
+                target.data.len()
+            ));
+        }
+
+        let ruler = format!(
+            "{}|{}",
+            ruler.bright_black(),
+            file_info.bright_white().bold()
+        );
+
+        // 行宽超出终端宽度时随数据行左右滚动，并在两端标出被遮住的方向
+        let content_width = self.content_width();
+        let (scroll, more) =
+            self.horizontal_scroll(content_width);
+        if scroll == 0 && !more {
+            area.push(truncate_styled_to_width(
+                &ruler, width,
+            ));
+            return Ok(());
+        }
+        let (left_mark, right_mark) =
+            if self.args.accessible {
+                ("<", ">")
+            } else {
+                ("«", "»")
+            };
+        let left = if scroll > 0 { left_mark } else { " " };
+        let right = if more { right_mark } else { " " };
+        let inner = content_width.saturating_sub(2);
+        area.push(format!(
+            "{}{}{}",
+            left.bright_yellow().bold(),
+            fit_styled_to_width(
+                &skip_styled_width(&ruler, scroll + 1),
+                inner
+            ),
+            right.bright_yellow().bold()
+        ));
+        Ok(())
+    }
+
+    /// 显示吞吐量面板（包速率与字节速率折线，标记当前视口所在时间）
+    fn display_throughput_panel(
+        &self,
+        area: &mut Region<'_>,
+    ) -> Result<()> {
+        if !self.show_throughput {
+            return Ok(());
+        }
+
+        let (width, _) = self.terminal_manager.get_size();
+        let buckets = width
+            .saturating_sub(THROUGHPUT_LABEL_WIDTH + 1)
+            .max(1);
+
+        let mut lines = Vec::new();
+        match Throughput::compute(
+            self.parser.packets(),
+            buckets,
+        ) {
+            Some(throughput) => {
+                let packet_rates =
+                    throughput.packets_per_second();
+                let byte_rates =
+                    throughput.bytes_per_second();
+                let peak = |rates: &[f64]| {
+                    rates
+                        .iter()
+                        .copied()
+                        .fold(0.0, f64::max)
+                };
+
+                lines.push(
+                    truncate_to_width(
+                        &tr!(
+                            "── 吞吐量: 每格 {} 秒 | 峰值 {} 包/秒, {} 字节/秒 ──",
+                            format!("{:.3}", throughput.bucket_seconds()),
+                            format_rate(peak(&packet_rates)),
+                            format_rate(peak(&byte_rates))
+                        ),
+                        width,
+                    )
+                    .bright_white()
+                    .bold()
+                    .to_string(),
+                );
+                lines.push(format!(
+                    "{} {}",
+                    pad_to_width(
+                        tr!("包/秒"),
+                        THROUGHPUT_LABEL_WIDTH
+                    ),
+                    sparkline(&packet_rates).bright_cyan()
+                ));
+                lines.push(format!(
+                    "{} {}",
+                    pad_to_width(
+                        tr!("字节/秒"),
+                        THROUGHPUT_LABEL_WIDTH
+                    ),
+                    sparkline(&byte_rates).bright_green()
+                ));
+
+                // 在当前视口所在数据包的时间格下方标记
+                if let Some(packet) = self.packet_at_offset(
+                    self.viewport_offset(),
+                ) {
+                    let bucket = throughput
+                        .bucket_of(packet.header());
+                    lines.push(format!(
+                        "{}{}",
+                        " ".repeat(
+                            THROUGHPUT_LABEL_WIDTH
+                                + 1
+                                + bucket
+                        ),
+                        "▲".bright_yellow().bold()
+                    ));
+                }
+            }
+            None => {
+                lines.push(
+                    tr!("── 吞吐量: 无数据包 ──")
+                        .bright_white()
+                        .bold()
+                        .to_string(),
+                );
+            }
+        }
+
+        for line in lines {
+            area.push(line);
+        }
+
+        Ok(())
+    }
+
+    /// 偏移处的文件头或数据包头字段，如 `包 #52 头: timestamp_nanoseconds 字节 2/4`
+    fn header_field_at(
+        &self,
+        offset: usize,
+    ) -> Option<String> {
+        let (owner, name, range, position) = if offset
+            < FILE_HEADER_SIZE
+        {
+            let (name, range) =
+                PcapFileHeader::field_containing(offset)?;
+            (
+                tr!("文件头").to_string(),
+                name.to_string(),
+                range,
+                offset,
+            )
+        } else {
+            let index =
+                self.parser.packet_index_at(offset)?;
+            let position = offset
+                - self.parser.packets()[index].offset;
+            let (field, range) = self
+                .args
+                .parser_options()
+                .record
+                .field_containing(position)?;
+            (
+                tr!("包 #{} 头", index + 1),
+                field.to_string(),
+                range,
+                position,
+            )
+        };
+        Some(tr!(
+            "{}: {} 字节 {}/{}",
+            owner,
+            name,
+            position - range.start + 1,
+            range.len()
+        ))
+    }
+
+    /// 查找包含指定偏移（或位于其后）的第一个数据包
+    fn packet_at_offset(
+        &self,
+        offset: usize,
+    ) -> Option<PacketRef<'_>> {
+        let index =
+            self.parser.packet_index_from(offset)?;
+        self.file_data.packet_ref(&self.parser, index)
+    }
+
+    /// 终端过小时显示单列摘要视图，避免输出错乱
+    fn display_compact_summary(
+        &self,
+        area: &mut Region<'_>,
+    ) -> Result<()> {
+        let (width, height) =
+            self.terminal_manager.get_size();
+        let file_name = self
+            .args
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let lines = [
+            tr!("文件: {}", file_name),
+            tr!("大小: {} 字节", self.file_data.len()),
+            tr!("数据包: {}", self.parser.packets().len()),
+            tr!(
+                "行: {}/{}",
+                self.top_line() + 1,
+                self.content_len()
+                    .div_ceil(self.args.bytes_per_line())
+            ),
+            tr!(
+                "偏移: {}",
+                format!("0x{:08X}", self.viewport_offset())
+            ),
+            tr!("终端过小 ({}x{})", width, height),
+            tr!(
+                "需要 {}x{}",
+                self.min_screen_width(),
+                MIN_LAYOUT_HEIGHT
+            ),
+            tr!("q 退出").to_string(),
+        ];
+
+        // 逐行截断到终端宽度，超出终端高度的行由区域截断
+        for line in &lines {
+            area.push(truncate_to_width(line, width));
+        }
+
+        Ok(())
+    }
+
+    /// 显示当前页
+    fn display_current_page(
+        &self,
+        area: &mut Region<'_>,
+    ) -> Result<()> {
+        let start_offset = self.viewport_offset();
+        if start_offset >= self.content_len() {
+            return Ok(());
+        }
+
+        let dual = self.dual_column_width();
+        let minimap = self
+            .show_minimap
+            .then(|| self.minimap_column(start_offset));
+        let (width, _) = self.terminal_manager.get_size();
+        let content_width = self.content_width();
+        let (scroll, _) =
+            self.horizontal_scroll(content_width);
+
+        let texts = self
+            .page_rows()
+            .map(|row| self.row_text(&row))
+            .collect::<Result<Vec<_>>>()?;
+        // 并排两栏时一个屏幕行依次显示相邻的两行数据
+        for (index, panes) in
+            texts.chunks(self.lines_per_row()).enumerate()
+        {
+            let mut line_output = String::new();
+            for (pane, line) in panes.iter().enumerate() {
+                match dual {
+                    Some(half) if pane == 0 => line_output
+                        .push_str(&fit_styled_to_width(
+                            line, half,
+                        )),
+                    Some(_) => {
+                        line_output.push_str(&format!(
+                            "{}{}",
+                            "│".bright_black(),
+                            line
+                        ))
+                    }
+                    None => line_output.push_str(line),
+                }
+            }
+
+            // 超出终端宽度的部分被截断（可左右滚动），避免折行打乱布局
+            let line_output = Self::scroll_line(
+                &line_output,
+                scroll,
+                content_width,
+            );
+
+            // 先在最右列绘制小地图，再回到行首输出内容
+            match minimap
+                .as_ref()
+                .and_then(|column| column.get(index))
+            {
+                Some(cell) => area.push(format!(
+                    "\x1b[{}G{}\r{}",
+                    width, cell, line_output
+                )),
+                None => area.push(line_output),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 当前页的显示行（到内容末尾为止）
+    fn page_rows(&self) -> impl Iterator<Item = Row> + '_ {
+        let bytes_per_line = self.args.bytes_per_line();
+        let content_len = self.content_len();
+        (self.pagination.display_start_line()..)
+            .map(|row| self.folds.row(row))
+            .take_while(move |row| {
+                let line = match row {
+                    Row::Line(line) => *line,
+                    Row::Fold(lines) => lines.start,
+                };
+                line * bytes_per_line < content_len
+            })
+            .take(self.pagination.lines_per_page())
+    }
+
+    /// 显示行的内容：数据行或折叠摘要
+    fn row_text(&self, row: &Row) -> Result<String> {
+        let bytes_per_line = self.args.bytes_per_line();
+        match row {
+            Row::Line(line) => {
+                let start = line * bytes_per_line;
+                self.cached_line(
+                    start
+                        ..self
+                            .content_len()
+                            .min(start + bytes_per_line),
+                )
+            }
+            Row::Fold(lines) => {
+                Ok(self.format_fold_row(lines.clone()))
+            }
+        }
+    }
+
+    /// 折叠摘要行：与十六进制列对齐，显示负载字节数与熵
+    fn format_fold_row(
+        &self,
+        lines: Range<usize>,
+    ) -> String {
+        let bytes_per_line = self.args.bytes_per_line();
+        let indent = self
+            .columns
+            .start_of(Column::Hex, |spec| {
+                self.column_width(spec)
+            })
+            .unwrap_or(0);
+        let summary = match self
+            .packet_at_offset(lines.start * bytes_per_line)
+        {
+            Some(packet) => {
+                let payload = packet.payload();
+                tr!(
+                    "… {} 字节负载，熵 {} …",
+                    payload.len(),
+                    format!(
+                        "{:.1}",
+                        ByteFrequency::compute(payload)
+                            .entropy()
+                    )
+                )
+            }
+            None => tr!(
+                "… {} 字节 …",
+                lines.len() * bytes_per_line
+            ),
+        };
+        format!(
+            "{}{}",
+            " ".repeat(indent),
+            summary.bright_black().italic()
+        )
+    }
+
+    /// 格式化一行，优先使用渲染缓存
+    fn cached_line(
+        &self,
+        range: Range<usize>,
+    ) -> Result<String> {
+        let key = LineKey {
+            range: range.clone(),
+            bytes_per_line: self.args.bytes_per_line(),
+            color_mode: self.color_mode,
+            columns: self.columns.clone(),
+            relative_offsets: self.relative_offsets,
+            colorize: colored::control::SHOULD_COLORIZE
+                .should_colorize(),
+        };
+        if let Some(line) =
+            self.line_cache.borrow_mut().get(&key)
+        {
+            return Ok(line.to_string());
+        }
+        let line = self.format_line(range.clone())?;
+        // 分页读取时内容尚未取回的行不缓存
+        if self
+            .file_data
+            .get(
+                range.start
+                    ..range.end.min(self.file_data.len()),
+            )
+            .is_some()
+        {
+            self.line_cache
+                .borrow_mut()
+                .insert(key, line.clone());
+        }
+        Ok(line)
+    }
+
+    /// 清空渲染缓存（行内容可能已变化）
+    fn invalidate_lines(&mut self) {
+        self.line_cache.get_mut().clear();
+    }
+
+    /// 是否为只移动主视图视口、不改变行内容的按键（移动与计数）
+    fn is_view_only_key(
+        &self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> bool {
+        !self.editing
+            && self.prompt.is_none()
+            && !self.show_help
+            && !self.show_legend
+            && self.stats_view.is_none()
+            && self.frequency_view.is_none()
+            && self.stream_view.is_none()
+            && (self
+                .args
+                .config
+                .keymap
+                .motion_for(code, modifiers)
+                .is_some()
+                || matches!(code, KeyCode::Char('0'..='9')))
+    }
+
+    /// 格式化一行：按列布局依次输出各列，最后是行尾标记；
+    /// 并排比较时十六进制列显示两栏，不显示 ASCII 与解析信息
+    fn format_line(
+        &self,
+        range: Range<usize>,
+    ) -> Result<String> {
+        let (offset, line_end) = (range.start, range.end);
+        let line_data = self
+            .file_data
+            .get(offset..line_end.min(self.file_data.len()))
+            .unwrap_or_default();
+
+        let mut line_output = String::new();
+        for spec in self.columns.columns() {
+            let text = match (spec.column, &self.diff) {
+                (Column::Packet, _) => {
+                    self.format_gutter(offset)
                 }
-                Event::Mouse(_) => {
-                    // 忽略所有鼠标事件（包括滚轮滚动）
-                    continue;
+                (Column::Offset, _) => {
+                    self.format_offset(offset)
                 }
-                _ => {
-                    // 忽略其他事件
-                    continue;
+                (Column::Hex, Some(target)) => self
+                    .format_diff_line(
+                        target,
+                        offset..line_end,
+                    ),
+                (Column::Hex, None) => {
+                    self.format_hex_line(line_data, offset)?
                 }
+                (Column::Ascii | Column::Info, Some(_)) => {
+                    continue
+                }
+                (Column::Ascii, None) => self
+                    .format_ascii_line(line_data, offset),
+                (Column::Info, None) => format!(
+                    "|{}",
+                    self.format_parsed_info(
+                        line_data, offset
+                    )
+                ),
+                (Column::Delta, _) => self
+                    .find_packet_header_in_line(offset)
+                    .map(|packet| {
+                        self.format_packet_delta(&packet)
+                    })
+                    .unwrap_or_default(),
+            };
+            match spec.width {
+                Some(width) => line_output.push_str(
+                    &fit_styled_to_width(&text, width),
+                ),
+                None => line_output.push_str(&text),
             }
         }
+        line_output.push_str(
+            &self.format_line_marks(offset..line_end),
+        );
 
-        // 恢复终端（由 TerminalManager 的 Drop trait 自动处理）
+        Ok(line_output)
+    }
 
-        Ok(())
+    /// 生成小地图列：每行一个字符，滑块表示视口位置，颜色表示该段内的重要区域
+    fn minimap_column(
+        &self,
+        start_offset: usize,
+    ) -> Vec<String> {
+        let lines = self.pagination.lines_per_page();
+        let rows = lines.div_ceil(self.lines_per_row());
+        let minimap =
+            Minimap::new(rows, self.content_len());
+
+        let file_header = (self.file_data.len()
+            >= FILE_HEADER_SIZE)
+            .then_some((0, MinimapMark::FileHeader));
+        let packet_headers =
+            self.parser.packets().iter().map(|packet| {
+                (packet.offset, MinimapMark::PacketHeader)
+            });
+        let crc_errors =
+            self.crc_errors.iter().filter_map(|&index| {
+                let packet =
+                    self.parser.packets().get(index)?;
+                Some((packet.offset, MinimapMark::CrcError))
+            });
+        let marks = minimap.marks(
+            file_header
+                .into_iter()
+                .chain(packet_headers)
+                .chain(crc_errors),
+        );
+
+        let viewport_end = self.viewport_end();
+        let thumb =
+            minimap.thumb(start_offset..viewport_end);
+
+        marks
+            .iter()
+            .enumerate()
+            .map(|(row, mark)| {
+                let in_thumb = thumb.contains(&row);
+                let cell = match (in_thumb, mark) {
+                    (true, _) => "█",
+                    (false, Some(_)) => "▐",
+                    (false, None) => "│",
+                };
+                match mark {
+                    Some(MinimapMark::CrcError) => {
+                        cell.bright_red().to_string()
+                    }
+                    Some(MinimapMark::FileHeader) => {
+                        cell.bright_magenta().to_string()
+                    }
+                    Some(MinimapMark::PacketHeader) => {
+                        cell.bright_cyan().to_string()
+                    }
+                    None if in_thumb => {
+                        cell.bright_white().to_string()
+                    }
+                    None => cell.bright_black().to_string(),
+                }
+            })
+            .collect()
     }
 
-    /// 更新终端尺寸
-    fn update_terminal_size(&mut self) -> Result<bool> {
-        // 重新计算分页信息
-        let new_lines_per_page = self
-            .terminal_manager
-            .calculate_display_lines(6);
-        let size_changed = new_lines_per_page
-            != self.pagination.lines_per_page();
+    /// 显示底部的状态栏与提示信息（或按键帮助）
+    fn display_footer(
+        &self,
+        area: &mut Region<'_>,
+    ) -> Result<()> {
+        let width = area.width();
+        area.push_bar(&self.status_bar_text());
 
-        if size_changed {
-            // 更新分页信息
-            self.pagination
-                .update_lines_per_page(new_lines_per_page);
+        // 状态栏下方一行：输入框、提示信息或按键帮助
+        if let Some(prompt) = &self.prompt {
+            let editor = &prompt.editor;
+            let (before, after): (String, String) = {
+                let chars: Vec<char> =
+                    editor.text().chars().collect();
+                (
+                    chars[..editor.cursor()]
+                        .iter()
+                        .collect(),
+                    chars[editor.cursor()..]
+                        .iter()
+                        .collect(),
+                )
+            };
+            let mut after = after.chars();
+            let under_cursor = after
+                .next()
+                .map_or(" ".to_string(), String::from);
+            area.push(format!(
+                "{} {}{}{}",
+                prompt.kind.label().bright_white().bold(),
+                before,
+                under_cursor.reversed(),
+                after.as_str()
+            ));
+        } else if let Some(message) = &self.status_message {
+            area.push(
+                truncate_to_width(message, width)
+                    .bright_yellow()
+                    .to_string(),
+            );
+        } else {
+            // 超出终端宽度时截断，避免折行打乱布局
+            area.push(
+                truncate_to_width(&self.help_hint(), width)
+                    .bright_black()
+                    .to_string(),
+            );
         }
 
-        Ok(size_changed)
+        Ok(())
     }
 
-    /// 显示当前页
-    fn display_current_page(&self) -> Result<()> {
-        // 从显示起始行开始，绘制 n 行
-        let start_offset =
-            self.pagination.display_start_line()
-                * self.args.bytes_per_line();
+    /// 状态栏内容：文件、位置、所在数据包及当前模式
+    fn status_bar_text(&self) -> String {
+        let file_len = self.file_data.len();
+        let offset = if self.editing {
+            self.edit.cursor()
+        } else {
+            self.viewport_offset()
+        };
 
-        if start_offset >= self.file_data.len() {
-            return Ok(());
+        let mut items = Vec::new();
+        if self.sessions.len() > 1 {
+            items.push(self.file_label());
+        } else {
+            items.push(
+                self.args
+                    .file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_else(|| {
+                        self.args
+                            .file_path
+                            .to_string_lossy()
+                    })
+                    .to_string(),
+            );
         }
+        if self.indexer.is_some() {
+            items.push(tr!(
+                "{} / {} 字节",
+                file_len,
+                self.full_len()
+            ));
+        } else {
+            items.push(tr!("{} 字节", file_len));
+        }
+        items
+            .push(format!("0x{:08X} ({})", offset, offset));
 
-        let mut current_offset = start_offset;
-        let mut lines_displayed = 0;
+        let detection =
+            format::detect(self.file_data.head());
+        items.push(format!(
+            "{}{}",
+            detection.variant,
+            if detection.is_ambiguous() { "?" } else { "" }
+        ));
 
-        while lines_displayed
-            < self.pagination.lines_per_page()
-        {
-            if current_offset >= self.file_data.len() {
-                break;
+        let total_packets = self.parser.packets().len();
+        match self.packet_at_offset(offset) {
+            Some(packet) => {
+                items.push(tr!(
+                    "包 {}/{}",
+                    packet.index() + 1,
+                    total_packets
+                ));
+                items.push(
+                    if self.checksum_matches(&packet) {
+                        "CRC ✓"
+                    } else {
+                        "CRC ✗"
+                    }
+                    .to_string(),
+                );
             }
+            None => {
+                items.push(tr!("包 -/{}", total_packets));
+            }
+        }
 
-            // 计算当前行的数据
-            let line_end = std::cmp::min(
-                current_offset + self.args.bytes_per_line(),
-                self.file_data.len(),
-            );
-            let line_data =
-                &self.file_data[current_offset..line_end];
-
-            // 构建完整的行输出
-            let mut line_output = String::new();
+        // 以视口末尾计算进度，最后一页为 100%
+        let visible_end = self.viewport_end().min(file_len);
+        let percent = (visible_end * 100)
+            .checked_div(self.full_len())
+            .unwrap_or(100);
+        items.push(format!("{}%", percent));
 
-            // 添加地址偏移
-            line_output.push_str(&format!(
-                "{:08X}: ",
-                current_offset
+        if let Some(indexer) = &self.indexer {
+            let progress = indexer.progress();
+            items.push(tr!(
+                "索引中 {}% · 剩余 {}",
+                progress.percent(),
+                loading::format_eta(&progress)
             ));
-
-            // 添加十六进制数据
-            line_output.push_str(&self.format_hex_line(
-                line_data,
-                current_offset,
-            )?);
-
-            // 添加解析信息分隔符和内容
-            line_output.push('|');
-            line_output.push_str(&self.format_parsed_info(
-                line_data,
-                current_offset,
+        }
+        if let Some(search) = &self.search {
+            items.push(tr!(
+                "查找 {} {}% (ESC 取消)",
+                search.pattern(),
+                search.percent(&self.file_data)
             ));
-
-            // 输出完整的一行（在原始模式下使用显式的\r\n）
-            print!("{}\r\n", line_output);
-
-            current_offset = line_end;
-            lines_displayed += 1;
+        }
+        match self.pending_jump {
+            Some(PendingJump::Offset(offset)) => items
+                .push(tr!(
+                    "等待加载 {} (ESC 取消)",
+                    format!("0x{:08X}", offset)
+                )),
+            Some(PendingJump::Packet(index)) => items.push(
+                tr!("等待索引 #{} (ESC 取消)", index + 1),
+            ),
+            None => {}
+        }
+        if let Some(filter) = &self.packet_filter {
+            items.push(tr!("过滤 {}", filter));
+        }
+        if self.args.follow {
+            items.push(tr!("跟随中").to_string());
+        }
+        if let Some(stream) = &self.stream {
+            items.push(tr!(
+                "缓冲 {}/{}",
+                stream.ring().len(),
+                stream.ring().capacity()
+            ));
+        }
+        if self.editing {
+            items.push(
+                text(match self.edit.mode() {
+                    InputMode::Hex => "编辑 HEX",
+                    InputMode::Ascii => "编辑 ASCII",
+                })
+                .to_string(),
+            );
+            items.extend(self.header_field_at(offset));
+        }
+        match self.color_mode {
+            ColorMode::Structure => {}
+            ColorMode::ByteValue => items.push(
+                tr!("着色: 字节值 · 00 空白 控制 ASCII 高位 FF")
+                    .to_string(),
+            ),
+            ColorMode::Entropy => items.push(
+                tr!("着色: 局部熵 · 低→高 蓝 青 黄 红")
+                    .to_string(),
+            ),
+        }
+        if self.baseline.is_some() {
+            items.push(tr!(
+                "与基线不同 {} 字节",
+                self.baseline_differences
+            ));
+        }
+        if self.edit.is_dirty() {
+            items.push(tr!(
+                "已修改 {} 字节 / {} 处",
+                self.edit.modified_count(),
+                self.edit.dirty_ranges().len()
+            ));
+        }
+        if let Some(selection) = &self.selection {
+            items.push(tr!(
+                "已选择 {} 字节",
+                selection.len()
+            ));
+        }
+        if let Some(count) = self.count_prefix.pending() {
+            items.push(tr!("计数: {}", count));
         }
 
-        // 刷新输出缓冲区
-        io::stdout().flush()?;
-        Ok(())
+        let separator = if self.args.accessible {
+            " | "
+        } else {
+            " │ "
+        };
+        format!(" {}", items.join(separator))
     }
 
-    /// 显示帮助信息
-    fn display_help(&self) -> Result<()> {
-        let current_page = self.pagination.current_page();
-        let total_pages = self.pagination.total_pages();
-
-        print!("\r\n");
-        print!("{}\r\n", "=".repeat(80));
-        print!(
-            "{}\r\n",
-            format!(
-                "第 {} 行 / 共 {} 行 (第 {} 页 / 共 {} 页)",
-                self.pagination.display_start_line() + 1,
-                self.pagination.total_lines(),
-                current_page,
-                total_pages
+    /// 底部按键帮助（编辑模式与浏览模式不同）
+    fn help_hint(&self) -> String {
+        if self.editing {
+            return tr!(
+                "编辑: 方向键 移动光标 | 0-9A-F/字符 改写 | Tab 切换HEX/ASCII | Ctrl-Z/Y 撤销/重做 | Ctrl-S 保存 | Ctrl-A 另存为 | ESC 结束编辑"
             )
-            .bright_white()
-            .bold()
-        );
-        print!("{}\r\n", "导航: ↑↓ 逐行滚动 | ←→ 翻页 | Home/End 首页/末页 | r 刷新 | ESC/q 退出".bright_black());
-        print!("{}\r\n", "=".repeat(80));
-
-        // 刷新输出缓冲区
-        io::stdout().flush()?;
-        Ok(())
+                .to_string();
+        }
+        // 打开多个文件或并排比较时提示相应按键
+        let mut extra_hint = String::new();
+        if self.diff.is_some() || self.baseline.is_some() {
+            extra_hint
+                .push_str(tr!(" d/D 下/上一处差异 |"));
+        }
+        if self.sessions.len() > 1 {
+            extra_hint
+                .push_str(tr!(" n/N/F1-F9 切换文件 |"));
+        }
+        tr!(
+            "? 全部按键 |{} ↑↓ 滚动 | ←→ 翻页 | g 跳转 | : 命令 | e 编辑 | s 统计 | ESC/q 退出",
+            extra_hint
+        )
     }
 
     /// 格式化十六进制行数据（带颜色标记）
@@ -287,7 +5943,14 @@ impl HexViewer {
         data: &[u8],
         offset: usize,
     ) -> Result<String> {
+        if self.args.accessible {
+            return Ok(self
+                .format_accessible_hex_line(data, offset));
+        }
+
         let mut output = String::new();
+        let highlights = self
+            .highlight_colors(offset..offset + data.len());
 
         // 简化逻辑：直接按字节顺序显示，根据位置应用颜色
         for i in 0..self.args.bytes_per_line() {
@@ -298,38 +5961,123 @@ impl HexViewer {
                 // 根据字节位置确定颜色
                 let color_type = self
                     .get_byte_color_type(current_offset);
-                let formatted_byte = match color_type {
-                    ByteColorType::FileHeader => {
-                        // 文件头区域 - 紫色背景
-                        format!("{:02X} ", byte)
-                            .on_bright_magenta()
-                            .bright_white()
-                            .bold()
-                            .to_string()
-                    }
-                    ByteColorType::PacketHeader => {
-                        // 数据包头区域 - 青色背景
-                        format!("{:02X} ", byte)
-                            .on_bright_cyan()
-                            .black()
-                            .bold()
-                            .to_string()
-                    }
-                    ByteColorType::PacketData => {
-                        // 数据包体区域 - 黄色背景
-                        format!("{:02X} ", byte)
-                            .on_bright_yellow()
-                            .black()
-                            .bold()
-                            .to_string()
-                    }
-                    ByteColorType::Unknown => {
-                        // 未知区域 - 无颜色
-                        format!("{:02X} ", byte)
+                let formatted_byte = if self.editing
+                    && current_offset == self.edit.cursor()
+                {
+                    // 编辑光标 - 反色
+                    format!("{:02X}", byte)
+                        .reversed()
+                        .bold()
+                        .to_string()
+                        + " "
+                } else if self
+                    .selection
+                    .as_ref()
+                    .is_some_and(|range| {
+                        range.contains(&current_offset)
+                    })
+                {
+                    // 选中的字节 - 白色背景
+                    format!("{:02X} ", byte)
+                        .on_white()
+                        .black()
+                        .to_string()
+                } else if self
+                    .edit
+                    .is_modified(current_offset)
+                {
+                    // 已修改字节 - 红色背景
+                    self.shade(
+                        format!("{:02X} ", byte),
+                        self.palette.modified,
+                    )
+                } else if self
+                    .differs_from_baseline(current_offset)
+                {
+                    // 与基线不同 - 红色下划线
+                    format!("{:02X}", byte)
+                        .bright_red()
+                        .bold()
+                        .underline()
+                        .to_string()
+                        + " "
+                } else if let Some(color) =
+                    highlights.get(i).copied().flatten()
+                {
+                    // 用户高亮 - 覆盖结构着色
+                    format!("{:02X} ", byte)
+                        .on_color(
+                            self.palette.highlight(color),
+                        )
+                        .black()
+                        .bold()
+                        .to_string()
+                } else if self
+                    .is_filtered_out(current_offset)
+                {
+                    // 不匹配过滤条件的数据包 - 暗色
+                    format!("{:02X} ", byte)
+                        .bright_black()
+                        .to_string()
+                } else if let Some(formatted) = self
+                    .format_heatmap_byte(
+                        byte,
+                        current_offset,
+                    )
+                {
+                    formatted
+                } else {
+                    match color_type {
+                        ByteColorType::FileHeader => {
+                            // 文件头区域 - 紫色背景
+                            self.shade(
+                                format!("{:02X} ", byte),
+                                self.palette.file_header,
+                            )
+                        }
+                        ByteColorType::InvalidField => {
+                            // 文件头中无效的字段 - 红色背景
+                            self.shade(
+                                format!("{:02X} ", byte),
+                                self.palette.invalid,
+                            )
+                        }
+                        ByteColorType::PacketHeader => {
+                            // 数据包头区域 - 青色背景
+                            self.shade(
+                                format!("{:02X} ", byte),
+                                self.palette.packet_header,
+                            )
+                        }
+                        ByteColorType::PacketData => {
+                            // 数据包体区域 - 黄色背景
+                            self.shade(
+                                format!("{:02X} ", byte),
+                                self.palette.packet_data,
+                            )
+                        }
+                        ByteColorType::Unknown => {
+                            // 未知区域 - 无颜色
+                            format!("{:02X} ", byte)
+                        }
                     }
                 };
 
-                output.push_str(&formatted_byte);
+                // 有注释的字节加下划线
+                if self
+                    .annotations
+                    .get(current_offset)
+                    .is_some()
+                    && colored::control::SHOULD_COLORIZE
+                        .should_colorize()
+                {
+                    output.push_str(&format!(
+                        "\x1b[4m{}\x1b[24m",
+                        formatted_byte
+                    ));
+                } else {
+                    output.push_str(&formatted_byte);
+                }
             } else {
                 // 填充空白
                 output.push_str("   ");
@@ -339,6 +6087,170 @@ impl HexViewer {
         Ok(output)
     }
 
+    /// 并排比较的一行：左栏为当前文件，右栏为比较文件，不同的字节红底显示
+    fn format_diff_line(
+        &self,
+        target: &DiffTarget,
+        range: Range<usize>,
+    ) -> String {
+        let pane = |data: &FileData, cursor: bool| {
+            let mut output = String::new();
+            for i in 0..self.args.bytes_per_line() {
+                let offset = range.start + i;
+                let Some(byte) = data
+                    .byte(offset)
+                    .filter(|_| offset < range.end)
+                else {
+                    output.push_str("   ");
+                    continue;
+                };
+                let text = format!("{:02X}", byte);
+                let text = if cursor
+                    && self.editing
+                    && offset == self.edit.cursor()
+                {
+                    text.reversed().bold().to_string()
+                } else if self.differs_from(target, offset)
+                {
+                    text.on_red()
+                        .bright_white()
+                        .bold()
+                        .to_string()
+                } else {
+                    text
+                };
+                output.push_str(&text);
+                output.push(' ');
+            }
+            output
+        };
+
+        let changed = range
+            .clone()
+            .filter(|&offset| {
+                self.differs_from(target, offset)
+            })
+            .count();
+        let summary = if changed > 0 {
+            format!(" ≠ {}", changed)
+                .bright_red()
+                .to_string()
+        } else {
+            String::new()
+        };
+        format!(
+            "{}| {}|{}",
+            pane(&self.file_data, true),
+            pane(&target.data, false),
+            summary
+        )
+    }
+
+    /// 以区域着色（背景色加粗体前景色）显示文本
+    fn shade(&self, text: String, shade: Shade) -> String {
+        text.on_color(shade.background)
+            .color(shade.foreground)
+            .bold()
+            .to_string()
+    }
+
+    /// 按字节值或局部熵着色（按结构着色时返回 None）
+    fn format_heatmap_byte(
+        &self,
+        byte: u8,
+        offset: usize,
+    ) -> Option<String> {
+        let text = format!("{:02X} ", byte);
+        match self.color_mode {
+            ColorMode::Structure => None,
+            ColorMode::ByteValue => {
+                Some(match ByteCategory::of(byte) {
+                    ByteCategory::Null => {
+                        text.bright_black().to_string()
+                    }
+                    ByteCategory::Whitespace => {
+                        text.bright_green().to_string()
+                    }
+                    ByteCategory::Control => {
+                        text.bright_magenta().to_string()
+                    }
+                    ByteCategory::Printable => text
+                        .bright_cyan()
+                        .bold()
+                        .to_string(),
+                    ByteCategory::NonAscii => {
+                        text.bright_yellow().to_string()
+                    }
+                    ByteCategory::Full => {
+                        text.bright_red().bold().to_string()
+                    }
+                })
+            }
+            ColorMode::Entropy => {
+                let (start, data) = self.file_data.window();
+                let entropy = heatmap::local_entropy(
+                    data,
+                    offset.saturating_sub(start),
+                );
+                Some(if entropy < 0.25 {
+                    text.on_blue()
+                        .bright_white()
+                        .to_string()
+                } else if entropy < 0.5 {
+                    text.on_cyan().black().to_string()
+                } else if entropy < 0.75 {
+                    text.on_yellow().black().to_string()
+                } else {
+                    text.on_red().bright_white().to_string()
+                })
+            }
+        }
+    }
+
+    /// 行内书签与注释（显示在解析信息之后）
+    fn format_line_marks(
+        &self,
+        range: Range<usize>,
+    ) -> String {
+        if self.args.accessible {
+            let bookmark =
+                if self.bookmarks.any_in(range.clone()) {
+                    tr!(" [书签]")
+                } else {
+                    ""
+                };
+            let notes = self
+                .annotations
+                .in_range(range)
+                .map(|(offset, note)| {
+                    tr!(
+                        " [注释 {}: {}]",
+                        format!("{:X}", offset),
+                        note
+                    )
+                })
+                .collect::<String>();
+            return format!("{}{}", bookmark, notes);
+        }
+        let bookmark =
+            if self.bookmarks.any_in(range.clone()) {
+                " ⚑".bright_magenta().bold().to_string()
+            } else {
+                String::new()
+            };
+        let notes = self
+            .annotations
+            .in_range(range)
+            .map(|(offset, note)| {
+                format!(" ✎ {:X}: {}", offset, note)
+                    .bright_blue()
+                    .bold()
+                    .to_string()
+            })
+            .collect::<String>();
+        format!("{}{}", bookmark, notes)
+    }
+
     /// 格式化解析信息
     fn format_parsed_info(
         &self,
@@ -350,14 +6262,10 @@ impl HexViewer {
             self.format_file_header_info(data, offset)
         }
         // 数据包区域
-        else if let Some(packet_info) =
+        else if let Some(packet) =
             self.find_packet_header_in_line(offset)
         {
-            self.format_packet_info(
-                data,
-                offset,
-                &packet_info,
-            )
+            self.format_packet_info(data, offset, &packet)
         }
         // 其他区域 - 解析失败时不显示原始数据
         else {
@@ -451,10 +6359,10 @@ impl HexViewer {
         &self,
         data: &[u8],
         offset: usize,
-        packet_info: &PacketInfo,
+        packet: &PacketRef<'_>,
     ) -> String {
-        let packet_start = packet_info.start;
-        let header_end = packet_start + 16;
+        let packet_start = packet.offset();
+        let header_end = packet.payload_offset();
         let data_start = header_end;
 
         // 检查当前行是否与数据包头区域有重叠
@@ -470,16 +6378,12 @@ impl HexViewer {
             if packet_start >= offset
                 && packet_start < line_end
             {
-                let seconds = packet_info
-                    .packet
-                    .header
-                    .timestamp_seconds;
-                let nanoseconds = packet_info
-                    .packet
-                    .header
-                    .timestamp_nanoseconds;
-                let (time_text, is_time_valid) =
-                    Self::format_packet_time(
+                let seconds =
+                    packet.header().timestamp_seconds;
+                let nanoseconds =
+                    packet.header().timestamp_nanoseconds;
+                let (time_text, is_time_valid) = self
+                    .format_packet_time(
                         seconds,
                         nanoseconds,
                     );
@@ -497,16 +6401,38 @@ impl HexViewer {
                 // 数据包长度通常都是有效的，显示为绿色
                 let colored_len = format!(
                     "{}",
-                    packet_info.packet.header.packet_length
+                    packet.header().packet_length
                 )
                 .bright_green()
                 .to_string();
 
+                let record = &self.args.record;
+                let sequence = record
+                    .read(
+                        packet.header_bytes(),
+                        RecordField::Sequence,
+                        self.parser.endianness(),
+                    )
+                    .map(|sequence| {
+                        format!(" SEQ: {}", sequence)
+                    })
+                    .unwrap_or_default();
+                let checksum = if record.has_checksum() {
+                    format!(
+                        " CRC: 0x{:08X}",
+                        packet.header().checksum
+                    )
+                } else {
+                    String::new()
+                };
+
                 format!(
-                    " TIME: {} LEN: {} CRC: 0x{:08X}",
+                    " TIME: {} LEN: {}{}{}{}",
                     colored_time,
                     colored_len,
-                    packet_info.packet.header.checksum
+                    sequence,
+                    checksum,
+                    self.format_timing_marker(packet)
                 )
             }
             // 如果当前行包含数据包头的后半部分（长度和校验和），不显示额外信息
@@ -521,19 +6447,40 @@ impl HexViewer {
         }
     }
 
-    /// 格式化数据包时间戳为 YYYY-MM-dd HH:mm:ss.ns，返回(时间字符串, 是否有效)
+    /// 时间戳显示方式（按文件头的时区偏移与时间精度，--utc 时使用 UTC）
+    fn timestamp_style(&self) -> TimestampStyle {
+        let origin = self
+            .parser
+            .packets()
+            .first()
+            .map_or(0, |packet| {
+                packet.header.timestamp_nanos()
+            });
+        self.parser
+            .file_header()
+            .map_or_else(
+                TimestampStyle::default,
+                |header| {
+                    TimestampStyle::from_header(
+                        header,
+                        self.args.utc,
+                    )
+                },
+            )
+            .with_format(self.args.time_format())
+            .with_origin(origin)
+    }
+
+    /// 格式化数据包时间戳为 YYYY-MM-dd HH:mm:ss.fff，返回(时间字符串, 是否有效)
     fn format_packet_time(
+        &self,
         seconds: u32,
         nanoseconds: u32,
     ) -> (String, bool) {
-        if let Some(dt) = DateTime::from_timestamp(
-            seconds as i64,
-            nanoseconds,
-        ) {
-            let base =
-                dt.format("%Y-%m-%dT%H:%M:%S").to_string();
-            let time_str =
-                format!("{}.{:09}", base, nanoseconds);
+        if let Some(time_str) = self
+            .timestamp_style()
+            .format(seconds, nanoseconds)
+        {
             (time_str, true) // 有效时间戳
         } else {
             let time_str = format!(
@@ -544,6 +6491,37 @@ impl HexViewer {
         }
     }
 
+    /// ASCII 列：可打印字符原样显示，其余显示为暗色的 `.`，选中的字节反色
+    fn format_ascii_line(
+        &self,
+        data: &[u8],
+        offset: usize,
+    ) -> String {
+        let mut output = String::new();
+        for (i, &byte) in data.iter().enumerate() {
+            let text = if (32..=126).contains(&byte) {
+                (byte as char).to_string().normal()
+            } else {
+                ".".bright_black()
+            };
+            let selected =
+                self.selection.as_ref().is_some_and(
+                    |range| range.contains(&(offset + i)),
+                );
+            if selected {
+                output.push_str(
+                    &text.on_white().black().to_string(),
+                );
+            } else {
+                output.push_str(&text.to_string());
+            }
+        }
+        let padding =
+            self.args.bytes_per_line() - data.len() + 1;
+        output.push_str(&" ".repeat(padding));
+        output
+    }
+
     /// 格式化原始数据
     fn format_raw_data(&self, data: &[u8]) -> String {
         let mut output = String::new();
@@ -562,32 +6540,37 @@ impl HexViewer {
     fn find_packet_header_in_line(
         &self,
         line_offset: usize,
-    ) -> Option<PacketInfo> {
-        let mut current_offset = 16; // 跳过文件头
-        let line_end = line_offset + 16; // 当前行结束位置
-
-        for packet in self.parser.packets() {
-            let packet_start = current_offset;
-            let packet_header_size = 16;
-            let packet_data_size =
-                packet.header.packet_length as usize;
-            let packet_total_size =
-                packet_header_size + packet_data_size;
-
-            // 检查数据包头是否在当前行内
-            if packet_start >= line_offset
-                && packet_start < line_end
-            {
-                return Some(PacketInfo {
-                    start: packet_start,
-                    packet: packet.clone(),
-                });
-            }
+    ) -> Option<PacketRef<'_>> {
+        let line_end =
+            line_offset + self.args.bytes_per_line(); // 当前行结束位置
 
-            current_offset += packet_total_size;
-        }
+        // 第一个结束位置在行首之后的数据包，检查其头部是否从当前行内开始
+        let index =
+            self.parser.packet_index_from(line_offset)?;
+        let packet = self
+            .file_data
+            .packet_ref(&self.parser, index)?;
+        let packet = if packet.offset() < line_offset {
+            self.file_data
+                .packet_ref(&self.parser, index + 1)?
+        } else {
+            packet
+        };
+
+        (packet.offset() < line_end).then_some(packet)
+    }
 
-        None
+    /// 字节是否属于不匹配过滤条件的数据包
+    fn is_filtered_out(&self, offset: usize) -> bool {
+        let Some(filter) = &self.packet_filter else {
+            return false;
+        };
+        self.parser.packet_index_at(offset).is_some_and(
+            |index| {
+                !filter
+                    .matches(&self.parser.packets()[index])
+            },
+        )
     }
 
     /// 获取指定字节位置的颜色类型（用于颜色标记）
@@ -596,47 +6579,269 @@ impl HexViewer {
         byte_offset: usize,
     ) -> ByteColorType {
         // 文件头区域
-        if byte_offset < 16 {
+        if byte_offset < FILE_HEADER_SIZE {
+            if self
+                .invalid_header_fields()
+                .iter()
+                .any(|field| field.contains(&byte_offset))
+            {
+                return ByteColorType::InvalidField;
+            }
             return ByteColorType::FileHeader;
         }
 
-        let mut current_offset = 16; // 跳过文件头
+        match self.parser.packet_index_at(byte_offset) {
+            Some(index) => {
+                let packet = &self.parser.packets()[index];
+                if byte_offset
+                    < packet.offset
+                        + self.parser.header_size()
+                {
+                    ByteColorType::PacketHeader
+                } else {
+                    ByteColorType::PacketData
+                }
+            }
+            None => ByteColorType::Unknown,
+        }
+    }
+}
+
+/// 格式化速率（K / M / G 单位）
+fn format_rate(rate: f64) -> String {
+    const UNITS: [(f64, &str); 3] =
+        [(1e9, "G"), (1e6, "M"), (1e3, "K")];
+    UNITS.iter().find(|(scale, _)| rate >= *scale).map_or(
+        format!("{:.1}", rate),
+        |(scale, unit)| {
+            format!("{:.1}{}", rate / scale, unit)
+        },
+    )
+}
 
-        for packet in self.parser.packets() {
-            let packet_start = current_offset;
-            let packet_header_end = packet_start + 16;
-            let packet_data_end = packet_header_end
-                + packet.header.packet_length as usize;
+/// 无障碍模式的标记说明（L 显示）
+const ACCESSIBLE_LEGEND: [(&str, &str); 7] = [
+    ("[文件头] [包 N 头]", "区域开始处的标记"),
+    ("[包 N 负载]", "数据包负载开始"),
+    ("A1<", "编辑光标"),
+    ("A1+", "查找匹配与选中范围"),
+    ("A1*", "已修改的字节"),
+    ("A1!", "与基线不同（--baseline）"),
+    ("A1~", "高亮模式匹配的字节"),
+];
 
-            if byte_offset >= packet_start
-                && byte_offset < packet_header_end
-            {
-                return ByteColorType::PacketHeader;
-            } else if byte_offset >= packet_header_end
-                && byte_offset < packet_data_end
-            {
-                return ByteColorType::PacketData;
-            }
+/// 命令行支持的命令（Tab 补全与未知命令提示）
+const COMMANDS: [&str; 15] = [
+    "goto",
+    "packet",
+    "jump",
+    "search",
+    "filter",
+    "select",
+    "export",
+    "copy",
+    "theme",
+    "columns",
+    "fold",
+    "unfold",
+    "highlight",
+    "nohighlight",
+    "workspace",
+];
+
+/// 补全命令名：唯一匹配时补全并追加空格，多个匹配时补全公共前缀
+fn complete_command(editor: &mut LineEditor) {
+    let text = editor.text();
+    if text.contains(char::is_whitespace) {
+        return;
+    }
+    if let Some(completion) =
+        complete_prefix(text, COMMANDS.iter().copied(), " ")
+    {
+        editor.set_text(&completion);
+    }
+}
+
+/// 补全查找框中的 `@名称`
+fn complete_saved_search(
+    editor: &mut LineEditor,
+    searches: &BTreeMap<String, String>,
+) {
+    let Some(text) = editor.text().strip_prefix('@') else {
+        return;
+    };
+    if let Some(completion) = complete_prefix(
+        text,
+        searches.keys().map(String::as_str),
+        "",
+    ) {
+        editor.set_text(&format!("@{}", completion));
+    }
+}
+
+/// 以 `text` 开头的候选：唯一匹配时返回该候选加 `suffix`，
+/// 多个匹配时返回公共前缀
+fn complete_prefix<'a>(
+    text: &str,
+    candidates: impl Iterator<Item = &'a str>,
+    suffix: &str,
+) -> Option<String> {
+    let candidates: Vec<&str> = candidates
+        .filter(|name| name.starts_with(text))
+        .collect();
+    let completion = match candidates.as_slice() {
+        [] => return None,
+        [name] => format!("{}{}", name, suffix),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(
+                first.len(),
+                |len, name| {
+                    first
+                        .bytes()
+                        .zip(name.bytes())
+                        .take(len)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                },
+            );
+            first[..common].to_string()
+        }
+    };
+    Some(completion)
+}
 
-            current_offset = packet_data_end;
+/// 以空格分隔的十六进制字节（如 `DE AD BE EF`）
+fn format_hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 问题描述
+pub(crate) fn diagnostic_label(
+    diagnostic: &Diagnostic,
+) -> String {
+    let packet = diagnostic
+        .packet
+        .map(|index| tr!("数据包 #{} ", index + 1))
+        .unwrap_or_default();
+    let label = match diagnostic.kind {
+        DiagnosticKind::InvalidHeader => {
+            tr!("文件头魔数或版本号无效")
+        }
+        DiagnosticKind::BadChecksum => tr!("CRC 校验失败"),
+        DiagnosticKind::BackwardsTimestamp => {
+            tr!("时间倒退")
+        }
+        DiagnosticKind::Truncated => {
+            tr!("文件末尾数据不完整 (数据包被截断)")
         }
+        DiagnosticKind::Skipped => {
+            tr!("无法解析的字节 (已跳过)")
+        }
+        DiagnosticKind::Rule => diagnostic
+            .message
+            .as_deref()
+            .unwrap_or(tr!("违反校验规则")),
+    };
+    format!("{}{}", packet, label)
+}
 
-        ByteColorType::Unknown
+/// 时间异常类型名称
+fn timing_issue_label(
+    kind: TimingIssueKind,
+) -> &'static str {
+    match kind {
+        TimingIssueKind::Gap => tr!("间隔"),
+        TimingIssueKind::Backwards => tr!("倒退"),
     }
 }
 
-/// 数据包信息
-#[derive(Debug, Clone)]
-struct PacketInfo {
-    start: usize,
-    packet: DataPacket,
+/// 将纳秒格式化为带符号的秒（保留 3 位小数）
+fn format_delta(nanos: i128) -> String {
+    format!("{:+.3}", nanos as f64 / 1e9)
+}
+
+/// 底部输入框
+struct Prompt {
+    kind: PromptKind,
+    editor: LineEditor,
+}
+
+/// 渲染缓存的键：行的字节范围与影响渲染结果的显示设置
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct LineKey {
+    range: Range<usize>,
+    bytes_per_line: usize,
+    color_mode: ColorMode,
+    columns: ColumnLayout,
+    relative_offsets: bool,
+    colorize: bool, // 是否输出颜色（:theme）
+}
+
+/// 后台加载中目标尚未到达的跳转
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingJump {
+    Offset(usize), // 字节偏移
+    Packet(usize), // 数据包序号（从 0 开始）
+}
+
+/// 输入框用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PromptKind {
+    SaveAs,          // 另存为路径
+    Goto,            // 跳转目标（偏移或数据包序号）
+    TimeShift,       // 时间偏移或对齐时间
+    Command,         // `:` 命令
+    Annotate(usize), // 指定偏移处的注释
+    CopyAs,          // 复制数据包负载的格式
+    Search,          // 查找模式
+}
+
+impl PromptKind {
+    /// 输入框提示文字
+    fn label(self) -> String {
+        let label = match self {
+            PromptKind::Annotate(offset) => {
+                return tr!(
+                    "注释 {} (留空删除):",
+                    format!("0x{:08X}", offset)
+                );
+            }
+            PromptKind::SaveAs => "另存为:",
+            PromptKind::Goto => {
+                "跳转到 (0x偏移 / 偏移 / 0x起-0x止 / #包序号 / #起-止):"
+            }
+            PromptKind::TimeShift => {
+                "时间平移 (如 -3600s，或 @2024-01-01T00:00:00Z 对齐首包):"
+            }
+            PromptKind::Command => ":",
+            PromptKind::Search => {
+                "查找 (DE AD BE EF / \"文本\" / re:正则):"
+            }
+            PromptKind::CopyAs => {
+                "复制负载为 (c / rust / python / base64):"
+            }
+        };
+        text(label).to_string()
+    }
 }
 
 /// 字节颜色类型
 #[derive(Debug, Clone, PartialEq)]
 enum ByteColorType {
     FileHeader,   // 文件头 - 紫色
+    InvalidField, // 文件头中无效的字段 - 红色
     PacketHeader, // 数据包头 - 青色
     PacketData,   // 数据包数据 - 黄色
     Unknown,      // 未知区域 - 无颜色
 }
+
+/// 在路径后追加后缀（如 `a.pcap` → `a.pcap.bak`）
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
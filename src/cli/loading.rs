@@ -0,0 +1,161 @@
+//! 本地文件的分页加载：内容按需读取，索引在后台建立（大文件带进度显示）
+
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::builder::ParserOptions;
+use pcap_viewer::core::pcap::indexer::{
+    BackgroundIndexer, IndexProgress,
+};
+use pcap_viewer::core::pcap::parser::PcapParser;
+use pcap_viewer::core::source;
+use pcap_viewer::core::source::file::FileSource;
+use pcap_viewer::core::viewer::file_data::FileData;
+use pcap_viewer::tr;
+
+use super::quiet;
+use super::remote::format_size;
+
+/// 不小于该大小的本地文件在后台索引，先显示第一屏
+const BACKGROUND_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// 打开查看器前至少读取的字节数（足够显示第一屏）
+const FIRST_SCREEN_BYTES: usize = 64 * 1024;
+
+/// 等待第一屏数据时刷新进度的间隔
+const PROGRESS_INTERVAL: Duration =
+    Duration::from_millis(100);
+
+/// 进度条宽度（字符数）
+const BAR_WIDTH: usize = 20;
+
+/// 是否按需分页读取该文件（交互模式下不跟随的本地文件）
+pub fn should_page(
+    file_path: &Path,
+    follow: bool,
+    interactive: bool,
+) -> bool {
+    !follow && interactive && !source::is_remote(file_path)
+}
+
+/// 是否应在后台索引该文件（需分页读取的大文件）
+pub fn should_load_in_background(file_path: &Path) -> bool {
+    std::fs::metadata(file_path).is_ok_and(|metadata| {
+        metadata.len() >= BACKGROUND_THRESHOLD
+    })
+}
+
+/// 以分页方式打开文件并等待索引完成（索引时分块读取，不保留文件内容）
+pub fn open(
+    file_path: &Path,
+    options: &ParserOptions,
+) -> Result<(PcapParser, FileData)> {
+    let (mut data, mut indexer) =
+        spawn(file_path, index_options(options))?;
+    let mut parser = PcapParser::empty(options.clone());
+    while !indexer.is_finished() {
+        indexer.receive(
+            &mut parser,
+            &mut data,
+            PROGRESS_INTERVAL,
+        )?;
+    }
+    Ok((parser, data))
+}
+
+/// 启动后台索引，索引到第一屏后返回，其余部分由查看器继续取回。
+/// 等待期间在终端中显示进度
+pub fn start(
+    file_path: &Path,
+    options: &ParserOptions,
+) -> Result<(PcapParser, FileData, BackgroundIndexer)> {
+    let (mut data, mut indexer) =
+        spawn(file_path, index_options(options))?;
+
+    let show_progress =
+        io::stderr().is_terminal() && !quiet();
+    let mut parser = PcapParser::empty(options.clone());
+    while indexer.progress().bytes_read < FIRST_SCREEN_BYTES
+        && !indexer.is_finished()
+    {
+        indexer.receive(
+            &mut parser,
+            &mut data,
+            PROGRESS_INTERVAL,
+        )?;
+        if show_progress {
+            eprint!(
+                "\r{}\x1b[K",
+                tr!(
+                    "加载中 {}: {}",
+                    file_path.display(),
+                    format_progress(&indexer.progress())
+                )
+            );
+            let _ = io::stderr().flush();
+        }
+    }
+
+    if show_progress {
+        eprint!("\r\x1b[K");
+    }
+    Ok((parser, data, indexer))
+}
+
+/// 打开分页读取的文件内容，并在后台只建立索引
+fn spawn(
+    file_path: &Path,
+    options: ParserOptions,
+) -> Result<(FileData, BackgroundIndexer)> {
+    let data = open_paged(file_path)?;
+    let indexer = BackgroundIndexer::spawn_index_only(
+        Box::new(FileSource::open(file_path)?),
+        options,
+    );
+    Ok((data, indexer))
+}
+
+/// 按需分页读取本地文件（只读取文件头）
+pub fn open_paged(file_path: &Path) -> Result<FileData> {
+    FileData::paged(Box::new(FileSource::open(file_path)?))
+}
+
+/// 后台索引的解析选项：在工作线程中顺带校验 CRC
+fn index_options(options: &ParserOptions) -> ParserOptions {
+    ParserOptions {
+        verify_crc: true,
+        ..options.clone()
+    }
+}
+
+/// 格式化进度：进度条、已读取/总大小、数据包数与剩余时间
+pub fn format_progress(progress: &IndexProgress) -> String {
+    let percent = progress.percent().min(100);
+    let filled = percent * BAR_WIDTH / 100;
+    format!(
+        "[{}{}] {}% {} / {} · {}",
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        percent,
+        format_size(progress.bytes_read as u64),
+        format_size(progress.total_bytes as u64),
+        tr!(
+            "{} 个数据包 · 剩余 {}",
+            progress.packets,
+            format_eta(progress)
+        )
+    )
+}
+
+/// 格式化剩余时间（如 `1:05`，尚无法估算时为 `--:--`）
+pub fn format_eta(progress: &IndexProgress) -> String {
+    match progress.eta() {
+        Some(eta) => {
+            let seconds = eta.as_secs();
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        }
+        None => "--:--".to_string(),
+    }
+}
@@ -1,37 +1,437 @@
 //! 命令行界面模块
 
 pub mod args;
+pub mod batch;
+pub mod commands;
+pub mod exit;
+pub mod frequency_view;
 pub mod hex_viewer;
+pub mod loading;
+pub mod pager;
+pub mod recording;
+pub mod remote;
+pub mod stats_view;
+pub mod stream_view;
 
 use clap::Parser;
 use colored::*;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::app::error::types::Result;
+use pcap_viewer::app::config::settings::Config;
+use pcap_viewer::app::error::types::{
+    ParseError, PcapViewerError, Result,
+};
+use pcap_viewer::app::i18n::{self, Lang};
+use pcap_viewer::app::logging::setup::init_logging;
+use pcap_viewer::tr;
 
-use self::args::CliArgs;
+use self::args::{Cli, CliArgs, Command};
 use self::hex_viewer::HexViewer;
-use crate::core::pcap::parser::PcapParser;
+#[cfg(feature = "scripting")]
+use pcap_viewer::core::dissect::script::ScriptDissector;
+use pcap_viewer::core::pcap::builder::ParserOptions;
+use pcap_viewer::core::pcap::indexer::BackgroundIndexer;
+use pcap_viewer::core::pcap::parser::{
+    PacketLimit, PcapFileHeader, PcapParser,
+};
+use pcap_viewer::core::pcap::ring::PacketStream;
+use pcap_viewer::core::pcap::spec::RecordSpec;
+use pcap_viewer::core::source;
+use pcap_viewer::core::viewer::console::Console;
+use pcap_viewer::core::viewer::file_data::FileData;
+use pcap_viewer::core::viewer::workspace::Workspace;
+
+/// --quiet：只输出机器可读的结果与错误，不输出进度、提示与摘要
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// 是否处于 --quiet 模式
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
 
 /// 运行命令行界面
 pub fn run_cli() -> Result<()> {
-    let args = CliArgs::parse();
+    // 帮助与版本信息正常退出；其余用法错误的退出码为 1
+    // （clap 默认为 2，与“文件不存在”冲突）
+    let cli = Cli::try_parse().unwrap_or_else(|error| {
+        let _ = error.print();
+        exit::exit(if error.use_stderr() {
+            exit::USAGE
+        } else {
+            exit::SUCCESS
+        })
+    });
+    QUIET.store(cli.quiet, Ordering::Relaxed);
+
+    // 界面语言：--lang 优先，否则按区域设置
+    i18n::set_lang(
+        cli.lang
+            .or_else(Lang::from_locale)
+            .unwrap_or_default(),
+    );
+
+    // 初始化日志系统
+    let perf = init_logging(&cli.log_options())?;
+
+    // 是否着色：--color 为 auto 时取决于环境变量与输出是否为终端，
+    // 无法启用 VT 序列的 Windows 控制台不着色
+    let is_terminal = io::stdout().is_terminal();
+    colored::control::set_override(
+        cli.color.should_colorize(is_terminal)
+            && (Console::current().ansi || !is_terminal),
+    );
+
+    // 查看器相关命令使用的配置：配置文件 ← 环境变量，命令行参数最后覆盖
+    let config_path = cli.config.clone();
+    let load_config =
+        || Config::load(config_path.as_deref());
 
-    // 检查文件是否存在
-    if !args.file_path.exists() {
+    let view = |mut args: CliArgs| {
+        load_config().and_then(|config| {
+            args.config = config;
+            run_viewer(args)
+        })
+    };
+
+    let result = match (cli.command, cli.view) {
+        (Some(Command::View(args)), _) => view(*args),
+        (None, Some(args)) => view(args),
+        (Some(Command::Stats(args)), _) => {
+            commands::stats::run(args)
+        }
+        (Some(Command::Info(mut args)), _) => load_config()
+            .and_then(|config| {
+                args.config = config;
+                commands::info::run(args)
+            }),
+        (Some(Command::Report(mut args)), _) => {
+            load_config().and_then(|config| {
+                args.config = config;
+                commands::report::run(args)
+            })
+        }
+        (Some(Command::Validate(args)), _) => {
+            commands::validate::run(args)
+        }
+        #[cfg(feature = "capture")]
+        (Some(Command::Capture(mut args)), _) => {
+            load_config().and_then(|config| {
+                args.config = config;
+                commands::capture::run(args)
+            })
+        }
+        (Some(Command::Listen(mut args)), _) => {
+            load_config().and_then(|config| {
+                args.config = config;
+                commands::listen::run(args)
+            })
+        }
+        (Some(Command::Watch(mut args)), _) => {
+            load_config().and_then(|config| {
+                args.config = config;
+                commands::watch::run(args)
+            })
+        }
+        (Some(Command::Replay(args)), _) => {
+            commands::replay::run(args)
+        }
+        (Some(Command::Timeshift(args)), _) => {
+            commands::timeshift::run(args)
+        }
+        (Some(Command::Export(mut args)), _) => {
+            load_config().and_then(|config| {
+                args.config = config;
+                commands::export::run(args)
+            })
+        }
+        (Some(Command::Truncate(args)), _) => {
+            commands::truncate::run(args)
+        }
+        (Some(Command::Sort(args)), _) => {
+            commands::sort::run(args)
+        }
+        #[cfg(feature = "remote")]
+        (Some(Command::Serve(args)), _) => {
+            commands::serve::run(args)
+        }
+        (Some(Command::Config(args)), _) => {
+            commands::config::run(args, config_path)
+        }
+        (Some(Command::Completions(args)), _) => {
+            commands::completions::run(args)
+        }
+        // clap 保证未指定子命令时必须提供文件路径
+        (None, None) => unreachable!(),
+    };
+
+    if let Some(perf) = perf {
+        eprint!("{}", perf);
+    }
+    result
+}
+
+/// 输出错误信息。解析错误另外输出出错位置附近的十六进制片段，
+/// 并在其下方标出出错的字段
+pub fn report_error(error: &anyhow::Error) {
+    eprintln!("{} {}", tr!("错误").red().bold(), error);
+    if let Some([hex, marker]) = ParseError::of(error)
+        .map(ParseError::snippet_lines)
+        .as_deref()
+    {
+        eprintln!();
+        eprintln!("  {}", hex);
+        eprintln!("  {}", marker.red().bold());
+    }
+}
+
+/// 检查文件是否存在，不存在时输出错误并退出
+pub fn ensure_file_exists(file_path: &Path) {
+    if !file_path.exists() {
         eprintln!(
-            "{} 文件不存在: {}",
-            "错误".red().bold(),
-            args.file_path.display()
+            "{} {}",
+            tr!("错误").red().bold(),
+            tr!("文件不存在: {}", file_path.display())
         );
-        std::process::exit(1);
+        exit::exit(exit::NOT_FOUND);
     }
+}
+
+/// 运行十六进制查看器
+pub fn run_viewer(mut args: CliArgs) -> Result<()> {
+    // 打开工作区文件时打开其中的所有文件，并使用工作区保存的状态
+    let workspace =
+        if Workspace::is_workspace_path(&args.file_path) {
+            ensure_file_exists(&args.file_path);
+            let workspace_path = args.file_path.clone();
+            let workspace =
+                Workspace::load(&workspace_path)?;
+            let mut paths = workspace
+                .files
+                .iter()
+                .map(|file| file.path.clone());
+            args.file_path =
+                paths.next().ok_or_else(|| {
+                    PcapViewerError::Usage(
+                        "Workspace contains no files"
+                            .to_string(),
+                    )
+                })?;
+            args.more_files = paths.collect();
+            args.fresh = true;
+            Some((workspace_path, workspace))
+        } else {
+            None
+        };
+
+    let limit = args.packet_limit();
 
-    // 创建 PCAP 解析器
-    let parser = PcapParser::new(&args.file_path)?;
+    // 自定义数据包头布局
+    if let Some(path) = &args.spec {
+        args.record = RecordSpec::load(path)?;
+    }
+
+    // 读取文件内容并创建 PCAP 解析器
+    let follow = args.follow;
+    let interactive = !args.dump_output();
+    let options = args.parser_options();
+    let more_files = std::mem::take(&mut args.more_files);
+    let diff_path = args.diff.take();
+    let baseline_path = args.baseline.take();
+    let record_to = args.record_to.take();
+    if follow
+        && more_files
+            .iter()
+            .any(|path| source::is_stream(path))
+    {
+        return Err(PcapViewerError::Usage(
+            "a FIFO or device can only be followed as the first file"
+                .to_string(),
+        )
+        .into());
+    }
+    let mut stream = None;
+    let (parser, file_data, indexer) =
+        if follow && source::is_stream(&args.file_path) {
+            let (parser, file_data, packets) = open_stream(
+                &args.file_path,
+                &options,
+                args.ring_size as usize,
+            )?;
+            stream = Some(packets);
+            (parser, file_data, None)
+        } else {
+            open_file(
+                &args.file_path,
+                follow,
+                interactive,
+                &options,
+                limit,
+            )?
+        };
 
     // 创建十六进制查看器
-    let mut viewer = HexViewer::new(parser, args)?;
+    #[cfg(feature = "scripting")]
+    let script_path = args.script.clone();
+    let mut viewer =
+        HexViewer::new(parser, file_data, indexer, args)?;
+
+    if let Some(stream) = stream {
+        viewer.set_stream(stream);
+    }
+    if let Some(path) = record_to {
+        viewer.start_recording(&path)?;
+    }
+
+    // 加载自定义解析脚本
+    #[cfg(feature = "scripting")]
+    if let Some(script_path) = script_path {
+        let dissector =
+            ScriptDissector::from_file(&script_path)?;
+        viewer.set_dissector(Box::new(dissector));
+    }
+
+    // 并排比较的文件
+    if let Some(diff_path) = diff_path {
+        let diff_data = load_compare_data(&diff_path)?;
+        viewer.set_diff(diff_path, diff_data)?;
+    }
+
+    // 比较基线
+    if let Some(baseline_path) = baseline_path {
+        let baseline_data =
+            load_compare_data(&baseline_path)?;
+        viewer
+            .set_baseline(baseline_path, baseline_data)?;
+    }
+
+    // 其余文件在后台打开，按 n/N 或数字键切换
+    for file_path in more_files {
+        let (parser, file_data, indexer) = open_file(
+            &file_path,
+            follow,
+            interactive,
+            &options,
+            limit,
+        )?;
+        viewer.add_file(
+            file_path, parser, file_data, indexer,
+        );
+    }
+
+    if let Some((workspace_path, workspace)) = workspace {
+        viewer.open_workspace(workspace_path, &workspace);
+    }
 
     // 运行查看器
     viewer.run()
 }
+
+/// 打开查看器的文件：交互查看本地文件时内容按需分页读取，
+/// 大文件索引到第一屏即返回，其余部分在后台继续索引。
+/// 指定 `limit` 时只解析开头或末尾的若干个数据包
+fn open_file(
+    file_path: &Path,
+    follow: bool,
+    interactive: bool,
+    options: &ParserOptions,
+    limit: Option<PacketLimit>,
+) -> Result<(PcapParser, FileData, Option<BackgroundIndexer>)>
+{
+    if loading::should_page(file_path, follow, interactive)
+    {
+        ensure_file_exists(file_path);
+        if loading::should_load_in_background(file_path) {
+            let (parser, file_data, indexer) =
+                loading::start(file_path, options)?;
+            return Ok((parser, file_data, Some(indexer)));
+        }
+        let (parser, file_data) =
+            loading::open(file_path, options)?;
+        return Ok((parser, file_data, None));
+    }
+
+    let file_data = load_file_data(file_path, follow)?;
+    let parser = match limit {
+        Some(limit)
+            if PcapFileHeader::read(
+                &file_data,
+                options.endianness,
+            )
+            .is_ok() =>
+        {
+            PcapParser::from_bytes_limited(
+                &file_data,
+                options.clone(),
+                limit,
+            )?
+        }
+        _ => PcapParser::from_bytes_or_raw(
+            &file_data,
+            options.clone(),
+        )?,
+    };
+    Ok((parser, file_data.into(), None))
+}
+
+/// 跟随命名管道或字符设备：等待写入端写出文件头后在后台顺序读取，
+/// 只保留最近 `capacity` 个数据包
+fn open_stream(
+    file_path: &Path,
+    options: &ParserOptions,
+    capacity: usize,
+) -> Result<(PcapParser, FileData, PacketStream)> {
+    ensure_file_exists(file_path);
+    if io::stderr().is_terminal() && !quiet() {
+        eprintln!(
+            "{}",
+            tr!("等待写入端: {}", file_path.display())
+        );
+    }
+    let file = std::fs::File::open(file_path)?;
+    let stream = PacketStream::spawn(
+        file,
+        options.clone(),
+        capacity,
+    )?;
+    let file_data = stream.ring().to_bytes();
+    let parser = PcapParser::from_bytes_or_raw(
+        &file_data,
+        options.clone(),
+    )?;
+    Ok((parser, file_data.into(), stream))
+}
+
+/// 打开比较文件：本地文件按需分页读取，远程文件整体下载
+fn load_compare_data(file_path: &Path) -> Result<FileData> {
+    if source::is_remote(file_path) {
+        return Ok(load_file_data(file_path, false)?.into());
+    }
+    ensure_file_exists(file_path);
+    loading::open_paged(file_path)
+}
+
+/// 读取查看器的文件内容（本地路径或远程 URL）
+fn load_file_data(
+    file_path: &Path,
+    follow: bool,
+) -> Result<Vec<u8>> {
+    let _span = tracing::trace_span!("load").entered();
+
+    if !source::is_remote(file_path) {
+        // 检查文件是否存在
+        ensure_file_exists(file_path);
+        return Ok(std::fs::read(file_path)?);
+    }
+
+    if follow {
+        return Err(PcapViewerError::Network(
+            "--follow is not supported for remote files"
+                .to_string(),
+        )
+        .into());
+    }
+    let source = source::open(file_path)?;
+    remote::download(source.as_ref())
+}
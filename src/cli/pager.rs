@@ -0,0 +1,107 @@
+//! 转储输出的分页：输出为终端且超过一屏时交给 `$PAGER`（默认 `less -R`）
+
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use crossterm::terminal;
+use pcap_viewer::core::viewer::text::styled_display_width;
+
+/// 未设置 `$PAGER` 时使用的分页器
+const DEFAULT_PAGER: &str = "less -R";
+
+/// 输出各行：`paginate` 且行数超过一屏时写入分页器，否则直接写到标准输出。
+/// 分页器启动失败时退回标准输出；下游提前关闭（退出分页器、| head）时静默结束
+pub fn write_lines(
+    lines: impl Iterator<Item = io::Result<String>>,
+    paginate: bool,
+) -> io::Result<()> {
+    let result = if paginate {
+        write_paged(lines)
+    } else {
+        write_all(&mut io::stdout().lock(), lines)
+    };
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// 先缓存一屏，超过一屏才启动分页器
+fn write_paged(
+    mut lines: impl Iterator<Item = io::Result<String>>,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()
+        .map(|(width, height)| {
+            (width.max(1) as usize, height as usize)
+        })
+        .unwrap_or((80, 24));
+    // 按折行后占用的终端行数计算，留出 shell 提示符的一行
+    let mut rows = 0;
+    let mut first_screen = Vec::new();
+    for line in lines.by_ref() {
+        let line = line?;
+        rows += styled_display_width(&line)
+            .div_ceil(width)
+            .max(1);
+        first_screen.push(line);
+        if rows >= height {
+            break;
+        }
+    }
+    let fits = rows < height;
+    let buffered = first_screen.into_iter().map(Ok);
+    if fits {
+        return write_all(
+            &mut io::stdout().lock(),
+            buffered,
+        );
+    }
+
+    let command = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    let mut child = match Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!(
+                "failed to start pager `{}`: {}",
+                command,
+                e
+            );
+            return write_all(
+                &mut io::stdout().lock(),
+                buffered.chain(lines),
+            );
+        }
+    };
+
+    let mut stdin =
+        child.stdin.take().expect("piped stdin");
+    let result =
+        write_all(&mut stdin, buffered.chain(lines));
+    // 关闭管道后等待分页器退出，再把终端交还给 shell
+    drop(stdin);
+    child.wait()?;
+    result
+}
+
+fn write_all(
+    out: &mut impl Write,
+    lines: impl Iterator<Item = io::Result<String>>,
+) -> io::Result<()> {
+    let mut out = io::BufWriter::new(out);
+    for line in lines {
+        writeln!(out, "{}", line?)?;
+    }
+    out.flush()
+}
@@ -0,0 +1,92 @@
+//! 跟随时把查看器读到的数据包同时录制到文件（--record）
+
+use std::path::Path;
+use std::time::Instant;
+
+use pcap_viewer::app::error::types::{
+    PcapViewerError, Result,
+};
+use pcap_viewer::core::pcap::parser::{
+    PcapParser, FILE_HEADER_SIZE,
+};
+use pcap_viewer::core::pcap::rollover::{
+    RingSpec, RollingWriter,
+};
+
+use super::commands::live::{self, SYNC_INTERVAL};
+
+/// 进行中的录制
+pub struct Recording {
+    target: String, // 录制目标的描述
+    writer: RollingWriter,
+    written: usize, // 数据源累计读到的数据包中已写入的数量
+    last_sync: Instant,
+}
+
+impl Recording {
+    /// 创建录制文件（`ring` 为滚动方式），按原样写入数据源的文件头与
+    /// `parser` 中已有的数据包。数据源还没有完整的文件头时返回错误
+    pub fn create(
+        path: &Path,
+        ring: Option<RingSpec>,
+        parser: &PcapParser,
+        data: &[u8],
+        total: usize,
+    ) -> Result<Self> {
+        let header =
+            data.get(..FILE_HEADER_SIZE).ok_or_else(|| {
+                PcapViewerError::InvalidFormat(format!(
+                    "Cannot record to {}: the source has no complete file header yet ({} of {} bytes)",
+                    path.display(),
+                    data.len(),
+                    FILE_HEADER_SIZE
+                ))
+            })?;
+        let writer = RollingWriter::create(
+            path,
+            header.to_vec(),
+            ring,
+        )?;
+        let mut recording = Self {
+            target: live::describe_output(path, ring),
+            writer,
+            written: 0,
+            last_sync: Instant::now(),
+        };
+        recording.write_new(parser, data, total)?;
+        Ok(recording)
+    }
+
+    /// 写入新读到的数据包。`total` 为数据源累计读到的数据包数，
+    /// 新数据包位于 `parser` 的末尾（环形缓冲区已丢弃的部分无法再写入）
+    pub fn write_new(
+        &mut self,
+        parser: &PcapParser,
+        data: &[u8],
+        total: usize,
+    ) -> Result<()> {
+        let packets = parser.packets().len();
+        let new =
+            total.saturating_sub(self.written).min(packets);
+        for packet in
+            parser.packet_refs(data).skip(packets - new)
+        {
+            self.writer.copy_packet(&packet)?;
+        }
+        self.written = total.max(self.written);
+
+        self.writer.flush()?;
+        if self.last_sync.elapsed() >= SYNC_INTERVAL {
+            self.writer.sync()?;
+            self.last_sync = Instant::now();
+        }
+        Ok(())
+    }
+
+    /// 同步并关闭文件，返回录制目标的描述与写入的数据包数量
+    pub fn finish(self) -> Result<(String, usize)> {
+        let count = self.writer.packets_written();
+        self.writer.finish()?;
+        Ok((self.target, count))
+    }
+}
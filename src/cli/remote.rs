@@ -0,0 +1,57 @@
+//! 远程数据源下载（带进度显示）
+
+use std::io::{self, IsTerminal, Write};
+
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::source::DataSource;
+use pcap_viewer::tr;
+
+/// 下载数据源的完整内容，并在终端中显示进度
+pub fn download(
+    source: &dyn DataSource,
+) -> Result<Vec<u8>> {
+    let show_progress =
+        io::stderr().is_terminal() && !super::quiet();
+    let mut last_percent = None;
+
+    let data = source.read_all(&mut |read, total| {
+        if !show_progress {
+            return;
+        }
+        let percent = (read * 100).checked_div(total);
+        if percent.is_some() && percent == last_percent {
+            return;
+        }
+        last_percent = percent;
+
+        let status = match percent {
+            Some(percent) => format!(
+                "{} / {} ({}%)",
+                format_size(read),
+                format_size(total),
+                percent
+            ),
+            None => format_size(read),
+        };
+        eprint!(
+            "\r{}\x1b[K",
+            tr!("下载中 {}: {}", source.name(), status)
+        );
+        let _ = io::stderr().flush();
+    })?;
+
+    if show_progress {
+        eprint!("\r\x1b[K");
+    }
+    Ok(data)
+}
+
+/// 格式化字节数
+pub fn format_size(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB)
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
@@ -0,0 +1,139 @@
+//! 统计视图：数据包长度汇总与负载长度分布直方图
+
+use colored::*;
+
+use crate::cli::commands::stats::histogram_lines;
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::pcap::histogram::SizeHistogram;
+use pcap_viewer::core::pcap::parser::PcapParser;
+use pcap_viewer::core::viewer::layout::Region;
+use pcap_viewer::core::viewer::pagination::PaginationState;
+use pcap_viewer::tr;
+
+/// 直方图条形的最大宽度
+const BAR_WIDTH: usize = 40;
+
+/// 分桶大小的调整范围（字节）
+const MIN_BUCKET_SIZE: u32 = 1;
+const MAX_BUCKET_SIZE: u32 = 65536;
+
+/// 统计视图（打开时的快照，不随文件变化更新）
+pub struct StatsView {
+    bucket_size: u32,
+    lines: Vec<String>,
+    pagination: PaginationState,
+}
+
+impl StatsView {
+    /// 根据当前解析结果创建视图
+    pub fn new(
+        parser: &PcapParser,
+        bucket_size: u32,
+        lines_per_page: usize,
+    ) -> Self {
+        let mut view = Self {
+            bucket_size,
+            lines: Vec::new(),
+            pagination: PaginationState::new(
+                lines_per_page,
+                0,
+            ),
+        };
+        view.rebuild(parser);
+        view
+    }
+
+    /// 获取分页状态
+    pub fn pagination_mut(
+        &mut self,
+    ) -> &mut PaginationState {
+        &mut self.pagination
+    }
+
+    /// 将分桶大小加倍或减半后重新统计
+    pub fn resize_buckets(
+        &mut self,
+        parser: &PcapParser,
+        larger: bool,
+    ) {
+        self.bucket_size = if larger {
+            self.bucket_size.saturating_mul(2)
+        } else {
+            self.bucket_size / 2
+        }
+        .clamp(MIN_BUCKET_SIZE, MAX_BUCKET_SIZE);
+        self.rebuild(parser);
+    }
+
+    /// 重新生成视图内容
+    fn rebuild(&mut self, parser: &PcapParser) {
+        let packets = parser.packets();
+        let lengths = packets.iter().map(|packet| {
+            packet.header.packet_length as u64
+        });
+        let total: u64 = lengths.clone().sum();
+
+        let mut lines = vec![tr!(
+            "{} {} 个数据包, {} 字节负载",
+            tr!("总计:").bright_white().bold(),
+            packets.len(),
+            total
+        )];
+        if let (Some(min), Some(max)) =
+            (lengths.clone().min(), lengths.max())
+        {
+            lines.push(tr!(
+                "{} 最小 {} / 平均 {} / 最大 {} 字节",
+                tr!("负载长度:").bright_white().bold(),
+                min,
+                format!(
+                    "{:.1}",
+                    total as f64 / packets.len() as f64
+                ),
+                max
+            ));
+        }
+        lines.push(String::new());
+
+        let histogram = SizeHistogram::compute(
+            packets,
+            self.bucket_size,
+        );
+        lines
+            .extend(histogram_lines(&histogram, BAR_WIDTH));
+
+        self.pagination.update_total_lines(lines.len());
+        self.lines = lines;
+    }
+
+    /// 绘制视图（内容页 + 底部信息）
+    pub fn render(
+        &self,
+        content: &mut Region<'_>,
+        footer: &mut Region<'_>,
+    ) -> Result<()> {
+        let start_line =
+            self.pagination.display_start_line();
+        let end_line = (start_line
+            + self.pagination.lines_per_page())
+        .min(self.lines.len());
+
+        for line in &self.lines[start_line..end_line] {
+            content.push(line.as_str());
+        }
+
+        footer.push_bar(&tr!(
+            " 统计 │ 分桶 {} 字节 │ 第 {} 行 / 共 {} 行",
+            self.bucket_size,
+            start_line + 1,
+            self.lines.len()
+        ));
+        footer.push(
+            tr!("↑↓ ←→ Home/End | +/- 调整分桶 | ESC/s 返回")
+                .bright_black()
+                .to_string(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+//! TCP 流跟踪视图
+
+use colored::*;
+
+use pcap_viewer::app::error::types::Result;
+use pcap_viewer::core::protocol::tcp_stream::{
+    Direction, StreamChunk, StreamKey,
+};
+use pcap_viewer::core::viewer::layout::Region;
+use pcap_viewer::core::viewer::pagination::PaginationState;
+use pcap_viewer::tr;
+
+/// 每行显示的字节数
+const BYTES_PER_LINE: usize = 16;
+
+/// TCP 流跟踪视图：按顺序显示会话双方重组后的负载
+pub struct StreamView {
+    key: StreamKey,
+    data: Vec<u8>,
+    chunk_starts: Vec<(usize, Direction)>, // 每段数据的起始偏移与方向
+    gaps: Vec<(usize, u64)>, // 缺口位置（其后数据的起始偏移）与缺失的字节数
+    packet_count: usize,
+    client_bytes: usize,
+    server_bytes: usize,
+    pagination: PaginationState,
+}
+
+impl StreamView {
+    /// 根据重组结果创建视图
+    pub fn new(
+        key: StreamKey,
+        chunks: Vec<StreamChunk>,
+        packet_count: usize,
+        lines_per_page: usize,
+    ) -> Self {
+        let mut data = Vec::new();
+        let mut chunk_starts =
+            Vec::with_capacity(chunks.len());
+        let mut gaps = Vec::new();
+        let mut client_bytes = 0;
+        let mut server_bytes = 0;
+
+        for chunk in chunks {
+            chunk_starts
+                .push((data.len(), chunk.direction));
+            if chunk.missing > 0 {
+                gaps.push((data.len(), chunk.missing));
+            }
+            match chunk.direction {
+                Direction::ClientToServer => {
+                    client_bytes += chunk.data.len()
+                }
+                Direction::ServerToClient => {
+                    server_bytes += chunk.data.len()
+                }
+            }
+            data.extend_from_slice(&chunk.data);
+        }
+
+        let total_lines =
+            data.len().div_ceil(BYTES_PER_LINE);
+        Self {
+            key,
+            data,
+            chunk_starts,
+            gaps,
+            packet_count,
+            client_bytes,
+            server_bytes,
+            pagination: PaginationState::new(
+                lines_per_page,
+                total_lines,
+            ),
+        }
+    }
+
+    /// 获取分页状态
+    pub fn pagination_mut(
+        &mut self,
+    ) -> &mut PaginationState {
+        &mut self.pagination
+    }
+
+    /// 获取指定偏移字节所属的方向
+    fn direction_at(&self, offset: usize) -> Direction {
+        let index = self
+            .chunk_starts
+            .partition_point(|(start, _)| *start <= offset);
+        self.chunk_starts
+            .get(index.saturating_sub(1))
+            .map(|(_, direction)| *direction)
+            .unwrap_or(Direction::ClientToServer)
+    }
+
+    /// 按方向着色
+    fn colorize(
+        text: String,
+        direction: Direction,
+    ) -> String {
+        match direction {
+            Direction::ClientToServer => {
+                text.bright_red().to_string()
+            }
+            Direction::ServerToClient => {
+                text.bright_blue().to_string()
+            }
+        }
+    }
+
+    /// 绘制视图（数据页 + 底部信息）
+    pub fn render(
+        &self,
+        content: &mut Region<'_>,
+        footer: &mut Region<'_>,
+    ) -> Result<()> {
+        if self.data.is_empty() {
+            content.push(
+                tr!("(该会话没有负载数据)")
+                    .bright_black()
+                    .to_string(),
+            );
+        }
+
+        let start_line =
+            self.pagination.display_start_line();
+        let end_line = (start_line
+            + self.pagination.lines_per_page())
+        .min(self.pagination.total_lines());
+
+        for line in start_line..end_line {
+            let offset = line * BYTES_PER_LINE;
+            let line_end = (offset + BYTES_PER_LINE)
+                .min(self.data.len());
+
+            let mut hex = String::new();
+            let mut ascii = String::new();
+            for i in offset..offset + BYTES_PER_LINE {
+                if i < line_end {
+                    let byte = self.data[i];
+                    let direction = self.direction_at(i);
+                    hex.push_str(&Self::colorize(
+                        format!("{:02X} ", byte),
+                        direction,
+                    ));
+                    let ch = if (32..=126).contains(&byte) {
+                        byte as char
+                    } else {
+                        '.'
+                    };
+                    ascii.push_str(&Self::colorize(
+                        ch.to_string(),
+                        direction,
+                    ));
+                } else {
+                    hex.push_str("   ");
+                }
+            }
+
+            // 在缺口之后的第一行末尾标出缺失的字节数
+            let first =
+                self.gaps.partition_point(|(start, _)| {
+                    *start < offset
+                });
+            let gap = self.gaps[first..]
+                .iter()
+                .take_while(|(start, _)| {
+                    *start < offset + BYTES_PER_LINE
+                })
+                .map(|(_, missing)| missing)
+                .sum::<u64>();
+            let note = if gap > 0 {
+                format!(
+                    "  {}",
+                    tr!("⚠ 此前缺失 {} 字节", gap)
+                        .bright_yellow()
+                )
+            } else {
+                String::new()
+            };
+            content.push(format!(
+                "{:08X}: {}|{}{}",
+                offset, hex, ascii, note
+            ));
+        }
+
+        footer.push_bar(&tr!(
+            " TCP 流: {} ⇄ {} │ {} 个数据包 │ 第 {} 行 / 共 {} 行",
+            self.key.client,
+            self.key.server,
+            self.packet_count,
+            start_line + 1,
+            self.pagination.total_lines()
+        ));
+        footer.push(format!(
+            "{}  {}  {}",
+            Self::colorize(
+                tr!(
+                    "■ 客户端→服务端 {} 字节",
+                    self.client_bytes
+                ),
+                Direction::ClientToServer
+            ),
+            Self::colorize(
+                tr!(
+                    "■ 服务端→客户端 {} 字节",
+                    self.server_bytes
+                ),
+                Direction::ServerToClient
+            ),
+            tr!("| ↑↓ ←→ Home/End | ESC/f 返回")
+                .bright_black()
+        ));
+
+        Ok(())
+    }
+}
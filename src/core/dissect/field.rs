@@ -0,0 +1,19 @@
+//! 解析字段定义
+
+use crate::app::error::types::Result;
+
+/// 解析器输出的带标签字段
+#[derive(Debug, Clone)]
+pub struct DissectedField {
+    pub name: String,  // 字段名称
+    pub value: String, // 字段值（已格式化）
+}
+
+/// 负载解析器：接收数据包负载，返回带标签的字段列表
+pub trait Dissector {
+    /// 解析数据包负载
+    fn dissect(
+        &self,
+        payload: &[u8],
+    ) -> Result<Vec<DissectedField>>;
+}
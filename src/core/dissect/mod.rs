@@ -0,0 +1,5 @@
+//! 负载解析（自定义解析器）模块
+
+pub mod field;
+#[cfg(feature = "scripting")]
+pub mod script;
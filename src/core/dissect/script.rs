@@ -0,0 +1,141 @@
+//! Rhai 脚本解析器
+//!
+//! 脚本需定义 `dissect(payload)` 函数，参数为负载字节（Blob），
+//! 返回字段数组 `[#{ name: "seq", value: 1 }, ...]` 或对象映射
+//! `#{ seq: 1, kind: "ack" }`。
+
+use std::path::Path;
+
+use rhai::{Array, Blob, Dynamic, Engine, Map, Scope, AST};
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::dissect::field::{
+    DissectedField, Dissector,
+};
+
+/// 单次解析允许执行的最大操作数，防止脚本死循环卡住界面
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// 脚本入口函数名
+const ENTRY_FUNCTION: &str = "dissect";
+
+/// 基于 Rhai 脚本的负载解析器
+pub struct ScriptDissector {
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptDissector {
+    /// 从脚本文件加载解析器
+    pub fn from_file<P: AsRef<Path>>(
+        script_path: P,
+    ) -> Result<Self> {
+        let source =
+            std::fs::read_to_string(script_path.as_ref())?;
+        Self::from_source(&source)
+    }
+
+    /// 从脚本源码创建解析器
+    pub fn from_source(source: &str) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let ast = engine.compile(source).map_err(|e| {
+            PcapViewerError::Script(format!(
+                "compile error: {}",
+                e
+            ))
+        })?;
+
+        // 检查入口函数是否存在
+        let has_entry = ast.iter_functions().any(|f| {
+            f.name == ENTRY_FUNCTION && f.params.len() == 1
+        });
+        if !has_entry {
+            return Err(PcapViewerError::Script(format!(
+                "missing function `{}(payload)`",
+                ENTRY_FUNCTION
+            ))
+            .into());
+        }
+
+        Ok(Self { engine, ast })
+    }
+
+    /// 将脚本返回值转换为字段列表
+    fn convert_result(
+        result: Dynamic,
+    ) -> Result<Vec<DissectedField>> {
+        if result.is_map() {
+            let map = result.cast::<Map>();
+            return Ok(map
+                .into_iter()
+                .map(|(name, value)| DissectedField {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+                .collect());
+        }
+
+        if result.is_array() {
+            let array = result.cast::<Array>();
+            let mut fields =
+                Vec::with_capacity(array.len());
+            for item in array {
+                let Some(mut map) = item.try_cast::<Map>()
+                else {
+                    return Err(PcapViewerError::Script(
+                        "array items must be maps with `name` and `value`"
+                            .to_string(),
+                    )
+                    .into());
+                };
+                let name = map
+                    .remove("name")
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                let value = map
+                    .remove("value")
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                fields.push(DissectedField { name, value });
+            }
+            return Ok(fields);
+        }
+
+        if result.is_unit() {
+            return Ok(Vec::new());
+        }
+
+        Err(PcapViewerError::Script(format!(
+            "unsupported return type: {}",
+            result.type_name()
+        ))
+        .into())
+    }
+}
+
+impl Dissector for ScriptDissector {
+    fn dissect(
+        &self,
+        payload: &[u8],
+    ) -> Result<Vec<DissectedField>> {
+        let blob: Blob = payload.to_vec();
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(
+                &mut Scope::new(),
+                &self.ast,
+                ENTRY_FUNCTION,
+                (blob,),
+            )
+            .map_err(|e| {
+                PcapViewerError::Script(format!(
+                    "runtime error: {}",
+                    e
+                ))
+            })?;
+
+        Self::convert_result(result)
+    }
+}
@@ -0,0 +1,59 @@
+//! 命令的数字计数前缀（如 25↓、10]）
+
+use crossterm::event::KeyCode;
+
+/// 计数上限，避免误输入过长的数字导致长时间重复执行
+const MAX_COUNT: usize = 99_999;
+
+/// 输入计数前缀后的按键处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountInput {
+    Pending,            // 数字已记入计数，等待命令按键
+    Cancelled,          // ESC 取消了正在输入的计数
+    Key(Option<usize>), // 命令按键，附带输入的计数（未输入时为 None）
+}
+
+/// 计数前缀状态机：空闲 → 输入数字 → 命令按键（回到空闲）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountPrefix {
+    pending: Option<usize>,
+}
+
+impl CountPrefix {
+    /// 创建空闲状态的计数前缀
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 正在输入的计数
+    pub fn pending(&self) -> Option<usize> {
+        self.pending
+    }
+
+    /// 处理一个按键：数字累加到计数中（首位不能为 0），其他按键取出计数
+    pub fn handle(&mut self, code: KeyCode) -> CountInput {
+        match (code, self.pending) {
+            (KeyCode::Char(ch @ '1'..='9'), None) => {
+                self.pending =
+                    ch.to_digit(10).map(|d| d as usize);
+                CountInput::Pending
+            }
+            (
+                KeyCode::Char(ch @ '0'..='9'),
+                Some(count),
+            ) => {
+                let digit =
+                    ch.to_digit(10).unwrap_or(0) as usize;
+                self.pending = Some(
+                    (count * 10 + digit).min(MAX_COUNT),
+                );
+                CountInput::Pending
+            }
+            (KeyCode::Esc, Some(_)) => {
+                self.pending = None;
+                CountInput::Cancelled
+            }
+            _ => CountInput::Key(self.pending.take()),
+        }
+    }
+}
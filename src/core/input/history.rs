@@ -0,0 +1,84 @@
+//! 输入历史：在输入框中用 ↑/↓ 翻阅之前提交过的内容
+
+/// 默认保留的条数
+pub const DEFAULT_CAPACITY: usize = 100;
+
+/// 输入历史（最新的在末尾，重复提交的内容移到末尾）
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: Vec<String>,
+    capacity: usize,
+    /// 正在查看的条目序号（None 表示未翻阅）
+    cursor: Option<usize>,
+    /// 开始翻阅前输入框中的内容，翻回最新处时恢复
+    draft: String,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl History {
+    /// 创建最多保留 `capacity` 条的历史
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity: capacity.max(1),
+            cursor: None,
+            draft: String::new(),
+        }
+    }
+
+    /// 所有条目（最新的在末尾）
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// 记录一次提交，并结束翻阅
+    pub fn push(&mut self, entry: &str) {
+        self.reset();
+        if entry.is_empty() {
+            return;
+        }
+        self.entries.retain(|existing| existing != entry);
+        self.entries.push(entry.to_string());
+        if self.entries.len() > self.capacity {
+            self.entries.remove(0);
+        }
+    }
+
+    /// 结束翻阅（下次从最新的条目开始）
+    pub fn reset(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+
+    /// 上一条（更早的）；`current` 为输入框中的内容，第一次翻阅时保存
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// 下一条（更新的）；越过最新的条目时回到翻阅前的内容
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.cursor? + 1;
+        if index < self.entries.len() {
+            self.cursor = Some(index);
+            return self
+                .entries
+                .get(index)
+                .map(String::as_str);
+        }
+        self.cursor = None;
+        Some(&self.draft)
+    }
+}
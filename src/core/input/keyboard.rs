@@ -1,55 +1,75 @@
 //! 键盘输入处理
 
-use crossterm::event::KeyCode;
-use std::time::Instant;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use std::time::{Duration, Instant};
 
-/// 键盘输入处理器
+/// 默认的按住加速最大步长
+pub const DEFAULT_MAX_STEP: usize = 4;
+
+/// 默认的按住判定间隔：同一按键两次按下的间隔小于该值时视为按住不放
+/// （终端不报告重复事件时使用）
+pub const DEFAULT_REPEAT_WINDOW: Duration =
+    Duration::from_millis(80);
+
+/// 连续重复多少次后步长加一
+const REPEATS_PER_STEP: usize = 10;
+
+/// 键盘输入处理器：忽略松开事件，识别按住不放的重复按键并计算加速步长
 pub struct KeyboardHandler {
     last_key_time: Instant,
     last_key_code: Option<KeyCode>,
-    debounce_duration_ms: u64,
+    repeat_streak: usize, // 当前按键连续重复的次数
+    max_step: usize, // 加速后的最大步长（1 表示不加速）
+    repeat_window: Duration, // 按住判定间隔
 }
 
 impl KeyboardHandler {
     /// 创建新的键盘处理器
-    pub fn new(debounce_duration_ms: u64) -> Self {
+    pub fn new(
+        max_step: usize,
+        repeat_window: Duration,
+    ) -> Self {
         Self {
             last_key_time: Instant::now(),
             last_key_code: None,
-            debounce_duration_ms,
+            repeat_streak: 0,
+            max_step: max_step.max(1),
+            repeat_window,
         }
     }
 
-    /// 创建默认的键盘处理器（150ms 防抖）
-    pub fn default() -> Self {
-        Self::new(150)
-    }
-
-    /// 判断是否应该处理按键（防抖处理）
-    pub fn should_process_key(
-        &mut self,
-        code: &KeyCode,
-    ) -> bool {
-        let now = Instant::now();
-        let time_since_last =
-            now.duration_since(self.last_key_time);
-
-        // 检查是否是同一个键
-        let is_same_key =
-            self.last_key_code.as_ref() == Some(code);
-
-        // 防抖逻辑：同一个键必须间隔指定时间
-        if is_same_key
-            && time_since_last.as_millis()
-                < self.debounce_duration_ms as u128
-        {
+    /// 记录一次按键事件，返回是否需要处理（松开事件不处理）
+    pub fn accept(&mut self, event: &KeyEvent) -> bool {
+        if event.kind == KeyEventKind::Release {
             return false;
         }
 
-        // 更新状态
-        self.last_key_code = Some(*code);
+        let now = Instant::now();
+        let is_repeat = event.kind == KeyEventKind::Repeat
+            || (self.last_key_code == Some(event.code)
+                && now.duration_since(self.last_key_time)
+                    < self.repeat_window);
+        self.repeat_streak = if is_repeat {
+            self.repeat_streak + 1
+        } else {
+            0
+        };
+        self.last_key_code = Some(event.code);
         self.last_key_time = now;
 
         true
     }
+
+    /// 当前按键的移动步长：按住越久步长越大，不超过最大步长
+    pub fn step(&self) -> usize {
+        (1 + self.repeat_streak / REPEATS_PER_STEP)
+            .min(self.max_step)
+    }
+}
+
+impl Default for KeyboardHandler {
+    /// 创建默认的键盘处理器
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_STEP, DEFAULT_REPEAT_WINDOW)
+    }
 }
@@ -0,0 +1,178 @@
+//! 移动按键绑定表
+
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// 视图移动操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    LineUp,       // 上移一行
+    LineDown,     // 下移一行
+    HalfPageUp,   // 上移半页
+    HalfPageDown, // 下移半页
+    PageUp,       // 上一页
+    PageDown,     // 下一页
+    First,        // 首页
+    Last,         // 末页
+}
+
+/// 移动操作在配置文件中的名称
+const MOTION_NAMES: &[(&str, Motion)] = &[
+    ("line_up", Motion::LineUp),
+    ("line_down", Motion::LineDown),
+    ("half_page_up", Motion::HalfPageUp),
+    ("half_page_down", Motion::HalfPageDown),
+    ("page_up", Motion::PageUp),
+    ("page_down", Motion::PageDown),
+    ("first", Motion::First),
+    ("last", Motion::Last),
+];
+
+impl FromStr for Motion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MOTION_NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|&(_, motion)| motion)
+            .ok_or_else(|| {
+                format!("unknown motion `{}`", s)
+            })
+    }
+}
+
+/// 按键绑定：按键、是否需要 Ctrl、对应的移动操作
+const MOTION_BINDINGS: &[(KeyCode, bool, Motion)] = &[
+    (KeyCode::Up, false, Motion::LineUp),
+    (KeyCode::Char('k'), false, Motion::LineUp),
+    (KeyCode::Down, false, Motion::LineDown),
+    (KeyCode::Char('j'), false, Motion::LineDown),
+    (KeyCode::Char('u'), true, Motion::HalfPageUp),
+    (KeyCode::Char('d'), true, Motion::HalfPageDown),
+    (KeyCode::Left, false, Motion::PageUp),
+    (KeyCode::Char('h'), false, Motion::PageUp),
+    (KeyCode::Char('b'), true, Motion::PageUp),
+    (KeyCode::Right, false, Motion::PageDown),
+    (KeyCode::Char('l'), false, Motion::PageDown),
+    (KeyCode::Char('f'), true, Motion::PageDown),
+    (KeyCode::Home, false, Motion::First),
+    (KeyCode::End, false, Motion::Last),
+    (KeyCode::Char('G'), false, Motion::Last),
+];
+
+/// 移动按键绑定表（默认绑定可由配置文件替换）
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyCode, bool, Motion)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: MOTION_BINDINGS.to_vec(),
+        }
+    }
+}
+
+impl Keymap {
+    /// 以 `keys` 替换移动操作的全部绑定，这些按键原有的其他绑定一并移除
+    pub fn bind(
+        &mut self,
+        motion: Motion,
+        keys: &[(KeyCode, bool)],
+    ) {
+        self.bindings.retain(|&(code, ctrl, bound)| {
+            bound != motion && !keys.contains(&(code, ctrl))
+        });
+        self.bindings.extend(
+            keys.iter()
+                .map(|&(code, ctrl)| (code, ctrl, motion)),
+        );
+    }
+
+    /// 绑定到指定移动操作的按键名称（按绑定表顺序，如 `↑`、`k`）
+    pub fn keys_for(&self, motion: Motion) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|(_, _, bound)| *bound == motion)
+            .map(|&(code, ctrl, _)| key_label(code, ctrl))
+            .collect()
+    }
+
+    /// 查找按键对应的移动操作（gg 由跳转输入框处理）
+    pub fn motion_for(
+        &self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Option<Motion> {
+        let ctrl =
+            modifiers.contains(KeyModifiers::CONTROL);
+        self.bindings
+            .iter()
+            .find(|(key, needs_ctrl, _)| {
+                *key == code && *needs_ctrl == ctrl
+            })
+            .map(|&(_, _, motion)| motion)
+    }
+}
+
+/// 解析按键名称：单个字符、`Up` / `Down` / `Left` / `Right` /
+/// `Home` / `End` / `PageUp` / `PageDown`，可带 `Ctrl-` 前缀（如 `Ctrl-d`）
+pub fn parse_key(
+    text: &str,
+) -> Result<(KeyCode, bool), String> {
+    let (name, ctrl) = match text.strip_prefix("Ctrl-") {
+        Some(name) => (name, true),
+        None => (text, false),
+    };
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(ch), None) if ctrl => {
+            KeyCode::Char(ch.to_ascii_lowercase())
+        }
+        (Some(ch), None) => KeyCode::Char(ch),
+        _ => match name {
+            "Up" | "↑" => KeyCode::Up,
+            "Down" | "↓" => KeyCode::Down,
+            "Left" | "←" => KeyCode::Left,
+            "Right" | "→" => KeyCode::Right,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            _ => {
+                return Err(format!(
+                    "unknown key `{}`",
+                    text
+                ))
+            }
+        },
+    };
+    Ok((code, ctrl))
+}
+
+/// 按键的显示名称
+fn key_label(code: KeyCode, ctrl: bool) -> String {
+    let name = match code {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::Char(ch) if ctrl => {
+            ch.to_ascii_uppercase().to_string()
+        }
+        KeyCode::Char(ch) => ch.to_string(),
+        other => format!("{:?}", other),
+    };
+    if ctrl {
+        format!("Ctrl-{}", name)
+    } else {
+        name
+    }
+}
@@ -0,0 +1,88 @@
+//! 单行文本输入（用于文件名等提示输入）
+
+/// 单行文本编辑器
+#[derive(Debug, Clone, Default)]
+pub struct LineEditor {
+    text: String,
+    cursor: usize, // 光标位置（字符索引）
+}
+
+impl LineEditor {
+    /// 以初始内容创建编辑器，光标位于末尾
+    pub fn new(initial: &str) -> Self {
+        Self {
+            text: initial.to_string(),
+            cursor: initial.chars().count(),
+        }
+    }
+
+    /// 当前内容
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 光标位置（字符索引）
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// 替换全部内容，光标移到末尾
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.cursor = text.chars().count();
+    }
+
+    /// 在光标处插入字符
+    pub fn insert(&mut self, ch: char) {
+        let index = self.byte_index(self.cursor);
+        self.text.insert(index, ch);
+        self.cursor += 1;
+    }
+
+    /// 删除光标前的字符
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let index = self.byte_index(self.cursor);
+        self.text.remove(index);
+    }
+
+    /// 删除光标处的字符
+    pub fn delete(&mut self) {
+        if self.cursor < self.text.chars().count() {
+            let index = self.byte_index(self.cursor);
+            self.text.remove(index);
+        }
+    }
+
+    /// 光标左移
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// 光标右移
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1)
+            .min(self.text.chars().count());
+    }
+
+    /// 光标移到行首
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// 光标移到行尾
+    pub fn move_end(&mut self) {
+        self.cursor = self.text.chars().count();
+    }
+
+    /// 字符索引对应的字节索引
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+}
@@ -1,3 +1,7 @@
 //! 输入处理模块
 
+pub mod count;
+pub mod history;
 pub mod keyboard;
+pub mod keymap;
+pub mod line_editor;
@@ -1,5 +1,10 @@
 //! 核心业务逻辑模块
 
+pub mod dissect;
+#[cfg(feature = "cli")]
 pub mod input;
 pub mod pcap;
+pub mod protocol;
+pub mod range;
+pub mod source;
 pub mod viewer;
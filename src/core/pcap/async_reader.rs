@@ -0,0 +1,113 @@
+//! 异步 PCAP 读取器（增量解析网络流或对象存储中的文件）
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::app::error::types::Result;
+use crate::core::pcap::builder::{
+    Endianness, ParserOptions,
+};
+use crate::core::pcap::packet_ref::OwnedPacket;
+use crate::core::pcap::parser::{
+    PcapFileHeader, Record, RecordDecoder, FILE_HEADER_SIZE,
+};
+
+/// 异步 PCAP 读取器：逐个读取数据包，不需要完整文件
+pub struct AsyncPcapReader<R> {
+    reader: R,
+    file_header: PcapFileHeader,
+    records: RecordDecoder,
+}
+
+impl<R: AsyncRead + Unpin> AsyncPcapReader<R> {
+    /// 创建读取器并读取文件头
+    pub async fn new(reader: R) -> Result<Self> {
+        Self::with_options(reader, ParserOptions::default())
+            .await
+    }
+
+    /// 按指定选项创建读取器（lazy 选项不适用于流式读取）
+    pub async fn with_options(
+        mut reader: R,
+        options: ParserOptions,
+    ) -> Result<Self> {
+        let mut buffer = [0u8; FILE_HEADER_SIZE];
+        reader.read_exact(&mut buffer).await?;
+
+        let endianness =
+            options.endianness.resolve(&buffer);
+        let file_header =
+            PcapFileHeader::read(&buffer, endianness)?;
+
+        Ok(Self {
+            reader,
+            file_header,
+            records: RecordDecoder::new(
+                options, endianness,
+            ),
+        })
+    }
+
+    /// 获取文件头
+    pub fn file_header(&self) -> &PcapFileHeader {
+        &self.file_header
+    }
+
+    /// 获取实际使用的字节序
+    pub fn endianness(&self) -> Endianness {
+        self.records.endianness()
+    }
+
+    /// 读取下一个数据包（抽样时跳过未保留的数据包），数据源结束时返回 None
+    pub async fn next_packet(
+        &mut self,
+    ) -> Result<Option<OwnedPacket>> {
+        while !self.records.is_finished() {
+            let mut header_bytes =
+                vec![0u8; self.records.header_size()];
+            let read =
+                self.read_fully(&mut header_bytes).await?;
+            let Some(header) =
+                self.records.header(&header_bytes, read)?
+            else {
+                break;
+            };
+
+            let mut payload =
+                vec![0u8; header.packet_length as usize];
+            let read =
+                self.read_fully(&mut payload).await?;
+            match self.records.packet(
+                header,
+                header_bytes,
+                payload,
+                read,
+            )? {
+                Record::Packet(packet) => {
+                    return Ok(Some(packet))
+                }
+                Record::Skipped => {}
+                Record::End => break,
+            }
+        }
+        Ok(None)
+    }
+
+    /// 尽量填满缓冲区，返回实际读取的字节数（遇到 EOF 时可能不足）
+    async fn read_fully(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let read = self
+                .reader
+                .read(&mut buf[filled..])
+                .await?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+        Ok(filled)
+    }
+}
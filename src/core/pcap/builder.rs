@@ -0,0 +1,337 @@
+//! PCAP 解析器配置（构建器）
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::app::error::types::{
+    ParseError, PcapViewerError, Result,
+};
+use crate::core::pcap::checksum::ChecksumAlgorithm;
+use crate::core::pcap::parser::{
+    DataPacketHeader, PcapParser, MAGIC_NUMBER,
+};
+use crate::core::pcap::sample::Sampling;
+use crate::core::pcap::spec::{RecordField, RecordSpec};
+
+/// 字节序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    /// 根据文件头魔数自动检测
+    #[default]
+    Auto,
+    /// 小端序（标准格式）
+    Little,
+    /// 大端序
+    Big,
+}
+
+impl Endianness {
+    /// 根据文件头确定实际字节序（Auto 时按魔数检测，默认小端序）
+    pub fn resolve(
+        self,
+        header_bytes: &[u8],
+    ) -> Endianness {
+        match self {
+            Endianness::Auto => {
+                let magic_be = u32::from_be_bytes([
+                    header_bytes[0],
+                    header_bytes[1],
+                    header_bytes[2],
+                    header_bytes[3],
+                ]);
+                if magic_be == MAGIC_NUMBER {
+                    Endianness::Big
+                } else {
+                    Endianness::Little
+                }
+            }
+            fixed => fixed,
+        }
+    }
+
+    /// 按字节序读取 u32（Auto 视为小端序）
+    pub fn read_u32(self, bytes: &[u8]) -> u32 {
+        let bytes =
+            [bytes[0], bytes[1], bytes[2], bytes[3]];
+        match self {
+            Endianness::Big => u32::from_be_bytes(bytes),
+            _ => u32::from_le_bytes(bytes),
+        }
+    }
+
+    /// 按字节序编码 u32（Auto 视为小端序）
+    pub fn write_u32(self, value: u32) -> [u8; 4] {
+        match self {
+            Endianness::Big => value.to_be_bytes(),
+            _ => value.to_le_bytes(),
+        }
+    }
+
+    /// 按字节序读取 u16（Auto 视为小端序）
+    pub fn read_u16(self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        match self {
+            Endianness::Big => u16::from_be_bytes(bytes),
+            _ => u16::from_le_bytes(bytes),
+        }
+    }
+}
+
+/// 解析模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// 遇到任何损坏的结构（含 CRC 不匹配）立即返回错误
+    Strict,
+    /// 尽量解析，遇到损坏的数据包时停止（默认）
+    #[default]
+    BestEffort,
+    /// 尽量解析，遇到损坏的数据包时向后搜索下一个有效的数据包继续
+    Lenient,
+}
+
+/// 解析器选项
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// 严格模式：遇到截断、超长或校验失败的数据包时返回错误
+    pub strict: bool,
+    /// 恢复扫描：数据包损坏时逐字节向后搜索包头合理且校验和匹配的数据包，
+    /// 从那里继续解析（仅 `PcapParser` 支持）
+    pub recover: bool,
+    /// 延迟索引：只解析文件头，数据包在调用 `index_packets` 时解析
+    pub lazy: bool,
+    /// 允许的最大数据包长度（超出视为损坏）
+    pub max_packet_len: Option<u32>,
+    /// 是否校验数据包校验和
+    pub verify_crc: bool,
+    /// 校验和算法
+    pub checksum: ChecksumAlgorithm,
+    /// 数据包头布局
+    pub record: RecordSpec,
+    /// 字节序
+    pub endianness: Endianness,
+    /// 抽样：只保留部分数据包（None 为全部保留）
+    pub sample: Option<Sampling>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            recover: false,
+            lazy: false,
+            max_packet_len: None,
+            verify_crc: false,
+            checksum: ChecksumAlgorithm::default(),
+            record: RecordSpec::default(),
+            endianness: Endianness::Auto,
+            sample: None,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// 指定解析模式的选项（严格模式同时校验 CRC32）
+    pub fn for_mode(mode: ParseMode) -> Self {
+        Self::default().with_mode(mode)
+    }
+
+    /// 按解析模式设置严格、恢复扫描与 CRC32 校验
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.strict = mode == ParseMode::Strict;
+        self.recover = mode == ParseMode::Lenient;
+        self.verify_crc |= self.strict;
+        self
+    }
+
+    /// 检查数据包长度上限：严格模式下超限返回错误，否则返回 false 表示应停止解析。
+    /// `header_bytes` 为包头原始字节，附在错误中
+    pub fn check_packet_length(
+        &self,
+        header: &DataPacketHeader,
+        header_bytes: &[u8],
+        file_offset: usize,
+    ) -> Result<bool> {
+        let Some(max_len) = self.max_packet_len else {
+            return Ok(true);
+        };
+        if header.packet_length <= max_len {
+            return Ok(true);
+        }
+        if self.strict {
+            return Err(PcapViewerError::from(
+                ParseError::new(
+                    "Packet length exceeds limit",
+                    self.record.field_at(
+                        RecordField::Length,
+                        file_offset,
+                    ),
+                )
+                .expected(
+                    format!("at most {}", max_len),
+                    header.packet_length,
+                )
+                .snippet(file_offset, header_bytes),
+            )
+            .into());
+        }
+        Ok(false)
+    }
+
+    /// 按选项校验校验和（未启用或包头中没有校验和时返回 None），
+    /// 严格模式下不匹配返回错误
+    pub fn check_checksum(
+        &self,
+        header: &DataPacketHeader,
+        header_bytes: &[u8],
+        payload: &[u8],
+        file_offset: usize,
+    ) -> Result<Option<bool>> {
+        if !self.verify_crc || !self.record.has_checksum() {
+            return Ok(None);
+        }
+        let valid =
+            self.checksum.matches(payload, header.checksum);
+        if !valid && self.strict {
+            return Err(PcapViewerError::from(
+                ParseError::new(
+                    "Checksum mismatch",
+                    self.record.field_at(
+                        RecordField::Checksum,
+                        file_offset,
+                    ),
+                )
+                .expected(
+                    format!(
+                        "0x{:08X}",
+                        self.checksum.compute(payload)
+                    ),
+                    format!("0x{:08X}", header.checksum),
+                )
+                .snippet(file_offset, header_bytes),
+            )
+            .into());
+        }
+        Ok(Some(valid))
+    }
+}
+
+/// PCAP 解析器构建器
+#[derive(Debug, Clone, Default)]
+pub struct PcapParserBuilder {
+    options: ParserOptions,
+}
+
+impl PcapParserBuilder {
+    /// 创建默认配置的构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置严格模式
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options.strict = strict;
+        self
+    }
+
+    /// 设置恢复扫描
+    pub fn recover(mut self, recover: bool) -> Self {
+        self.options.recover = recover;
+        self
+    }
+
+    /// 按解析模式设置选项
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.options = self.options.with_mode(mode);
+        self
+    }
+
+    /// 设置延迟索引
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.options.lazy = lazy;
+        self
+    }
+
+    /// 设置允许的最大数据包长度
+    pub fn max_packet_len(
+        mut self,
+        max_packet_len: u32,
+    ) -> Self {
+        self.options.max_packet_len = Some(max_packet_len);
+        self
+    }
+
+    /// 设置是否校验 CRC32
+    pub fn verify_crc(mut self, verify_crc: bool) -> Self {
+        self.options.verify_crc = verify_crc;
+        self
+    }
+
+    /// 设置校验和算法
+    pub fn checksum(
+        mut self,
+        checksum: ChecksumAlgorithm,
+    ) -> Self {
+        self.options.checksum = checksum;
+        self
+    }
+
+    /// 设置数据包头布局
+    pub fn record(mut self, record: RecordSpec) -> Self {
+        self.options.record = record;
+        self
+    }
+
+    /// 设置字节序
+    pub fn endianness(
+        mut self,
+        endianness: Endianness,
+    ) -> Self {
+        self.options.endianness = endianness;
+        self
+    }
+
+    /// 设置抽样方式
+    pub fn sample(
+        mut self,
+        sample: Option<Sampling>,
+    ) -> Self {
+        self.options.sample = sample;
+        self
+    }
+
+    /// 获取当前选项
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// 打开并解析文件
+    pub fn open<P: AsRef<Path>>(
+        self,
+        file_path: P,
+    ) -> Result<PcapParser> {
+        let file = File::open(file_path.as_ref())?;
+        self.parse_reader(BufReader::new(file))
+    }
+
+    /// 从任意数据源解析
+    pub fn parse_reader<R: Read + Send + 'static>(
+        self,
+        reader: R,
+    ) -> Result<PcapParser> {
+        PcapParser::with_options(
+            Box::new(reader),
+            self.options,
+        )
+    }
+
+    /// 从内存中的完整文件内容解析
+    pub fn parse_bytes(
+        self,
+        data: &[u8],
+    ) -> Result<PcapParser> {
+        self.parse_reader(std::io::Cursor::new(
+            data.to_vec(),
+        ))
+    }
+}
@@ -0,0 +1,183 @@
+//! 数据包校验和算法：不同代的录制设备在包头校验和字段中
+//! 分别写入 CRC32、CRC32C 或简单的异或和
+
+use std::fmt;
+use std::str::FromStr;
+
+/// 自动检测时最多参考的数据包数
+const DETECT_SAMPLE: usize = 32;
+
+/// CRC32C（Castagnoli）的反射多项式
+const CRC32C_POLY: u32 = 0x82F6_3B78;
+
+/// CRC32C 查找表
+const CRC32C_TABLE: [u32; 256] = crc32c_table();
+
+/// 校验和算法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// 自动：校验时任一已知算法一致即视为通过，
+    /// 写入时按已有数据包检测（无法检测时使用 CRC32）
+    #[default]
+    Auto,
+    /// CRC32（IEEE）
+    Crc32,
+    /// CRC32C（Castagnoli）
+    Crc32c,
+    /// 按小端序 u32 逐字异或（末尾不足 4 字节时补零）
+    Xor,
+    /// 不使用校验和（写入 0，校验总是通过）
+    None,
+}
+
+impl ChecksumAlgorithm {
+    /// 可检测的算法（按优先级排列）
+    const KNOWN: [Self; 3] =
+        [Self::Crc32, Self::Crc32c, Self::Xor];
+
+    /// 计算负载的校验和（`Auto` 按 CRC32 计算）
+    pub fn compute(self, payload: &[u8]) -> u32 {
+        match self {
+            Self::Auto | Self::Crc32 => {
+                crc32fast::hash(payload)
+            }
+            Self::Crc32c => crc32c(payload),
+            Self::Xor => xor_sum(payload),
+            Self::None => 0,
+        }
+    }
+
+    /// 校验和是否与负载一致
+    pub fn matches(
+        self,
+        payload: &[u8],
+        checksum: u32,
+    ) -> bool {
+        match self {
+            Self::Auto => {
+                Self::KNOWN.iter().any(|algorithm| {
+                    algorithm.compute(payload) == checksum
+                })
+            }
+            Self::None => true,
+            algorithm => {
+                algorithm.compute(payload) == checksum
+            }
+        }
+    }
+
+    /// 按数据包（负载与包头中的校验和）检测使用的算法：
+    /// 取前 32 个数据包中一致次数最多的已知算法，都不一致时返回 None
+    pub fn detect<'a, I>(packets: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (&'a [u8], u32)>,
+    {
+        let mut counts = [0usize; Self::KNOWN.len()];
+        for (payload, checksum) in
+            packets.into_iter().take(DETECT_SAMPLE)
+        {
+            for (count, algorithm) in
+                counts.iter_mut().zip(Self::KNOWN)
+            {
+                if algorithm.compute(payload) == checksum {
+                    *count += 1;
+                }
+            }
+        }
+        // 次数相同时取优先级高的算法
+        let best = counts
+            .iter()
+            .copied()
+            .max()
+            .filter(|&n| n > 0)?;
+        counts
+            .iter()
+            .position(|&count| count == best)
+            .map(|index| Self::KNOWN[index])
+    }
+
+    /// `Auto` 时按数据包检测实际算法（无法检测时为 CRC32），其余原样返回
+    pub fn resolve<'a, I>(self, packets: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a [u8], u32)>,
+    {
+        match self {
+            Self::Auto => {
+                Self::detect(packets).unwrap_or(Self::Crc32)
+            }
+            algorithm => algorithm,
+        }
+    }
+}
+
+impl FromStr for ChecksumAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "crc32" => Ok(Self::Crc32),
+            "crc32c" => Ok(Self::Crc32c),
+            "xor" => Ok(Self::Xor),
+            "none" => Ok(Self::None),
+            other => Err(format!(
+                "unknown checksum `{}` (expected auto, crc32, crc32c, xor or none)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Auto => "auto",
+            Self::Crc32 => "crc32",
+            Self::Crc32c => "crc32c",
+            Self::Xor => "xor",
+            Self::None => "none",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 计算 CRC32C
+fn crc32c(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC32C_TABLE[((crc ^ byte as u32) & 0xFF) as usize]
+            ^ (crc >> 8)
+    })
+}
+
+/// 生成 CRC32C 查找表
+const fn crc32c_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32C_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// 按小端序 u32 逐字异或（末尾不足 4 字节时补零）
+fn xor_sum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum ^ u32::from_le_bytes(word)
+    })
+}
@@ -0,0 +1,134 @@
+//! 文件问题诊断
+//!
+//! 汇总文件头错误、末尾截断与校验规则（CRC 校验失败、时间倒退及自定义规则）
+//! 发现的违规，按偏移排序，供查看器逐个跳转。
+
+use crate::core::pcap::parser::{
+    DataPacket, PcapFileHeader, PcapParser,
+    FILE_HEADER_SIZE,
+};
+use crate::core::pcap::rules::{FileCtx, RuleSet};
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// 文件头魔数或版本号无效
+    InvalidHeader,
+    /// 数据包负载的校验和与包头不一致
+    BadChecksum,
+    /// 时间戳早于前一个数据包
+    BackwardsTimestamp,
+    /// 文件末尾的数据不足一个完整数据包
+    Truncated,
+    /// 两个数据包之间无法解析的字节（宽松模式下跳过）
+    Skipped,
+    /// 违反自定义校验规则
+    Rule,
+}
+
+/// 单个问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 问题所在的文件偏移
+    pub offset: usize,
+    pub kind: DiagnosticKind,
+    /// 相关数据包序号（从 0 开始）
+    pub packet: Option<usize>,
+    /// 校验规则给出的说明（`规则名: 说明`）
+    pub message: Option<String>,
+}
+
+/// 按内置规则诊断文件内容与解析结果，按偏移排序返回所有问题
+pub fn diagnose(
+    data: &[u8],
+    parser: &PcapParser,
+) -> Vec<Diagnostic> {
+    diagnose_with(data, parser, &RuleSet::builtin())
+}
+
+/// 按给定规则诊断文件内容与解析结果，按偏移排序返回所有问题
+pub fn diagnose_with(
+    data: &[u8],
+    parser: &PcapParser,
+    rules: &RuleSet,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // 文件头（解析器可能是修改前的结构，按当前字节重新校验）
+    let header_valid = data.len() >= FILE_HEADER_SIZE
+        && PcapFileHeader::decode(
+            &data[..FILE_HEADER_SIZE],
+            parser.endianness(),
+        )
+        .validate()
+        .is_ok();
+    if !header_valid {
+        diagnostics.push(Diagnostic {
+            offset: 0,
+            kind: DiagnosticKind::InvalidHeader,
+            packet: None,
+            message: None,
+        });
+    }
+
+    // 逐个数据包运行校验规则
+    let ctx = FileCtx::new(data, parser);
+    diagnostics.extend(rules.check(&ctx).into_iter().map(
+        |(rule, violation)| Diagnostic {
+            offset: violation.offset,
+            kind: rule.kind(),
+            packet: violation.packet,
+            message: Some(format!(
+                "{}: {}",
+                rule.name(),
+                violation.message
+            )),
+        },
+    ));
+
+    // 相邻数据包之间被恢复扫描跳过的字节
+    diagnostics.extend(
+        parser
+            .packets()
+            .windows(2)
+            .filter(|pair| {
+                packet_end(parser, &pair[0])
+                    < pair[1].offset
+            })
+            .map(|pair| Diagnostic {
+                offset: packet_end(parser, &pair[0]),
+                kind: DiagnosticKind::Skipped,
+                packet: None,
+                message: None,
+            }),
+    );
+
+    // 最后一个完整数据包之后的剩余字节
+    let parsed_end = parser
+        .packets()
+        .last()
+        .map_or(FILE_HEADER_SIZE, |packet| {
+            packet_end(parser, packet)
+        });
+    if parsed_end < data.len() {
+        diagnostics.push(Diagnostic {
+            offset: parsed_end,
+            kind: DiagnosticKind::Truncated,
+            packet: None,
+            message: None,
+        });
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.offset);
+    diagnostics
+}
+
+/// 数据包（包头与负载）之后的文件偏移
+fn packet_end(
+    parser: &PcapParser,
+    packet: &DataPacket,
+) -> usize {
+    packet.offset
+        + parser.header_size()
+        + packet.header.packet_length as usize
+}
@@ -0,0 +1,228 @@
+//! 数据包过滤表达式，如 `len>100`、`ts>=1700000000`、`crc!=ok`，
+//! 以及引用配置文件中命名过滤的 `@名称`
+
+use crate::app::error::types::PcapViewerError;
+use crate::core::pcap::parser::DataPacket;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// 过滤字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Len, // 负载长度（字节）
+    Ts,  // 时间戳秒部分
+    Crc, // 校验结果（1 = 通过，0 = 失败）
+}
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// 单个比较条件：字段 运算符 数值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketFilter {
+    field: FilterField,
+    comparison: Comparison,
+    value: u64,
+}
+
+/// 运算符文本，较长的放在前面以便优先匹配
+const OPERATORS: [(&str, Comparison); 7] = [
+    ("<=", Comparison::Le),
+    (">=", Comparison::Ge),
+    ("==", Comparison::Eq),
+    ("!=", Comparison::Ne),
+    ("<", Comparison::Lt),
+    (">", Comparison::Gt),
+    ("=", Comparison::Eq),
+];
+
+impl PacketFilter {
+    /// 数据包是否满足条件（未校验 CRC 的数据包不匹配 crc 条件）
+    pub fn matches(&self, packet: &DataPacket) -> bool {
+        let actual = match self.field {
+            FilterField::Len => {
+                u64::from(packet.header.packet_length)
+            }
+            FilterField::Ts => {
+                u64::from(packet.header.timestamp_seconds)
+            }
+            FilterField::Crc => match packet.checksum_valid
+            {
+                Some(valid) => u64::from(valid),
+                None => return false,
+            },
+        };
+        match self.comparison {
+            Comparison::Lt => actual < self.value,
+            Comparison::Le => actual <= self.value,
+            Comparison::Gt => actual > self.value,
+            Comparison::Ge => actual >= self.value,
+            Comparison::Eq => actual == self.value,
+            Comparison::Ne => actual != self.value,
+        }
+    }
+}
+
+/// 过滤表达式或命名过滤（`@名称`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterSpec {
+    Expr(PacketFilter), // 过滤表达式
+    Preset(String),     // 命名过滤的名称
+}
+
+impl FilterSpec {
+    /// 得到过滤条件，命名过滤在 `presets` 中查找
+    pub fn resolve(
+        &self,
+        presets: &BTreeMap<String, PacketFilter>,
+    ) -> std::result::Result<PacketFilter, PcapViewerError>
+    {
+        match self {
+            Self::Expr(filter) => Ok(*filter),
+            Self::Preset(name) => {
+                presets.get(name).copied().ok_or_else(|| {
+                    let known = presets
+                        .keys()
+                        .map(|name| format!("@{}", name))
+                        .collect::<Vec<_>>();
+                    PcapViewerError::Usage(format!(
+                        "Unknown filter preset @{} (defined: {})",
+                        name,
+                        if known.is_empty() {
+                            "none".to_string()
+                        } else {
+                            known.join(", ")
+                        }
+                    ))
+                })
+            }
+        }
+    }
+}
+
+impl FromStr for FilterSpec {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match text.trim().strip_prefix('@') {
+            Some(name) => {
+                Ok(Self::Preset(name.trim().to_string()))
+            }
+            None => text.parse().map(Self::Expr),
+        }
+    }
+}
+
+impl fmt::Display for FilterSpec {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Expr(filter) => write!(f, "{}", filter),
+            Self::Preset(name) => write!(f, "@{}", name),
+        }
+    }
+}
+
+impl FromStr for PacketFilter {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid packet filter: {}",
+                text
+            ))
+        };
+
+        let text = text.trim();
+        let (position, operator, comparison) = OPERATORS
+            .iter()
+            .filter_map(|&(operator, comparison)| {
+                Some((
+                    text.find(operator)?,
+                    operator,
+                    comparison,
+                ))
+            })
+            .min_by_key(|&(position, operator, _)| {
+                (position, usize::MAX - operator.len())
+            })
+            .ok_or_else(invalid)?;
+
+        let field = match text[..position].trim() {
+            "len" => FilterField::Len,
+            "ts" => FilterField::Ts,
+            "crc" => FilterField::Crc,
+            _ => return Err(invalid()),
+        };
+        let value =
+            text[position + operator.len()..].trim();
+        let value = match (field, value) {
+            (FilterField::Crc, "ok") => 1,
+            (FilterField::Crc, "bad") => 0,
+            (_, value) => match value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+            {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => value.parse(),
+            }
+            .map_err(|_| invalid())?,
+        };
+
+        Ok(Self {
+            field,
+            comparison,
+            value,
+        })
+    }
+}
+
+impl fmt::Display for PacketFilter {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let field = match self.field {
+            FilterField::Len => "len",
+            FilterField::Ts => "ts",
+            FilterField::Crc => "crc",
+        };
+        let operator = match self.comparison {
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+        };
+        match (self.field, self.value) {
+            (FilterField::Crc, 1) => {
+                write!(f, "{}{}ok", field, operator)
+            }
+            (FilterField::Crc, 0) => {
+                write!(f, "{}{}bad", field, operator)
+            }
+            _ => write!(
+                f,
+                "{}{}{}",
+                field, operator, self.value
+            ),
+        }
+    }
+}
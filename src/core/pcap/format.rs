@@ -0,0 +1,152 @@
+//! 文件格式识别：区分本工具的自定义格式、经典 libpcap（大小端、微秒/纳秒）
+//! 与 pcapng
+//!
+//! 自定义格式与 libpcap 的魔数字节相同（字节序相反），只能靠版本号的
+//! 字节序区分；版本号两种都对不上时按自定义格式处理并给出可能的另一种格式，
+//! 不静默猜测。
+
+use std::fmt;
+
+use crate::core::pcap::builder::Endianness;
+
+/// 自定义格式小端 / libpcap 大端（微秒）的魔数字节
+const MAGIC_A1B2C3D4: [u8; 4] = [0xA1, 0xB2, 0xC3, 0xD4];
+/// 自定义格式大端 / libpcap 小端（微秒）的魔数字节
+const MAGIC_D4C3B2A1: [u8; 4] = [0xD4, 0xC3, 0xB2, 0xA1];
+/// libpcap 纳秒精度（小端）的魔数字节
+const MAGIC_NANOS_LE: [u8; 4] = [0x4D, 0x3C, 0xB2, 0xA1];
+/// libpcap 纳秒精度（大端）的魔数字节
+const MAGIC_NANOS_BE: [u8; 4] = [0xA1, 0xB2, 0x3C, 0x4D];
+/// pcapng 节头块类型
+const PCAPNG_BLOCK_TYPE: [u8; 4] = [0x0A, 0x0D, 0x0D, 0x0A];
+/// 版本号 2.4 按小端序编码
+const VERSION_LE: [u8; 4] = [0x02, 0x00, 0x04, 0x00];
+/// 版本号 2.4 按大端序编码
+const VERSION_BE: [u8; 4] = [0x00, 0x02, 0x00, 0x04];
+
+/// 文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatVariant {
+    /// 本工具的自定义格式
+    Custom(Endianness),
+    /// 经典 libpcap
+    Libpcap {
+        endianness: Endianness,
+        /// 纳秒精度时间戳（否则为微秒）
+        nanoseconds: bool,
+    },
+    /// pcapng
+    Pcapng,
+    /// 无法识别
+    Unknown,
+}
+
+impl FormatVariant {
+    /// 是否为本工具可以解析的格式
+    pub fn is_supported(self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+}
+
+impl fmt::Display for FormatVariant {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let order = |endianness: &Endianness| {
+            if *endianness == Endianness::Big {
+                "BE"
+            } else {
+                "LE"
+            }
+        };
+        match self {
+            Self::Custom(endianness) => {
+                write!(f, "custom {}", order(endianness))
+            }
+            Self::Libpcap {
+                endianness,
+                nanoseconds,
+            } => write!(
+                f,
+                "libpcap {} {}",
+                order(endianness),
+                if *nanoseconds { "ns" } else { "µs" }
+            ),
+            Self::Pcapng => f.write_str("pcapng"),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// 格式识别结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatDetection {
+    /// 识别出的格式
+    pub variant: FormatVariant,
+    /// 字段不足以区分时另一种可能的格式
+    pub alternative: Option<FormatVariant>,
+}
+
+impl FormatDetection {
+    /// 是否无法确定格式
+    pub fn is_ambiguous(&self) -> bool {
+        self.alternative.is_some()
+    }
+}
+
+/// 按文件开头的魔数与版本号识别格式
+pub fn detect(data: &[u8]) -> FormatDetection {
+    let certain = |variant| FormatDetection {
+        variant,
+        alternative: None,
+    };
+    let Some(magic) = data.get(..4) else {
+        return certain(FormatVariant::Unknown);
+    };
+    let version = data.get(4..8);
+    let libpcap =
+        |endianness, nanoseconds| FormatVariant::Libpcap {
+            endianness,
+            nanoseconds,
+        };
+
+    // 魔数字节相同时，自定义格式与 libpcap 的字节序相反
+    let shared = |custom: Endianness, other: Endianness| {
+        let (custom_version, other_version) =
+            if custom == Endianness::Big {
+                (VERSION_BE, VERSION_LE)
+            } else {
+                (VERSION_LE, VERSION_BE)
+            };
+        match version {
+            Some(v) if v == custom_version => {
+                certain(FormatVariant::Custom(custom))
+            }
+            Some(v) if v == other_version => {
+                certain(libpcap(other, false))
+            }
+            _ => FormatDetection {
+                variant: FormatVariant::Custom(custom),
+                alternative: Some(libpcap(other, false)),
+            },
+        }
+    };
+
+    match <[u8; 4]>::try_from(magic).unwrap_or_default() {
+        MAGIC_A1B2C3D4 => {
+            shared(Endianness::Little, Endianness::Big)
+        }
+        MAGIC_D4C3B2A1 => {
+            shared(Endianness::Big, Endianness::Little)
+        }
+        MAGIC_NANOS_LE => {
+            certain(libpcap(Endianness::Little, true))
+        }
+        MAGIC_NANOS_BE => {
+            certain(libpcap(Endianness::Big, true))
+        }
+        PCAPNG_BLOCK_TYPE => certain(FormatVariant::Pcapng),
+        _ => certain(FormatVariant::Unknown),
+    }
+}
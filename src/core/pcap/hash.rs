@@ -0,0 +1,58 @@
+//! 文件与数据包负载摘要（SHA-256 / BLAKE3），用于在流转过程中校验抓包文件是否被改动
+
+use std::fmt;
+use std::str::FromStr;
+
+use sha2::{Digest, Sha256};
+
+/// 摘要算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// 计算摘要，返回小写十六进制文本
+    pub fn digest(&self, data: &[u8]) -> String {
+        let bytes: Vec<u8> = match self {
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Blake3 => {
+                blake3::hash(data).as_bytes().to_vec()
+            }
+        };
+        bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" | "sha-256" => Ok(Self::Sha256),
+            "blake3" => Ok(Self::Blake3),
+            other => Err(format!(
+                "unknown hash `{}` (expected sha256 or blake3)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        };
+        f.write_str(name)
+    }
+}
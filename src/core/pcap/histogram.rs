@@ -0,0 +1,75 @@
+//! 数据包负载长度分布
+
+use std::ops::Range;
+
+use crate::core::pcap::parser::DataPacket;
+
+/// 默认分桶大小（字节）
+pub const DEFAULT_BUCKET_SIZE: u32 = 64;
+
+/// 负载长度直方图：按固定大小分桶统计数据包数量
+#[derive(Debug, Clone)]
+pub struct SizeHistogram {
+    bucket_size: u32,
+    counts: Vec<u64>, // 下标为 长度 / 分桶大小
+    total: u64,
+}
+
+impl SizeHistogram {
+    /// 统计所有数据包的负载长度（分桶大小至少为 1）
+    pub fn compute(
+        packets: &[DataPacket],
+        bucket_size: u32,
+    ) -> Self {
+        let bucket_size = bucket_size.max(1);
+        let mut counts = Vec::new();
+        for packet in packets {
+            let bucket = (packet.header.packet_length
+                / bucket_size)
+                as usize;
+            if bucket >= counts.len() {
+                counts.resize(bucket + 1, 0);
+            }
+            counts[bucket] += 1;
+        }
+        Self {
+            bucket_size,
+            counts,
+            total: packets.len() as u64,
+        }
+    }
+
+    /// 分桶大小（字节）
+    pub fn bucket_size(&self) -> u32 {
+        self.bucket_size
+    }
+
+    /// 数据包总数
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// 单个分桶的最大数量
+    pub fn max_count(&self) -> u64 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// 从第一个到最后一个非空分桶的长度范围与数量（中间的空分桶也包含在内）
+    pub fn buckets(
+        &self,
+    ) -> impl Iterator<Item = (Range<u64>, u64)> + '_ {
+        let first = self
+            .counts
+            .iter()
+            .position(|&count| count > 0)
+            .unwrap_or(self.counts.len());
+        let bucket_size = self.bucket_size as u64;
+        self.counts[first..].iter().enumerate().map(
+            move |(i, &count)| {
+                let start =
+                    (first + i) as u64 * bucket_size;
+                (start..start + bucket_size, count)
+            },
+        )
+    }
+}
@@ -0,0 +1,289 @@
+//! 后台索引：在工作线程中分块读取数据源并解析数据包，
+//! 主线程按批次取回已读取的数据与数据包索引
+
+use std::sync::mpsc::{
+    self, Receiver, RecvTimeoutError, Sender,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::app::error::types::Result;
+use crate::core::pcap::builder::{
+    Endianness, ParserOptions,
+};
+use crate::core::pcap::parser::{
+    DataPacket, PcapFileHeader, PcapParser,
+    FILE_HEADER_SIZE,
+};
+use crate::core::source::{DataSource, CHUNK_SIZE};
+
+/// 索引进度
+#[derive(Debug, Clone, Copy)]
+pub struct IndexProgress {
+    /// 已读取的字节数
+    pub bytes_read: usize,
+    /// 数据源总字节数
+    pub total_bytes: usize,
+    /// 已索引的数据包数
+    pub packets: usize,
+    /// 已用时间
+    pub elapsed: Duration,
+}
+
+impl IndexProgress {
+    /// 完成百分比（0-100）
+    pub fn percent(&self) -> usize {
+        (self.bytes_read * 100)
+            .checked_div(self.total_bytes)
+            .unwrap_or(100)
+    }
+
+    /// 按目前的读取速度估算剩余时间（尚未读取任何数据时为 None）
+    pub fn eta(&self) -> Option<Duration> {
+        if self.bytes_read == 0 {
+            return None;
+        }
+        let remaining = self
+            .total_bytes
+            .saturating_sub(self.bytes_read);
+        Some(self.elapsed.mul_f64(
+            remaining as f64 / self.bytes_read as f64,
+        ))
+    }
+}
+
+/// 工作线程发给主线程的消息
+enum IndexMessage {
+    /// 文件头解析完成
+    Header(PcapFileHeader, Endianness),
+    /// 新读取的字节数、数据（只索引时为 None）、其中按抽样保留的数据包
+    /// 及解析到的数据包数（含未保留的）
+    Batch(usize, Option<Vec<u8>>, Vec<DataPacket>, usize),
+    /// 读取或解析失败
+    Failed(anyhow::Error),
+}
+
+/// 后台索引任务
+pub struct BackgroundIndexer {
+    receiver: Receiver<IndexMessage>,
+    total_bytes: usize,
+    bytes_read: usize,
+    packets: usize,
+    started: Instant,
+    finished: bool,
+}
+
+impl BackgroundIndexer {
+    /// 启动工作线程读取并索引数据源，读取的数据随批次一起取回
+    pub fn spawn(
+        source: Box<dyn DataSource + Send>,
+        options: ParserOptions,
+    ) -> Self {
+        Self::start(source, options, true)
+    }
+
+    /// 启动工作线程只索引数据源（调用方已能直接访问数据，如内存映射）
+    pub fn spawn_index_only(
+        source: Box<dyn DataSource + Send>,
+        options: ParserOptions,
+    ) -> Self {
+        Self::start(source, options, false)
+    }
+
+    fn start(
+        source: Box<dyn DataSource + Send>,
+        options: ParserOptions,
+        send_data: bool,
+    ) -> Self {
+        let total_bytes = source.len() as usize;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            if let Err(e) = index_source(
+                source.as_ref(),
+                options,
+                send_data,
+                &sender,
+            ) {
+                let _ =
+                    sender.send(IndexMessage::Failed(e));
+            }
+        });
+
+        Self {
+            receiver,
+            total_bytes,
+            bytes_read: 0,
+            packets: 0,
+            started: Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// 取回工作线程已完成的批次，追加到 `data`（只索引时不追加）与 `parser` 中。
+    /// 最多等待 `timeout`，返回是否收到了新数据
+    pub fn receive<D: Extend<u8>>(
+        &mut self,
+        parser: &mut PcapParser,
+        data: &mut D,
+        timeout: Duration,
+    ) -> Result<bool> {
+        let mut received = false;
+        let mut message =
+            match self.receiver.recv_timeout(timeout) {
+                Ok(message) => Some(message),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.finished = true;
+                    None
+                }
+            };
+
+        while let Some(current) = message.take() {
+            match current {
+                IndexMessage::Header(
+                    header,
+                    endianness,
+                ) => {
+                    parser.begin_index(header, endianness);
+                }
+                IndexMessage::Batch(
+                    len,
+                    chunk,
+                    packets,
+                    seen,
+                ) => {
+                    self.bytes_read += len;
+                    self.packets += seen;
+                    if let Some(chunk) = chunk {
+                        data.extend(chunk);
+                    }
+                    parser.append_packets(packets, seen);
+                    received = true;
+                }
+                IndexMessage::Failed(e) => {
+                    self.finished = true;
+                    return Err(e);
+                }
+            }
+            message = match self.receiver.try_recv() {
+                Ok(message) => Some(message),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    None
+                }
+            };
+        }
+
+        Ok(received)
+    }
+
+    /// 当前进度
+    pub fn progress(&self) -> IndexProgress {
+        IndexProgress {
+            bytes_read: self.bytes_read,
+            total_bytes: self.total_bytes,
+            packets: self.packets,
+            elapsed: self.started.elapsed(),
+        }
+    }
+
+    /// 是否已读取并索引完整个数据源
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// 工作线程：分块读取数据源，每块解析出其中完整的数据包后连同数据一起发送
+fn index_source(
+    source: &dyn DataSource,
+    options: ParserOptions,
+    send_data: bool,
+    sender: &Sender<IndexMessage>,
+) -> Result<()> {
+    let _span = tracing::trace_span!("index").entered();
+    let total = source.len() as usize;
+    let strict = options.strict;
+    let mut parser = PcapParser::empty(options);
+    let mut read = 0;
+    // 尚未解析的字节（从下一个数据包头开始）及其在文件中的偏移
+    let mut pending = Vec::new();
+    let mut pending_offset = 0;
+    let mut header_parsed = false;
+    let mut stopped = false;
+
+    while read < total {
+        let mut chunk =
+            vec![0u8; CHUNK_SIZE.min(total - read)];
+        let len =
+            source.read_at(read as u64, &mut chunk)?;
+        if len == 0 {
+            break; // 数据源提前结束
+        }
+        chunk.truncate(len);
+        read += len;
+
+        let mut packets = Vec::new();
+        let mut seen = 0;
+        if !stopped {
+            pending.extend_from_slice(&chunk);
+            if !header_parsed
+                && pending.len() >= FILE_HEADER_SIZE
+            {
+                match parser.parse_file_header(
+                    &mut &pending[..FILE_HEADER_SIZE],
+                ) {
+                    Ok(header) => {
+                        let endianness =
+                            parser.endianness();
+                        if sender
+                            .send(IndexMessage::Header(
+                                header, endianness,
+                            ))
+                            .is_err()
+                        {
+                            return Ok(()); // 主线程已不再需要结果
+                        }
+                        pending.drain(..FILE_HEADER_SIZE);
+                        pending_offset = FILE_HEADER_SIZE;
+                        header_parsed = true;
+                    }
+                    Err(e) if strict => return Err(e),
+                    // 文件头无效：不再解析，之后只读取原始数据
+                    Err(_) => stopped = true,
+                }
+            }
+            if header_parsed {
+                let scan = parser.scan_sampled(
+                    &pending,
+                    pending_offset,
+                    read >= total,
+                    usize::MAX,
+                )?;
+                pending.drain(..scan.consumed);
+                pending_offset += scan.consumed;
+                packets = scan.packets;
+                seen = scan.seen;
+                stopped = scan.stopped;
+            }
+        }
+
+        let message = IndexMessage::Batch(
+            len,
+            send_data.then_some(chunk),
+            packets,
+            seen,
+        );
+        if sender.send(message).is_err() {
+            return Ok(());
+        }
+    }
+
+    if !header_parsed && strict {
+        // 数据不足一个文件头，按普通解析报告错误
+        parser
+            .parse_file_header(&mut pending.as_slice())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! 按长度定位数据包：`max`（最大）、`min`（最小的非空包）、
+//! `len~1500`（长度最接近 1500）或过滤条件（如 `len>60000`）
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::app::error::types::PcapViewerError;
+use crate::core::pcap::filter::PacketFilter;
+use crate::core::pcap::parser::DataPacket;
+
+/// 跳转目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketTarget {
+    /// 负载最长的数据包
+    Largest,
+    /// 负载最短的非空数据包
+    Smallest,
+    /// 负载长度最接近给定值的数据包
+    NearestLength(u32),
+    /// 满足过滤条件的下一个数据包
+    Matching(PacketFilter),
+}
+
+impl PacketTarget {
+    /// 目标数据包的下标；长度相同时取靠前的数据包，
+    /// 过滤条件取偏移 `after` 之后的第一个匹配（没有时从头查找）
+    pub fn find(
+        &self,
+        packets: &[DataPacket],
+        after: usize,
+    ) -> Option<usize> {
+        let lengths = packets
+            .iter()
+            .map(|packet| packet.header.packet_length)
+            .enumerate();
+        match *self {
+            Self::Largest => lengths
+                .max_by_key(|&(index, len)| {
+                    (len, Reverse(index))
+                })
+                .map(|(index, _)| index),
+            Self::Smallest => lengths
+                .filter(|&(_, len)| len > 0)
+                .min_by_key(|&(index, len)| (len, index))
+                .map(|(index, _)| index),
+            Self::NearestLength(target) => lengths
+                .min_by_key(|&(index, len)| {
+                    (len.abs_diff(target), index)
+                })
+                .map(|(index, _)| index),
+            Self::Matching(filter) => packets
+                .iter()
+                .position(|packet| {
+                    packet.offset > after
+                        && filter.matches(packet)
+                })
+                .or_else(|| {
+                    packets.iter().position(|packet| {
+                        filter.matches(packet)
+                    })
+                }),
+        }
+    }
+}
+
+impl FromStr for PacketTarget {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let text = text.trim();
+        match text.to_ascii_lowercase().as_str() {
+            "max" | "largest" => return Ok(Self::Largest),
+            "min" | "smallest" => {
+                return Ok(Self::Smallest);
+            }
+            _ => {}
+        }
+        if let Some((field, value)) = text.split_once('~') {
+            return match (
+                field.trim(),
+                value.trim().parse(),
+            ) {
+                ("len", Ok(len)) => {
+                    Ok(Self::NearestLength(len))
+                }
+                _ => Err(PcapViewerError::InvalidFormat(
+                    format!(
+                        "Invalid jump target: {}",
+                        text
+                    ),
+                )),
+            };
+        }
+        text.parse().map(Self::Matching)
+    }
+}
+
+impl fmt::Display for PacketTarget {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Largest => f.write_str("max"),
+            Self::Smallest => f.write_str("min"),
+            Self::NearestLength(len) => {
+                write!(f, "len~{}", len)
+            }
+            Self::Matching(filter) => {
+                write!(f, "{}", filter)
+            }
+        }
+    }
+}
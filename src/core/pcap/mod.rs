@@ -1,3 +1,26 @@
 //! PCAP 文件处理模块
 
+#[cfg(feature = "async")]
+pub mod async_reader;
+pub mod builder;
+pub mod checksum;
+pub mod diagnostics;
+pub mod filter;
+pub mod format;
+#[cfg(feature = "hash")]
+pub mod hash;
+pub mod histogram;
+pub mod indexer;
+pub mod locate;
+pub mod packet_ref;
 pub mod parser;
+pub mod reader;
+pub mod ring;
+pub mod rollover;
+pub mod rules;
+pub mod sample;
+pub mod sort;
+pub mod spec;
+pub mod timeshift;
+pub mod timing;
+pub mod writer;
@@ -0,0 +1,122 @@
+//! 零拷贝数据包视图
+
+use crate::core::pcap::parser::{
+    DataPacket, DataPacketHeader,
+};
+
+/// 借用已加载缓冲区的数据包视图（头部与负载均为切片，不复制数据）
+#[derive(Debug, Clone, Copy)]
+pub struct PacketRef<'a> {
+    index: usize,
+    packet: &'a DataPacket,
+    header_size: usize,
+    header_bytes: &'a [u8],
+    payload: &'a [u8],
+}
+
+impl<'a> PacketRef<'a> {
+    /// 在文件缓冲区中定位数据包（`header_size` 为数据包头长度，
+    /// 缓冲区不足时切片按实际长度截断）
+    pub fn new(
+        index: usize,
+        packet: &'a DataPacket,
+        data: &'a [u8],
+        header_size: usize,
+    ) -> Self {
+        Self::with_base(index, packet, data, 0, header_size)
+    }
+
+    /// 在从文件偏移 `base` 开始的部分缓冲区中定位数据包，
+    /// 缓冲区之外的部分按空切片处理
+    pub fn with_base(
+        index: usize,
+        packet: &'a DataPacket,
+        data: &'a [u8],
+        base: usize,
+        header_size: usize,
+    ) -> Self {
+        let local = |offset: usize| {
+            offset.saturating_sub(base).min(data.len())
+        };
+        let header_start = local(packet.offset);
+        let payload_start =
+            local(packet.offset + header_size);
+        let payload_end = local(
+            packet.offset
+                + header_size
+                + packet.header.packet_length as usize,
+        );
+
+        Self {
+            index,
+            packet,
+            header_size,
+            header_bytes: &data
+                [header_start..payload_start],
+            payload: &data[payload_start..payload_end],
+        }
+    }
+
+    /// 数据包序号（从 0 开始）
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// 数据包头在文件中的偏移
+    pub fn offset(&self) -> usize {
+        self.packet.offset
+    }
+
+    /// 负载在文件中的偏移
+    pub fn payload_offset(&self) -> usize {
+        self.packet.offset + self.header_size
+    }
+
+    /// 数据包结束偏移（不含）
+    pub fn end_offset(&self) -> usize {
+        self.payload_offset()
+            + self.packet.header.packet_length as usize
+    }
+
+    /// 解析后的数据包头
+    pub fn header(&self) -> &'a DataPacketHeader {
+        &self.packet.header
+    }
+
+    /// 数据包头原始字节
+    pub fn header_bytes(&self) -> &'a [u8] {
+        self.header_bytes
+    }
+
+    /// 负载字节
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// 校验和校验结果（未启用校验时为 None）
+    pub fn checksum_valid(&self) -> Option<bool> {
+        self.packet.checksum_valid
+    }
+
+    /// 判断偏移是否位于该数据包内（头部或负载）
+    pub fn contains(&self, offset: usize) -> bool {
+        offset >= self.offset()
+            && offset < self.end_offset()
+    }
+}
+
+/// 拥有负载数据的数据包（用于流式读取，数据源不保留在内存中）
+#[derive(Debug, Clone)]
+pub struct OwnedPacket {
+    pub packet: DataPacket,
+    /// 原始数据包头字节（按源文件的字节序与布局）
+    pub header_bytes: Vec<u8>,
+    pub payload: Vec<u8>,
+}
+
+impl OwnedPacket {
+    /// 数据包头
+    pub fn header(&self) -> &DataPacketHeader {
+        &self.packet.header
+    }
+}
@@ -2,12 +2,58 @@
 
 use std::fs::File;
 use std::io::{BufReader, Read};
+use std::ops::Range;
 use std::path::Path;
 
-use crate::app::error::types::Result;
+use crate::app::error::types::{
+    ParseError, PcapViewerError, Result,
+};
+use crate::core::pcap::builder::{
+    Endianness, ParserOptions, PcapParserBuilder,
+};
+use crate::core::pcap::format::{self, FormatVariant};
+use crate::core::pcap::packet_ref::{
+    OwnedPacket, PacketRef,
+};
+use crate::core::pcap::sample::Sampler;
+use crate::core::pcap::spec::{RecordField, RecordSpec};
+
+/// 文件头魔数
+pub const MAGIC_NUMBER: u32 = 0xD4C3B2A1;
+
+/// 支持的主版本号
+pub const MAJOR_VERSION: u16 = 0x0002;
+
+/// 支持的次版本号
+pub const MINOR_VERSION: u16 = 0x0004;
+
+/// 文件头长度（字节）
+pub const FILE_HEADER_SIZE: usize = 16;
+
+/// 标准数据包头长度（字节，其他布局见 `spec`）
+pub const PACKET_HEADER_SIZE: usize = 16;
+
+/// 向后扫描末尾数据包时的初始窗口（字节），不够时加倍
+const TAIL_WINDOW: usize = 64 * 1024;
+
+/// 每个窗口内尝试同步的候选包头数
+const MAX_SYNC_ATTEMPTS: usize = 16;
+
+/// 只解析开头或末尾的若干个数据包
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketLimit {
+    /// 前 N 个数据包
+    Head(usize),
+    /// 后 N 个数据包
+    Tail(usize),
+}
 
 /// PCAP 文件头结构 (16字节)
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct PcapFileHeader {
     pub magic_number: u32,    // 0xD4C3B2A1
     pub major_version: u16,   // 0x0002
@@ -16,26 +62,268 @@ pub struct PcapFileHeader {
     pub timestamp_accuracy: u32, // 固定为 0
 }
 
+impl Default for PcapFileHeader {
+    fn default() -> Self {
+        Self {
+            magic_number: MAGIC_NUMBER,
+            major_version: MAJOR_VERSION,
+            minor_version: MINOR_VERSION,
+            timezone_offset: 0,
+            timestamp_accuracy: 0,
+        }
+    }
+}
+
+impl PcapFileHeader {
+    /// 字段名称与在文件头中的字节范围
+    pub const FIELDS: [(&'static str, Range<usize>); 5] = [
+        ("magic_number", 0..4),
+        ("major_version", 4..6),
+        ("minor_version", 6..8),
+        ("timezone_offset", 8..12),
+        ("timestamp_accuracy", 12..16),
+    ];
+
+    /// 包含文件头中第 `position` 个字节的字段名称及其字节范围
+    pub fn field_containing(
+        position: usize,
+    ) -> Option<(&'static str, Range<usize>)> {
+        Self::FIELDS
+            .iter()
+            .find(|(_, range)| range.contains(&position))
+            .map(|(name, range)| (*name, range.clone()))
+    }
+
+    /// 按指定字节序解码 16 字节文件头（不做校验）
+    pub fn decode(
+        bytes: &[u8],
+        endianness: Endianness,
+    ) -> Self {
+        Self {
+            magic_number: endianness.read_u32(&bytes[0..4]),
+            major_version: endianness
+                .read_u16(&bytes[4..6]),
+            minor_version: endianness
+                .read_u16(&bytes[6..8]),
+            timezone_offset: endianness
+                .read_u32(&bytes[8..12]),
+            timestamp_accuracy: endianness
+                .read_u32(&bytes[12..16]),
+        }
+    }
+
+    /// 校验魔数与版本号
+    pub fn validate(&self) -> Result<()> {
+        self.check().map_err(PcapViewerError::from)?;
+        Ok(())
+    }
+
+    /// 校验魔数与版本号，错误中记录出错字段与期望值
+    fn check(&self) -> std::result::Result<(), ParseError> {
+        if self.magic_number != MAGIC_NUMBER {
+            return Err(ParseError::new(
+                "Invalid magic number",
+                0..4,
+            )
+            .expected(
+                format!("0x{:08X}", MAGIC_NUMBER),
+                format!("0x{:08X}", self.magic_number),
+            ));
+        }
+        if self.major_version != MAJOR_VERSION
+            || self.minor_version != MINOR_VERSION
+        {
+            return Err(ParseError::new(
+                "Unsupported version",
+                4..8,
+            )
+            .expected(
+                format!(
+                    "{}.{}",
+                    MAJOR_VERSION, MINOR_VERSION
+                ),
+                format!(
+                    "{}.{}",
+                    self.major_version, self.minor_version
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 从数据开头读取并校验文件头（`Auto` 时按魔数检测字节序），
+    /// 识别为 libpcap / pcapng 等其他格式时报错，错误中附带文件头的原始字节
+    pub fn read(
+        data: &[u8],
+        endianness: Endianness,
+    ) -> Result<Self> {
+        let Some(bytes) = data.get(..FILE_HEADER_SIZE)
+        else {
+            return Err(PcapViewerError::from(
+                ParseError::new(
+                    "File too short for header",
+                    data.len()..FILE_HEADER_SIZE,
+                )
+                .expected(
+                    format!("{} bytes", FILE_HEADER_SIZE),
+                    format!("{} bytes", data.len()),
+                )
+                .snippet(0, data),
+            )
+            .into());
+        };
+        let variant = format::detect(bytes).variant;
+        if !variant.is_supported()
+            && variant != FormatVariant::Unknown
+        {
+            return Err(PcapViewerError::from(
+                ParseError::new("Unsupported format", 0..4)
+                    .expected("custom", variant)
+                    .snippet(0, bytes),
+            )
+            .into());
+        }
+        let header =
+            Self::decode(bytes, endianness.resolve(bytes));
+        header.check().map_err(|e| {
+            PcapViewerError::from(e.snippet(0, bytes))
+        })?;
+        Ok(header)
+    }
+
+    /// 未通过校验的字段在文件头中的字节范围
+    pub fn invalid_fields(&self) -> Vec<Range<usize>> {
+        let mut fields = Vec::new();
+        if self.magic_number != MAGIC_NUMBER {
+            fields.push(0..4);
+        }
+        if self.major_version != MAJOR_VERSION {
+            fields.push(4..6);
+        }
+        if self.minor_version != MINOR_VERSION {
+            fields.push(6..8);
+        }
+        fields
+    }
+
+    /// 编码为 16 字节的小端序文件头
+    pub fn to_bytes(&self) -> [u8; FILE_HEADER_SIZE] {
+        let mut bytes = [0u8; FILE_HEADER_SIZE];
+        bytes[0..4].copy_from_slice(
+            &self.magic_number.to_le_bytes(),
+        );
+        bytes[4..6].copy_from_slice(
+            &self.major_version.to_le_bytes(),
+        );
+        bytes[6..8].copy_from_slice(
+            &self.minor_version.to_le_bytes(),
+        );
+        bytes[8..12].copy_from_slice(
+            &self.timezone_offset.to_le_bytes(),
+        );
+        bytes[12..16].copy_from_slice(
+            &self.timestamp_accuracy.to_le_bytes(),
+        );
+        bytes
+    }
+}
+
 /// 数据包头部结构 (16字节)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct DataPacketHeader {
     pub timestamp_seconds: u32, // 时间戳秒部分 (UTC)
     pub timestamp_nanoseconds: u32, // 时间戳纳秒部分 (UTC)
     pub packet_length: u32,     // 数据包长度（字节）
-    pub checksum: u32,          // 数据包校验和（CRC32）
+    pub checksum: u32, // 数据包校验和（默认 CRC32）
+}
+
+impl DataPacketHeader {
+    /// 按指定字节序解码 16 字节数据包头
+    pub fn decode(
+        bytes: &[u8],
+        endianness: Endianness,
+    ) -> Self {
+        Self {
+            timestamp_seconds: endianness
+                .read_u32(&bytes[0..4]),
+            timestamp_nanoseconds: endianness
+                .read_u32(&bytes[4..8]),
+            packet_length: endianness
+                .read_u32(&bytes[8..12]),
+            checksum: endianness.read_u32(&bytes[12..16]),
+        }
+    }
+
+    /// 编码为 16 字节的小端序数据包头
+    pub fn to_bytes(&self) -> [u8; PACKET_HEADER_SIZE] {
+        let mut bytes = [0u8; PACKET_HEADER_SIZE];
+        bytes[0..4].copy_from_slice(
+            &self.timestamp_seconds.to_le_bytes(),
+        );
+        bytes[4..8].copy_from_slice(
+            &self.timestamp_nanoseconds.to_le_bytes(),
+        );
+        bytes[8..12].copy_from_slice(
+            &self.packet_length.to_le_bytes(),
+        );
+        bytes[12..16]
+            .copy_from_slice(&self.checksum.to_le_bytes());
+        bytes
+    }
+
+    /// 时间戳（Unix 纳秒）
+    pub fn timestamp_nanos(&self) -> i128 {
+        self.timestamp_seconds as i128 * 1_000_000_000
+            + self.timestamp_nanoseconds as i128
+    }
 }
 
 /// 数据包结构
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct DataPacket {
     pub header: DataPacketHeader,
+    /// 数据包头在文件中的偏移
+    pub offset: usize,
+    /// 校验和校验结果（未启用校验时为 None）
+    pub checksum_valid: Option<bool>,
+}
+
+/// 一次数据包扫描的结果
+#[derive(Default)]
+pub(crate) struct PacketScan {
+    pub packets: Vec<DataPacket>,
+    /// 解析到的完整数据包数（含抽样未保留的）
+    pub seen: usize,
+    /// 已完整解析的字节数（下次从此处继续）
+    pub consumed: usize,
+    /// 遇到损坏的长度字段，无法继续解析
+    pub stopped: bool,
 }
 
 /// PCAP 文件解析器
 pub struct PcapParser {
-    file_path: std::path::PathBuf,
+    options: ParserOptions,
+    endianness: Endianness, // 实际使用的字节序（Little 或 Big）
     file_header: Option<PcapFileHeader>,
     packets: Vec<DataPacket>,
+    pending_source: Option<Box<dyn Read + Send>>, // 延迟索引时保留的数据源
+    sampler: Option<Sampler>, // 按选项抽样时决定保留哪些数据包
+    seen: usize, // 解析到的数据包总数（含抽样未保留的）
+}
+
+impl Default for PcapParser {
+    /// 空解析器（没有文件头和数据包）
+    fn default() -> Self {
+        Self::empty(ParserOptions::default())
+    }
 }
 
 impl PcapParser {
@@ -43,76 +331,253 @@ impl PcapParser {
     pub fn new<P: AsRef<Path>>(
         file_path: P,
     ) -> Result<Self> {
-        let file_path = file_path.as_ref().to_path_buf();
+        let file = File::open(file_path.as_ref())?;
+        Self::from_reader(BufReader::new(file))
+    }
 
-        let mut parser = Self {
-            file_path,
-            file_header: None,
-            packets: Vec::new(),
+    /// 创建解析器构建器，用于调整解析选项
+    pub fn builder() -> PcapParserBuilder {
+        PcapParserBuilder::new()
+    }
+
+    /// 从任意数据源解析（如网络流或内存缓冲区）
+    pub fn from_reader<R: Read>(
+        mut reader: R,
+    ) -> Result<Self> {
+        let mut parser =
+            Self::empty(ParserOptions::default());
+        parser.parse(&mut reader)?;
+        Ok(parser)
+    }
+
+    /// 从内存中的完整文件内容解析
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        Self::from_reader(data)
+    }
+
+    /// 按指定选项解析内存中的完整文件内容（忽略延迟索引）
+    pub fn from_bytes_with_options(
+        mut data: &[u8],
+        options: ParserOptions,
+    ) -> Result<Self> {
+        let mut parser = Self::empty(options);
+        parser.parse(&mut data)?;
+        Ok(parser)
+    }
+
+    /// 按指定选项解析内存中的文件内容。非严格模式下文件头无效
+    /// （或不足一个文件头）时不报错，返回不含数据包的解析器，
+    /// 由调用方按原始数据显示
+    pub fn from_bytes_or_raw(
+        data: &[u8],
+        options: ParserOptions,
+    ) -> Result<Self> {
+        if !options.strict
+            && PcapFileHeader::read(
+                data,
+                options.endianness,
+            )
+            .is_err()
+        {
+            return Ok(Self::empty(options));
+        }
+        Self::from_bytes_with_options(data, options)
+    }
+
+    /// 按指定选项只解析开头或末尾的若干个数据包，不解析整个文件。
+    /// 末尾的数据包从文件末尾按窗口向后查找包头同步点，
+    /// 窗口加倍直到找到足够的数据包（最坏情况下退化为完整解析）
+    pub fn from_bytes_limited(
+        data: &[u8],
+        options: ParserOptions,
+        limit: PacketLimit,
+    ) -> Result<Self> {
+        let mut parser = Self::empty(options);
+        let mut reader = data;
+        parser.file_header =
+            Some(parser.parse_file_header(&mut reader)?);
+        let body = &data[FILE_HEADER_SIZE..];
+
+        let scan = match limit {
+            PacketLimit::Head(count) => parser
+                .scan_sampled(
+                    body,
+                    FILE_HEADER_SIZE,
+                    true,
+                    count,
+                )?,
+            PacketLimit::Tail(count) => {
+                // 末尾的数据包已经找到，只需按抽样选项筛选
+                let packets =
+                    parser.scan_tail(body, count)?;
+                let seen = packets.len();
+                let packets = match &mut parser.sampler {
+                    Some(sampler) => packets
+                        .into_iter()
+                        .filter(|_| sampler.keep())
+                        .collect(),
+                    None => packets,
+                };
+                PacketScan {
+                    packets,
+                    seen,
+                    ..PacketScan::default()
+                }
+            }
         };
+        parser.append_packets(scan.packets, scan.seen);
+        Ok(parser)
+    }
+
+    /// 从缓冲区末尾向前查找最后 `count` 个数据包
+    fn scan_tail(
+        &self,
+        body: &[u8],
+        count: usize,
+    ) -> Result<Vec<DataPacket>> {
+        let mut window = TAIL_WINDOW;
+        while window < body.len() {
+            let start = body.len() - window;
+            // 从窗口内的候选包头开始解析，一直解析到文件末尾才算同步成功
+            let mut candidate = start;
+            for _ in 0..MAX_SYNC_ATTEMPTS {
+                let Some(offset) =
+                    self.find_next_packet(body, candidate)
+                else {
+                    break;
+                };
+                if let Ok(scan) = self.scan_packets(
+                    &body[offset..],
+                    FILE_HEADER_SIZE + offset,
+                    true,
+                ) {
+                    if !scan.stopped
+                        && offset + scan.consumed
+                            == body.len()
+                        && scan.packets.len() >= count
+                    {
+                        return Ok(last(
+                            scan.packets,
+                            count,
+                        ));
+                    }
+                }
+                candidate = offset + 1;
+            }
+            window *= 2;
+        }
+
+        let scan = self.scan_packets(
+            body,
+            FILE_HEADER_SIZE,
+            true,
+        )?;
+        Ok(last(scan.packets, count))
+    }
+
+    /// 按指定选项解析（延迟索引时保留数据源）
+    pub(crate) fn with_options(
+        mut reader: Box<dyn Read + Send>,
+        options: ParserOptions,
+    ) -> Result<Self> {
+        let lazy = options.lazy;
+        let mut parser = Self::empty(options);
+
+        if lazy {
+            // 只解析文件头，数据包推迟到 index_packets
+            parser.file_header = Some(
+                parser.parse_file_header(&mut reader)?,
+            );
+            parser.pending_source = Some(reader);
+        } else {
+            parser.parse(&mut reader)?;
+        }
 
-        parser.parse_file()?;
         Ok(parser)
     }
 
-    /// 解析整个文件
-    fn parse_file(&mut self) -> Result<()> {
-        let file = File::open(&self.file_path)?;
-        let mut reader = BufReader::new(file);
+    /// 创建尚未解析的解析器
+    pub fn empty(options: ParserOptions) -> Self {
+        Self {
+            endianness: options.endianness,
+            sampler: options.sample.map(Sampler::new),
+            options,
+            file_header: None,
+            packets: Vec::new(),
+            pending_source: None,
+            seen: 0,
+        }
+    }
+
+    /// 解析文件头与所有数据包
+    fn parse<R: Read>(
+        &mut self,
+        reader: &mut R,
+    ) -> Result<()> {
+        let _span = tracing::trace_span!("parse").entered();
 
         // 解析文件头
         self.file_header =
-            Some(self.parse_file_header(&mut reader)?);
+            Some(self.parse_file_header(reader)?);
 
         // 解析所有数据包
-        self.parse_packets(&mut reader)?;
+        self.parse_packets(reader)?;
+
+        Ok(())
+    }
 
+    /// 解析延迟索引的数据包（非延迟模式或已索引时无操作）
+    pub fn index_packets(&mut self) -> Result<()> {
+        if let Some(mut reader) = self.pending_source.take()
+        {
+            self.parse_packets(&mut reader)?;
+        }
         Ok(())
     }
 
+    /// 数据包是否已完成索引
+    pub fn is_indexed(&self) -> bool {
+        self.pending_source.is_none()
+    }
+
+    /// 开始增量索引：设置文件头并清空已有数据包
+    pub(crate) fn begin_index(
+        &mut self,
+        file_header: PcapFileHeader,
+        endianness: Endianness,
+    ) {
+        self.file_header = Some(file_header);
+        self.endianness = endianness;
+        self.packets.clear();
+        self.pending_source = None;
+        self.sampler =
+            self.options.sample.map(Sampler::new);
+        self.seen = 0;
+    }
+
+    /// 追加已按抽样选项筛选的数据包（`seen` 为含未保留数据包在内的总数）
+    pub(crate) fn append_packets(
+        &mut self,
+        packets: Vec<DataPacket>,
+        seen: usize,
+    ) {
+        self.packets.extend(packets);
+        self.seen += seen;
+    }
+
     /// 解析文件头
-    fn parse_file_header<R: Read>(
-        &self,
+    pub(crate) fn parse_file_header<R: Read>(
+        &mut self,
         reader: &mut R,
     ) -> Result<PcapFileHeader> {
-        let mut buffer = [0u8; 16];
+        let mut buffer = [0u8; FILE_HEADER_SIZE];
         reader.read_exact(&mut buffer)?;
 
-        let magic_number = u32::from_le_bytes([
-            buffer[0], buffer[1], buffer[2], buffer[3],
-        ]);
-        let major_version =
-            u16::from_le_bytes([buffer[4], buffer[5]]);
-        let minor_version =
-            u16::from_le_bytes([buffer[6], buffer[7]]);
-        let timezone_offset = u32::from_le_bytes([
-            buffer[8], buffer[9], buffer[10], buffer[11],
-        ]);
-        let timestamp_accuracy = u32::from_le_bytes([
-            buffer[12], buffer[13], buffer[14], buffer[15],
-        ]);
-
-        // 验证文件格式
-        if magic_number != 0xD4C3B2A1 {
-            return Err(crate::app::error::types::PcapViewerError::InvalidFormat(
-                format!("Invalid magic number: 0x{:08X}", magic_number)
-            ).into());
-        }
-        if major_version != 0x0002
-            || minor_version != 0x0004
-        {
-            return Err(crate::app::error::types::PcapViewerError::InvalidFormat(
-                format!("Unsupported version: {}.{}", major_version, minor_version)
-            ).into());
-        }
+        // 根据魔数确定字节序
+        self.endianness =
+            self.options.endianness.resolve(&buffer);
 
-        Ok(PcapFileHeader {
-            magic_number,
-            major_version,
-            minor_version,
-            timezone_offset,
-            timestamp_accuracy,
-        })
+        PcapFileHeader::read(&buffer, self.endianness)
     }
 
     /// 解析所有数据包
@@ -123,33 +588,226 @@ impl PcapParser {
         let mut buffer = Vec::new();
         reader.read_to_end(&mut buffer)?;
 
+        let scan = self.scan_sampled(
+            &buffer,
+            FILE_HEADER_SIZE,
+            true,
+            usize::MAX,
+        )?;
+        self.append_packets(scan.packets, scan.seen);
+
+        Ok(())
+    }
+
+    /// 依次解析缓冲区中的数据包（`base_offset` 为缓冲区在文件中的偏移）。
+    /// `complete` 为 false 时缓冲区之后还有数据，末尾不完整的数据包留待下次解析
+    pub(crate) fn scan_packets(
+        &self,
+        buffer: &[u8],
+        base_offset: usize,
+        complete: bool,
+    ) -> Result<PacketScan> {
+        self.scan_packets_up_to(
+            buffer,
+            base_offset,
+            complete,
+            usize::MAX,
+            None,
+        )
+    }
+
+    /// 同 `scan_packets_up_to`，但按抽样选项只保留部分数据包
+    pub(crate) fn scan_sampled(
+        &mut self,
+        buffer: &[u8],
+        base_offset: usize,
+        complete: bool,
+        limit: usize,
+    ) -> Result<PacketScan> {
+        let mut sampler = self.sampler.take();
+        let scan = self.scan_packets_up_to(
+            buffer,
+            base_offset,
+            complete,
+            limit,
+            sampler.as_mut(),
+        );
+        self.sampler = sampler;
+        scan
+    }
+
+    /// 同 `scan_packets`，解析到 `limit` 个数据包时停止。
+    /// 抽样未保留的数据包跳过校验和检查，也不记录
+    fn scan_packets_up_to(
+        &self,
+        buffer: &[u8],
+        base_offset: usize,
+        complete: bool,
+        limit: usize,
+        mut sampler: Option<&mut Sampler>,
+    ) -> Result<PacketScan> {
+        let mut scan = PacketScan::default();
         let mut offset = 0;
+        let header_size = self.header_size();
 
-        while offset < buffer.len() {
-            if offset + 16 > buffer.len() {
-                break; // 没有足够的数据读取数据包头
+        while offset < buffer.len() && scan.seen < limit {
+            scan.consumed = offset;
+
+            // 文件中的绝对偏移，用于错误信息
+            let file_offset = base_offset + offset;
+
+            if offset + header_size > buffer.len() {
+                if !complete {
+                    return Ok(scan); // 等待更多数据
+                }
+                // 没有足够的数据读取数据包头
+                if self.options.strict {
+                    return Err(PcapViewerError::from(
+                        truncated_header(
+                            &self.options.record,
+                            file_offset,
+                            &buffer[offset..],
+                        ),
+                    )
+                    .into());
+                }
+                break;
             }
 
             // 解析数据包头
-            let header_bytes = &buffer[offset..offset + 16];
+            let header_bytes =
+                &buffer[offset..offset + header_size];
             let header =
                 self.parse_packet_header(header_bytes);
-            offset += 16;
+            let payload_start = offset + header_size;
+
+            // 检查数据包长度上限
+            if !self.options.check_packet_length(
+                &header,
+                header_bytes,
+                file_offset,
+            )? {
+                if self.options.recover {
+                    match self.find_next_packet(
+                        buffer,
+                        offset + 1,
+                    ) {
+                        Some(next) => {
+                            offset = next;
+                            continue;
+                        }
+                        None if !complete => {
+                            return Ok(scan); // 等待更多数据后再搜索
+                        }
+                        None => break,
+                    }
+                }
+                scan.stopped = true;
+                break; // 长度字段已损坏，无法继续定位后续数据包
+            }
 
             // 读取数据包数据
-            if offset + header.packet_length as usize
-                > buffer.len()
+            let payload_end = payload_start
+                + header.packet_length as usize;
+            if payload_end > buffer.len() {
+                if !complete {
+                    return Ok(scan); // 等待更多数据
+                }
+                // 没有足够的数据读取数据包体
+                if self.options.strict {
+                    return Err(PcapViewerError::from(
+                        truncated_body(
+                            &self.options.record,
+                            &header,
+                            header_bytes,
+                            file_offset,
+                            buffer.len() - payload_start,
+                        ),
+                    )
+                    .into());
+                }
+                // 长度字段可能已损坏，尝试从后面的有效数据包继续
+                if let Some(next) = self
+                    .options
+                    .recover
+                    .then(|| {
+                        self.find_next_packet(
+                            buffer,
+                            offset + 1,
+                        )
+                    })
+                    .flatten()
+                {
+                    offset = next;
+                    continue;
+                }
+                break;
+            }
+
+            scan.seen += 1;
+            if sampler
+                .as_mut()
+                .is_some_and(|sampler| !sampler.keep())
             {
-                break; // 没有足够的数据读取数据包体
+                offset = payload_end;
+                continue;
             }
 
+            let payload =
+                &buffer[payload_start..payload_end];
+
+            // 检查校验和
+            let checksum_valid =
+                self.options.check_checksum(
+                    &header,
+                    header_bytes,
+                    payload,
+                    file_offset,
+                )?;
+
             // 跳过数据包体数据
-            offset += header.packet_length as usize;
+            offset = payload_end;
 
-            self.packets.push(DataPacket { header });
+            scan.packets.push(DataPacket {
+                header,
+                offset: file_offset,
+                checksum_valid,
+            });
         }
 
-        Ok(())
+        scan.consumed = offset;
+        Ok(scan)
+    }
+
+    /// 恢复扫描：从 `start` 起逐字节查找包头合理（纳秒小于 1 秒、长度非零且未超限）
+    /// 且负载校验和与包头一致的数据包，返回其在缓冲区中的偏移
+    fn find_next_packet(
+        &self,
+        buffer: &[u8],
+        start: usize,
+    ) -> Option<usize> {
+        let header_size = self.header_size();
+        let last =
+            (buffer.len() + 1).saturating_sub(header_size);
+        (start..last).find(|&offset| {
+            let header = self.parse_packet_header(
+                &buffer[offset..offset + header_size],
+            );
+            let payload_start = offset + header_size;
+            let payload_end = payload_start
+                + header.packet_length as usize;
+            header.timestamp_nanoseconds < 1_000_000_000
+                && header.packet_length > 0
+                && self.options.max_packet_len.is_none_or(
+                    |max| header.packet_length <= max,
+                )
+                && payload_end <= buffer.len()
+                && (!self.options.record.has_checksum()
+                    || self.options.checksum.matches(
+                        &buffer[payload_start..payload_end],
+                        header.checksum,
+                    ))
+        })
     }
 
     /// 解析数据包头
@@ -157,25 +815,22 @@ impl PcapParser {
         &self,
         bytes: &[u8],
     ) -> DataPacketHeader {
-        let timestamp_seconds = u32::from_le_bytes([
-            bytes[0], bytes[1], bytes[2], bytes[3],
-        ]);
-        let timestamp_nanoseconds = u32::from_le_bytes([
-            bytes[4], bytes[5], bytes[6], bytes[7],
-        ]);
-        let packet_length = u32::from_le_bytes([
-            bytes[8], bytes[9], bytes[10], bytes[11],
-        ]);
-        let checksum = u32::from_le_bytes([
-            bytes[12], bytes[13], bytes[14], bytes[15],
-        ]);
-
-        DataPacketHeader {
-            timestamp_seconds,
-            timestamp_nanoseconds,
-            packet_length,
-            checksum,
-        }
+        self.options.record.decode(bytes, self.endianness)
+    }
+
+    /// 数据包头长度（按数据包头布局，标准格式为 16 字节）
+    pub fn header_size(&self) -> usize {
+        self.options.record.size()
+    }
+
+    /// 获取解析选项
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// 获取实际使用的字节序
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
     }
 
     /// 获取文件头
@@ -183,8 +838,289 @@ impl PcapParser {
         self.file_header.as_ref()
     }
 
-    /// 获取所有数据包
+    /// 解析到的数据包总数（抽样时包含未保留的数据包）
+    pub fn packets_seen(&self) -> usize {
+        self.seen
+    }
+
+    /// 获取所有数据包（延迟模式下需先调用 index_packets，抽样时只含保留的数据包）
     pub fn packets(&self) -> &[DataPacket] {
         &self.packets
     }
+
+    /// 以零拷贝方式遍历数据包（data 为完整文件内容）
+    pub fn packet_refs<'a>(
+        &'a self,
+        data: &'a [u8],
+    ) -> impl Iterator<Item = PacketRef<'a>> + 'a {
+        self.packets.iter().enumerate().map(
+            move |(index, packet)| {
+                PacketRef::new(
+                    index,
+                    packet,
+                    data,
+                    self.header_size(),
+                )
+            },
+        )
+    }
+
+    /// 获取指定序号的数据包视图
+    pub fn packet_ref<'a>(
+        &'a self,
+        data: &'a [u8],
+        index: usize,
+    ) -> Option<PacketRef<'a>> {
+        self.packet_ref_in(data, 0, index)
+    }
+
+    /// 获取指定序号的数据包视图（data 为从文件偏移 `base` 开始的部分内容）
+    pub fn packet_ref_in<'a>(
+        &'a self,
+        data: &'a [u8],
+        base: usize,
+        index: usize,
+    ) -> Option<PacketRef<'a>> {
+        self.packets.get(index).map(|packet| {
+            PacketRef::with_base(
+                index,
+                packet,
+                data,
+                base,
+                self.header_size(),
+            )
+        })
+    }
+
+    /// 查找包含指定偏移的数据包序号（二分查找）
+    pub fn packet_index_at(
+        &self,
+        offset: usize,
+    ) -> Option<usize> {
+        let index = self
+            .packets
+            .partition_point(|packet| {
+                packet.offset <= offset
+            })
+            .checked_sub(1)?;
+        let packet = &self.packets[index];
+        let end = packet.offset
+            + self.header_size()
+            + packet.header.packet_length as usize;
+        (offset < end).then_some(index)
+    }
+
+    /// 查找第一个结束位置在指定偏移之后的数据包序号
+    pub fn packet_index_from(
+        &self,
+        offset: usize,
+    ) -> Option<usize> {
+        let index =
+            self.packets.partition_point(|packet| {
+                packet.offset
+                    + self.header_size()
+                    + packet.header.packet_length as usize
+                    <= offset
+            });
+        (index < self.packets.len()).then_some(index)
+    }
+}
+
+/// 最后 `count` 个数据包
+fn last(
+    mut packets: Vec<DataPacket>,
+    count: usize,
+) -> Vec<DataPacket> {
+    packets.drain(..packets.len().saturating_sub(count));
+    packets
+}
+
+/// 流式读取到的一条数据包记录
+pub(crate) enum Record {
+    /// 完整的数据包
+    Packet(OwnedPacket),
+    /// 抽样未保留的数据包
+    Skipped,
+    /// 数据源结束（或无法继续定位数据包）
+    End,
+}
+
+/// 流式读取器（`PcapReader` 与 `AsyncPcapReader`）共用的数据包记录解析。
+/// 读取器只负责读取字节，包头解码、长度与校验和检查、抽样及构造数据包都在这里
+pub(crate) struct RecordDecoder {
+    options: ParserOptions,
+    endianness: Endianness,
+    offset: usize, // 下一个数据包头在文件中的偏移
+    finished: bool,
+    sampler: Option<Sampler>,
+}
+
+impl RecordDecoder {
+    /// 文件头之后开始解码
+    pub fn new(
+        options: ParserOptions,
+        endianness: Endianness,
+    ) -> Self {
+        Self {
+            sampler: options.sample.map(Sampler::new),
+            options,
+            endianness,
+            offset: FILE_HEADER_SIZE,
+            finished: false,
+        }
+    }
+
+    /// 实际使用的字节序
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// 解析选项
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// 数据包头长度
+    pub fn header_size(&self) -> usize {
+        self.options.record.size()
+    }
+
+    /// 是否已读取结束
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 解码读到的数据包头（`read` 为实际读到的字节数），返回需要继续读取负载的包头。
+    /// 在包边界处结束、包头截断（非严格模式）或长度字段损坏时返回 None
+    pub fn header(
+        &mut self,
+        header_bytes: &[u8],
+        read: usize,
+    ) -> Result<Option<DataPacketHeader>> {
+        if read == 0 {
+            self.finished = true;
+            return Ok(None);
+        }
+        if read < header_bytes.len() {
+            self.truncated(truncated_header(
+                &self.options.record,
+                self.offset,
+                &header_bytes[..read],
+            ))?;
+            return Ok(None);
+        }
+
+        let header = self
+            .options
+            .record
+            .decode(header_bytes, self.endianness);
+        if !self.options.check_packet_length(
+            &header,
+            header_bytes,
+            self.offset,
+        )? {
+            // 长度字段已损坏，无法继续定位后续数据包
+            self.finished = true;
+            return Ok(None);
+        }
+        Ok(Some(header))
+    }
+
+    /// 读到负载后（`read` 为实际读到的字节数）检查截断与校验和并构造数据包，
+    /// 抽样未保留的数据包不做校验和检查
+    pub fn packet(
+        &mut self,
+        header: DataPacketHeader,
+        header_bytes: Vec<u8>,
+        payload: Vec<u8>,
+        read: usize,
+    ) -> Result<Record> {
+        let file_offset = self.offset;
+        if read < payload.len() {
+            let error = truncated_body(
+                &self.options.record,
+                &header,
+                &header_bytes,
+                file_offset,
+                read,
+            );
+            self.truncated(error)?;
+            return Ok(Record::End);
+        }
+        self.offset += header_bytes.len() + payload.len();
+
+        if self
+            .sampler
+            .as_mut()
+            .is_some_and(|sampler| !sampler.keep())
+        {
+            return Ok(Record::Skipped);
+        }
+
+        let checksum_valid = self.options.check_checksum(
+            &header,
+            &header_bytes,
+            &payload,
+            file_offset,
+        )?;
+        Ok(Record::Packet(OwnedPacket {
+            packet: DataPacket {
+                header,
+                offset: file_offset,
+                checksum_valid,
+            },
+            header_bytes,
+            payload,
+        }))
+    }
+
+    /// 数据包被截断：严格模式返回错误，否则结束读取
+    fn truncated(
+        &mut self,
+        error: ParseError,
+    ) -> Result<()> {
+        self.finished = true;
+        if self.options.strict {
+            return Err(PcapViewerError::from(error).into());
+        }
+        Ok(())
+    }
+}
+
+/// 数据包头不完整的错误（`bytes` 为文件末尾剩余的字节）
+pub(crate) fn truncated_header(
+    record: &RecordSpec,
+    file_offset: usize,
+    bytes: &[u8],
+) -> ParseError {
+    ParseError::new(
+        "Truncated packet header",
+        file_offset + bytes.len()
+            ..file_offset + record.size(),
+    )
+    .expected(
+        format!("{} bytes", record.size()),
+        format!("{} bytes", bytes.len()),
+    )
+    .snippet(file_offset, bytes)
+}
+
+/// 数据包负载不完整的错误（`available` 为包头之后剩余的字节数），
+/// 标出包头中的长度字段
+pub(crate) fn truncated_body(
+    record: &RecordSpec,
+    header: &DataPacketHeader,
+    header_bytes: &[u8],
+    file_offset: usize,
+    available: usize,
+) -> ParseError {
+    ParseError::new(
+        "Truncated packet body",
+        record.field_at(RecordField::Length, file_offset),
+    )
+    .expected(
+        format!("{} bytes", header.packet_length),
+        format!("{} bytes", available),
+    )
+    .snippet(file_offset, header_bytes)
 }
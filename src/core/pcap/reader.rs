@@ -0,0 +1,138 @@
+//! 流式 PCAP 读取器（逐个读取数据包，不需要完整文件）
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::app::error::types::Result;
+use crate::core::pcap::builder::{
+    Endianness, ParserOptions,
+};
+use crate::core::pcap::packet_ref::OwnedPacket;
+use crate::core::pcap::parser::{
+    PcapFileHeader, Record, RecordDecoder, FILE_HEADER_SIZE,
+};
+
+/// 流式 PCAP 读取器：逐个读取数据包，内存占用与文件大小无关
+pub struct PcapReader<R> {
+    reader: R,
+    file_header: PcapFileHeader,
+    header_bytes: [u8; FILE_HEADER_SIZE], // 文件头的原始字节
+    records: RecordDecoder,
+}
+
+impl PcapReader<BufReader<File>> {
+    /// 打开文件并读取文件头
+    pub fn open<P: AsRef<Path>>(
+        file_path: P,
+    ) -> Result<Self> {
+        let file = File::open(file_path.as_ref())?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> PcapReader<R> {
+    /// 创建读取器并读取文件头
+    pub fn new(reader: R) -> Result<Self> {
+        Self::with_options(reader, ParserOptions::default())
+    }
+
+    /// 按指定选项创建读取器（lazy 选项不适用于流式读取）
+    pub fn with_options(
+        mut reader: R,
+        options: ParserOptions,
+    ) -> Result<Self> {
+        let mut buffer = [0u8; FILE_HEADER_SIZE];
+        reader.read_exact(&mut buffer)?;
+
+        let endianness =
+            options.endianness.resolve(&buffer);
+        let file_header =
+            PcapFileHeader::read(&buffer, endianness)?;
+
+        Ok(Self {
+            reader,
+            file_header,
+            header_bytes: buffer,
+            records: RecordDecoder::new(
+                options, endianness,
+            ),
+        })
+    }
+
+    /// 获取文件头
+    pub fn file_header(&self) -> &PcapFileHeader {
+        &self.file_header
+    }
+
+    /// 文件头的原始字节（按源文件的字节序）
+    pub fn file_header_bytes(&self) -> &[u8] {
+        &self.header_bytes
+    }
+
+    /// 获取实际使用的字节序
+    pub fn endianness(&self) -> Endianness {
+        self.records.endianness()
+    }
+
+    /// 获取解析选项
+    pub fn options(&self) -> &ParserOptions {
+        self.records.options()
+    }
+
+    /// 底层读取器的可变引用
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// 读取下一个数据包（抽样时跳过未保留的数据包），数据源结束时返回 None
+    pub fn next_packet(
+        &mut self,
+    ) -> Result<Option<OwnedPacket>> {
+        while !self.records.is_finished() {
+            let mut header_bytes =
+                vec![0u8; self.records.header_size()];
+            let read =
+                self.read_fully(&mut header_bytes)?;
+            let Some(header) =
+                self.records.header(&header_bytes, read)?
+            else {
+                break;
+            };
+
+            let mut payload =
+                vec![0u8; header.packet_length as usize];
+            let read = self.read_fully(&mut payload)?;
+            match self.records.packet(
+                header,
+                header_bytes,
+                payload,
+                read,
+            )? {
+                Record::Packet(packet) => {
+                    return Ok(Some(packet))
+                }
+                Record::Skipped => {}
+                Record::End => break,
+            }
+        }
+        Ok(None)
+    }
+
+    /// 尽量填满缓冲区，返回实际读取的字节数（遇到 EOF 时可能不足）
+    fn read_fully(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let read =
+                self.reader.read(&mut buf[filled..])?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+        Ok(filled)
+    }
+}
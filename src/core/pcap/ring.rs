@@ -0,0 +1,219 @@
+//! 流式输入：顺序读取不可定位的数据源（命名管道、字符设备），
+//! 只在有界环形缓冲区中保留最近的数据包
+
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::app::error::types::Result;
+use crate::core::pcap::builder::ParserOptions;
+use crate::core::pcap::reader::PcapReader;
+
+/// 默认保留的数据包数量
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// 有界环形缓冲区：保存文件头与最近若干个数据包的原始字节，
+/// 超出容量时丢弃最早的数据包
+#[derive(Debug, Clone)]
+pub struct PacketRing {
+    header: Vec<u8>,
+    records: VecDeque<Vec<u8>>, // 每个数据包的包头与负载
+    capacity: usize,
+    len: usize, // 缓冲区中的总字节数（含文件头）
+    received: usize,
+    evicted: usize,
+    evicted_bytes: usize,
+}
+
+impl PacketRing {
+    /// 以文件头的原始字节创建缓冲区，最多保留 `capacity` 个数据包（至少 1 个）
+    pub fn new(header: Vec<u8>, capacity: usize) -> Self {
+        Self {
+            len: header.len(),
+            header,
+            records: VecDeque::new(),
+            capacity: capacity.max(1),
+            received: 0,
+            evicted: 0,
+            evicted_bytes: 0,
+        }
+    }
+
+    /// 追加一个数据包的原始字节，返回是否因此丢弃了最早的数据包
+    pub fn push(&mut self, record: Vec<u8>) -> bool {
+        let full = self.records.len() == self.capacity;
+        if full {
+            if let Some(oldest) = self.records.pop_front() {
+                self.len -= oldest.len();
+                self.evicted += 1;
+                self.evicted_bytes += oldest.len();
+            }
+        }
+        self.len += record.len();
+        self.records.push_back(record);
+        self.received += 1;
+        full
+    }
+
+    /// 缓冲区中的数据包数量
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// 缓冲区中是否没有数据包
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// 最多保留的数据包数量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 累计收到的数据包数量
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// 累计丢弃的数据包数量
+    pub fn evicted(&self) -> usize {
+        self.evicted
+    }
+
+    /// 累计丢弃的字节数
+    pub fn evicted_bytes(&self) -> usize {
+        self.evicted_bytes
+    }
+
+    /// 拼接为完整的 PCAP 数据（文件头后接缓冲区中的数据包）
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len);
+        bytes.extend_from_slice(&self.header);
+        for record in &self.records {
+            bytes.extend_from_slice(record);
+        }
+        bytes
+    }
+}
+
+/// 工作线程发给主线程的消息
+enum StreamMessage {
+    /// 一个完整数据包的原始字节
+    Record(Vec<u8>),
+    /// 读取或解析失败
+    Failed(anyhow::Error),
+}
+
+/// 流式输入：工作线程顺序读取数据包，主线程取回后放入环形缓冲区
+pub struct PacketStream {
+    receiver: Receiver<StreamMessage>,
+    ring: PacketRing,
+    finished: bool,
+}
+
+impl PacketStream {
+    /// 读取文件头（阻塞直到写入端写出文件头），再启动工作线程读取后续数据包。
+    /// 流式读取不支持抽样
+    pub fn spawn<R: Read + Send + 'static>(
+        reader: R,
+        options: ParserOptions,
+        capacity: usize,
+    ) -> Result<Self> {
+        let options = ParserOptions {
+            sample: None,
+            ..options
+        };
+        let mut reader = PcapReader::with_options(
+            Recorder::new(reader),
+            options,
+        )?;
+        let header = reader.get_mut().take();
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            let message = match reader.next_packet() {
+                Ok(Some(_)) => StreamMessage::Record(
+                    reader.get_mut().take(),
+                ),
+                Ok(None) => break,
+                Err(e) => StreamMessage::Failed(e),
+            };
+            let failed =
+                matches!(message, StreamMessage::Failed(_));
+            if sender.send(message).is_err() || failed {
+                break;
+            }
+        });
+
+        Ok(Self {
+            receiver,
+            ring: PacketRing::new(header, capacity),
+            finished: false,
+        })
+    }
+
+    /// 取回工作线程已读取的数据包（不等待），返回新数据包的数量
+    pub fn receive(&mut self) -> Result<usize> {
+        let mut received = 0;
+        loop {
+            match self.receiver.try_recv() {
+                Ok(StreamMessage::Record(record)) => {
+                    self.ring.push(record);
+                    received += 1;
+                }
+                Ok(StreamMessage::Failed(e)) => {
+                    self.finished = true;
+                    return Err(e);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+        Ok(received)
+    }
+
+    /// 环形缓冲区
+    pub fn ring(&self) -> &PacketRing {
+        &self.ring
+    }
+
+    /// 数据源是否已结束（写入端关闭或读取失败）
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// 记录经过的字节，用于取出每个数据包的原始包头与负载
+struct Recorder<R> {
+    inner: R,
+    recorded: Vec<u8>,
+}
+
+impl<R> Recorder<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            recorded: Vec::new(),
+        }
+    }
+
+    /// 取出上次调用以来读取的字节
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.recorded)
+    }
+}
+
+impl<R: Read> Read for Recorder<R> {
+    fn read(
+        &mut self,
+        buf: &mut [u8],
+    ) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.recorded.extend_from_slice(&buf[..read]);
+        Ok(read)
+    }
+}
@@ -0,0 +1,273 @@
+//! 滚动录制：`10x100M` 表示每个文件写到 100 MB 后换新文件，只保留最近 10 个，
+//! 用于长时间运行的录制
+//!
+//! 滚动文件按序号命名，如 `out.pcap` 依次写为 `out_00001.pcap`、`out_00002.pcap`。
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::pcap::checksum::ChecksumAlgorithm;
+use crate::core::pcap::packet_ref::PacketRef;
+use crate::core::pcap::parser::PACKET_HEADER_SIZE;
+use crate::core::pcap::writer::PcapWriter;
+
+/// 大小后缀及其倍数（十进制，与 tcpdump -C 一致）
+const SIZE_UNITS: [(char, u64); 3] = [
+    ('K', 1_000),
+    ('M', 1_000_000),
+    ('G', 1_000_000_000),
+];
+
+/// 滚动方式：保留的文件数与每个文件的大小上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingSpec {
+    /// 最多保留的文件数
+    pub files: usize,
+    /// 每个文件的大小上限（字节）
+    pub size: u64,
+}
+
+impl FromStr for RingSpec {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid ring `{}` (expected FILESxSIZE, e.g. 10x100M)",
+                text
+            ))
+        };
+        let (files, size) = text
+            .trim()
+            .split_once(['x', 'X'])
+            .ok_or_else(invalid)?;
+        let files = match files.parse::<usize>() {
+            Ok(files) if files > 0 => files,
+            _ => return Err(invalid()),
+        };
+        let size = size.to_ascii_uppercase();
+        let (digits, unit) = SIZE_UNITS
+            .iter()
+            .find_map(|&(suffix, unit)| {
+                size.strip_suffix(suffix)
+                    .map(|digits| (digits, unit))
+            })
+            .unwrap_or((size.as_str(), 1));
+        match digits.parse::<u64>() {
+            Ok(size) if size > 0 => Ok(Self {
+                files,
+                size: size.saturating_mul(unit),
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for RingSpec {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let (suffix, unit) = SIZE_UNITS
+            .iter()
+            .rev()
+            .find(|&&(_, unit)| {
+                self.size.is_multiple_of(unit)
+            })
+            .map_or((None, 1), |&(suffix, unit)| {
+                (Some(suffix), unit)
+            });
+        write!(f, "{}x{}", self.files, self.size / unit)?;
+        match suffix {
+            Some(suffix) => write!(f, "{}", suffix),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 第 `sequence` 个滚动文件的路径（在文件名与扩展名之间插入序号）
+pub fn file_path(base: &Path, sequence: usize) -> PathBuf {
+    let stem = base
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let name = match base.extension() {
+        Some(ext) => format!(
+            "{}_{:05}.{}",
+            stem,
+            sequence,
+            ext.to_string_lossy()
+        ),
+        None => format!("{}_{:05}", stem, sequence),
+    };
+    base.with_file_name(name)
+}
+
+/// 录制写入器：不滚动时写入单个文件，滚动时写满后换新文件并删除最早的文件。
+/// 每个文件都以相同的文件头开始
+pub struct RollingWriter {
+    base: PathBuf,
+    ring: Option<RingSpec>,
+    header: Vec<u8>,
+    checksum: ChecksumAlgorithm,
+    writer: PcapWriter<BufWriter<File>>,
+    files: VecDeque<PathBuf>, // 保留中的文件，最后一个为正在写入的文件
+    sequence: usize,
+    packets_written: usize, // 所有文件累计写入的数据包数量
+}
+
+impl RollingWriter {
+    /// 创建第一个文件并写入文件头（`header` 为文件头的原始字节）
+    pub fn create(
+        base: &Path,
+        header: Vec<u8>,
+        ring: Option<RingSpec>,
+    ) -> Result<Self> {
+        let path = match ring {
+            Some(_) => file_path(base, 1),
+            None => base.to_path_buf(),
+        };
+        let writer = Self::open(&path, &header)?;
+        Ok(Self {
+            base: base.to_path_buf(),
+            ring,
+            header,
+            checksum: ChecksumAlgorithm::Crc32,
+            writer,
+            files: VecDeque::from([path]),
+            sequence: 1,
+            packets_written: 0,
+        })
+    }
+
+    /// 设置 `write_packet` 使用的校验和算法（`Auto` 按 CRC32 计算）
+    pub fn with_checksum(
+        mut self,
+        checksum: ChecksumAlgorithm,
+    ) -> Self {
+        self.checksum = checksum;
+        self.writer = self.writer.with_checksum(checksum);
+        self
+    }
+
+    /// 写入一个数据包，自动计算长度与校验和
+    pub fn write_packet(
+        &mut self,
+        timestamp_seconds: u32,
+        timestamp_nanoseconds: u32,
+        payload: &[u8],
+    ) -> Result<()> {
+        self.roll_if_full(
+            PACKET_HEADER_SIZE + payload.len(),
+        )?;
+        self.writer.write_packet(
+            timestamp_seconds,
+            timestamp_nanoseconds,
+            payload,
+        )?;
+        self.packets_written += 1;
+        Ok(())
+    }
+
+    /// 按原样复制已有文件中的数据包（包头字节与负载都不改动）
+    pub fn copy_packet(
+        &mut self,
+        packet: &PacketRef<'_>,
+    ) -> Result<()> {
+        self.roll_if_full(
+            packet.header_bytes().len()
+                + packet.payload().len(),
+        )?;
+        self.writer.copy_packet(packet)?;
+        self.packets_written += 1;
+        Ok(())
+    }
+
+    /// 所有文件累计写入的数据包数量
+    pub fn packets_written(&self) -> usize {
+        self.packets_written
+    }
+
+    /// 正在写入的文件
+    pub fn current_path(&self) -> &Path {
+        self.files.back().unwrap_or(&self.base)
+    }
+
+    /// 滚动方式（不滚动时为 None）
+    pub fn ring(&self) -> Option<RingSpec> {
+        self.ring
+    }
+
+    /// 刷新缓冲区
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+
+    /// 刷新缓冲区并把正在写入的文件同步到磁盘
+    pub fn sync(&mut self) -> Result<()> {
+        self.writer.sync()
+    }
+
+    /// 完成写入，同步并关闭正在写入的文件，返回保留中的文件
+    pub fn finish(mut self) -> Result<Vec<PathBuf>> {
+        self.writer.sync()?;
+        self.writer.finish()?;
+        Ok(self.files.into())
+    }
+
+    /// 写入 `len` 字节会超出大小上限时换新文件（空文件不换，超大的数据包单独成一个文件）
+    fn roll_if_full(&mut self, len: usize) -> Result<()> {
+        let Some(ring) = self.ring else {
+            return Ok(());
+        };
+        if self.writer.packets_written() == 0
+            || self.writer.bytes_written() + len as u64
+                <= ring.size
+        {
+            return Ok(());
+        }
+
+        self.sequence += 1;
+        let path = file_path(&self.base, self.sequence);
+        let writer = Self::open(&path, &self.header)?
+            .with_checksum(self.checksum);
+        std::mem::replace(&mut self.writer, writer)
+            .finish()?
+            .get_ref()
+            .sync_data()?;
+        self.files.push_back(path);
+
+        while self.files.len() > ring.files {
+            let Some(oldest) = self.files.pop_front()
+            else {
+                break;
+            };
+            if let Err(e) = std::fs::remove_file(&oldest) {
+                if e.kind() != ErrorKind::NotFound {
+                    return Err(e.into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 创建文件并写入文件头
+    fn open(
+        path: &Path,
+        header: &[u8],
+    ) -> Result<PcapWriter<BufWriter<File>>> {
+        let mut writer =
+            PcapWriter::create_with_raw_header(
+                path, header,
+            )?;
+        writer.flush()?;
+        Ok(writer)
+    }
+}
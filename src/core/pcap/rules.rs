@@ -0,0 +1,235 @@
+//! 校验规则：逐个数据包检查并报告违规，结果汇入文件问题诊断
+//!
+//! 内置规则为校验和、时间单调与负载长度范围；库的使用者可以实现
+//! [`ValidationRule`] 并注册到 [`RuleSet`]，例如检查负载前 4 字节的序号是否递增。
+
+use crate::core::pcap::diagnostics::DiagnosticKind;
+use crate::core::pcap::packet_ref::PacketRef;
+use crate::core::pcap::parser::PcapParser;
+
+/// 规则检查时可见的文件上下文
+#[derive(Clone, Copy)]
+pub struct FileCtx<'a> {
+    /// 文件内容
+    pub data: &'a [u8],
+    /// 解析结果
+    pub parser: &'a PcapParser,
+}
+
+impl<'a> FileCtx<'a> {
+    /// 创建上下文
+    pub fn new(
+        data: &'a [u8],
+        parser: &'a PcapParser,
+    ) -> Self {
+        Self { data, parser }
+    }
+
+    /// 前一个数据包（第一个数据包时为 None）
+    pub fn previous(
+        &self,
+        packet: &PacketRef<'_>,
+    ) -> Option<PacketRef<'a>> {
+        let index = packet.index().checked_sub(1)?;
+        self.parser.packet_ref(self.data, index)
+    }
+}
+
+/// 规则发现的一处违规
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 违规所在的文件偏移
+    pub offset: usize,
+    /// 相关数据包序号（从 0 开始）
+    pub packet: Option<usize>,
+    /// 违规说明
+    pub message: String,
+}
+
+impl Violation {
+    /// 针对整个数据包的违规（偏移为数据包头）
+    pub fn packet(
+        packet: &PacketRef<'_>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            offset: packet.offset(),
+            packet: Some(packet.index()),
+            message: message.into(),
+        }
+    }
+}
+
+/// 校验规则
+pub trait ValidationRule {
+    /// 规则名称，显示在诊断信息中
+    fn name(&self) -> &str;
+
+    /// 违规对应的诊断类型（自定义规则默认为 [`DiagnosticKind::Rule`]）
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::Rule
+    }
+
+    /// 检查一个数据包
+    fn check(
+        &self,
+        pkt: &PacketRef<'_>,
+        ctx: &FileCtx<'_>,
+    ) -> Vec<Violation>;
+}
+
+/// 负载校验和与包头一致（数据包头布局中没有校验和字段时跳过）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChecksumRule;
+
+impl ValidationRule for ChecksumRule {
+    fn name(&self) -> &str {
+        "checksum"
+    }
+
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::BadChecksum
+    }
+
+    fn check(
+        &self,
+        pkt: &PacketRef<'_>,
+        ctx: &FileCtx<'_>,
+    ) -> Vec<Violation> {
+        let options = ctx.parser.options();
+        if !options.record.has_checksum()
+            || options.checksum.matches(
+                pkt.payload(),
+                pkt.header().checksum,
+            )
+        {
+            return Vec::new();
+        }
+        vec![Violation::packet(
+            pkt,
+            format!(
+                "checksum mismatch (header 0x{:08X})",
+                pkt.header().checksum
+            ),
+        )]
+    }
+}
+
+/// 时间戳不早于前一个数据包
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonotonicTimeRule;
+
+impl ValidationRule for MonotonicTimeRule {
+    fn name(&self) -> &str {
+        "monotonic-time"
+    }
+
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::BackwardsTimestamp
+    }
+
+    fn check(
+        &self,
+        pkt: &PacketRef<'_>,
+        ctx: &FileCtx<'_>,
+    ) -> Vec<Violation> {
+        let Some(previous) = ctx.previous(pkt) else {
+            return Vec::new();
+        };
+        let delta = pkt.header().timestamp_nanos()
+            - previous.header().timestamp_nanos();
+        if delta >= 0 {
+            return Vec::new();
+        }
+        vec![Violation::packet(
+            pkt,
+            format!("timestamp goes back {} ns", -delta),
+        )]
+    }
+}
+
+/// 负载长度在给定范围内（两端包含）
+#[derive(Debug, Clone, Copy)]
+pub struct LengthBoundsRule {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl ValidationRule for LengthBoundsRule {
+    fn name(&self) -> &str {
+        "length"
+    }
+
+    fn check(
+        &self,
+        pkt: &PacketRef<'_>,
+        _ctx: &FileCtx<'_>,
+    ) -> Vec<Violation> {
+        let len = pkt.header().packet_length;
+        if (self.min..=self.max).contains(&len) {
+            return Vec::new();
+        }
+        vec![Violation::packet(
+            pkt,
+            format!(
+                "{} bytes outside {}..={}",
+                len, self.min, self.max
+            ),
+        )]
+    }
+}
+
+/// 一组校验规则
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl RuleSet {
+    /// 空规则集
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 内置的校验和与时间单调规则（文件问题诊断的默认规则）
+    pub fn builtin() -> Self {
+        let mut rules = Self::new();
+        rules.register(ChecksumRule);
+        rules.register(MonotonicTimeRule);
+        rules
+    }
+
+    /// 注册规则
+    pub fn register(
+        &mut self,
+        rule: impl ValidationRule + 'static,
+    ) -> &mut Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// 已注册的规则
+    pub fn rules(
+        &self,
+    ) -> impl Iterator<Item = &dyn ValidationRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// 对所有数据包运行所有规则，返回(规则, 违规)
+    pub fn check<'r>(
+        &'r self,
+        ctx: &FileCtx<'_>,
+    ) -> Vec<(&'r dyn ValidationRule, Violation)> {
+        let mut found = Vec::new();
+        for packet in ctx.parser.packet_refs(ctx.data) {
+            for rule in self.rules() {
+                found.extend(
+                    rule.check(&packet, ctx)
+                        .into_iter()
+                        .map(|violation| (rule, violation)),
+                );
+            }
+        }
+        found
+    }
+}
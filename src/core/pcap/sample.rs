@@ -0,0 +1,119 @@
+//! 数据包抽样：`every:100`（每 100 个取 1 个）或 `random:0.01`（随机取 1%），
+//! 用于在超大文件上快速得到近似的统计结果
+//!
+//! 随机抽样使用固定种子的伪随机数，同一文件每次抽到的数据包相同。
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::app::error::types::PcapViewerError;
+
+/// 随机抽样的固定种子
+const RANDOM_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// 抽样方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sampling {
+    /// 每 N 个数据包取第一个
+    Every(u64),
+    /// 每个数据包以给定概率（0-1）被选中
+    Random(f64),
+}
+
+impl Sampling {
+    /// 抽样比例（0-1）
+    pub fn rate(&self) -> f64 {
+        match *self {
+            Self::Every(n) => 1.0 / n as f64,
+            Self::Random(probability) => probability,
+        }
+    }
+}
+
+impl FromStr for Sampling {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid sampling `{}` (expected every:N or random:P)",
+                text
+            ))
+        };
+        let (kind, value) = text
+            .trim()
+            .split_once(':')
+            .ok_or_else(invalid)?;
+        match kind.to_ascii_lowercase().as_str() {
+            "every" => match value.parse::<u64>() {
+                Ok(n) if n > 0 => Ok(Self::Every(n)),
+                _ => Err(invalid()),
+            },
+            "random" => match value.parse::<f64>() {
+                Ok(p) if p > 0.0 && p <= 1.0 => {
+                    Ok(Self::Random(p))
+                }
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Sampling {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Every(n) => write!(f, "every:{}", n),
+            Self::Random(p) => write!(f, "random:{}", p),
+        }
+    }
+}
+
+/// 按抽样方式依次决定每个数据包是否保留
+#[derive(Debug, Clone)]
+pub struct Sampler {
+    sampling: Sampling,
+    /// 已判断过的数据包数
+    seen: u64,
+    /// xorshift64 随机数状态
+    state: u64,
+}
+
+impl Sampler {
+    /// 创建抽样器
+    pub fn new(sampling: Sampling) -> Self {
+        Self {
+            sampling,
+            seen: 0,
+            state: RANDOM_SEED,
+        }
+    }
+
+    /// 下一个数据包是否保留
+    pub fn keep(&mut self) -> bool {
+        let index = self.seen;
+        self.seen += 1;
+        match self.sampling {
+            Sampling::Every(n) => index.is_multiple_of(n),
+            Sampling::Random(probability) => {
+                self.state ^= self.state << 13;
+                self.state ^= self.state >> 7;
+                self.state ^= self.state << 17;
+                // 取高 53 位作为 [0, 1) 之间的浮点数
+                let value = (self.state >> 11) as f64
+                    / (1u64 << 53) as f64;
+                value < probability
+            }
+        }
+    }
+
+    /// 已判断过的数据包数（含未保留的）
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+}
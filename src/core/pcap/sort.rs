@@ -0,0 +1,245 @@
+//! 按时间戳排序（外部归并排序，支持大于内存的文件）
+//!
+//! 先将输入切分为若干在内存中排序的分段并写入临时文件，
+//! 再对所有分段做多路归并。相同时间戳的数据包保持原有顺序。
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::app::error::types::Result;
+use crate::core::pcap::builder::ParserOptions;
+use crate::core::pcap::packet_ref::OwnedPacket;
+use crate::core::pcap::reader::PcapReader;
+use crate::core::pcap::writer::PcapWriter;
+
+/// 默认每个分段占用的内存上限（256 MiB）
+pub const DEFAULT_CHUNK_BYTES: usize = 256 * 1024 * 1024;
+
+/// 排序结果统计
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SortSummary {
+    /// 数据包总数
+    pub packets: usize,
+    /// 时间戳早于前一个数据包的数据包数
+    pub out_of_order: usize,
+    /// 写入临时文件的分段数（全部放入内存时为 0）
+    pub runs: usize,
+}
+
+/// 外部归并排序器
+#[derive(Debug, Clone)]
+pub struct ExternalSorter {
+    chunk_bytes: usize,
+    temp_dir: PathBuf,
+}
+
+impl Default for ExternalSorter {
+    fn default() -> Self {
+        Self {
+            chunk_bytes: DEFAULT_CHUNK_BYTES,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+impl ExternalSorter {
+    /// 创建默认配置的排序器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置每个分段占用的内存上限（字节）
+    pub fn chunk_bytes(
+        mut self,
+        chunk_bytes: usize,
+    ) -> Self {
+        self.chunk_bytes = chunk_bytes.max(1);
+        self
+    }
+
+    /// 设置临时文件目录
+    pub fn temp_dir<P: Into<PathBuf>>(
+        mut self,
+        temp_dir: P,
+    ) -> Self {
+        self.temp_dir = temp_dir.into();
+        self
+    }
+
+    /// 读取全部数据包，按 (秒, 纳秒) 稳定排序后写入输出。
+    /// 数据包头按原样写出，保留输入文件的字节序与数据包头布局
+    pub fn sort<R: Read, W: Write>(
+        &self,
+        mut reader: PcapReader<R>,
+        writer: &mut PcapWriter<W>,
+    ) -> Result<SortSummary> {
+        let mut summary = SortSummary::default();
+        let mut runs = RunFiles::new(
+            &self.temp_dir,
+            reader.file_header_bytes(),
+            reader.options(),
+        );
+        let mut chunk = Vec::new();
+        let mut chunk_bytes = 0;
+        let mut last_key = None;
+
+        while let Some(packet) = reader.next_packet()? {
+            let key = sort_key(&packet);
+            if last_key.is_some_and(|last| key < last) {
+                summary.out_of_order += 1;
+            }
+            last_key = Some(key);
+            summary.packets += 1;
+
+            chunk_bytes += packet.payload.len()
+                + std::mem::size_of::<OwnedPacket>();
+            chunk.push(packet);
+            if chunk_bytes >= self.chunk_bytes {
+                runs.write_run(&mut chunk)?;
+                chunk_bytes = 0;
+            }
+        }
+
+        // 全部数据包都在内存中，直接输出
+        if runs.paths.is_empty() {
+            chunk.sort_by_key(sort_key);
+            for packet in &chunk {
+                writer.write_record(
+                    &packet.header_bytes,
+                    &packet.payload,
+                )?;
+            }
+            return Ok(summary);
+        }
+
+        if !chunk.is_empty() {
+            runs.write_run(&mut chunk)?;
+        }
+        summary.runs = runs.paths.len();
+        runs.merge_into(writer)?;
+        Ok(summary)
+    }
+}
+
+/// 排序键：(秒, 纳秒)
+fn sort_key(packet: &OwnedPacket) -> (u32, u32) {
+    let header = packet.header();
+    (
+        header.timestamp_seconds,
+        header.timestamp_nanoseconds,
+    )
+}
+
+/// 已排序分段的临时文件（与输入文件格式相同），离开作用域时删除
+struct RunFiles {
+    temp_dir: PathBuf,
+    header: Vec<u8>, // 输入文件头的原始字节
+    options: ParserOptions, // 读回分段时使用的解析选项
+    paths: Vec<PathBuf>,
+}
+
+impl RunFiles {
+    fn new(
+        temp_dir: &Path,
+        header: &[u8],
+        options: &ParserOptions,
+    ) -> Self {
+        Self {
+            temp_dir: temp_dir.to_path_buf(),
+            header: header.to_vec(),
+            // 分段由输入原样写出，抽样与校验在读取输入时已完成
+            options: ParserOptions {
+                sample: None,
+                verify_crc: false,
+                ..options.clone()
+            },
+            paths: Vec::new(),
+        }
+    }
+
+    /// 排序当前分段并写入新的临时文件
+    fn write_run(
+        &mut self,
+        chunk: &mut Vec<OwnedPacket>,
+    ) -> Result<()> {
+        chunk.sort_by_key(sort_key);
+
+        let path = self.temp_dir.join(format!(
+            "pcap-viewer-sort-{}-{}.pcap",
+            std::process::id(),
+            self.paths.len()
+        ));
+        self.paths.push(path.clone());
+
+        let mut writer =
+            PcapWriter::create_with_raw_header(
+                &path,
+                &self.header,
+            )?;
+        for packet in chunk.drain(..) {
+            writer.write_record(
+                &packet.header_bytes,
+                &packet.payload,
+            )?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// 多路归并所有分段；时间戳相同时按分段顺序输出以保持稳定
+    fn merge_into<W: Write>(
+        &self,
+        writer: &mut PcapWriter<W>,
+    ) -> Result<()> {
+        let mut readers = self
+            .paths
+            .iter()
+            .map(|path| {
+                PcapReader::with_options(
+                    BufReader::new(File::open(path)?),
+                    self.options.clone(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut heads: Vec<Option<OwnedPacket>> =
+            Vec::with_capacity(readers.len());
+        let mut heap = BinaryHeap::new();
+        for (run, reader) in readers.iter_mut().enumerate()
+        {
+            let head = reader.next_packet()?;
+            if let Some(packet) = &head {
+                heap.push(Reverse((sort_key(packet), run)));
+            }
+            heads.push(head);
+        }
+
+        while let Some(Reverse((_, run))) = heap.pop() {
+            let Some(packet) = heads[run].take() else {
+                continue;
+            };
+            writer.write_record(
+                &packet.header_bytes,
+                &packet.payload,
+            )?;
+
+            let next = readers[run].next_packet()?;
+            if let Some(packet) = &next {
+                heap.push(Reverse((sort_key(packet), run)));
+            }
+            heads[run] = next;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for RunFiles {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! 数据包头（记录头）布局描述：字段顺序、宽度、字节序与是否带校验和，
+//! 用于查看自定义格式的小变体（如带序号的 24 字节包头）而不必修改解析器
+//!
+//! 描述文件为 TOML，例如：
+//!
+//! ```toml
+//! endianness = "auto"   # little / big / auto（与文件头一致）
+//! fields = [
+//!     { name = "timestamp_seconds", width = 4 },
+//!     { name = "timestamp_nanoseconds", width = 4 },
+//!     { name = "sequence", width = 8 },
+//!     { name = "length", width = 4 },
+//!     { name = "checksum", width = 4 },
+//! ]
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::pcap::builder::Endianness;
+use crate::core::pcap::parser::DataPacketHeader;
+
+/// 记录头字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordField {
+    /// 时间戳秒部分
+    TimestampSeconds,
+    /// 时间戳纳秒部分
+    TimestampNanoseconds,
+    /// 负载长度（必需）
+    Length,
+    /// 负载校验和
+    Checksum,
+    /// 序号
+    Sequence,
+    /// 保留字节（不解析）
+    Reserved,
+}
+
+impl FromStr for RecordField {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "timestamp_seconds" => Ok(Self::TimestampSeconds),
+            "timestamp_nanoseconds" => {
+                Ok(Self::TimestampNanoseconds)
+            }
+            "length" => Ok(Self::Length),
+            "checksum" => Ok(Self::Checksum),
+            "sequence" => Ok(Self::Sequence),
+            "reserved" => Ok(Self::Reserved),
+            other => Err(format!(
+                "unknown field `{}` (expected timestamp_seconds, timestamp_nanoseconds, length, checksum, sequence or reserved)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RecordField {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::TimestampSeconds => "timestamp_seconds",
+            Self::TimestampNanoseconds => {
+                "timestamp_nanoseconds"
+            }
+            Self::Length => "length",
+            Self::Checksum => "checksum",
+            Self::Sequence => "sequence",
+            Self::Reserved => "reserved",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 字段在记录头中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldLayout {
+    field: RecordField,
+    offset: usize,
+    width: usize,
+}
+
+/// 记录头布局
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordSpec {
+    fields: Vec<FieldLayout>,
+    size: usize,
+    /// 记录头字节序（Auto 时与文件头一致）
+    endianness: Endianness,
+}
+
+impl Default for RecordSpec {
+    /// 标准的 16 字节包头：秒、纳秒、长度、校验和各 4 字节
+    fn default() -> Self {
+        Self::from_fields(
+            &[
+                (RecordField::TimestampSeconds, 4),
+                (RecordField::TimestampNanoseconds, 4),
+                (RecordField::Length, 4),
+                (RecordField::Checksum, 4),
+            ],
+            Endianness::Auto,
+        )
+    }
+}
+
+impl RecordSpec {
+    /// 按顺序排列的字段（字段, 宽度）组成的布局
+    fn from_fields(
+        fields: &[(RecordField, usize)],
+        endianness: Endianness,
+    ) -> Self {
+        let mut offset = 0;
+        let fields = fields
+            .iter()
+            .map(|&(field, width)| {
+                let layout = FieldLayout {
+                    field,
+                    offset,
+                    width,
+                };
+                offset += width;
+                layout
+            })
+            .collect();
+        Self {
+            fields,
+            size: offset,
+            endianness,
+        }
+    }
+
+    /// 读取布局描述文件
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let error = |message: String| {
+            PcapViewerError::InvalidFormat(format!(
+                "{}: {}",
+                path.display(),
+                message
+            ))
+        };
+        let text = fs::read_to_string(path)
+            .map_err(|e| error(e.to_string()))?;
+        Ok(Self::from_toml(&text).map_err(error)?)
+    }
+
+    /// 解析布局描述
+    pub fn from_toml(
+        text: &str,
+    ) -> std::result::Result<Self, String> {
+        let table: toml::Table = toml::from_str(text)
+            .map_err(|e| e.message().to_string())?;
+
+        let endianness = match table
+            .get("endianness")
+            .map(|value| value.as_str())
+        {
+            None => Endianness::Auto,
+            Some(Some("auto")) => Endianness::Auto,
+            Some(Some("little")) => Endianness::Little,
+            Some(Some("big")) => Endianness::Big,
+            Some(_) => return Err(
+                "`endianness` must be little, big or auto"
+                    .to_string(),
+            ),
+        };
+
+        let entries = table
+            .get("fields")
+            .and_then(|value| value.as_array())
+            .ok_or("`fields` must be a list of fields")?;
+        let mut fields = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let name = entry
+                .get("name")
+                .and_then(|value| value.as_str())
+                .ok_or("each field needs a `name`")?;
+            let field: RecordField = name.parse()?;
+            let width = entry
+                .get("width")
+                .and_then(|value| value.as_integer())
+                .ok_or_else(|| {
+                    format!(
+                        "field `{}` needs a `width`",
+                        name
+                    )
+                })?;
+            let valid = match field {
+                RecordField::Reserved => {
+                    (1..=64).contains(&width)
+                }
+                _ => matches!(width, 1 | 2 | 4 | 8),
+            };
+            if !valid {
+                return Err(format!(
+                    "invalid width {} for field `{}` (expected 1, 2, 4 or 8)",
+                    width, name
+                ));
+            }
+            if field != RecordField::Reserved
+                && fields
+                    .iter()
+                    .any(|&(other, _)| other == field)
+            {
+                return Err(format!(
+                    "duplicate field `{}`",
+                    name
+                ));
+            }
+            fields.push((field, width as usize));
+        }
+        if !fields
+            .iter()
+            .any(|&(field, _)| field == RecordField::Length)
+        {
+            return Err(
+                "missing `length` field".to_string()
+            );
+        }
+
+        Ok(Self::from_fields(&fields, endianness))
+    }
+
+    /// 记录头长度（字节）
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// 是否带校验和字段
+    pub fn has_checksum(&self) -> bool {
+        self.field_range(RecordField::Checksum).is_some()
+    }
+
+    /// 是否带序号字段
+    pub fn has_sequence(&self) -> bool {
+        self.field_range(RecordField::Sequence).is_some()
+    }
+
+    /// 字段在记录头中的字节范围（布局中没有该字段时为 None）
+    pub fn field_range(
+        &self,
+        field: RecordField,
+    ) -> Option<Range<usize>> {
+        self.layout(field).map(|layout| {
+            layout.offset..layout.offset + layout.width
+        })
+    }
+
+    /// 起始于文件偏移 `record_offset` 的记录头中，字段在文件中的偏移范围
+    /// （没有该字段时为记录头起始处的空范围）
+    pub fn field_at(
+        &self,
+        field: RecordField,
+        record_offset: usize,
+    ) -> Range<usize> {
+        self.field_range(field).map_or(
+            record_offset..record_offset,
+            |range| {
+                record_offset + range.start
+                    ..record_offset + range.end
+            },
+        )
+    }
+
+    /// 记录头实际使用的字节序（Auto 时取文件头的字节序）
+    pub fn endianness(
+        &self,
+        file_endianness: Endianness,
+    ) -> Endianness {
+        match self.endianness {
+            Endianness::Auto => file_endianness,
+            fixed => fixed,
+        }
+    }
+
+    /// 读取字段的值（布局中没有该字段时为 None）
+    pub fn read(
+        &self,
+        bytes: &[u8],
+        field: RecordField,
+        file_endianness: Endianness,
+    ) -> Option<u64> {
+        let layout = self.layout(field)?;
+        let bytes = bytes.get(
+            layout.offset..layout.offset + layout.width,
+        )?;
+        let big = self.endianness(file_endianness)
+            == Endianness::Big;
+        let fold = |value: u64, byte: &u8| {
+            value << 8 | *byte as u64
+        };
+        Some(if big {
+            bytes.iter().fold(0, fold)
+        } else {
+            bytes.iter().rev().fold(0, fold)
+        })
+    }
+
+    /// 编码字段的值，返回其在记录头中的偏移与字节（布局中没有该字段时为 None）
+    pub fn encode(
+        &self,
+        field: RecordField,
+        value: u64,
+        file_endianness: Endianness,
+    ) -> Option<(usize, Vec<u8>)> {
+        let layout = self.layout(field)?;
+        let mut bytes =
+            value.to_le_bytes()[..layout.width].to_vec();
+        if self.endianness(file_endianness)
+            == Endianness::Big
+        {
+            bytes.reverse();
+        }
+        Some((layout.offset, bytes))
+    }
+
+    /// 解码记录头（缺少的时间戳与校验和字段为 0，超出 u32 的值截断）
+    pub fn decode(
+        &self,
+        bytes: &[u8],
+        file_endianness: Endianness,
+    ) -> DataPacketHeader {
+        let read = |field| {
+            self.read(bytes, field, file_endianness)
+                .unwrap_or(0) as u32
+        };
+        DataPacketHeader {
+            timestamp_seconds: read(
+                RecordField::TimestampSeconds,
+            ),
+            timestamp_nanoseconds: read(
+                RecordField::TimestampNanoseconds,
+            ),
+            packet_length: read(RecordField::Length),
+            checksum: read(RecordField::Checksum),
+        }
+    }
+
+    /// 包含记录头中第 `position` 个字节的字段及其字节范围
+    pub fn field_containing(
+        &self,
+        position: usize,
+    ) -> Option<(RecordField, Range<usize>)> {
+        self.fields
+            .iter()
+            .find(|layout| {
+                (layout.offset
+                    ..layout.offset + layout.width)
+                    .contains(&position)
+            })
+            .map(|layout| {
+                (
+                    layout.field,
+                    layout.offset
+                        ..layout.offset + layout.width,
+                )
+            })
+    }
+
+    fn layout(
+        &self,
+        field: RecordField,
+    ) -> Option<&FieldLayout> {
+        self.fields
+            .iter()
+            .find(|layout| layout.field == field)
+    }
+}
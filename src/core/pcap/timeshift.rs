@@ -0,0 +1,169 @@
+//! 数据包时间戳平移
+//!
+//! 支持整体加减固定偏移，或将第一个数据包对齐到指定时间。
+//! 只修改时间戳字段，长度与校验和保持不变。
+
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::pcap::parser::DataPacketHeader;
+
+/// 每秒纳秒数
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// 时间平移方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeShift {
+    /// 所有时间戳加上固定偏移（纳秒，可为负）
+    Offset(i128),
+    /// 将第一个数据包的时间对齐到指定时刻（Unix 纳秒）
+    Rebase(i128),
+}
+
+impl TimeShift {
+    /// 计算实际偏移量（纳秒），`first` 为第一个数据包的时间戳
+    pub fn offset_nanos(
+        self,
+        first: Option<&DataPacketHeader>,
+    ) -> i128 {
+        match self {
+            TimeShift::Offset(offset) => offset,
+            TimeShift::Rebase(target) => first
+                .map_or(0, |first| {
+                    target - first.timestamp_nanos()
+                }),
+        }
+    }
+}
+
+/// 返回平移后的数据包头（时间超出 u32 秒范围时报错）
+pub fn shift_header(
+    header: &DataPacketHeader,
+    offset_nanos: i128,
+) -> Result<DataPacketHeader> {
+    let shifted = header.timestamp_nanos() + offset_nanos;
+    let seconds = shifted.div_euclid(NANOS_PER_SECOND);
+    let nanoseconds = shifted.rem_euclid(NANOS_PER_SECOND);
+
+    let timestamp_seconds = u32::try_from(seconds)
+        .map_err(|_| {
+            PcapViewerError::Usage(format!(
+                "Shifted timestamp out of range: {}s",
+                seconds
+            ))
+        })?;
+
+    Ok(DataPacketHeader {
+        timestamp_seconds,
+        timestamp_nanoseconds: nanoseconds as u32,
+        ..*header
+    })
+}
+
+/// 解析时间偏移，如 `-3600s`、`1h30m`、`250ms`、`+2.5s`（无单位时为秒）
+pub fn parse_offset(text: &str) -> Result<i128> {
+    let invalid = || {
+        PcapViewerError::InvalidFormat(format!(
+            "Invalid time offset: {}",
+            text
+        ))
+    };
+
+    let trimmed = text.trim();
+    let (negative, body) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (
+            false,
+            trimmed.strip_prefix('+').unwrap_or(trimmed),
+        ),
+    };
+    if body.is_empty() {
+        return Err(invalid().into());
+    }
+
+    let mut total: i128 = 0;
+    let mut rest = body;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| {
+                !(c.is_ascii_digit() || c == '.')
+            })
+            .unwrap_or(rest.len());
+        let (whole, fraction) = rest[..number_len]
+            .split_once('.')
+            .unwrap_or((&rest[..number_len], ""));
+        if (whole.is_empty() && fraction.is_empty())
+            || fraction.contains('.')
+        {
+            return Err(invalid().into());
+        }
+        rest = &rest[number_len..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale: i128 = match &rest[..unit_len] {
+            "h" => 3600 * NANOS_PER_SECOND,
+            "m" => 60 * NANOS_PER_SECOND,
+            "" | "s" => NANOS_PER_SECOND,
+            "ms" => 1_000_000,
+            "us" => 1_000,
+            "ns" => 1,
+            _ => return Err(invalid().into()),
+        };
+        rest = &rest[unit_len..];
+        total = total
+            .checked_add(
+                scaled_nanos(whole, fraction, scale)
+                    .ok_or_else(invalid)?,
+            )
+            .ok_or_else(invalid)?;
+    }
+
+    Ok(if negative { -total } else { total })
+}
+
+/// 按单位换算为纳秒（整数运算，小数部分四舍五入到纳秒）
+fn scaled_nanos(
+    whole: &str,
+    fraction: &str,
+    scale: i128,
+) -> Option<i128> {
+    let whole: i128 = match whole {
+        "" => 0,
+        digits => digits.parse().ok()?,
+    };
+    // 超出纳秒精度的小数位不影响结果（单位最大为小时，19 位足够）
+    let fraction = &fraction[..fraction.len().min(19)];
+    let numerator: i128 = match fraction {
+        "" => 0,
+        digits => digits.parse().ok()?,
+    };
+    let denominator = 10i128.pow(fraction.len() as u32);
+    let part =
+        (numerator * scale + denominator / 2) / denominator;
+    whole.checked_mul(scale)?.checked_add(part)
+}
+
+/// 解析目标时间（RFC 3339，或按 UTC 解释的 `YYYY-MM-DD HH:MM:SS[.f]`），返回 Unix 纳秒
+pub fn parse_time(text: &str) -> Result<i128> {
+    let text = text.trim();
+    let datetime = DateTime::parse_from_rfc3339(text)
+        .map(|dt| dt.to_utc())
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(
+                text,
+                "%Y-%m-%d %H:%M:%S%.f",
+            )
+            .map(|naive| naive.and_utc())
+        })
+        .map_err(|_| {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid time: {}",
+                text
+            ))
+        })?;
+
+    Ok(datetime.timestamp() as i128 * NANOS_PER_SECOND
+        + datetime.timestamp_subsec_nanos() as i128)
+}
@@ -0,0 +1,162 @@
+//! 时间戳异常检测
+//!
+//! 找出相邻数据包之间超过阈值的时间间隔以及时间倒退，
+//! 用于排查丢包或合并错误的抓包文件；
+//! 并统计到达间隔与抖动，检查周期性数据的时间规律。
+
+use crate::core::pcap::parser::{
+    DataPacket, DataPacketHeader,
+};
+
+/// 默认间隔阈值（1 秒）
+pub const DEFAULT_GAP_THRESHOLD: i128 = 1_000_000_000;
+
+/// 时间戳异常类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingIssueKind {
+    /// 与前一个数据包的间隔超过阈值
+    Gap,
+    /// 时间戳早于前一个数据包
+    Backwards,
+}
+
+/// 时间戳异常
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingIssue {
+    /// 数据包序号（从 0 开始）
+    pub index: usize,
+    /// 数据包头在文件中的偏移
+    pub offset: usize,
+    pub kind: TimingIssueKind,
+    /// 与前一个数据包的时间差（纳秒，倒退时为负）
+    pub delta_nanos: i128,
+}
+
+/// 检查相邻两个数据包的时间戳，返回异常类型与时间差
+pub fn check_pair(
+    previous: &DataPacketHeader,
+    current: &DataPacketHeader,
+    gap_threshold: i128,
+) -> Option<(TimingIssueKind, i128)> {
+    let delta = current.timestamp_nanos()
+        - previous.timestamp_nanos();
+    if delta < 0 {
+        Some((TimingIssueKind::Backwards, delta))
+    } else if delta > gap_threshold {
+        Some((TimingIssueKind::Gap, delta))
+    } else {
+        None
+    }
+}
+
+/// 找出所有时间戳异常，按数据包顺序返回
+pub fn find_issues(
+    packets: &[DataPacket],
+    gap_threshold: i128,
+) -> Vec<TimingIssue> {
+    packets
+        .windows(2)
+        .enumerate()
+        .filter_map(|(i, pair)| {
+            let (kind, delta_nanos) = check_pair(
+                &pair[0].header,
+                &pair[1].header,
+                gap_threshold,
+            )?;
+            Some(TimingIssue {
+                index: i + 1,
+                offset: pair[1].offset,
+                kind,
+                delta_nanos,
+            })
+        })
+        .collect()
+}
+
+/// 一组时间值（纳秒）的分位数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percentiles {
+    pub p50: i128,
+    pub p90: i128,
+    pub p99: i128,
+    pub max: i128,
+}
+
+impl Percentiles {
+    /// 从已排序的非空序列按最近秩法取分位数
+    fn from_sorted(sorted: &[i128]) -> Self {
+        let rank = |percent: usize| {
+            let index =
+                (sorted.len() * percent).div_ceil(100);
+            sorted[index.saturating_sub(1)]
+        };
+        Self {
+            p50: rank(50),
+            p90: rank(90),
+            p99: rank(99),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// 相邻数据包的到达间隔统计，用于检查周期性数据的时间规律
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalStats {
+    /// 间隔个数（数据包数减一）
+    pub count: usize,
+    /// 平均间隔（纳秒）
+    pub mean: f64,
+    /// 间隔的标准差（纳秒）
+    pub stddev: f64,
+    /// 最小间隔（纳秒，有时间倒退时为负）
+    pub min: i128,
+    /// 间隔的分位数
+    pub interval: Percentiles,
+    /// 抖动（间隔与中位间隔之差的绝对值）的分位数
+    pub jitter: Percentiles,
+}
+
+/// 计算到达间隔与抖动，少于两个数据包时为 None
+pub fn interval_stats(
+    packets: &[DataPacket],
+) -> Option<IntervalStats> {
+    let mut intervals: Vec<i128> = packets
+        .windows(2)
+        .map(|pair| {
+            pair[1].header.timestamp_nanos()
+                - pair[0].header.timestamp_nanos()
+        })
+        .collect();
+    if intervals.is_empty() {
+        return None;
+    }
+
+    let count = intervals.len();
+    let mean = intervals
+        .iter()
+        .map(|&interval| interval as f64)
+        .sum::<f64>()
+        / count as f64;
+    let variance = intervals
+        .iter()
+        .map(|&interval| (interval as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count as f64;
+
+    intervals.sort_unstable();
+    let interval = Percentiles::from_sorted(&intervals);
+    let mut jitter: Vec<i128> = intervals
+        .iter()
+        .map(|&value| (value - interval.p50).abs())
+        .collect();
+    jitter.sort_unstable();
+
+    Some(IntervalStats {
+        count,
+        mean,
+        stddev: variance.sqrt(),
+        min: intervals[0],
+        interval,
+        jitter: Percentiles::from_sorted(&jitter),
+    })
+}
@@ -0,0 +1,224 @@
+//! PCAP 文件写入器
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::app::error::types::Result;
+use crate::core::pcap::builder::Endianness;
+use crate::core::pcap::checksum::ChecksumAlgorithm;
+use crate::core::pcap::packet_ref::PacketRef;
+use crate::core::pcap::parser::{
+    DataPacketHeader, PcapFileHeader,
+};
+use crate::core::pcap::spec::{RecordField, RecordSpec};
+
+/// PCAP 文件写入器（自定义格式，小端序，默认 CRC32 校验和）
+pub struct PcapWriter<W: Write> {
+    writer: W,
+    checksum: ChecksumAlgorithm,
+    packets_written: usize,
+    bytes_written: u64,
+}
+
+impl PcapWriter<BufWriter<File>> {
+    /// 创建文件并写入默认文件头
+    pub fn create<P: AsRef<Path>>(
+        file_path: P,
+    ) -> Result<Self> {
+        Self::create_with_header(
+            file_path,
+            &PcapFileHeader::default(),
+        )
+    }
+
+    /// 创建文件并写入指定文件头
+    pub fn create_with_header<P: AsRef<Path>>(
+        file_path: P,
+        header: &PcapFileHeader,
+    ) -> Result<Self> {
+        let file = File::create(file_path.as_ref())?;
+        Self::new(BufWriter::new(file), header)
+    }
+
+    /// 创建文件并按原样写入文件头字节（保留源文件的字节序与格式变体）
+    pub fn create_with_raw_header<P: AsRef<Path>>(
+        file_path: P,
+        header: &[u8],
+    ) -> Result<Self> {
+        let file = File::create(file_path.as_ref())?;
+        let mut writer =
+            Self::without_header(BufWriter::new(file));
+        writer.write_raw_bytes(header)?;
+        Ok(writer)
+    }
+}
+
+impl PcapWriter<BufWriter<File>> {
+    /// 刷新缓冲区并把文件数据同步到磁盘
+    pub fn sync(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> PcapWriter<W> {
+    /// 在任意输出上创建写入器，并立即写入文件头
+    pub fn new(
+        mut writer: W,
+        header: &PcapFileHeader,
+    ) -> Result<Self> {
+        let header_bytes = header.to_bytes();
+        writer.write_all(&header_bytes)?;
+
+        Ok(Self {
+            writer,
+            checksum: ChecksumAlgorithm::Crc32,
+            packets_written: 0,
+            bytes_written: header_bytes.len() as u64,
+        })
+    }
+
+    /// 在任意输出上创建写入器，不写入文件头（用于写出已包含文件头的原始内容）
+    pub fn without_header(writer: W) -> Self {
+        Self {
+            writer,
+            checksum: ChecksumAlgorithm::Crc32,
+            packets_written: 0,
+            bytes_written: 0,
+        }
+    }
+
+    /// 设置 `write_packet` 使用的校验和算法（`Auto` 按 CRC32 计算）
+    pub fn with_checksum(
+        mut self,
+        checksum: ChecksumAlgorithm,
+    ) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// 按原样写入字节（不解析、不校验，可用于手工构造的畸形数据）
+    pub fn write_raw_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<()> {
+        self.writer.write_all(bytes)?;
+        self.bytes_written += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// 写入一个数据包，自动计算长度与校验和
+    pub fn write_packet(
+        &mut self,
+        timestamp_seconds: u32,
+        timestamp_nanoseconds: u32,
+        payload: &[u8],
+    ) -> Result<()> {
+        let header = DataPacketHeader {
+            timestamp_seconds,
+            timestamp_nanoseconds,
+            packet_length: payload.len() as u32,
+            checksum: self.checksum.compute(payload),
+        };
+        self.write_raw_packet(&header, payload)
+    }
+
+    /// 按原样写入数据包头与负载（不重新计算长度与校验和）
+    pub fn write_raw_packet(
+        &mut self,
+        header: &DataPacketHeader,
+        payload: &[u8],
+    ) -> Result<()> {
+        let header_bytes = header.to_bytes();
+        self.writer.write_all(&header_bytes)?;
+        self.writer.write_all(payload)?;
+
+        self.packets_written += 1;
+        self.bytes_written +=
+            (header_bytes.len() + payload.len()) as u64;
+        Ok(())
+    }
+
+    /// 按源文件的数据包头布局写入新负载：沿用原包头字节（字节序与其他字段不变），
+    /// 只按 `payload` 重新计算长度字段，`recompute_checksum` 为 true 时也重新计算校验和字段
+    pub fn write_packet_as(
+        &mut self,
+        header_bytes: &[u8],
+        record: &RecordSpec,
+        endianness: Endianness,
+        payload: &[u8],
+        recompute_checksum: bool,
+    ) -> Result<()> {
+        let mut header = header_bytes.to_vec();
+        let checksum = recompute_checksum.then(|| {
+            (
+                RecordField::Checksum,
+                self.checksum.compute(payload) as u64,
+            )
+        });
+        let fields = std::iter::once((
+            RecordField::Length,
+            payload.len() as u64,
+        ))
+        .chain(checksum);
+        for (field, value) in fields {
+            if let Some((offset, bytes)) =
+                record.encode(field, value, endianness)
+            {
+                header[offset..offset + bytes.len()]
+                    .copy_from_slice(&bytes);
+            }
+        }
+        self.write_record(&header, payload)
+    }
+
+    /// 按原样复制已有文件中的数据包（包头字节与负载都不改动）
+    pub fn copy_packet(
+        &mut self,
+        packet: &PacketRef<'_>,
+    ) -> Result<()> {
+        self.write_record(
+            packet.header_bytes(),
+            packet.payload(),
+        )
+    }
+
+    /// 按原样写入包头字节与负载
+    pub fn write_record(
+        &mut self,
+        header_bytes: &[u8],
+        payload: &[u8],
+    ) -> Result<()> {
+        self.writer.write_all(header_bytes)?;
+        self.writer.write_all(payload)?;
+
+        self.packets_written += 1;
+        self.bytes_written +=
+            (header_bytes.len() + payload.len()) as u64;
+        Ok(())
+    }
+
+    /// 已写入的数据包数量
+    pub fn packets_written(&self) -> usize {
+        self.packets_written
+    }
+
+    /// 已写入的字节数（含文件头）
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// 刷新缓冲区
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// 完成写入，刷新并返回底层输出
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
@@ -0,0 +1,372 @@
+//! 数据包负载的协议解码（以太网 / IPv4 / IPv6 / TCP / UDP 等）
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// 负载的链路层类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum LinkType {
+    /// 以太网帧
+    #[default]
+    Ethernet,
+    /// 裸 IP 报文（无链路层头）
+    #[cfg_attr(feature = "serde", serde(rename = "raw"))]
+    RawIp,
+    /// 不解码
+    None,
+}
+
+impl FromStr for LinkType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ethernet" | "eth" => Ok(Self::Ethernet),
+            "raw" | "ip" => Ok(Self::RawIp),
+            "none" => Ok(Self::None),
+            other => Err(format!(
+                "unknown link type `{}` (expected ethernet, raw or none)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LinkType {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Ethernet => "ethernet",
+            Self::RawIp => "raw",
+            Self::None => "none",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 解码得到的协议层
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Layer {
+    Ethernet {
+        ethertype: u16,
+    },
+    Vlan {
+        id: u16,
+    },
+    Arp,
+    Ipv4 {
+        src: Ipv4Addr,
+        dst: Ipv4Addr,
+        protocol: u8,
+    },
+    Ipv6 {
+        src: Ipv6Addr,
+        dst: Ipv6Addr,
+        next_header: u8,
+    },
+    Icmp,
+    Icmpv6,
+    Tcp(TcpHeader),
+    Udp {
+        src_port: u16,
+        dst_port: u16,
+    },
+    Dns,
+    /// 无法继续解码的剩余数据
+    Data,
+}
+
+impl Layer {
+    /// 协议名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ethernet { .. } => "Ethernet",
+            Self::Vlan { .. } => "802.1Q",
+            Self::Arp => "ARP",
+            Self::Ipv4 { .. } => "IPv4",
+            Self::Ipv6 { .. } => "IPv6",
+            Self::Icmp => "ICMP",
+            Self::Icmpv6 => "ICMPv6",
+            Self::Tcp(_) => "TCP",
+            Self::Udp { .. } => "UDP",
+            Self::Dns => "DNS",
+            Self::Data => "Data",
+        }
+    }
+}
+
+/// TCP 头部关键字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct TcpHeader {
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub seq: u32,
+    pub ack: u32,
+    pub flags: u8,
+}
+
+impl TcpHeader {
+    pub const SYN: u8 = 0x02;
+}
+
+/// 解码结果
+#[derive(Debug, Clone)]
+pub struct DecodedPacket<'a> {
+    pub layers: Vec<Layer>,
+    pub src_ip: Option<IpAddr>,
+    pub dst_ip: Option<IpAddr>,
+    /// 最内层协议的负载（如 TCP/UDP 数据）
+    pub payload: &'a [u8],
+}
+
+impl DecodedPacket<'_> {
+    /// 获取 TCP 头部（如果存在）
+    pub fn tcp(&self) -> Option<&TcpHeader> {
+        self.layers.iter().find_map(|layer| match layer {
+            Layer::Tcp(tcp) => Some(tcp),
+            _ => None,
+        })
+    }
+}
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_ARP: u16 = 0x0806;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+
+const IP_PROTO_ICMP: u8 = 1;
+const IP_PROTO_TCP: u8 = 6;
+const IP_PROTO_UDP: u8 = 17;
+const IP_PROTO_ICMPV6: u8 = 58;
+
+const DNS_PORT: u16 = 53;
+
+/// 按链路层类型解码数据包负载
+pub fn decode(
+    link_type: LinkType,
+    bytes: &[u8],
+) -> DecodedPacket<'_> {
+    let mut decoded = DecodedPacket {
+        layers: Vec::new(),
+        src_ip: None,
+        dst_ip: None,
+        payload: bytes,
+    };
+
+    match link_type {
+        LinkType::Ethernet => decode_ethernet(&mut decoded),
+        LinkType::RawIp => decode_ip(&mut decoded),
+        LinkType::None => {}
+    }
+
+    if !decoded.payload.is_empty() {
+        decoded.layers.push(Layer::Data);
+    }
+    decoded
+}
+
+/// 解码以太网头（支持单层或多层 VLAN 标签）
+fn decode_ethernet(decoded: &mut DecodedPacket<'_>) {
+    let bytes = decoded.payload;
+    if bytes.len() < 14 {
+        return;
+    }
+
+    let mut ethertype =
+        u16::from_be_bytes([bytes[12], bytes[13]]);
+    decoded.layers.push(Layer::Ethernet { ethertype });
+    let mut offset = 14;
+
+    while ethertype == ETHERTYPE_VLAN {
+        if bytes.len() < offset + 4 {
+            decoded.payload = &bytes[offset..];
+            return;
+        }
+        let tci = u16::from_be_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+        ]);
+        ethertype = u16::from_be_bytes([
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ]);
+        decoded
+            .layers
+            .push(Layer::Vlan { id: tci & 0x0FFF });
+        offset += 4;
+    }
+
+    decoded.payload = &bytes[offset..];
+    match ethertype {
+        ETHERTYPE_IPV4 | ETHERTYPE_IPV6 => {
+            decode_ip(decoded)
+        }
+        ETHERTYPE_ARP => {
+            decoded.layers.push(Layer::Arp);
+            decoded.payload = &[];
+        }
+        _ => {}
+    }
+}
+
+/// 根据版本号解码 IPv4 / IPv6 报文
+fn decode_ip(decoded: &mut DecodedPacket<'_>) {
+    match decoded.payload.first().map(|b| b >> 4) {
+        Some(4) => decode_ipv4(decoded),
+        Some(6) => decode_ipv6(decoded),
+        _ => {}
+    }
+}
+
+/// 解码 IPv4 头
+fn decode_ipv4(decoded: &mut DecodedPacket<'_>) {
+    let bytes = decoded.payload;
+    if bytes.len() < 20 {
+        return;
+    }
+    let header_len = ((bytes[0] & 0x0F) as usize) * 4;
+    let total_len =
+        u16::from_be_bytes([bytes[2], bytes[3]]) as usize;
+    if header_len < 20 || bytes.len() < header_len {
+        return;
+    }
+
+    let protocol = bytes[9];
+    let src = Ipv4Addr::new(
+        bytes[12], bytes[13], bytes[14], bytes[15],
+    );
+    let dst = Ipv4Addr::new(
+        bytes[16], bytes[17], bytes[18], bytes[19],
+    );
+    decoded.layers.push(Layer::Ipv4 { src, dst, protocol });
+    decoded.src_ip = Some(IpAddr::V4(src));
+    decoded.dst_ip = Some(IpAddr::V4(dst));
+
+    // 以总长度截断以太网填充字节
+    let end = if total_len >= header_len {
+        total_len.min(bytes.len())
+    } else {
+        bytes.len()
+    };
+    decoded.payload = &bytes[header_len..end];
+
+    // 非首分片不包含传输层头
+    let fragment_offset =
+        u16::from_be_bytes([bytes[6], bytes[7]]) & 0x1FFF;
+    if fragment_offset == 0 {
+        decode_transport(decoded, protocol);
+    }
+}
+
+/// 解码 IPv6 头（不处理扩展头）
+fn decode_ipv6(decoded: &mut DecodedPacket<'_>) {
+    let bytes = decoded.payload;
+    if bytes.len() < 40 {
+        return;
+    }
+    let payload_len =
+        u16::from_be_bytes([bytes[4], bytes[5]]) as usize;
+    let next_header = bytes[6];
+    let mut src = [0u8; 16];
+    let mut dst = [0u8; 16];
+    src.copy_from_slice(&bytes[8..24]);
+    dst.copy_from_slice(&bytes[24..40]);
+    let src = Ipv6Addr::from(src);
+    let dst = Ipv6Addr::from(dst);
+
+    decoded.layers.push(Layer::Ipv6 {
+        src,
+        dst,
+        next_header,
+    });
+    decoded.src_ip = Some(IpAddr::V6(src));
+    decoded.dst_ip = Some(IpAddr::V6(dst));
+    decoded.payload =
+        &bytes[40..(40 + payload_len).min(bytes.len())];
+
+    decode_transport(decoded, next_header);
+}
+
+/// 解码传输层协议
+fn decode_transport(
+    decoded: &mut DecodedPacket<'_>,
+    protocol: u8,
+) {
+    let bytes = decoded.payload;
+    match protocol {
+        IP_PROTO_TCP => {
+            if bytes.len() < 20 {
+                return;
+            }
+            let data_offset =
+                ((bytes[12] >> 4) as usize) * 4;
+            if data_offset < 20 || bytes.len() < data_offset
+            {
+                return;
+            }
+            decoded.layers.push(Layer::Tcp(TcpHeader {
+                src_port: u16::from_be_bytes([
+                    bytes[0], bytes[1],
+                ]),
+                dst_port: u16::from_be_bytes([
+                    bytes[2], bytes[3],
+                ]),
+                seq: u32::from_be_bytes([
+                    bytes[4], bytes[5], bytes[6], bytes[7],
+                ]),
+                ack: u32::from_be_bytes([
+                    bytes[8], bytes[9], bytes[10],
+                    bytes[11],
+                ]),
+                flags: bytes[13],
+            }));
+            decoded.payload = &bytes[data_offset..];
+        }
+        IP_PROTO_UDP => {
+            if bytes.len() < 8 {
+                return;
+            }
+            let src_port =
+                u16::from_be_bytes([bytes[0], bytes[1]]);
+            let dst_port =
+                u16::from_be_bytes([bytes[2], bytes[3]]);
+            decoded
+                .layers
+                .push(Layer::Udp { src_port, dst_port });
+            decoded.payload = &bytes[8..];
+
+            if (src_port == DNS_PORT
+                || dst_port == DNS_PORT)
+                && decoded.payload.len() >= 12
+            {
+                decoded.layers.push(Layer::Dns);
+                decoded.payload = &[];
+            }
+        }
+        IP_PROTO_ICMP => {
+            decoded.layers.push(Layer::Icmp);
+            decoded.payload = &[];
+        }
+        IP_PROTO_ICMPV6 => {
+            decoded.layers.push(Layer::Icmpv6);
+            decoded.payload = &[];
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,118 @@
+//! 协议层级统计
+
+use crate::core::protocol::decoder::Layer;
+
+/// 没有解码出任何协议层（负载为空）的数据包归入的顶层节点
+const EMPTY_NODE: &str = "Raw/Empty";
+
+/// 协议层级树节点
+#[derive(Debug, Clone)]
+pub struct ProtocolNode {
+    pub name: &'static str,
+    pub packets: usize,
+    pub bytes: u64,
+    pub children: Vec<ProtocolNode>,
+}
+
+impl ProtocolNode {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            packets: 0,
+            bytes: 0,
+            children: Vec::new(),
+        }
+    }
+
+    /// 获取或创建子节点
+    fn child_mut(
+        &mut self,
+        name: &'static str,
+    ) -> &mut ProtocolNode {
+        let index = match self
+            .children
+            .iter()
+            .position(|child| child.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.children.push(ProtocolNode::new(name));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index]
+    }
+
+    /// 按数据包数量降序排序（递归）
+    fn sort(&mut self) {
+        self.children.sort_by_key(|child| {
+            std::cmp::Reverse(child.packets)
+        });
+        for child in &mut self.children {
+            child.sort();
+        }
+    }
+}
+
+/// 协议层级统计：按解码出的协议栈累计数据包数与字节数
+#[derive(Debug, Clone)]
+pub struct ProtocolHierarchy {
+    root: ProtocolNode,
+}
+
+impl ProtocolHierarchy {
+    /// 创建空的统计
+    pub fn new() -> Self {
+        Self {
+            root: ProtocolNode::new("Frame"),
+        }
+    }
+
+    /// 累计一个数据包的协议栈
+    pub fn add(
+        &mut self,
+        layers: &[Layer],
+        packet_len: usize,
+    ) {
+        let bytes = packet_len as u64;
+        self.root.packets += 1;
+        self.root.bytes += bytes;
+
+        // 使各顶层节点的数据包数之和等于总数
+        if layers.is_empty() {
+            let node = self.root.child_mut(EMPTY_NODE);
+            node.packets += 1;
+            node.bytes += bytes;
+            return;
+        }
+
+        let mut node = &mut self.root;
+        for layer in layers {
+            node = node.child_mut(layer.name());
+            node.packets += 1;
+            node.bytes += bytes;
+        }
+    }
+
+    /// 数据包总数
+    pub fn total_packets(&self) -> usize {
+        self.root.packets
+    }
+
+    /// 字节总数
+    pub fn total_bytes(&self) -> u64 {
+        self.root.bytes
+    }
+
+    /// 完成统计，返回按数据包数排序的顶层协议节点
+    pub fn finish(mut self) -> Vec<ProtocolNode> {
+        self.root.sort();
+        self.root.children
+    }
+}
+
+impl Default for ProtocolHierarchy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,5 @@
+//! 协议解码模块
+
+pub mod decoder;
+pub mod hierarchy;
+pub mod tcp_stream;
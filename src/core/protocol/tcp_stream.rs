@@ -0,0 +1,294 @@
+//! TCP 会话流重组（Follow TCP Stream）
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::IpAddr;
+
+use crate::core::protocol::decoder::{
+    DecodedPacket, TcpHeader,
+};
+
+/// TCP 会话端点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoint {
+    pub ip: IpAddr,
+    pub port: u16,
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self.ip {
+            IpAddr::V4(ip) => {
+                write!(f, "{}:{}", ip, self.port)
+            }
+            IpAddr::V6(ip) => {
+                write!(f, "[{}]:{}", ip, self.port)
+            }
+        }
+    }
+}
+
+/// TCP 会话四元组（client 为首个数据包的发送方）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamKey {
+    pub client: Endpoint,
+    pub server: Endpoint,
+}
+
+impl StreamKey {
+    /// 从解码结果提取四元组
+    pub fn from_decoded(
+        decoded: &DecodedPacket<'_>,
+    ) -> Option<Self> {
+        let tcp = decoded.tcp()?;
+        Some(Self {
+            client: Endpoint {
+                ip: decoded.src_ip?,
+                port: tcp.src_port,
+            },
+            server: Endpoint {
+                ip: decoded.dst_ip?,
+                port: tcp.dst_port,
+            },
+        })
+    }
+
+    /// 判断数据包所属方向，不属于该会话时返回 None
+    pub fn direction_of(
+        &self,
+        decoded: &DecodedPacket<'_>,
+    ) -> Option<Direction> {
+        let key = Self::from_decoded(decoded)?;
+        if key == *self {
+            Some(Direction::ClientToServer)
+        } else if key.client == self.server
+            && key.server == self.client
+        {
+            Some(Direction::ServerToClient)
+        } else {
+            None
+        }
+    }
+}
+
+/// 数据流方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+/// 重组后的一段连续数据
+#[derive(Debug, Clone)]
+pub struct StreamChunk {
+    pub direction: Direction,
+    /// 该段之前缺失（未捕获）的字节数，0 表示与前面的数据连续
+    pub missing: u64,
+    pub data: Vec<u8>,
+}
+
+/// 单方向的序列号重组状态。分段按相对首个序列号的流偏移排序，
+/// 因此序列号回绕不影响顺序
+#[derive(Debug, Default)]
+struct HalfStream {
+    next_seq: Option<u32>,
+    delivered: i64, // 已交付数据的流偏移
+    pending: BTreeMap<i64, Vec<u8>>,
+}
+
+impl HalfStream {
+    /// 接收一个分段，返回按序可交付的数据
+    fn accept(
+        &mut self,
+        tcp: &TcpHeader,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        // SYN 占用一个序列号
+        let data_seq = if tcp.flags & TcpHeader::SYN != 0 {
+            tcp.seq.wrapping_add(1)
+        } else {
+            tcp.seq
+        };
+        let next_seq =
+            *self.next_seq.get_or_insert(data_seq);
+
+        if !payload.is_empty() {
+            // 丢弃完全重传的分段，部分重叠的前缀在交付时截掉；
+            // 起点相同的分段保留较长的一个
+            let start = self.delivered
+                + data_seq.wrapping_sub(next_seq) as i32
+                    as i64;
+            if start + payload.len() as i64 > self.delivered
+            {
+                let data =
+                    self.pending.entry(start).or_default();
+                if payload.len() > data.len() {
+                    *data = payload.to_vec();
+                }
+            }
+        }
+
+        self.drain()
+    }
+
+    /// 交付从已交付位置开始的所有连续分段：起点不晚于该位置的分段
+    /// 只交付超出已交付数据的部分，完全被覆盖的直接丢弃
+    fn drain(&mut self) -> Vec<u8> {
+        let mut output = Vec::new();
+        while let Some(entry) = self.pending.first_entry() {
+            if *entry.key() > self.delivered {
+                break;
+            }
+            let (start, data) = entry.remove_entry();
+            self.deliver(start, &data, &mut output);
+        }
+        output
+    }
+
+    /// 交付剩余的全部分段（会话结束时），返回各段数据及其之前缺失的字节数
+    fn flush(&mut self) -> Vec<(u64, Vec<u8>)> {
+        let mut pieces: Vec<(u64, Vec<u8>)> = Vec::new();
+        while let Some((start, data)) =
+            self.pending.pop_first()
+        {
+            let missing = (start - self.delivered).max(0);
+            self.advance(missing);
+            match pieces.last_mut() {
+                Some((_, output)) if missing == 0 => {
+                    self.deliver(start, &data, output)
+                }
+                _ => {
+                    let mut output = Vec::new();
+                    self.deliver(start, &data, &mut output);
+                    pieces.push((missing as u64, output));
+                }
+            }
+        }
+        pieces
+    }
+
+    /// 把起点不晚于已交付位置的分段中超出已交付数据的部分追加到 `output`
+    fn deliver(
+        &mut self,
+        start: i64,
+        data: &[u8],
+        output: &mut Vec<u8>,
+    ) {
+        let behind = (self.delivered - start) as usize;
+        if behind < data.len() {
+            output.extend_from_slice(&data[behind..]);
+            self.advance((data.len() - behind) as i64);
+        }
+    }
+
+    /// 已交付位置前进 `len` 字节
+    fn advance(&mut self, len: i64) {
+        self.delivered += len;
+        if let Some(next_seq) = &mut self.next_seq {
+            *next_seq = next_seq.wrapping_add(len as u32);
+        }
+    }
+}
+
+/// TCP 会话重组器：按捕获顺序输入数据包，输出按方向分段的数据
+#[derive(Debug)]
+pub struct StreamReassembler {
+    key: StreamKey,
+    client: HalfStream,
+    server: HalfStream,
+    chunks: Vec<StreamChunk>,
+    packet_count: usize,
+}
+
+impl StreamReassembler {
+    /// 创建指定会话的重组器
+    pub fn new(key: StreamKey) -> Self {
+        Self {
+            key,
+            client: HalfStream::default(),
+            server: HalfStream::default(),
+            chunks: Vec::new(),
+            packet_count: 0,
+        }
+    }
+
+    /// 输入一个已解码的数据包（不属于该会话的会被忽略）
+    pub fn push(&mut self, decoded: &DecodedPacket<'_>) {
+        let Some(direction) =
+            self.key.direction_of(decoded)
+        else {
+            return;
+        };
+        let Some(tcp) = decoded.tcp() else {
+            return;
+        };
+        self.packet_count += 1;
+
+        let half = match direction {
+            Direction::ClientToServer => &mut self.client,
+            Direction::ServerToClient => &mut self.server,
+        };
+        let data = half.accept(tcp, decoded.payload);
+        self.append(direction, 0, data);
+    }
+
+    /// 追加一段数据：相同方向的连续数据合并为一段
+    fn append(
+        &mut self,
+        direction: Direction,
+        missing: u64,
+        data: Vec<u8>,
+    ) {
+        if data.is_empty() {
+            return;
+        }
+        match self.chunks.last_mut() {
+            Some(last)
+                if last.direction == direction
+                    && missing == 0 =>
+            {
+                last.data.extend_from_slice(&data);
+            }
+            _ => self.chunks.push(StreamChunk {
+                direction,
+                missing,
+                data,
+            }),
+        }
+    }
+
+    /// 会话四元组
+    pub fn key(&self) -> &StreamKey {
+        &self.key
+    }
+
+    /// 属于该会话的数据包数量
+    pub fn packet_count(&self) -> usize {
+        self.packet_count
+    }
+
+    /// 完成重组，返回数据段列表。缺口之后仍未交付的数据附在末尾，
+    /// 以 `missing` 标出缺失的字节数
+    pub fn finish(mut self) -> Vec<StreamChunk> {
+        for direction in [
+            Direction::ClientToServer,
+            Direction::ServerToClient,
+        ] {
+            let half = match direction {
+                Direction::ClientToServer => {
+                    &mut self.client
+                }
+                Direction::ServerToClient => {
+                    &mut self.server
+                }
+            };
+            for (missing, data) in half.flush() {
+                self.append(direction, missing, data);
+            }
+        }
+        self.chunks
+    }
+}
@@ -0,0 +1,216 @@
+//! 偏移范围与包序号列表的统一语法，供命令行参数与查看器共用
+//!
+//! 偏移范围：`0x1000-0x2000`（不含结束）、`0x1000-`（到文件末尾）、
+//! `-0x2000`（从文件开头），也接受 `0x1000..0x2000` 与 `0x1000+0x100`；
+//! `0x` 前缀为十六进制，否则为十进制。
+//! 包序号列表：`5-10,42,100-`（从 1 开始，区间包含两端，`100-` 到最后一个数据包）。
+
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+
+use crate::app::error::types::PcapViewerError;
+
+/// 解析偏移：`0x` 前缀为十六进制，否则为十进制
+pub fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+    {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// 字节偏移范围（不含结束，结束省略时到文件末尾）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetRange {
+    start: usize,
+    end: Option<usize>,
+}
+
+impl OffsetRange {
+    /// 按文件长度得到具体范围，为空或超出文件时为 None
+    pub fn resolve(
+        &self,
+        len: usize,
+    ) -> Option<Range<usize>> {
+        let end = self.end.unwrap_or(len);
+        (self.start < end && end <= len)
+            .then_some(self.start..end)
+    }
+
+    /// 截到文件长度以内的范围（可能为空）
+    pub fn clamp(&self, len: usize) -> Range<usize> {
+        let end = self.end.unwrap_or(len).min(len);
+        self.start.min(end)..end
+    }
+}
+
+impl FromStr for OffsetRange {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid offset range: {}",
+                text
+            ))
+        };
+        let text = text.trim();
+        let (start, end) = if let Some((start, end)) =
+            text.split_once("..")
+        {
+            (parse_offset(start), parse_offset(end))
+        } else if let Some((start, len)) =
+            text.split_once('+')
+        {
+            let start = parse_offset(start);
+            let end =
+                start.zip(parse_offset(len)).and_then(
+                    |(start, len)| start.checked_add(len),
+                );
+            (start, end)
+        } else {
+            let (start, end) =
+                text.split_once('-').ok_or_else(invalid)?;
+            let start = if start.trim().is_empty() {
+                Some(0)
+            } else {
+                parse_offset(start)
+            };
+            if end.trim().is_empty() {
+                let start = start.ok_or_else(invalid)?;
+                return Ok(Self { start, end: None });
+            }
+            (start, parse_offset(end))
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start < end => {
+                Ok(Self {
+                    start,
+                    end: Some(end),
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for OffsetRange {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self.end {
+            Some(end) => {
+                write!(f, "0x{:X}-0x{:X}", self.start, end)
+            }
+            None => write!(f, "0x{:X}-", self.start),
+        }
+    }
+}
+
+/// 包序号列表，如 `10-20,35,100-`（从 1 开始，区间包含两端）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketList {
+    ranges: Vec<RangeInclusive<usize>>,
+}
+
+impl PacketList {
+    /// 是否包含下标为 `index`（从 0 开始）的数据包
+    pub fn contains(&self, index: usize) -> bool {
+        self.ranges
+            .iter()
+            .any(|range| range.contains(&(index + 1)))
+    }
+
+    /// 第一个区间的起止下标（从 0 开始，开放的结束为 None）
+    pub fn first(&self) -> Option<(usize, Option<usize>)> {
+        self.ranges.first().map(|range| {
+            (
+                range.start() - 1,
+                (*range.end() != usize::MAX)
+                    .then(|| range.end() - 1),
+            )
+        })
+    }
+}
+
+impl FromStr for PacketList {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid packet list: {}",
+                text
+            ))
+        };
+        let number = |text: &str| {
+            text.trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|&number| number > 0)
+                .ok_or_else(invalid)
+        };
+
+        let ranges = text
+            .split(',')
+            .map(|item| {
+                let (start, end) =
+                    match item.split_once('-') {
+                        // `100-` 到最后一个数据包
+                        Some((start, end))
+                            if end.trim().is_empty() =>
+                        {
+                            (number(start)?, usize::MAX)
+                        }
+                        Some((start, end)) => {
+                            (number(start)?, number(end)?)
+                        }
+                        None => {
+                            let number = number(item)?;
+                            (number, number)
+                        }
+                    };
+                if start > end {
+                    return Err(invalid());
+                }
+                Ok(start..=end)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Self { ranges })
+    }
+}
+
+impl fmt::Display for PacketList {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let items: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|range| {
+                if range.start() == range.end() {
+                    range.start().to_string()
+                } else if *range.end() == usize::MAX {
+                    format!("{}-", range.start())
+                } else {
+                    format!(
+                        "{}-{}",
+                        range.start(),
+                        range.end()
+                    )
+                }
+            })
+            .collect();
+        f.write_str(&items.join(","))
+    }
+}
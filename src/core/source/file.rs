@@ -0,0 +1,63 @@
+//! 本地文件数据源
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::app::error::types::Result;
+use crate::core::source::DataSource;
+
+/// 本地文件数据源
+pub struct FileSource {
+    name: String,
+    file: Mutex<File>,
+    len: u64,
+}
+
+impl FileSource {
+    /// 打开本地文件
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            name: path.display().to_string(),
+            file: Mutex::new(file),
+            len,
+        })
+    }
+}
+
+impl DataSource for FileSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    fn read_at(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        file.seek(SeekFrom::Start(offset))?;
+
+        // 循环读取直到填满缓冲区或到达文件末尾
+        let mut total = 0;
+        while total < buf.len() {
+            let read = file.read(&mut buf[total..])?;
+            if read == 0 {
+                break;
+            }
+            total += read;
+        }
+        Ok(total)
+    }
+}
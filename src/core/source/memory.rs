@@ -0,0 +1,92 @@
+//! 内存数据源
+
+use crate::app::error::types::Result;
+use crate::core::source::DataSource;
+
+/// 已在内存中的数据（如下载完成的内容或测试数据）
+pub struct MemorySource {
+    name: String,
+    data: Vec<u8>,
+}
+
+impl MemorySource {
+    /// 以给定名称包装内存中的数据
+    pub fn new(
+        name: impl Into<String>,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data,
+        }
+    }
+}
+
+impl DataSource for MemorySource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    fn read_at(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let rest = self
+            .data
+            .get(offset as usize..)
+            .unwrap_or_default();
+        let len = rest.len().min(buf.len());
+        buf[..len].copy_from_slice(&rest[..len]);
+        Ok(len)
+    }
+
+    fn read_all(
+        &self,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<Vec<u8>> {
+        progress(self.len(), self.len());
+        Ok(self.data.clone())
+    }
+}
+
+/// 借用的内存数据（如查看器中已读入的文件内容）
+pub struct SliceSource<'a> {
+    name: &'a str,
+    data: &'a [u8],
+}
+
+impl<'a> SliceSource<'a> {
+    /// 以给定名称包装借用的数据
+    pub fn new(name: &'a str, data: &'a [u8]) -> Self {
+        Self { name, data }
+    }
+}
+
+impl DataSource for SliceSource<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    fn read_at(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let rest = self
+            .data
+            .get(offset as usize..)
+            .unwrap_or_default();
+        let len = rest.len().min(buf.len());
+        buf[..len].copy_from_slice(&rest[..len]);
+        Ok(len)
+    }
+}
@@ -0,0 +1,62 @@
+//! 内存映射的本地文件数据源
+//!
+//! 只映射不读取，访问到的页面才由系统从磁盘载入。
+//! 映射期间文件被其他进程截断时访问越界页面会导致进程崩溃（SIGBUS），
+//! 因此不用于跟随模式等文件仍在变化的场景。
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::app::error::types::Result;
+use crate::core::source::DataSource;
+
+/// 内存映射的本地文件
+pub struct MmapSource {
+    name: String,
+    map: Mmap,
+}
+
+impl MmapSource {
+    /// 以只读方式映射本地文件
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        // SAFETY: 只读映射；文件在映射期间被截断的风险见模块说明
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Self {
+            name: path.display().to_string(),
+            map,
+        })
+    }
+
+    /// 映射的全部内容
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl DataSource for MmapSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.map.len() as u64
+    }
+
+    fn read_at(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let rest = self
+            .map
+            .get(offset as usize..)
+            .unwrap_or_default();
+        let len = rest.len().min(buf.len());
+        buf[..len].copy_from_slice(&rest[..len]);
+        Ok(len)
+    }
+}
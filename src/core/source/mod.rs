@@ -0,0 +1,134 @@
+//! 数据源模块
+//!
+//! 通过 `DataSource` 统一本地文件、内存映射、内存数据与远程对象（HTTP/S3）的读取。
+//!
+//! 后台索引、查找、比较与远程加载按块调用 `read_at`；
+//! 查看器交互查看本地文件时也只按页读取可见的内容（见 `viewer::file_data`）。
+
+pub mod file;
+#[cfg(feature = "remote")]
+pub mod http;
+pub mod memory;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "remote")]
+pub mod s3;
+pub mod search;
+pub mod watch;
+
+use std::path::Path;
+
+use crate::app::error::types::Result;
+
+/// 分块读取时每次请求的字节数
+pub const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// 可按偏移读取的数据源
+pub trait DataSource {
+    /// 数据源描述（路径或 URL）
+    fn name(&self) -> &str;
+
+    /// 数据总长度（字节）
+    fn len(&self) -> u64;
+
+    /// 数据源是否为空
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 从指定偏移读取数据到缓冲区，返回实际读取的字节数
+    fn read_at(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize>;
+
+    /// 读取全部内容，每读完一块调用一次 `progress(已读取, 总长度)`
+    fn read_all(
+        &self,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<Vec<u8>> {
+        read_chunked(self, progress)
+    }
+}
+
+/// 按 `CHUNK_SIZE` 分块读取全部内容
+pub fn read_chunked<S: DataSource + ?Sized>(
+    source: &S,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<Vec<u8>> {
+    let total = source.len();
+    let mut data = vec![0u8; total as usize];
+    let mut offset = 0usize;
+    while offset < data.len() {
+        let end = (offset + CHUNK_SIZE).min(data.len());
+        let read = source.read_at(
+            offset as u64,
+            &mut data[offset..end],
+        )?;
+        if read == 0 {
+            break; // 数据源提前结束
+        }
+        offset += read;
+        progress(offset as u64, total);
+    }
+    data.truncate(offset);
+    Ok(data)
+}
+
+/// 路径是否指向不可定位的流（命名管道或字符设备）
+pub fn is_stream(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        std::fs::metadata(path).is_ok_and(|metadata| {
+            let file_type = metadata.file_type();
+            file_type.is_fifo()
+                || file_type.is_char_device()
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_str().is_some_and(|path| {
+            path.starts_with(r"\\.\pipe\")
+        })
+    }
+}
+
+/// 路径是否指向远程数据源（http://、https://、s3://）
+pub fn is_remote(path: &Path) -> bool {
+    path.to_str().is_some_and(|path| {
+        ["http://", "https://", "s3://"]
+            .iter()
+            .any(|scheme| path.starts_with(scheme))
+    })
+}
+
+/// 按路径或 URL 打开数据源
+pub fn open(path: &Path) -> Result<Box<dyn DataSource>> {
+    #[cfg(feature = "remote")]
+    if let Some(url) = path.to_str() {
+        if url.starts_with("s3://") {
+            return Ok(Box::new(s3::open(url)?));
+        }
+        if is_remote(path) {
+            return Ok(Box::new(http::HttpSource::open(
+                url,
+            )?));
+        }
+    }
+
+    #[cfg(not(feature = "remote"))]
+    if is_remote(path) {
+        return Err(crate::app::error::types::PcapViewerError::Network(
+            format!(
+                "Remote sources require the `remote` feature: {}",
+                path.display()
+            ),
+        )
+        .into());
+    }
+
+    Ok(Box::new(file::FileSource::open(path)?))
+}
@@ -0,0 +1,236 @@
+//! S3 数据源（s3://bucket/key）
+//!
+//! 通过 HTTPS Range 请求读取对象。设置了 `AWS_ACCESS_KEY_ID` /
+//! `AWS_SECRET_ACCESS_KEY`（可选 `AWS_SESSION_TOKEN`）时使用 SigV4
+//! 签名，否则以匿名方式访问。区域取自 `AWS_REGION` /
+//! `AWS_DEFAULT_REGION`，自定义端点取自 `AWS_ENDPOINT_URL`。
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::source::http::{
+    HttpSource, RequestSigner,
+};
+
+/// 空请求体的 SHA256
+const EMPTY_PAYLOAD_SHA256: &str =
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// 默认区域
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// 打开 s3:// URL
+pub fn open(url: &str) -> Result<HttpSource> {
+    let (bucket, key) = url
+        .strip_prefix("s3://")
+        .and_then(|rest| rest.split_once('/'))
+        .filter(|(bucket, key)| {
+            !bucket.is_empty() && !key.is_empty()
+        })
+        .ok_or_else(|| {
+            PcapViewerError::Network(format!(
+                "Invalid S3 URL (expected s3://bucket/key): {}",
+                url
+            ))
+        })?;
+
+    let region = env_var("AWS_REGION")
+        .or_else(|| env_var("AWS_DEFAULT_REGION"))
+        .unwrap_or_else(|| DEFAULT_REGION.to_string());
+    let key = encode_path(key);
+    let https_url = match env_var("AWS_ENDPOINT_URL") {
+        // 自定义端点（如 MinIO）使用路径风格
+        Some(endpoint) => format!(
+            "{}/{}/{}",
+            endpoint.trim_end_matches('/'),
+            bucket,
+            key
+        ),
+        None => format!(
+            "https://{}.s3.{}.amazonaws.com/{}",
+            bucket, region, key
+        ),
+    };
+
+    let signer = match (
+        env_var("AWS_ACCESS_KEY_ID"),
+        env_var("AWS_SECRET_ACCESS_KEY"),
+    ) {
+        (Some(access_key), Some(secret_key)) => {
+            Some(Box::new(SigV4Signer {
+                access_key,
+                secret_key,
+                session_token: env_var("AWS_SESSION_TOKEN"),
+                region,
+            }) as Box<dyn RequestSigner>)
+        }
+        _ => None,
+    };
+
+    HttpSource::with_signer(&https_url, signer)
+}
+
+/// AWS SigV4 请求签名器
+struct SigV4Signer {
+    access_key: String,
+    secret_key: String,
+    session_token: Option<String>,
+    region: String,
+}
+
+impl RequestSigner for SigV4Signer {
+    fn sign(
+        &self,
+        method: &str,
+        url: &str,
+        range: Option<&str>,
+    ) -> Result<Vec<(String, String)>> {
+        let amz_date =
+            Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        Ok(self.sign_at(method, url, range, &amz_date))
+    }
+}
+
+impl SigV4Signer {
+    /// 按指定时间生成签名请求头
+    fn sign_at(
+        &self,
+        method: &str,
+        url: &str,
+        range: Option<&str>,
+        amz_date: &str,
+    ) -> Vec<(String, String)> {
+        let without_scheme = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest);
+        let (host, path) = without_scheme
+            .find('/')
+            .map_or((without_scheme, "/"), |i| {
+                without_scheme.split_at(i)
+            });
+        let date = &amz_date[..8];
+
+        // 参与签名的请求头（按名称排序）
+        let mut headers = vec![("host", host.to_string())];
+        if let Some(range) = range {
+            headers.push(("range", range.to_string()));
+        }
+        headers.push((
+            "x-amz-content-sha256",
+            EMPTY_PAYLOAD_SHA256.to_string(),
+        ));
+        headers.push(("x-amz-date", amz_date.to_string()));
+        if let Some(token) = &self.session_token {
+            headers.push((
+                "x-amz-security-token",
+                token.clone(),
+            ));
+        }
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| {
+                format!("{}:{}\n", name, value)
+            })
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method,
+            path,
+            canonical_headers,
+            signed_headers,
+            EMPTY_PAYLOAD_SHA256
+        );
+
+        let scope = format!(
+            "{}/{}/s3/aws4_request",
+            date, self.region
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            to_hex(&Sha256::digest(canonical_request))
+        );
+
+        let signing_key = [
+            self.region.as_bytes(),
+            b"s3",
+            b"aws4_request",
+        ]
+        .iter()
+        .fold(
+            hmac_sha256(
+                format!("AWS4{}", self.secret_key)
+                    .as_bytes(),
+                date.as_bytes(),
+            ),
+            |key, part| hmac_sha256(&key, part),
+        );
+        let signature = to_hex(&hmac_sha256(
+            &signing_key,
+            string_to_sign.as_bytes(),
+        ));
+
+        // Host 与 Range 由 HTTP 数据源自行设置
+        let mut result: Vec<(String, String)> = headers
+            .into_iter()
+            .filter(|(name, _)| {
+                *name != "host" && *name != "range"
+            })
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        result.push((
+            "Authorization".to_string(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{},SignedHeaders={},Signature={}",
+                self.access_key, scope, signed_headers, signature
+            ),
+        ));
+        result
+    }
+}
+
+/// 读取非空环境变量
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+/// 对象键按路径段进行 URI 编码（保留 `/`）
+fn encode_path(key: &str) -> String {
+    key.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'/' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// HMAC-SHA256
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 字节序列转为小写十六进制字符串
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
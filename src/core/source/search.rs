@@ -0,0 +1,231 @@
+//! 分块查找：按 `CHUNK_SIZE` 逐块读取数据源并查找，不需要整个文件在内存中
+//!
+//! 相邻块之间保留重叠部分，跨越块边界的匹配也能找到（正则表达式的匹配
+//! 长度超过 `REGEX_OVERLAP` 时可能漏掉）。每次调用 [`Search::step`] 只处理一块，
+//! 调用方可以在块之间更新进度或取消查找。
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use regex::bytes::Regex;
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::source::{DataSource, CHUNK_SIZE};
+use crate::core::viewer::highlight::parse_hex_pattern;
+
+/// 正则表达式查找时相邻块的重叠字节数
+pub const REGEX_OVERLAP: usize = 64 * 1024;
+
+/// 查找模式：`DE AD BE EF`（十六进制）、`"text"`（文本）或 `re:<正则>`
+#[derive(Debug, Clone)]
+pub struct SearchPattern {
+    text: String,
+    regex: Regex,
+    /// 匹配的最大长度（正则表达式为 None）
+    max_len: Option<usize>,
+}
+
+impl SearchPattern {
+    /// 相邻块需要重叠的字节数
+    fn overlap(&self) -> usize {
+        self.max_len.map_or(REGEX_OVERLAP, |len| len - 1)
+    }
+
+    /// 在缓冲区中查找起始位置早于 `limit` 的第一个非空匹配
+    fn find(
+        &self,
+        buffer: &[u8],
+        limit: usize,
+    ) -> Option<Range<usize>> {
+        self.regex
+            .find_iter(buffer)
+            .take_while(|found| found.start() < limit)
+            .find(|found| !found.is_empty())
+            .map(|found| found.range())
+    }
+}
+
+impl FromStr for SearchPattern {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        text: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let invalid = |reason: String| {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid search pattern `{}`: {}",
+                text, reason
+            ))
+        };
+        let trimmed = text.trim();
+        let (source, max_len) = if let Some(pattern) =
+            trimmed.strip_prefix("re:")
+        {
+            (pattern.to_string(), None)
+        } else if let Some(literal) =
+            trimmed.strip_prefix('"').map(|rest| {
+                rest.strip_suffix('"').unwrap_or(rest)
+            })
+        {
+            (regex::escape(literal), Some(literal.len()))
+        } else {
+            let bytes =
+                parse_hex_pattern(trimmed).map_err(|_| {
+                    invalid(
+                        "expected hex bytes, \"text\" or re:<regex>"
+                            .to_string(),
+                    )
+                })?;
+            let escaped: String = bytes
+                .iter()
+                .map(|byte| format!("\\x{:02X}", byte))
+                .collect();
+            (
+                format!("(?-u:{})", escaped),
+                Some(bytes.len()),
+            )
+        };
+        if max_len == Some(0) {
+            return Err(invalid(
+                "empty pattern".to_string(),
+            ));
+        }
+        // 正则语法错误的说明有多行（含位置标记），只保留最后一行
+        let regex = Regex::new(&source).map_err(|e| {
+            invalid(
+                e.to_string()
+                    .lines()
+                    .last()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            )
+        })?;
+        Ok(Self {
+            text: trimmed.to_string(),
+            regex,
+            max_len,
+        })
+    }
+}
+
+impl fmt::Display for SearchPattern {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// 单步查找的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchStep {
+    /// 找到匹配的字节范围
+    Found(Range<u64>),
+    /// 本块没有匹配，还需继续
+    Pending,
+    /// 整个数据源都没有匹配
+    NotFound,
+}
+
+/// 进行中的查找：从起点向后查找到末尾，再从开头回到起点
+#[derive(Debug, Clone)]
+pub struct Search {
+    pattern: SearchPattern,
+    origin: u64,
+    /// 下一块的起始偏移（匹配必须从此处或之后开始）
+    position: u64,
+    /// 是否已从末尾回到开头
+    wrapped: bool,
+    /// 已查找的字节数
+    searched: u64,
+    buffer: Vec<u8>,
+}
+
+impl Search {
+    /// 从偏移 `origin` 开始查找
+    pub fn new(
+        pattern: SearchPattern,
+        origin: u64,
+    ) -> Self {
+        Self {
+            pattern,
+            origin,
+            position: origin,
+            wrapped: false,
+            searched: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// 查找模式
+    pub fn pattern(&self) -> &SearchPattern {
+        &self.pattern
+    }
+
+    /// 是否已从末尾回到开头继续查找
+    pub fn wrapped(&self) -> bool {
+        self.wrapped
+    }
+
+    /// 查找进度（0-100）
+    pub fn percent(&self, source: &dyn DataSource) -> u64 {
+        (self.searched * 100)
+            .checked_div(source.len())
+            .unwrap_or(100)
+            .min(100)
+    }
+
+    /// 读取并查找下一块
+    pub fn step(
+        &mut self,
+        source: &dyn DataSource,
+    ) -> Result<SearchStep> {
+        let len = source.len();
+        self.origin = self.origin.min(len);
+        let stop =
+            if self.wrapped { self.origin } else { len };
+        if self.position >= stop {
+            if self.wrapped || self.origin == 0 {
+                return Ok(SearchStep::NotFound);
+            }
+            self.wrapped = true;
+            self.position = 0;
+            return Ok(SearchStep::Pending);
+        }
+
+        let chunk_end =
+            (self.position + CHUNK_SIZE as u64).min(stop);
+        let read_end = (chunk_end
+            + self.pattern.overlap() as u64)
+            .min(len);
+        self.buffer
+            .resize((read_end - self.position) as usize, 0);
+        let mut filled = 0;
+        while filled < self.buffer.len() {
+            let read = source.read_at(
+                self.position + filled as u64,
+                &mut self.buffer[filled..],
+            )?;
+            if read == 0 {
+                break; // 数据源提前结束
+            }
+            filled += read;
+        }
+
+        let limit = (chunk_end - self.position) as usize;
+        if let Some(found) =
+            self.pattern.find(&self.buffer[..filled], limit)
+        {
+            let start = self.position + found.start as u64;
+            return Ok(SearchStep::Found(
+                start..self.position + found.end as u64,
+            ));
+        }
+        self.searched += chunk_end - self.position;
+        self.position = chunk_end;
+        Ok(SearchStep::Pending)
+    }
+}
@@ -0,0 +1,63 @@
+//! 监视目录：找出录制程序最近写入的抓包文件，供查看器在文件滚动时切换
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::app::error::types::Result;
+
+/// 被监视的目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchDir {
+    /// 目录路径
+    pub dir: PathBuf,
+    /// 抓包文件扩展名（不含点，不区分大小写）
+    pub extension: String,
+    /// 出现新文件时先询问，而不是自动切换
+    pub ask: bool,
+}
+
+impl WatchDir {
+    /// 目录中修改时间最新的抓包文件（修改时间相同时取文件名较大者），
+    /// 没有匹配的文件时为 None
+    pub fn newest(&self) -> Result<Option<PathBuf>> {
+        let mut newest: Option<(SystemTime, PathBuf)> =
+            None;
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if !self.matches(&path) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified()?;
+            let candidate = (modified, path);
+            if newest
+                .as_ref()
+                .is_none_or(|current| candidate > *current)
+            {
+                newest = Some(candidate);
+            }
+        }
+        Ok(newest.map(|(_, path)| path))
+    }
+
+    /// 是否为要监视的抓包文件（跳过隐藏文件）
+    fn matches(&self, path: &Path) -> bool {
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_none_or(|name| name.starts_with('.'));
+        !hidden
+            && path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| {
+                    extension.eq_ignore_ascii_case(
+                        &self.extension,
+                    )
+                })
+    }
+}
@@ -0,0 +1,150 @@
+//! 偏移注释及其旁路文件（`<文件>.notes.json`）持久化
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::error::types::{PcapViewerError, Result};
+
+/// 旁路文件格式版本
+const NOTES_VERSION: u32 = 1;
+
+/// 单条注释（旁路文件中的记录）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Note {
+    offset: usize,
+    note: String,
+}
+
+/// 旁路文件内容
+#[derive(Debug, Serialize, Deserialize)]
+struct NotesFile {
+    version: u32,
+    notes: Annotations,
+}
+
+/// 按偏移索引的注释集合（序列化为按偏移排序的注释列表）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Note>", into = "Vec<Note>")]
+pub struct Annotations {
+    notes: BTreeMap<usize, String>,
+}
+
+impl Annotations {
+    /// 创建空集合
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 数据文件对应的旁路文件路径（如 `a.pcap` → `a.pcap.notes.json`）
+    pub fn sidecar_path(file_path: &Path) -> PathBuf {
+        let mut name = file_path.as_os_str().to_owned();
+        name.push(".notes.json");
+        PathBuf::from(name)
+    }
+
+    /// 从旁路文件加载（文件不存在时返回空集合）
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let content = fs::read_to_string(path)?;
+        let file: NotesFile = serde_json::from_str(
+            &content,
+        )
+        .map_err(|e| {
+            PcapViewerError::InvalidFormat(format!(
+                "Invalid notes file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(file.notes)
+    }
+
+    /// 保存到旁路文件（没有注释时删除旁路文件）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if self.notes.is_empty() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+
+        let file = NotesFile {
+            version: NOTES_VERSION,
+            notes: self.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| {
+            PcapViewerError::InvalidFormat(e.to_string())
+        })?;
+        fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    /// 设置注释（内容为空时删除），返回是否有变化
+    pub fn set(
+        &mut self,
+        offset: usize,
+        note: &str,
+    ) -> bool {
+        let note = note.trim();
+        if note.is_empty() {
+            return self.notes.remove(&offset).is_some();
+        }
+        self.notes
+            .insert(offset, note.to_string())
+            .as_deref()
+            != Some(note)
+    }
+
+    /// 指定偏移的注释
+    pub fn get(&self, offset: usize) -> Option<&str> {
+        self.notes.get(&offset).map(String::as_str)
+    }
+
+    /// 指定范围内的注释（按偏移排序）
+    pub fn in_range(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (usize, &str)> {
+        self.notes
+            .range(range)
+            .map(|(&offset, note)| (offset, note.as_str()))
+    }
+
+    /// 注释数量
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// 是否没有注释
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
+impl From<Vec<Note>> for Annotations {
+    fn from(notes: Vec<Note>) -> Self {
+        Self {
+            notes: notes
+                .into_iter()
+                .map(|note| (note.offset, note.note))
+                .collect(),
+        }
+    }
+}
+
+impl From<Annotations> for Vec<Note> {
+    fn from(annotations: Annotations) -> Self {
+        annotations
+            .notes
+            .into_iter()
+            .map(|(offset, note)| Note { offset, note })
+            .collect()
+    }
+}
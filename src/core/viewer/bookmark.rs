@@ -0,0 +1,80 @@
+//! 书签（按字节偏移标记的位置）
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// 按偏移排序的书签集合
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    offsets: BTreeSet<usize>,
+}
+
+impl Bookmarks {
+    /// 创建空集合
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 切换指定偏移的书签，返回切换后是否存在书签
+    pub fn toggle(&mut self, offset: usize) -> bool {
+        if self.offsets.remove(&offset) {
+            return false;
+        }
+        self.offsets.insert(offset);
+        true
+    }
+
+    /// `offset` 之后的下一个书签（到末尾时回到第一个）
+    pub fn next_after(
+        &self,
+        offset: usize,
+    ) -> Option<usize> {
+        self.offsets
+            .range(offset + 1..)
+            .next()
+            .or_else(|| self.offsets.first())
+            .copied()
+    }
+
+    /// `offset` 之前的上一个书签（到开头时回到最后一个）
+    pub fn prev_before(
+        &self,
+        offset: usize,
+    ) -> Option<usize> {
+        self.offsets
+            .range(..offset)
+            .next_back()
+            .or_else(|| self.offsets.last())
+            .copied()
+    }
+
+    /// 指定范围内是否有书签
+    pub fn any_in(&self, range: Range<usize>) -> bool {
+        self.offsets.range(range).next().is_some()
+    }
+
+    /// 按偏移排序遍历所有书签
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.offsets.iter().copied()
+    }
+
+    /// 书签数量
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// 是否没有书签
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+impl FromIterator<usize> for Bookmarks {
+    fn from_iter<I: IntoIterator<Item = usize>>(
+        iter: I,
+    ) -> Self {
+        Self {
+            offsets: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! 剪贴板后端：OSC 52 转义序列（由终端写入剪贴板，SSH 会话中同样有效）、
+//! 经 tmux 透传的 OSC 52，或把内容交给外部命令（如 `wl-copy`、`pbcopy`）
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::viewer::literal;
+
+/// 剪贴板后端
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ClipboardBackend {
+    /// 输出 OSC 52 转义序列，由终端写入剪贴板
+    #[default]
+    Osc52,
+    /// 包在 tmux 透传序列中的 OSC 52
+    /// （tmux 需 `set -g allow-passthrough on`）
+    Tmux,
+    /// 通过标准输入交给外部命令（程序名与参数以空白分隔）
+    Command(String),
+}
+
+impl ClipboardBackend {
+    /// 写入剪贴板的转义序列（外部命令后端为 None）
+    pub fn escape_sequence(
+        &self,
+        text: &str,
+    ) -> Option<String> {
+        let osc52 = format!(
+            "\x1B]52;c;{}\x07",
+            literal::base64(text.as_bytes())
+        );
+        match self {
+            Self::Osc52 => Some(osc52),
+            // 透传内容中的 ESC 需要加倍
+            Self::Tmux => Some(format!(
+                "\x1BPtmux;{}\x1B\\",
+                osc52.replace('\x1B', "\x1B\x1B")
+            )),
+            Self::Command(_) => None,
+        }
+    }
+}
+
+impl FromStr for ClipboardBackend {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        if let Some(command) = s.strip_prefix("command:") {
+            let command = command.trim();
+            if command.is_empty() {
+                return Err(
+                    "clipboard command must not be empty"
+                        .to_string(),
+                );
+            }
+            return Ok(Self::Command(command.to_string()));
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "osc52" => Ok(Self::Osc52),
+            "tmux" => Ok(Self::Tmux),
+            other => Err(format!(
+                "unknown clipboard backend `{}` (expected osc52, tmux or command:<program>)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ClipboardBackend {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Osc52 => f.write_str("osc52"),
+            Self::Tmux => f.write_str("tmux"),
+            Self::Command(command) => {
+                write!(f, "command:{}", command)
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! 十六进制视图的列布局：显示哪些列、顺序与宽度
+//!
+//! 写法为逗号分隔的列名，可加 `:宽度` 固定列宽（超出截断、不足补空格），
+//! 如 `packet,offset,hex,ascii,info:60`。
+
+use std::fmt;
+use std::str::FromStr;
+
+/// 列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    /// 包序号与时间
+    Packet,
+    /// 地址偏移
+    Offset,
+    /// 十六进制数据
+    Hex,
+    /// ASCII 字符
+    Ascii,
+    /// 解析信息（文件头字段、数据包时间与长度等）
+    Info,
+    /// 与前一个数据包的时间差 Δt
+    Delta,
+}
+
+impl Column {
+    /// 所有列，按切换显示时插入的默认顺序排列
+    pub const ALL: [Column; 6] = [
+        Column::Packet,
+        Column::Offset,
+        Column::Hex,
+        Column::Ascii,
+        Column::Info,
+        Column::Delta,
+    ];
+
+    /// 列名
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Packet => "packet",
+            Column::Offset => "offset",
+            Column::Hex => "hex",
+            Column::Ascii => "ascii",
+            Column::Info => "info",
+            Column::Delta => "delta",
+        }
+    }
+
+    /// 在默认顺序中的位置
+    fn rank(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&column| column == self)
+            .unwrap_or(Self::ALL.len())
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|column| column.name() == name)
+            .ok_or_else(|| {
+                format!(
+                    "unknown column `{}` (expected packet, offset, hex, ascii, info or delta)",
+                    s.trim()
+                )
+            })
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 布局中的一列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColumnSpec {
+    pub column: Column,
+    pub width: Option<usize>, // 固定显示宽度（None 为内容本身的宽度）
+}
+
+/// 列布局
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnLayout {
+    columns: Vec<ColumnSpec>,
+}
+
+impl Default for ColumnLayout {
+    /// 地址偏移、十六进制数据与解析信息
+    fn default() -> Self {
+        Self {
+            columns: [
+                Column::Offset,
+                Column::Hex,
+                Column::Info,
+            ]
+            .into_iter()
+            .map(|column| ColumnSpec {
+                column,
+                width: None,
+            })
+            .collect(),
+        }
+    }
+}
+
+impl ColumnLayout {
+    /// 按显示顺序排列的列
+    pub fn columns(&self) -> &[ColumnSpec] {
+        &self.columns
+    }
+
+    /// 是否显示该列
+    pub fn contains(&self, column: Column) -> bool {
+        self.columns
+            .iter()
+            .any(|spec| spec.column == column)
+    }
+
+    /// 显示或隐藏一列，返回切换后是否显示；
+    /// 新显示的列插在默认顺序中排在它之后的第一列前面
+    pub fn toggle(&mut self, column: Column) -> bool {
+        if self.contains(column) {
+            self.columns
+                .retain(|spec| spec.column != column);
+            return false;
+        }
+        let index = self
+            .columns
+            .iter()
+            .position(|spec| {
+                spec.column.rank() > column.rank()
+            })
+            .unwrap_or(self.columns.len());
+        self.columns.insert(
+            index,
+            ColumnSpec {
+                column,
+                width: None,
+            },
+        );
+        true
+    }
+
+    /// 该列起始的显示列号；`width_of` 给出各列的宽度，
+    /// 排在前面的列宽度不确定或布局中没有该列时为 None
+    pub fn start_of(
+        &self,
+        column: Column,
+        width_of: impl Fn(&ColumnSpec) -> Option<usize>,
+    ) -> Option<usize> {
+        let mut start = 0;
+        for spec in &self.columns {
+            if spec.column == column {
+                return Some(start);
+            }
+            start += width_of(spec)?;
+        }
+        None
+    }
+}
+
+impl FromStr for ColumnLayout {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        let mut columns: Vec<ColumnSpec> = Vec::new();
+        for item in s.split(',').map(str::trim) {
+            let (name, width) = match item.split_once(':') {
+                Some((name, width)) => {
+                    let width = width
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|&width| width > 0)
+                        .ok_or_else(|| {
+                            format!(
+                                "invalid width in column `{}`",
+                                item
+                            )
+                        })?;
+                    (name, Some(width))
+                }
+                None => (item, None),
+            };
+            let column: Column = name.parse()?;
+            if columns
+                .iter()
+                .any(|spec| spec.column == column)
+            {
+                return Err(format!(
+                    "duplicate column `{}`",
+                    column
+                ));
+            }
+            columns.push(ColumnSpec { column, width });
+        }
+        Ok(Self { columns })
+    }
+}
+
+impl fmt::Display for ColumnLayout {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for (index, spec) in self.columns.iter().enumerate()
+        {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", spec.column)?;
+            if let Some(width) = spec.width {
+                write!(f, ":{}", width)?;
+            }
+        }
+        Ok(())
+    }
+}
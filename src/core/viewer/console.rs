@@ -0,0 +1,61 @@
+//! 控制台能力检测
+//!
+//! Windows 上先尝试启用虚拟终端处理（VT 序列）。cmd.exe 与旧版 ConHost
+//! 的亮色背景显示异常，这类控制台按 16 色着色，并跳过键盘增强协议的查询；
+//! 连 VT 序列都无法启用时不输出颜色。查看器本身不切换备用屏幕。
+
+use std::sync::OnceLock;
+
+use crate::core::viewer::palette::ColorDepth;
+
+/// 控制台能力
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Console {
+    /// 支持 ANSI 转义序列（颜色、光标移动）
+    pub ansi: bool,
+    /// 旧版 Windows 控制台（cmd.exe / ConHost）
+    pub legacy: bool,
+}
+
+impl Console {
+    /// 当前控制台的能力（只检测一次，Windows 上同时启用虚拟终端处理）
+    pub fn current() -> Self {
+        static CONSOLE: OnceLock<Console> = OnceLock::new();
+        *CONSOLE.get_or_init(Self::detect)
+    }
+
+    #[cfg(windows)]
+    fn detect() -> Self {
+        let ansi = crossterm::ansi_support::supports_ansi();
+        // Windows Terminal、ConEmu、mintty 等终端会设置其中之一
+        let modern = [
+            "WT_SESSION",
+            "ConEmuANSI",
+            "TERM_PROGRAM",
+            "TERM",
+        ]
+        .iter()
+        .any(|name| std::env::var_os(name).is_some());
+        Self {
+            ansi,
+            legacy: !modern,
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn detect() -> Self {
+        Self {
+            ansi: true,
+            legacy: false,
+        }
+    }
+
+    /// 适合该控制台的色深（旧版控制台固定为 16 色）
+    pub fn color_depth(&self) -> ColorDepth {
+        if self.legacy {
+            ColorDepth::Basic
+        } else {
+            ColorDepth::detect()
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! 两个文件的逐字节比较（按块读取数据源，不要求完整内容在内存中）
+
+use std::ops::Range;
+
+use crate::app::error::types::Result;
+use crate::core::source::{DataSource, CHUNK_SIZE};
+
+/// `from`（含）之后的第一个差异偏移
+pub fn next_difference(
+    a: &dyn DataSource,
+    b: &dyn DataSource,
+    from: usize,
+) -> Result<Option<usize>> {
+    let end = total_len(a, b);
+    let mut start = from;
+    while start < end {
+        let range = start..(start + CHUNK_SIZE).min(end);
+        let (x, y) = read_both(a, b, range.clone())?;
+        if let Some(index) = (0..range.len())
+            .find(|&index| differs(&x, &y, index))
+        {
+            return Ok(Some(start + index));
+        }
+        start = range.end;
+    }
+    Ok(None)
+}
+
+/// `before`（不含）之前的最后一个差异偏移
+pub fn prev_difference(
+    a: &dyn DataSource,
+    b: &dyn DataSource,
+    before: usize,
+) -> Result<Option<usize>> {
+    let mut end = before.min(total_len(a, b));
+    while end > 0 {
+        let range = end.saturating_sub(CHUNK_SIZE)..end;
+        let (x, y) = read_both(a, b, range.clone())?;
+        if let Some(index) = (0..range.len())
+            .rev()
+            .find(|&index| differs(&x, &y, index))
+        {
+            return Ok(Some(range.start + index));
+        }
+        end = range.start;
+    }
+    Ok(None)
+}
+
+/// 不同的字节总数（较长文件多出的部分全部计入）
+pub fn count_differences(
+    a: &dyn DataSource,
+    b: &dyn DataSource,
+) -> Result<usize> {
+    let end = total_len(a, b);
+    let mut count = 0;
+    for start in (0..end).step_by(CHUNK_SIZE) {
+        let range = start..(start + CHUNK_SIZE).min(end);
+        let (x, y) = read_both(a, b, range.clone())?;
+        count += (0..range.len())
+            .filter(|&index| differs(&x, &y, index))
+            .count();
+    }
+    Ok(count)
+}
+
+/// 两个数据源中较长者的长度
+fn total_len(
+    a: &dyn DataSource,
+    b: &dyn DataSource,
+) -> usize {
+    a.len().max(b.len()) as usize
+}
+
+/// 读取两边同一范围内的内容（到达末尾时较短）
+fn read_both(
+    a: &dyn DataSource,
+    b: &dyn DataSource,
+    range: Range<usize>,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let read =
+        |source: &dyn DataSource| -> Result<Vec<u8>> {
+            let mut chunk = vec![0; range.len()];
+            let read = source
+                .read_at(range.start as u64, &mut chunk)?;
+            chunk.truncate(read);
+            Ok(chunk)
+        };
+    Ok((read(a)?, read(b)?))
+}
+
+/// 块内相对偏移处两边的字节是否不同（只有一边有数据时也算不同）
+fn differs(a: &[u8], b: &[u8], index: usize) -> bool {
+    a.get(index) != b.get(index)
+}
@@ -0,0 +1,255 @@
+//! 十六进制编辑状态
+//!
+//! 记录光标位置、输入模式、被修改字节的原始值（用于高亮与保存）
+//! 以及撤销 / 重做历史。
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// 编辑输入模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputMode {
+    /// 输入十六进制数字，两次输入构成一个字节
+    #[default]
+    Hex,
+    /// 输入可打印 ASCII 字符
+    Ascii,
+}
+
+/// 单个字节的修改记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteChange {
+    pub offset: usize,
+    pub old: u8,
+    pub new: u8,
+}
+
+/// 编辑状态
+#[derive(Debug, Clone, Default)]
+pub struct EditState {
+    cursor: usize,
+    mode: InputMode,
+    low_nibble: bool, // 十六进制模式下是否正在输入低 4 位
+    original: BTreeMap<usize, u8>, // 被修改字节的原始值（上次保存时）
+    undo_stack: Vec<Vec<ByteChange>>, // 每项为一次操作（可包含多个字节）
+    redo_stack: Vec<Vec<ByteChange>>,
+}
+
+impl EditState {
+    /// 创建编辑状态
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 光标所在偏移
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// 移动光标到指定偏移（限制在数据范围内）
+    pub fn set_cursor(
+        &mut self,
+        offset: usize,
+        len: usize,
+    ) {
+        self.cursor = offset.min(len.saturating_sub(1));
+        self.low_nibble = false;
+    }
+
+    /// 相对移动光标
+    pub fn move_cursor(
+        &mut self,
+        delta: isize,
+        len: usize,
+    ) {
+        let target =
+            self.cursor.saturating_add_signed(delta);
+        self.set_cursor(target, len);
+    }
+
+    /// 当前输入模式
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    /// 切换十六进制 / ASCII 输入
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            InputMode::Hex => InputMode::Ascii,
+            InputMode::Ascii => InputMode::Hex,
+        };
+        self.low_nibble = false;
+    }
+
+    /// 十六进制模式下是否正在输入低 4 位
+    pub fn is_low_nibble(&self) -> bool {
+        self.low_nibble
+    }
+
+    /// 在光标处输入一个字符，返回产生的修改（字符无效时为 None）
+    pub fn input(
+        &mut self,
+        data: &mut [u8],
+        ch: char,
+    ) -> Option<ByteChange> {
+        let offset = self.cursor;
+        let old = *data.get(offset)?;
+
+        let new = match self.mode {
+            InputMode::Hex => {
+                let digit = ch.to_digit(16)? as u8;
+                if self.low_nibble {
+                    (old & 0xF0) | digit
+                } else {
+                    (old & 0x0F) | (digit << 4)
+                }
+            }
+            InputMode::Ascii => {
+                if !(ch.is_ascii_graphic() || ch == ' ') {
+                    return None;
+                }
+                ch as u8
+            }
+        };
+
+        self.apply(data, offset, new);
+        self.redo_stack.clear();
+        // 同一字节的低 4 位输入与高 4 位合并为一次撤销
+        match self.undo_stack.last_mut() {
+            Some(last)
+                if self.low_nibble
+                    && last.len() == 1
+                    && last[0].offset == offset =>
+            {
+                last[0].new = new;
+            }
+            _ => self.undo_stack.push(vec![ByteChange {
+                offset,
+                old,
+                new,
+            }]),
+        }
+
+        // 输入完整字节后光标前移
+        let advance = match self.mode {
+            InputMode::Hex => {
+                self.low_nibble = !self.low_nibble;
+                !self.low_nibble
+            }
+            InputMode::Ascii => true,
+        };
+        if advance && offset + 1 < data.len() {
+            self.cursor = offset + 1;
+        }
+
+        Some(ByteChange { offset, old, new })
+    }
+
+    /// 写入一个字节并记录原始值
+    pub fn apply(
+        &mut self,
+        data: &mut [u8],
+        offset: usize,
+        value: u8,
+    ) {
+        let Some(byte) = data.get_mut(offset) else {
+            return;
+        };
+        let original =
+            *self.original.entry(offset).or_insert(*byte);
+        *byte = value;
+        // 改回原值时不再视为已修改
+        if original == value {
+            self.original.remove(&offset);
+        }
+    }
+
+    /// 批量写入字节，作为一次可撤销的操作，返回实际修改的字节数
+    pub fn apply_batch(
+        &mut self,
+        data: &mut [u8],
+        values: impl IntoIterator<Item = (usize, u8)>,
+    ) -> usize {
+        let mut group = Vec::new();
+        for (offset, new) in values {
+            let Some(&old) = data.get(offset) else {
+                continue;
+            };
+            if old != new {
+                self.apply(data, offset, new);
+                group.push(ByteChange { offset, old, new });
+            }
+        }
+
+        let count = group.len();
+        if count > 0 {
+            self.undo_stack.push(group);
+            self.redo_stack.clear();
+        }
+        count
+    }
+
+    /// 撤销最近一次操作，返回其第一个字节的偏移
+    pub fn undo(
+        &mut self,
+        data: &mut [u8],
+    ) -> Option<usize> {
+        let group = self.undo_stack.pop()?;
+        for change in group.iter().rev() {
+            self.apply(data, change.offset, change.old);
+        }
+        let offset = group.first()?.offset;
+        self.redo_stack.push(group);
+        self.set_cursor(offset, data.len());
+        Some(offset)
+    }
+
+    /// 重做最近一次撤销的操作，返回其第一个字节的偏移
+    pub fn redo(
+        &mut self,
+        data: &mut [u8],
+    ) -> Option<usize> {
+        let group = self.redo_stack.pop()?;
+        for change in &group {
+            self.apply(data, change.offset, change.new);
+        }
+        let offset = group.first()?.offset;
+        self.undo_stack.push(group);
+        self.set_cursor(offset, data.len());
+        Some(offset)
+    }
+
+    /// 指定偏移是否已被修改
+    pub fn is_modified(&self, offset: usize) -> bool {
+        self.original.contains_key(&offset)
+    }
+
+    /// 是否存在未保存的修改
+    pub fn is_dirty(&self) -> bool {
+        !self.original.is_empty()
+    }
+
+    /// 已修改的字节数
+    pub fn modified_count(&self) -> usize {
+        self.original.len()
+    }
+
+    /// 合并相邻修改后的脏区间
+    pub fn dirty_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for &offset in self.original.keys() {
+            match ranges.last_mut() {
+                Some(range) if range.end == offset => {
+                    range.end = offset + 1;
+                }
+                _ => ranges.push(offset..offset + 1),
+            }
+        }
+        ranges
+    }
+
+    /// 保存后清除修改记录
+    pub fn mark_saved(&mut self) {
+        self.original.clear();
+    }
+}
@@ -0,0 +1,239 @@
+//! 查看器中的文件内容：读入内存的数据，或按需从数据源读取的分页数据
+//!
+//! 分页时查看器在绘制前用 `fetch` 经 `DataSource::read_at` 取回可见的一页，
+//! 只有编辑、保存、导出等需要完整内容的操作才用 `load` 读入整个文件。
+
+use std::ops::Range;
+
+use crate::app::error::types::Result;
+use crate::core::pcap::packet_ref::PacketRef;
+use crate::core::pcap::parser::{
+    PcapParser, FILE_HEADER_SIZE,
+};
+use crate::core::source::memory::SliceSource;
+use crate::core::source::DataSource;
+
+/// 文件内容
+pub enum FileData {
+    /// 完整读入内存
+    Memory(Vec<u8>),
+    /// 按需从数据源读取
+    Paged(PagedData),
+}
+
+/// 分页数据：只保留文件头与最近取回的一段连续内容
+pub struct PagedData {
+    source: Box<dyn DataSource + Send>,
+    len: usize,
+    head: Vec<u8>,
+    start: usize,
+    window: Vec<u8>,
+}
+
+impl FileData {
+    /// 按需从数据源读取（打开时只读取文件头）
+    pub fn paged(
+        source: Box<dyn DataSource + Send>,
+    ) -> Result<Self> {
+        let len = source.len() as usize;
+        let mut head = vec![0; FILE_HEADER_SIZE.min(len)];
+        let read = source.read_at(0, &mut head)?;
+        head.truncate(read);
+        Ok(FileData::Paged(PagedData {
+            source,
+            len,
+            head,
+            start: 0,
+            window: Vec::new(),
+        }))
+    }
+
+    /// 文件总长度
+    pub fn len(&self) -> usize {
+        match self {
+            FileData::Memory(data) => data.len(),
+            FileData::Paged(paged) => paged.len,
+        }
+    }
+
+    /// 文件是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 文件开头的字节（分页时只有文件头）
+    pub fn head(&self) -> &[u8] {
+        match self {
+            FileData::Memory(data) => data,
+            FileData::Paged(paged) => &paged.head,
+        }
+    }
+
+    /// 可直接访问的内容及其在文件中的起始偏移（分页时为最近取回的一段）
+    pub fn window(&self) -> (usize, &[u8]) {
+        match self {
+            FileData::Memory(data) => (0, data),
+            FileData::Paged(paged) => {
+                (paged.start, &paged.window)
+            }
+        }
+    }
+
+    /// 指定范围内的字节（须位于可直接访问的内容中）
+    pub fn get(
+        &self,
+        range: Range<usize>,
+    ) -> Option<&[u8]> {
+        let (start, data) = self.window();
+        data.get(
+            range.start.checked_sub(start)?
+                ..range.end.checked_sub(start)?,
+        )
+    }
+
+    /// 指定偏移处的字节（须位于可直接访问的内容中）
+    pub fn byte(&self, offset: usize) -> Option<u8> {
+        let (start, data) = self.window();
+        data.get(offset.checked_sub(start)?).copied()
+    }
+
+    /// 确保 `range` 内的内容可直接访问：分页时若不在已取回的一段中，
+    /// 则按该范围重新读取
+    pub fn fetch(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<()> {
+        let FileData::Paged(paged) = self else {
+            return Ok(());
+        };
+        let range = range.start.min(paged.len)
+            ..range.end.min(paged.len);
+        if range.start >= paged.start
+            && range.end <= paged.start + paged.window.len()
+        {
+            return Ok(());
+        }
+
+        let mut window = vec![0; range.len()];
+        let read = paged
+            .source
+            .read_at(range.start as u64, &mut window)?;
+        window.truncate(read);
+        paged.start = range.start;
+        paged.window = window;
+        Ok(())
+    }
+
+    /// 读入全部内容（之后不再分页），返回完整内容
+    pub fn load(&mut self) -> Result<&mut Vec<u8>> {
+        if let FileData::Paged(paged) = self {
+            let data =
+                paged.source.read_all(&mut |_, _| {})?;
+            *self = FileData::Memory(data);
+        }
+        match self {
+            FileData::Memory(data) => Ok(data),
+            FileData::Paged(_) => {
+                unreachable!("已读入内存")
+            }
+        }
+    }
+
+    /// 完整内容（分页数据尚未读入时为 None）
+    pub fn resident(&self) -> Option<&[u8]> {
+        match self {
+            FileData::Memory(data) => Some(data),
+            FileData::Paged(_) => None,
+        }
+    }
+
+    /// 完整内容的可变引用（分页数据尚未读入时为 None）
+    pub fn resident_mut(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            FileData::Memory(data) => Some(data),
+            FileData::Paged(_) => None,
+        }
+    }
+
+    /// 读取指定序号的数据包（包头与负载），返回其起始偏移与内容，
+    /// 可用 `PcapParser::packet_ref_in` 构造数据包视图
+    pub fn read_packet(
+        &self,
+        parser: &PcapParser,
+        index: usize,
+    ) -> Result<Option<(usize, Vec<u8>)>> {
+        let Some(packet) = parser.packet_ref(&[], index)
+        else {
+            return Ok(None);
+        };
+        let mut bytes =
+            vec![0; packet.end_offset() - packet.offset()];
+        let read = self
+            .read_at(packet.offset() as u64, &mut bytes)?;
+        bytes.truncate(read);
+        Ok(Some((packet.offset(), bytes)))
+    }
+
+    /// 指定序号的数据包视图（只含可直接访问的部分）
+    pub fn packet_ref<'a>(
+        &'a self,
+        parser: &'a PcapParser,
+        index: usize,
+    ) -> Option<PacketRef<'a>> {
+        let (start, data) = self.window();
+        parser.packet_ref_in(data, start, index)
+    }
+}
+
+impl DataSource for FileData {
+    fn name(&self) -> &str {
+        match self {
+            FileData::Memory(_) => "",
+            FileData::Paged(paged) => paged.source.name(),
+        }
+    }
+
+    fn len(&self) -> u64 {
+        FileData::len(self) as u64
+    }
+
+    fn read_at(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        match self {
+            FileData::Memory(data) => {
+                SliceSource::new("", data)
+                    .read_at(offset, buf)
+            }
+            FileData::Paged(paged) => {
+                paged.source.read_at(offset, buf)
+            }
+        }
+    }
+}
+
+impl Default for FileData {
+    fn default() -> Self {
+        FileData::Memory(Vec::new())
+    }
+}
+
+impl From<Vec<u8>> for FileData {
+    fn from(data: Vec<u8>) -> Self {
+        FileData::Memory(data)
+    }
+}
+
+impl Extend<u8> for FileData {
+    /// 追加数据（分页数据直接从数据源读取，不需要追加）
+    fn extend<I: IntoIterator<Item = u8>>(
+        &mut self,
+        iter: I,
+    ) {
+        if let FileData::Memory(data) = self {
+            data.extend(iter);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! 折叠：把连续的若干数据行收起为一个摘要行，便于对比相邻数据包的包头
+//!
+//! 数据行按每行字节数划分文件；显示行为屏幕上实际排列的行，
+//! 每个折叠区域只占一个显示行。
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// 显示行的内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Row {
+    Line(usize),        // 数据行
+    Fold(Range<usize>), // 折叠的数据行范围
+}
+
+/// 折叠的数据行范围（互不重叠）
+#[derive(Debug, Clone, Default)]
+pub struct Folds {
+    ranges: BTreeMap<usize, usize>, // 起始行 → 结束行（不含）
+}
+
+impl Folds {
+    /// 没有折叠
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 折叠区域数
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// 是否没有折叠
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// 折叠若干数据行；范围为空或与已有折叠重叠时忽略，返回是否折叠
+    pub fn fold(&mut self, lines: Range<usize>) -> bool {
+        if lines.is_empty()
+            || self
+                .ranges
+                .range(..lines.end)
+                .next_back()
+                .is_some_and(|(_, &end)| end > lines.start)
+        {
+            return false;
+        }
+        self.ranges.insert(lines.start, lines.end);
+        true
+    }
+
+    /// 包含该数据行的折叠
+    pub fn get(&self, line: usize) -> Option<Range<usize>> {
+        self.ranges
+            .range(..=line)
+            .next_back()
+            .filter(|(_, &end)| line < end)
+            .map(|(&start, &end)| start..end)
+    }
+
+    /// 展开包含该数据行的折叠，返回其范围
+    pub fn unfold(
+        &mut self,
+        line: usize,
+    ) -> Option<Range<usize>> {
+        let range = self.get(line)?;
+        self.ranges.remove(&range.start);
+        Some(range)
+    }
+
+    /// 展开所有折叠
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// 数据行所在的显示行（折叠内的数据行对应其摘要行）
+    pub fn row_of_line(&self, line: usize) -> usize {
+        let mut hidden = 0;
+        for (&start, &end) in self.ranges.range(..=line) {
+            if line < end {
+                return start - hidden;
+            }
+            hidden += end - start - 1;
+        }
+        line - hidden
+    }
+
+    /// 显示行开始处的数据行（摘要行为折叠的起始行）
+    pub fn line_of_row(&self, row: usize) -> usize {
+        let mut hidden = 0;
+        for (&start, &end) in &self.ranges {
+            if row <= start - hidden {
+                break;
+            }
+            hidden += end - start - 1;
+        }
+        row + hidden
+    }
+
+    /// 显示行的内容
+    pub fn row(&self, row: usize) -> Row {
+        let line = self.line_of_row(row);
+        match self.ranges.get(&line) {
+            Some(&end) => Row::Fold(line..end),
+            None => Row::Line(line),
+        }
+    }
+
+    /// 共 `total_lines` 个数据行时的显示行数
+    pub fn total_rows(&self, total_lines: usize) -> usize {
+        self.row_of_line(total_lines)
+    }
+}
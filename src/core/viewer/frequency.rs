@@ -0,0 +1,61 @@
+//! 字节值频率与香农熵，用于区分压缩/加密的负载与结构化数据
+
+/// 256 个字节值各自出现的次数
+#[derive(Debug, Clone)]
+pub struct ByteFrequency {
+    counts: [u64; 256],
+    total: u64,
+}
+
+impl ByteFrequency {
+    /// 统计数据中各字节值出现的次数
+    pub fn compute(data: &[u8]) -> Self {
+        let mut counts = [0u64; 256];
+        for &byte in data {
+            counts[byte as usize] += 1;
+        }
+        Self {
+            counts,
+            total: data.len() as u64,
+        }
+    }
+
+    /// 字节值出现的次数
+    pub fn count(&self, byte: u8) -> u64 {
+        self.counts[byte as usize]
+    }
+
+    /// 统计的总字节数
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// 出现次数最多的字节值的次数
+    pub fn max_count(&self) -> u64 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// 出现过的不同字节值个数
+    pub fn distinct(&self) -> usize {
+        self.counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .count()
+    }
+
+    /// 香农熵（位/字节，0.0 - 8.0）
+    pub fn entropy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let total = self.total as f64;
+        self.counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
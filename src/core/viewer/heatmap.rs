@@ -0,0 +1,112 @@
+//! 按字节值或局部熵着色
+//!
+//! 与按文件结构着色互为替代：字节值着色让 ASCII 文本与填充区域一目了然，
+//! 局部熵着色让压缩或加密数据（熵接近最大值）显得突出。
+
+use std::str::FromStr;
+
+/// 着色模式
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+pub enum ColorMode {
+    /// 按文件结构（文件头 / 数据包头 / 数据）着色
+    #[default]
+    Structure,
+    /// 按字节值类别着色
+    ByteValue,
+    /// 按字节周围窗口的香农熵着色
+    Entropy,
+}
+
+impl ColorMode {
+    /// 循环切换到下一种模式
+    pub fn next(self) -> Self {
+        match self {
+            ColorMode::Structure => ColorMode::ByteValue,
+            ColorMode::ByteValue => ColorMode::Entropy,
+            ColorMode::Entropy => ColorMode::Structure,
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "structure" => Ok(Self::Structure),
+            "bytes" | "byte-value" => Ok(Self::ByteValue),
+            "entropy" => Ok(Self::Entropy),
+            other => Err(format!(
+                "unknown color mode `{}` (expected structure, bytes or entropy)",
+                other
+            )),
+        }
+    }
+}
+
+/// 字节值类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteCategory {
+    /// 0x00
+    Null,
+    /// 可打印 ASCII 字符
+    Printable,
+    /// ASCII 空白（空格、制表、换行、回车）
+    Whitespace,
+    /// 其他 ASCII 控制字符
+    Control,
+    /// 0x80 - 0xFE
+    NonAscii,
+    /// 0xFF
+    Full,
+}
+
+impl ByteCategory {
+    /// 字节所属类别
+    pub fn of(byte: u8) -> Self {
+        match byte {
+            0x00 => ByteCategory::Null,
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                ByteCategory::Whitespace
+            }
+            0x21..=0x7E => ByteCategory::Printable,
+            0x01..=0x7F => ByteCategory::Control,
+            0xFF => ByteCategory::Full,
+            _ => ByteCategory::NonAscii,
+        }
+    }
+}
+
+/// 计算局部熵的窗口大小（字节）
+pub const ENTROPY_WINDOW: usize = 64;
+
+/// 以 `offset` 为中心的窗口内的归一化香农熵（0.0 - 1.0）
+///
+/// 窗口在数据边界处截断；1.0 表示窗口内字节分布完全均匀。
+pub fn local_entropy(data: &[u8], offset: usize) -> f64 {
+    let start = offset.saturating_sub(ENTROPY_WINDOW / 2);
+    let end = (start + ENTROPY_WINDOW).min(data.len());
+    let window = &data[start.min(end)..end];
+    if window.len() < 2 {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for &byte in window {
+        counts[byte as usize] += 1;
+    }
+    let len = window.len() as f64;
+    let entropy: f64 = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+
+    // 窗口内最多只能出现 min(长度, 256) 种字节
+    entropy / len.min(256.0).log2()
+}
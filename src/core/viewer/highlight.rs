@@ -0,0 +1,178 @@
+//! 用户自定义的字节模式高亮
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::app::error::types::{PcapViewerError, Result};
+
+/// 高亮颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl FromStr for HighlightColor {
+    type Err = PcapViewerError;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "red" => Ok(HighlightColor::Red),
+            "green" => Ok(HighlightColor::Green),
+            "yellow" => Ok(HighlightColor::Yellow),
+            "blue" => Ok(HighlightColor::Blue),
+            "magenta" | "purple" => {
+                Ok(HighlightColor::Magenta)
+            }
+            "cyan" => Ok(HighlightColor::Cyan),
+            "white" => Ok(HighlightColor::White),
+            _ => Err(PcapViewerError::InvalidFormat(
+                format!("Unknown highlight color: {}", s),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HighlightColor {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            HighlightColor::Red => "red",
+            HighlightColor::Green => "green",
+            HighlightColor::Yellow => "yellow",
+            HighlightColor::Blue => "blue",
+            HighlightColor::Magenta => "magenta",
+            HighlightColor::Cyan => "cyan",
+            HighlightColor::White => "white",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 解析十六进制字节模式，如 `DEADBEEF`、`de ad be ef`、`0xDEADBEEF`
+pub fn parse_hex_pattern(text: &str) -> Result<Vec<u8>> {
+    let invalid = || {
+        PcapViewerError::InvalidFormat(format!(
+            "Invalid hex pattern: {}",
+            text
+        ))
+    };
+
+    let digits: String = text
+        .trim()
+        .trim_start_matches("0x")
+        .trim_start_matches("0X")
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect();
+    if digits.is_empty()
+        || !digits.is_ascii()
+        || !digits.len().is_multiple_of(2)
+    {
+        return Err(invalid().into());
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| invalid().into())
+        })
+        .collect()
+}
+
+/// 高亮模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightPattern {
+    pub bytes: Vec<u8>,
+    pub color: HighlightColor,
+}
+
+/// 高亮模式集合
+#[derive(Debug, Clone, Default)]
+pub struct Highlighter {
+    patterns: Vec<HighlightPattern>,
+}
+
+impl Highlighter {
+    /// 创建空集合
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加模式（相同字节序列的已有模式会被替换）
+    pub fn add(&mut self, pattern: HighlightPattern) {
+        self.remove(&pattern.bytes);
+        self.patterns.push(pattern);
+    }
+
+    /// 移除指定字节序列的模式，返回是否存在
+    pub fn remove(&mut self, bytes: &[u8]) -> bool {
+        let len = self.patterns.len();
+        self.patterns
+            .retain(|pattern| pattern.bytes != bytes);
+        self.patterns.len() != len
+    }
+
+    /// 移除所有模式
+    pub fn clear(&mut self) {
+        self.patterns.clear();
+    }
+
+    /// 所有模式
+    pub fn patterns(&self) -> &[HighlightPattern] {
+        &self.patterns
+    }
+
+    /// 是否没有任何模式
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// 计算 `range` 内每个字节的高亮颜色（包括跨越范围边界的匹配，后添加的模式优先）
+    pub fn colors_in(
+        &self,
+        data: &[u8],
+        range: Range<usize>,
+    ) -> Vec<Option<HighlightColor>> {
+        let range = range.start.min(data.len())
+            ..range.end.min(data.len());
+        let mut colors = vec![None; range.len()];
+
+        for pattern in &self.patterns {
+            let len = pattern.bytes.len();
+            // 向前扩展以包含从范围之前开始的匹配
+            let search_start =
+                range.start.saturating_sub(len - 1);
+            let search_end =
+                (range.end + len - 1).min(data.len());
+            if search_end < search_start + len {
+                continue;
+            }
+
+            for start in search_start..=search_end - len {
+                if data[start..start + len] != pattern.bytes
+                {
+                    continue;
+                }
+                let covered = start.max(range.start)
+                    ..(start + len).min(range.end);
+                for offset in covered {
+                    colors[offset - range.start] =
+                        Some(pattern.color);
+                }
+            }
+        }
+        colors
+    }
+}
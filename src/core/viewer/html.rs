@@ -0,0 +1,208 @@
+//! 将十六进制视图导出为独立的 HTML 文件，按区域着色（与终端中的配色一致），
+//! 便于贴到工单或 wiki 中
+
+use std::fmt::Write;
+use std::ops::Range;
+
+use crate::core::pcap::parser::{
+    PcapFileHeader, PcapParser, FILE_HEADER_SIZE,
+};
+
+/// 内嵌样式：各区域的背景色取自真彩色配色
+const STYLE: &str = "\
+body { background: #1e1e1e; color: #d4d4d4; }
+pre { font-family: monospace; line-height: 1.3; }
+.offset { color: #808080; }
+.fh { background: rgb(186, 142, 222); color: #000; }
+.inv { background: rgb(160, 24, 24); color: #fff; }
+.ph { background: rgb(128, 208, 214); color: #000; }
+.pd { background: rgb(238, 214, 128); color: #000; }
+";
+
+/// 字节所属的区域
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRegion {
+    /// 文件头
+    FileHeader,
+    /// 文件头中无效的字段
+    InvalidField,
+    /// 数据包头
+    PacketHeader,
+    /// 数据包负载
+    PacketData,
+    /// 不属于任何已解析结构
+    Unknown,
+}
+
+impl ByteRegion {
+    /// 字节所属的区域
+    pub fn at(
+        parser: &PcapParser,
+        data: &[u8],
+        offset: usize,
+    ) -> Self {
+        if offset < FILE_HEADER_SIZE {
+            let invalid = data
+                .get(..FILE_HEADER_SIZE)
+                .map(|bytes| {
+                    let endianness = parser
+                        .options()
+                        .endianness
+                        .resolve(bytes);
+                    PcapFileHeader::decode(
+                        bytes, endianness,
+                    )
+                    .invalid_fields()
+                })
+                .unwrap_or_default();
+            return if invalid
+                .iter()
+                .any(|field| field.contains(&offset))
+            {
+                Self::InvalidField
+            } else {
+                Self::FileHeader
+            };
+        }
+        match parser.packet_index_at(offset) {
+            Some(index) => {
+                let packet = &parser.packets()[index];
+                if offset
+                    < packet.offset + parser.header_size()
+                {
+                    Self::PacketHeader
+                } else {
+                    Self::PacketData
+                }
+            }
+            None => Self::Unknown,
+        }
+    }
+
+    /// 对应的 CSS 类名（未知区域不着色）
+    fn class(self) -> Option<&'static str> {
+        match self {
+            Self::FileHeader => Some("fh"),
+            Self::InvalidField => Some("inv"),
+            Self::PacketHeader => Some("ph"),
+            Self::PacketData => Some("pd"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// 渲染 `range` 范围内的字节为独立 HTML 页面：
+/// 每行为偏移、十六进制与 ASCII，连续同区域的字节合并为一个 span
+pub fn render(
+    parser: &PcapParser,
+    data: &[u8],
+    range: Range<usize>,
+    bytes_per_line: usize,
+    title: &str,
+) -> String {
+    let mut body = String::new();
+    let start = range.start - range.start % bytes_per_line;
+    for line_start in
+        (start..range.end).step_by(bytes_per_line)
+    {
+        let line_end =
+            (line_start + bytes_per_line).min(range.end);
+        let cells: Vec<(ByteRegion, String, String)> =
+            (line_start..line_start + bytes_per_line)
+                .map(|offset| {
+                    if offset < range.start
+                        || offset >= line_end
+                    {
+                        return (
+                            ByteRegion::Unknown,
+                            "  ".to_string(),
+                            " ".to_string(),
+                        );
+                    }
+                    let byte = data[offset];
+                    let ch = if (32..=126).contains(&byte) {
+                        escape(&(byte as char).to_string())
+                    } else {
+                        ".".to_string()
+                    };
+                    (
+                        ByteRegion::at(
+                            parser, data, offset,
+                        ),
+                        format!("{:02X}", byte),
+                        ch,
+                    )
+                })
+                .collect();
+
+        let _ = write!(
+            body,
+            "<span class=\"offset\">{:08X}:</span> ",
+            line_start
+        );
+        body.push_str(&spans(
+            cells.iter().map(|(region, hex, _)| {
+                (*region, hex.as_str())
+            }),
+            " ",
+        ));
+        body.push_str(" | ");
+        body.push_str(&spans(
+            cells.iter().map(|(region, _, ch)| {
+                (*region, ch.as_str())
+            }),
+            "",
+        ));
+        body.push('\n');
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<pre>\n{}</pre>\n</body>\n</html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+/// 将同区域的连续单元合并为 span，单元之间以 `separator` 分隔
+fn spans<'a>(
+    cells: impl Iterator<Item = (ByteRegion, &'a str)>,
+    separator: &str,
+) -> String {
+    let mut output = String::new();
+    let mut current: Option<ByteRegion> = None;
+    for (i, (region, text)) in cells.enumerate() {
+        if current != Some(region) {
+            if current.and_then(ByteRegion::class).is_some()
+            {
+                output.push_str("</span>");
+            }
+            if i > 0 {
+                output.push_str(separator);
+            }
+            if let Some(class) = region.class() {
+                let _ = write!(
+                    output,
+                    "<span class=\"{}\">",
+                    class
+                );
+            }
+            current = Some(region);
+        } else if i > 0 {
+            output.push_str(separator);
+        }
+        output.push_str(text);
+    }
+    if current.and_then(ByteRegion::class).is_some() {
+        output.push_str("</span>");
+    }
+    output
+}
+
+/// 转义 HTML 特殊字符
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,54 @@
+//! 跳转历史（类似 Vim 的 Ctrl-O / Ctrl-I）
+
+/// 跳转历史保留的最大条目数
+const MAX_ENTRIES: usize = 100;
+
+/// 跳转历史，记录跳转前所在的字节偏移
+#[derive(Debug, Clone, Default)]
+pub struct JumpList {
+    entries: Vec<usize>,
+    index: usize, // 当前位置，等于 entries.len() 时表示不在历史中
+}
+
+impl JumpList {
+    /// 创建空的跳转历史
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次跳转的起点（丢弃当前位置之后的历史）
+    pub fn push(&mut self, offset: usize) {
+        self.entries.truncate(self.index);
+        self.entries.retain(|&entry| entry != offset);
+        self.entries.push(offset);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.index = self.entries.len();
+    }
+
+    /// 后退到上一个位置，`current` 为当前所在偏移
+    pub fn back(
+        &mut self,
+        current: usize,
+    ) -> Option<usize> {
+        if self.index == 0 {
+            return None;
+        }
+        // 首次后退时记录当前位置，以便前进时返回
+        if self.index == self.entries.len() {
+            self.entries.push(current);
+        }
+        self.index -= 1;
+        Some(self.entries[self.index])
+    }
+
+    /// 前进到下一个位置
+    pub fn forward(&mut self) -> Option<usize> {
+        if self.index + 1 >= self.entries.len() {
+            return None;
+        }
+        self.index += 1;
+        Some(self.entries[self.index])
+    }
+}
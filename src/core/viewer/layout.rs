@@ -0,0 +1,185 @@
+//! 终端界面布局：把屏幕按行划分为若干区域，各部件只在自己的区域内输出
+
+use super::text::{pad_to_width, truncate_to_width};
+
+/// 区域高度约束
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// 固定行数
+    Length(usize),
+    /// 占用剩余行数（多个时平分，余数给靠前的区域）
+    Fill,
+}
+
+/// 屏幕上按整行划分的矩形区域
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    pub top: usize,    // 起始行
+    pub height: usize, // 行数
+    pub width: usize,  // 列数
+}
+
+impl Rect {
+    /// 创建区域
+    pub fn new(
+        top: usize,
+        height: usize,
+        width: usize,
+    ) -> Self {
+        Self { top, height, width }
+    }
+
+    /// 区域下方第一行
+    pub fn bottom(&self) -> usize {
+        self.top + self.height
+    }
+}
+
+/// 自上而下划分区域；固定行数之和超出时，靠后的区域被压缩
+pub fn split_vertical(
+    area: Rect,
+    constraints: &[Constraint],
+) -> Vec<Rect> {
+    let fixed: usize = constraints
+        .iter()
+        .map(|constraint| match constraint {
+            Constraint::Length(lines) => *lines,
+            Constraint::Fill => 0,
+        })
+        .sum();
+    let fills = constraints
+        .iter()
+        .filter(|constraint| {
+            **constraint == Constraint::Fill
+        })
+        .count();
+    let spare = area.height.saturating_sub(fixed);
+
+    let mut top = area.top;
+    let mut fill_index = 0;
+    constraints
+        .iter()
+        .map(|constraint| {
+            let wanted = match constraint {
+                Constraint::Length(lines) => *lines,
+                Constraint::Fill => {
+                    let share = spare / fills
+                        + usize::from(
+                            fill_index < spare % fills,
+                        );
+                    fill_index += 1;
+                    share
+                }
+            };
+            let height = wanted.min(area.bottom() - top);
+            let rect = Rect::new(top, height, area.width);
+            top += height;
+            rect
+        })
+        .collect()
+}
+
+/// 帧缓冲：整屏每行一个（可含 ANSI 样式的）字符串
+#[derive(Debug, Clone)]
+pub struct Frame {
+    width: usize,
+    lines: Vec<String>,
+}
+
+impl Frame {
+    /// 创建指定尺寸的空白帧
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            lines: vec![String::new(); height],
+        }
+    }
+
+    /// 整个屏幕区域
+    pub fn area(&self) -> Rect {
+        Rect::new(0, self.lines.len(), self.width)
+    }
+
+    /// 在指定区域内逐行输出的画笔
+    pub fn region(&mut self, area: Rect) -> Region<'_> {
+        let end = area.bottom().min(self.lines.len());
+        let start = area.top.min(end);
+        Region {
+            lines: &mut self.lines[start..end],
+            width: area.width,
+            next: 0,
+        }
+    }
+
+    /// 同时取得多块区域的画笔（区域需自上而下排列且互不重叠）
+    pub fn regions(
+        &mut self,
+        areas: &[Rect],
+    ) -> Vec<Region<'_>> {
+        let mut regions = Vec::with_capacity(areas.len());
+        let mut rest: &mut [String] = &mut self.lines;
+        let mut consumed = 0;
+        for area in areas {
+            let start = area
+                .top
+                .saturating_sub(consumed)
+                .min(rest.len());
+            let end = area
+                .bottom()
+                .saturating_sub(consumed)
+                .clamp(start, rest.len());
+            let (head, tail) =
+                std::mem::take(&mut rest).split_at_mut(end);
+            regions.push(Region {
+                lines: &mut head[start..],
+                width: area.width,
+                next: 0,
+            });
+            rest = tail;
+            consumed += end;
+        }
+        regions
+    }
+
+    /// 各行内容
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+/// 帧中的一块区域，写满后多余的行被丢弃，不会挤占其他区域
+#[derive(Debug)]
+pub struct Region<'a> {
+    lines: &'a mut [String],
+    width: usize,
+    next: usize,
+}
+
+impl Region<'_> {
+    /// 区域宽度
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// 区域高度
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// 写入下一行（区域已满时忽略）
+    pub fn push(&mut self, line: impl Into<String>) {
+        if let Some(slot) = self.lines.get_mut(self.next) {
+            *slot = line.into();
+            self.next += 1;
+        }
+    }
+
+    /// 写入一行反色状态栏（按区域宽度补齐或截断）
+    pub fn push_bar(&mut self, text: &str) {
+        let text = pad_to_width(
+            &truncate_to_width(text, self.width),
+            self.width,
+        );
+        self.push(format!("\x1b[7m{}\x1b[0m", text));
+    }
+}
@@ -0,0 +1,55 @@
+//! 已渲染行的缓存：滚动时复用内容未变化的行，超出容量时淘汰最久未使用的行
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// 按最近使用顺序淘汰的渲染行缓存（LRU）
+#[derive(Debug, Clone)]
+pub struct LineCache<K> {
+    capacity: usize,
+    entries: HashMap<K, (String, u64)>, // 渲染结果与最近使用时刻
+    clock: u64,
+}
+
+impl<K: Hash + Eq + Clone> LineCache<K> {
+    /// 创建最多缓存 `capacity` 行的缓存
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// 查找已缓存的行，命中时记为最近使用
+    pub fn get(&mut self, key: &K) -> Option<&str> {
+        self.clock += 1;
+        let clock = self.clock;
+        let (line, used) = self.entries.get_mut(key)?;
+        *used = clock;
+        Some(line.as_str())
+    }
+
+    /// 缓存一行，已满时先淘汰最久未使用的行
+    pub fn insert(&mut self, key: K, line: String) {
+        if self.entries.len() >= self.capacity
+            && !self.entries.contains_key(&key)
+        {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(key, (line, self.clock));
+    }
+
+    /// 清空缓存（行内容可能已变化时调用）
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -0,0 +1,141 @@
+//! 将字节格式化为 C / Rust / Python 字面量或 base64，
+//! 便于把抓到的帧直接粘贴为单元测试数据
+
+use std::fmt;
+use std::str::FromStr;
+
+/// 数组字面量每行的字节数
+const ARRAY_BYTES_PER_LINE: usize = 12;
+
+/// Python 字节串每行的字节数
+const PYTHON_BYTES_PER_LINE: usize = 16;
+
+/// base64 字母表
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// 复制格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// C `uint8_t[]` 数组
+    C,
+    /// Rust `&[u8]` 字面量
+    Rust,
+    /// Python bytes 字面量
+    Python,
+    /// base64
+    Base64,
+}
+
+impl CopyFormat {
+    /// 将字节格式化为名为 `name` 的变量定义（base64 只输出编码结果）
+    pub fn format(
+        self,
+        bytes: &[u8],
+        name: &str,
+    ) -> String {
+        match self {
+            Self::C => format!(
+                "uint8_t {}[{}] = {{\n{}}};\n",
+                name,
+                bytes.len(),
+                array_lines(bytes)
+            ),
+            Self::Rust => format!(
+                "let {}: &[u8] = &[\n{}];\n",
+                name,
+                array_lines(bytes)
+            ),
+            Self::Python => {
+                let lines: String = bytes
+                    .chunks(PYTHON_BYTES_PER_LINE)
+                    .map(|chunk| {
+                        let escaped: String = chunk
+                            .iter()
+                            .map(|byte| {
+                                format!("\\x{:02x}", byte)
+                            })
+                            .collect();
+                        format!("    b\"{}\"\n", escaped)
+                    })
+                    .collect();
+                if lines.is_empty() {
+                    format!("{} = b\"\"\n", name)
+                } else {
+                    format!("{} = (\n{})\n", name, lines)
+                }
+            }
+            Self::Base64 => base64(bytes),
+        }
+    }
+}
+
+impl FromStr for CopyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "c" => Ok(Self::C),
+            "rust" => Ok(Self::Rust),
+            "python" => Ok(Self::Python),
+            "base64" => Ok(Self::Base64),
+            other => Err(format!(
+                "unknown copy format `{}` (expected c, rust, python or base64)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CopyFormat {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::C => "c",
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::Base64 => "base64",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 数组元素行：每行 12 个 `0x??,`，缩进 4 个空格
+fn array_lines(bytes: &[u8]) -> String {
+    bytes
+        .chunks(ARRAY_BYTES_PER_LINE)
+        .map(|chunk| {
+            let items: Vec<String> = chunk
+                .iter()
+                .map(|byte| format!("0x{:02x},", byte))
+                .collect();
+            format!("    {}\n", items.join(" "))
+        })
+        .collect()
+}
+
+/// 标准 base64 编码（带 `=` 填充）
+pub fn base64(bytes: &[u8]) -> String {
+    let mut output =
+        String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([
+            0, group[0], group[1], group[2],
+        ]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (bits >> (18 - 6 * i)) & 0x3F;
+                output.push(
+                    BASE64_ALPHABET[index as usize] as char,
+                );
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,71 @@
+//! 小地图（滚动条列）
+//!
+//! 将整个文件按行数等分，每行代表一段字节范围，
+//! 标记该范围内最重要的区域并指示当前视口位置。
+
+use std::ops::Range;
+
+/// 小地图标记类型（按优先级从低到高排列）
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum MinimapMark {
+    /// 数据包头
+    PacketHeader,
+    /// 文件头
+    FileHeader,
+    /// CRC 校验失败的数据包
+    CrcError,
+}
+
+/// 小地图：文件偏移与行之间的映射
+#[derive(Debug, Clone, Copy)]
+pub struct Minimap {
+    rows: usize,
+    data_len: usize,
+}
+
+impl Minimap {
+    /// 创建覆盖 `data_len` 字节、共 `rows` 行的小地图
+    pub fn new(rows: usize, data_len: usize) -> Self {
+        Self {
+            rows: rows.max(1),
+            data_len,
+        }
+    }
+
+    /// 偏移所在的行
+    pub fn row_of(&self, offset: usize) -> usize {
+        if self.data_len == 0 {
+            return 0;
+        }
+        let row = offset as u128 * self.rows as u128
+            / self.data_len as u128;
+        (row as usize).min(self.rows - 1)
+    }
+
+    /// 每行的最高优先级标记
+    pub fn marks(
+        &self,
+        marks: impl IntoIterator<Item = (usize, MinimapMark)>,
+    ) -> Vec<Option<MinimapMark>> {
+        let mut rows = vec![None; self.rows];
+        for (offset, mark) in marks {
+            let row = &mut rows[self.row_of(offset)];
+            *row = (*row).max(Some(mark));
+        }
+        rows
+    }
+
+    /// 视口（字节范围）对应的滑块行范围，至少占一行
+    pub fn thumb(
+        &self,
+        viewport: Range<usize>,
+    ) -> Range<usize> {
+        let start = self.row_of(viewport.start);
+        let end = self
+            .row_of(viewport.end.saturating_sub(1))
+            .max(start);
+        start..end + 1
+    }
+}
@@ -1,4 +1,34 @@
 //! 查看器核心逻辑模块
 
+#[cfg(feature = "cli")]
+pub mod annotation;
+pub mod bookmark;
+pub mod clipboard;
+pub mod columns;
+#[cfg(feature = "cli")]
+pub mod console;
+pub mod diff;
+pub mod edit;
+pub mod file_data;
+pub mod fold;
+pub mod frequency;
+pub mod heatmap;
+pub mod highlight;
+pub mod html;
+pub mod jump_list;
+pub mod layout;
+pub mod line_cache;
+pub mod literal;
+pub mod minimap;
 pub mod pagination;
+#[cfg(feature = "cli")]
+pub mod palette;
+#[cfg(feature = "cli")]
+pub mod session;
+pub mod sparkline;
+#[cfg(feature = "cli")]
 pub mod terminal;
+pub mod text;
+pub mod timestamp;
+#[cfg(feature = "cli")]
+pub mod workspace;
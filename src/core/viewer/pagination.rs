@@ -15,7 +15,8 @@ impl PaginationState {
         total_lines: usize,
     ) -> Self {
         Self {
-            lines_per_page,
+            // 每页至少一行，避免极小终端下除零
+            lines_per_page: lines_per_page.max(1),
             display_start_line: 0,
             total_lines,
         }
@@ -80,6 +81,28 @@ impl PaginationState {
             .min(max_start_line);
     }
 
+    /// 上移半页
+    pub fn half_page_up(&mut self) {
+        self.display_start_line = self
+            .display_start_line
+            .saturating_sub(self.half_page());
+    }
+
+    /// 下移半页
+    pub fn half_page_down(&mut self) {
+        let max_start_line = self
+            .total_lines
+            .saturating_sub(self.lines_per_page);
+        self.display_start_line = (self.display_start_line
+            + self.half_page())
+        .min(max_start_line);
+    }
+
+    /// 半页的行数（至少一行）
+    fn half_page(&self) -> usize {
+        (self.lines_per_page / 2).max(1)
+    }
+
     /// 跳转到第一页
     pub fn go_to_first_page(&mut self) {
         self.display_start_line = 0;
@@ -97,7 +120,7 @@ impl PaginationState {
         &mut self,
         lines_per_page: usize,
     ) {
-        self.lines_per_page = lines_per_page;
+        self.lines_per_page = lines_per_page.max(1);
         // 重新计算当前页位置，确保不超出范围
         let max_start_line = self
             .total_lines
@@ -105,4 +128,43 @@ impl PaginationState {
         self.display_start_line =
             self.display_start_line.min(max_start_line);
     }
+
+    /// 更新总行数（文件增长时调用）
+    pub fn update_total_lines(
+        &mut self,
+        total_lines: usize,
+    ) {
+        self.total_lines = total_lines;
+        let max_start_line = self
+            .total_lines
+            .saturating_sub(self.lines_per_page);
+        self.display_start_line =
+            self.display_start_line.min(max_start_line);
+    }
+
+    /// 是否已显示到最后一页
+    pub fn is_at_end(&self) -> bool {
+        self.display_start_line + self.lines_per_page
+            >= self.total_lines
+    }
+
+    /// 滚动到使指定行可见（已可见时不移动）
+    pub fn ensure_line_visible(&mut self, line: usize) {
+        if line < self.display_start_line {
+            self.display_start_line = line;
+        } else if line
+            >= self.display_start_line + self.lines_per_page
+        {
+            self.display_start_line =
+                line + 1 - self.lines_per_page;
+        }
+    }
+
+    /// 跳转到指定行（限制在有效范围内）
+    pub fn go_to_line(&mut self, line: usize) {
+        let max_start_line = self
+            .total_lines
+            .saturating_sub(self.lines_per_page);
+        self.display_start_line = line.min(max_start_line);
+    }
 }
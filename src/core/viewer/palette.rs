@@ -0,0 +1,260 @@
+//! 终端颜色支持：是否输出颜色、色深检测，以及按色深选择的背景色
+//!
+//! 只支持 16 色的终端往往不支持亮色背景（或显示为闪烁），
+//! 这类终端改用普通背景色，保证区域着色下的文字仍然清晰。
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+use colored::Color;
+
+use crate::core::viewer::console::Console;
+use crate::core::viewer::highlight::HighlightColor;
+
+/// 何时输出颜色（`--color`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// 输出到终端时着色，遵循 `NO_COLOR` / `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    /// 总是着色（如通过管道交给 `less -R`）
+    Always,
+    /// 从不着色
+    Never,
+}
+
+impl ColorChoice {
+    /// 是否输出颜色。`CLICOLOR_FORCE` 优先于 `NO_COLOR`，
+    /// 两者都未设置时只在输出到终端时着色
+    pub fn should_colorize(
+        self,
+        is_terminal: bool,
+    ) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if env::var("CLICOLOR_FORCE")
+                    .is_ok_and(|value| value != "0")
+                {
+                    true
+                } else if env::var("NO_COLOR")
+                    .is_ok_and(|value| !value.is_empty())
+                {
+                    false
+                } else {
+                    is_terminal
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(format!(
+                "unknown color choice `{}` (expected auto, always or never)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 终端支持的色深
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 16 色
+    Basic,
+    /// 256 色
+    Ansi256,
+    /// 24 位真彩色
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// 根据 `COLORTERM` 与 `TERM` 检测终端色深
+    /// （Windows Terminal 通过 `WT_SESSION` 识别，支持真彩色）
+    pub fn detect() -> Self {
+        let colorterm =
+            env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor"
+            || colorterm == "24bit"
+            || env::var_os("WT_SESSION").is_some()
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Basic
+        }
+    }
+}
+
+/// 一种区域着色：背景色与前景色
+#[derive(Debug, Clone, Copy)]
+pub struct Shade {
+    pub background: Color,
+    pub foreground: Color,
+}
+
+/// 按色深选择的区域着色
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    depth: ColorDepth,
+    /// 文件头
+    pub file_header: Shade,
+    /// 数据包头
+    pub packet_header: Shade,
+    /// 数据包体
+    pub packet_data: Shade,
+    /// 已修改的字节
+    pub modified: Shade,
+    /// 文件头中无效的字段
+    pub invalid: Shade,
+}
+
+impl Palette {
+    /// 适合指定色深的着色
+    pub fn for_depth(depth: ColorDepth) -> Self {
+        let shade = |background, foreground| Shade {
+            background,
+            foreground,
+        };
+        match depth {
+            ColorDepth::Basic => Self {
+                depth,
+                file_header: shade(
+                    Color::Magenta,
+                    Color::White,
+                ),
+                packet_header: shade(
+                    Color::Cyan,
+                    Color::Black,
+                ),
+                packet_data: shade(
+                    Color::Yellow,
+                    Color::Black,
+                ),
+                modified: shade(Color::Red, Color::White),
+                invalid: shade(
+                    Color::Red,
+                    Color::BrightWhite,
+                ),
+            },
+            ColorDepth::Ansi256 => Self {
+                depth,
+                file_header: shade(
+                    Color::BrightMagenta,
+                    Color::BrightWhite,
+                ),
+                packet_header: shade(
+                    Color::BrightCyan,
+                    Color::Black,
+                ),
+                packet_data: shade(
+                    Color::BrightYellow,
+                    Color::Black,
+                ),
+                modified: shade(
+                    Color::BrightRed,
+                    Color::BrightWhite,
+                ),
+                invalid: shade(
+                    Color::Red,
+                    Color::BrightWhite,
+                ),
+            },
+            ColorDepth::TrueColor => Self {
+                depth,
+                file_header: shade(
+                    rgb(186, 142, 222),
+                    Color::Black,
+                ),
+                packet_header: shade(
+                    rgb(128, 208, 214),
+                    Color::Black,
+                ),
+                packet_data: shade(
+                    rgb(238, 214, 128),
+                    Color::Black,
+                ),
+                modified: shade(
+                    rgb(214, 64, 64),
+                    Color::BrightWhite,
+                ),
+                invalid: shade(
+                    rgb(160, 24, 24),
+                    Color::BrightWhite,
+                ),
+            },
+        }
+    }
+
+    /// 用户高亮的背景色（16 色终端使用普通色）
+    pub fn highlight(
+        &self,
+        color: HighlightColor,
+    ) -> Color {
+        let (bright, normal) = match color {
+            HighlightColor::Red => {
+                (Color::BrightRed, Color::Red)
+            }
+            HighlightColor::Green => {
+                (Color::BrightGreen, Color::Green)
+            }
+            HighlightColor::Yellow => {
+                (Color::BrightYellow, Color::Yellow)
+            }
+            HighlightColor::Blue => {
+                (Color::BrightBlue, Color::Blue)
+            }
+            HighlightColor::Magenta => {
+                (Color::BrightMagenta, Color::Magenta)
+            }
+            HighlightColor::Cyan => {
+                (Color::BrightCyan, Color::Cyan)
+            }
+            HighlightColor::White => {
+                (Color::BrightWhite, Color::White)
+            }
+        };
+        if self.depth == ColorDepth::Basic {
+            normal
+        } else {
+            bright
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::for_depth(Console::current().color_depth())
+    }
+}
+
+/// 真彩色
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::TrueColor { r, g, b }
+}
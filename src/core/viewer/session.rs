@@ -0,0 +1,165 @@
+//! 按文件保存的查看状态（重新打开同一文件时恢复上次位置）
+//!
+//! 所有文件的状态保存在 XDG 数据目录下的单个 JSON 文件中
+//! （如 `~/.local/share/pcap-viewer/state.json`）。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::error::types::{PcapViewerError, Result};
+
+/// 状态文件格式版本
+const STATE_VERSION: u32 = 1;
+
+/// 最多保留的文件数，超出时丢弃最久未打开的记录
+const MAX_ENTRIES: usize = 200;
+
+/// 已保存的高亮模式
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct SavedHighlight {
+    pub pattern: String, // 十六进制字节串，如 "DE AD BE EF"
+    pub color: String,   // 颜色名称
+}
+
+/// 单个文件的查看状态
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct ViewerState {
+    pub offset: usize, // 视口起始处的字节偏移
+    pub bytes_per_line: usize, // 保存时每行显示的字节数
+    #[serde(default)]
+    pub highlights: Vec<SavedHighlight>, // 生效中的高亮模式
+    #[serde(default)]
+    pub bookmarks: Vec<usize>, // 书签偏移
+}
+
+/// 状态文件中的一条记录
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    saved_at: u64, // 保存时间（Unix 秒），用于淘汰旧记录
+    #[serde(flatten)]
+    state: ViewerState,
+}
+
+/// 状态文件内容
+#[derive(Debug, Serialize, Deserialize)]
+struct StateFile {
+    version: u32,
+    files: BTreeMap<String, Entry>,
+}
+
+/// 查看状态存储
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    path: PathBuf,
+}
+
+impl StateStore {
+    /// 使用指定的状态文件
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// 使用 XDG 数据目录下的默认状态文件（无法确定数据目录时返回 None）
+    pub fn open_default() -> Option<Self> {
+        let dir = dirs::data_dir()?.join("pcap-viewer");
+        Some(Self::new(dir.join("state.json")))
+    }
+
+    /// 状态文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 读取指定数据文件的状态
+    pub fn load(
+        &self,
+        file_path: &Path,
+    ) -> Result<Option<ViewerState>> {
+        let mut file = self.read()?;
+        Ok(file
+            .files
+            .remove(&state_key(file_path))
+            .map(|entry| entry.state))
+    }
+
+    /// 保存指定数据文件的状态
+    pub fn save(
+        &self,
+        file_path: &Path,
+        state: ViewerState,
+    ) -> Result<()> {
+        let mut file = self.read()?;
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        file.files.insert(
+            state_key(file_path),
+            Entry { saved_at, state },
+        );
+
+        // 淘汰最久未保存的记录
+        while file.files.len() > MAX_ENTRIES {
+            let oldest = file
+                .files
+                .iter()
+                .min_by_key(|(_, entry)| entry.saved_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                file.files.remove(&key);
+            }
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| {
+            PcapViewerError::InvalidFormat(e.to_string())
+        })?;
+        fs::write(&self.path, content + "\n")?;
+        Ok(())
+    }
+
+    /// 读取状态文件（不存在时为空）
+    fn read(&self) -> Result<StateFile> {
+        if !self.path.exists() {
+            return Ok(StateFile {
+                version: STATE_VERSION,
+                files: BTreeMap::new(),
+            });
+        }
+        let content = fs::read_to_string(&self.path)?;
+        let file = serde_json::from_str(&content).map_err(
+            |e| {
+                PcapViewerError::InvalidFormat(format!(
+                    "Invalid state file {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            },
+        )?;
+        Ok(file)
+    }
+}
+
+/// 数据文件在状态文件中的键（本地文件使用规范化的绝对路径）
+fn state_key(file_path: &Path) -> String {
+    fs::canonicalize(file_path)
+        .unwrap_or_else(|_| file_path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,124 @@
+//! 吞吐量统计与迷你折线图（sparkline）
+//!
+//! 将抓包时间范围均分为若干时间格，统计每格的数据包数与字节数，
+//! 并用方块字符绘制为一行高度图。
+
+use crate::core::pcap::parser::{
+    DataPacket, DataPacketHeader,
+};
+
+/// 由低到高的方块字符
+const BLOCKS: [char; 8] =
+    ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 按时间格统计的吞吐量
+#[derive(Debug, Clone)]
+pub struct Throughput {
+    start_nanos: i128,  // 最早数据包的时间戳
+    bucket_nanos: i128, // 每个时间格的时长
+    packets: Vec<u64>,
+    bytes: Vec<u64>,
+}
+
+impl Throughput {
+    /// 将抓包时间范围均分为 `buckets` 格并统计（无数据包时返回 None）
+    pub fn compute(
+        packets: &[DataPacket],
+        buckets: usize,
+    ) -> Option<Self> {
+        if buckets == 0 {
+            return None;
+        }
+        // 时间戳可能乱序，按最小 / 最大值确定范围
+        let (start, end) = packets.iter().fold(
+            None,
+            |range: Option<(i128, i128)>, packet| {
+                let nanos = packet.header.timestamp_nanos();
+                Some(match range {
+                    Some((start, end)) => {
+                        (start.min(nanos), end.max(nanos))
+                    }
+                    None => (nanos, nanos),
+                })
+            },
+        )?;
+
+        let span = end - start + 1;
+        let mut throughput = Self {
+            start_nanos: start,
+            bucket_nanos: (span + buckets as i128 - 1)
+                / buckets as i128,
+            packets: vec![0; buckets],
+            bytes: vec![0; buckets],
+        };
+        for packet in packets {
+            let bucket =
+                throughput.bucket_of(&packet.header);
+            throughput.packets[bucket] += 1;
+            throughput.bytes[bucket] +=
+                packet.header.packet_length as u64;
+        }
+        Some(throughput)
+    }
+
+    /// 时间格数量
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// 是否没有时间格
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// 数据包所在的时间格
+    pub fn bucket_of(
+        &self,
+        header: &DataPacketHeader,
+    ) -> usize {
+        let bucket = (header.timestamp_nanos()
+            - self.start_nanos)
+            / self.bucket_nanos;
+        (bucket.max(0) as usize).min(self.len() - 1)
+    }
+
+    /// 每个时间格的时长（秒）
+    pub fn bucket_seconds(&self) -> f64 {
+        self.bucket_nanos as f64 / 1e9
+    }
+
+    /// 每个时间格的包速率（包/秒）
+    pub fn packets_per_second(&self) -> Vec<f64> {
+        self.rates(&self.packets)
+    }
+
+    /// 每个时间格的字节速率（字节/秒）
+    pub fn bytes_per_second(&self) -> Vec<f64> {
+        self.rates(&self.bytes)
+    }
+
+    fn rates(&self, counts: &[u64]) -> Vec<f64> {
+        let seconds = self.bucket_seconds();
+        counts
+            .iter()
+            .map(|&count| count as f64 / seconds)
+            .collect()
+    }
+}
+
+/// 将数值绘制为方块高度图（按最大值归一化，0 显示为空格）
+pub fn sparkline(values: &[f64]) -> String {
+    let max = values.iter().copied().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|&value| {
+            if value <= 0.0 || max <= 0.0 {
+                return ' ';
+            }
+            let level = (value / max
+                * (BLOCKS.len() - 1) as f64)
+                .round() as usize;
+            BLOCKS[level.min(BLOCKS.len() - 1)]
+        })
+        .collect()
+}
@@ -1,29 +1,94 @@
 //! 终端管理模块
 
 use crate::app::error::types::Result;
+use crate::core::viewer::clipboard::ClipboardBackend;
+use crate::core::viewer::console::Console;
+use crate::core::viewer::layout::Frame;
 use crossterm::{
-    cursor::{Hide, Show},
-    execute, terminal,
+    cursor::{Hide, MoveTo, Show},
+    event::{
+        DisableMouseCapture, EnableMouseCapture,
+        KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType},
 };
 use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+/// 是否已启用键盘增强协议（kitty），恢复终端时需要撤销
+static KEYBOARD_ENHANCED: AtomicBool =
+    AtomicBool::new(false);
 
 /// 终端管理器
 pub struct TerminalManager {
     is_raw_mode: bool,
+    previous_frame: Vec<String>, // 上一帧各行内容，用于增量重绘
+    previous_size: (usize, usize), // 上一帧的终端尺寸，变化时整屏重绘
+    inline: bool, // 保留滚动缓冲：先把屏幕原有内容推入滚动缓冲，退出时保留最后一帧
 }
 
 impl TerminalManager {
     /// 创建新的终端管理器
     pub fn new() -> Self {
-        Self { is_raw_mode: false }
+        Self {
+            is_raw_mode: false,
+            previous_frame: Vec::new(),
+            previous_size: (0, 0),
+            inline: false,
+        }
+    }
+
+    /// 内联绘制（保留滚动缓冲）：首帧前把屏幕原有内容推入滚动缓冲，
+    /// 退出后最后一帧留在屏幕上，光标移到其下方
+    pub fn with_inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
     }
 
     /// 进入原始模式
     pub fn enter_raw_mode(&mut self) -> Result<()> {
         if !self.is_raw_mode {
+            install_restore_hooks();
             terminal::enable_raw_mode()?;
-            // 暂时不使用备用屏幕，直接隐藏光标
-            execute!(io::stdout(), Hide)?;
+            if self.inline {
+                // 在最后一行连续换行，把原有内容滚入滚动缓冲而不是清除
+                let (_, height) = self.get_size();
+                execute!(
+                    io::stdout(),
+                    MoveTo(
+                        0,
+                        height.saturating_sub(1) as u16
+                    ),
+                    Print("\r\n".repeat(height))
+                )?;
+            }
+            // 暂时不使用备用屏幕，直接隐藏光标并捕获鼠标事件
+            execute!(
+                io::stdout(),
+                Hide,
+                EnableMouseCapture
+            )?;
+            // 支持 kitty 键盘协议的终端会报告按键重复与松开事件
+            // （旧版 Windows 控制台不查询）
+            if !Console::current().legacy
+                && terminal::supports_keyboard_enhancement()
+                    .unwrap_or(false)
+            {
+                execute!(
+                    io::stdout(),
+                    PushKeyboardEnhancementFlags(
+                        KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                    )
+                )?;
+                KEYBOARD_ENHANCED
+                    .store(true, Ordering::Relaxed);
+            }
             self.is_raw_mode = true;
         }
         Ok(())
@@ -32,7 +97,26 @@ impl TerminalManager {
     /// 退出原始模式
     pub fn exit_raw_mode(&mut self) -> Result<()> {
         if self.is_raw_mode {
-            execute!(io::stdout(), Show)?;
+            pop_keyboard_enhancement()?;
+            execute!(
+                io::stdout(),
+                DisableMouseCapture,
+                Show
+            )?;
+            if self.inline
+                && !self.previous_frame.is_empty()
+            {
+                // 光标移到最后一帧下方，shell 提示符不会覆盖画面
+                execute!(
+                    io::stdout(),
+                    MoveTo(
+                        0,
+                        self.previous_frame.len() as u16
+                            - 1
+                    ),
+                    Print("\r\n")
+                )?;
+            }
             terminal::disable_raw_mode()?;
             self.is_raw_mode = false;
         }
@@ -47,6 +131,94 @@ impl TerminalManager {
         Ok(())
     }
 
+    /// 绘制一帧，只重绘与上一帧不同的行，
+    /// 所有输出排队后一次性刷新，避免整屏清除造成的闪烁
+    pub fn draw(&mut self, frame: &Frame) -> Result<()> {
+        let size = self.get_size();
+        let lines = frame.lines();
+
+        let mut stdout = io::stdout().lock();
+        if size != self.previous_size {
+            queue!(stdout, Clear(ClearType::All))?;
+            self.previous_frame.clear();
+            self.previous_size = size;
+        }
+
+        for (row, line) in lines.iter().enumerate() {
+            if self.previous_frame.get(row) == Some(line) {
+                continue;
+            }
+            // 先清除整行再输出：行内可能先绘制了右侧的小地图列
+            queue!(
+                stdout,
+                MoveTo(0, row as u16),
+                Clear(ClearType::CurrentLine),
+                Print(line)
+            )?;
+        }
+
+        // 清除上一帧多出的行
+        for row in lines.len()..self.previous_frame.len() {
+            queue!(
+                stdout,
+                MoveTo(0, row as u16),
+                Clear(ClearType::CurrentLine)
+            )?;
+        }
+
+        stdout.flush()?;
+        self.previous_frame = lines.to_vec();
+        Ok(())
+    }
+
+    /// 通过指定后端写入系统剪贴板（OSC 52 由终端完成，
+    /// SSH 会话中同样有效；不支持的终端会忽略）
+    pub fn set_clipboard(
+        &self,
+        text: &str,
+        backend: &ClipboardBackend,
+    ) -> Result<()> {
+        if let Some(sequence) =
+            backend.escape_sequence(text)
+        {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(sequence.as_bytes())?;
+            stdout.flush()?;
+            return Ok(());
+        }
+        let ClipboardBackend::Command(command) = backend
+        else {
+            return Ok(());
+        };
+
+        let mut words = command.split_whitespace();
+        let program = words.next().unwrap_or_default();
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "`{}` exited with {}",
+                program, status
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// 丢弃上一帧，下次绘制时整屏重绘
+    pub fn invalidate(&mut self) {
+        self.previous_frame.clear();
+        self.previous_size = (0, 0);
+    }
+
     /// 获取终端尺寸
     pub fn get_size(&self) -> (usize, usize) {
         terminal::size()
@@ -54,6 +226,16 @@ impl TerminalManager {
             .unwrap_or((80, 24))
     }
 
+    /// 判断终端是否小于指定的最小尺寸
+    pub fn is_smaller_than(
+        &self,
+        min_width: usize,
+        min_height: usize,
+    ) -> bool {
+        let (width, height) = self.get_size();
+        width < min_width || height < min_height
+    }
+
     /// 计算可用的显示行数（减去帮助信息占用的行数）
     pub fn calculate_display_lines(
         &self,
@@ -64,6 +246,67 @@ impl TerminalManager {
     }
 }
 
+/// 撤销已启用的键盘增强协议
+fn pop_keyboard_enhancement() -> io::Result<()> {
+    if KEYBOARD_ENHANCED.swap(false, Ordering::Relaxed) {
+        execute!(
+            io::stdout(),
+            PopKeyboardEnhancementFlags
+        )?;
+    }
+    Ok(())
+}
+
+/// 恢复终端：撤销键盘增强协议、停止捕获鼠标、显示光标并退出原始模式
+fn restore_terminal() {
+    let _ = pop_keyboard_enhancement();
+    let _ =
+        execute!(io::stdout(), DisableMouseCapture, Show);
+    let _ = terminal::disable_raw_mode();
+}
+
+/// 安装 panic 钩子与 SIGTERM / SIGHUP 处理：先恢复终端再输出 panic 信息或退出，
+/// 避免崩溃或被终止后终端停留在原始模式、光标隐藏
+fn install_restore_hooks() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            previous(info);
+        }));
+        #[cfg(unix)]
+        watch_signals();
+    });
+}
+
+/// 在后台线程等待 SIGTERM / SIGHUP，恢复终端后按信号的默认处理重新触发，
+/// 进程的退出状态与直接被信号终止时相同
+#[cfg(unix)]
+fn watch_signals() {
+    use signal_hook::consts::{SIGHUP, SIGTERM};
+    use signal_hook::iterator::Signals;
+
+    let Ok(mut signals) = Signals::new([SIGTERM, SIGHUP])
+    else {
+        return;
+    };
+    std::thread::spawn(move || {
+        if let Some(signal) = signals.forever().next() {
+            restore_terminal();
+            let _ = signal_hook::low_level::emulate_default_handler(
+                signal,
+            );
+        }
+    });
+}
+
+impl Default for TerminalManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for TerminalManager {
     fn drop(&mut self) {
         let _ = self.exit_raw_mode();
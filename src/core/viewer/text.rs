@@ -0,0 +1,202 @@
+//! 终端文本宽度与排版工具
+
+/// 计算单个字符的显示宽度（中日韩及全角字符按双宽计算）
+pub fn char_width(ch: char) -> usize {
+    match ch as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// 计算字符串的显示宽度
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// 按显示宽度截断字符串
+pub fn truncate_to_width(
+    text: &str,
+    max_width: usize,
+) -> String {
+    let mut width = 0;
+    let mut output = String::new();
+    for ch in text.chars() {
+        let ch_width = char_width(ch);
+        if width + ch_width > max_width {
+            break;
+        }
+        width += ch_width;
+        output.push(ch);
+    }
+    output
+}
+
+/// 按显示宽度截断含 ANSI 颜色序列的字符串（颜色序列不计宽度，
+/// 截断处之前有颜色序列时重置样式）
+pub fn truncate_styled_to_width(
+    text: &str,
+    max_width: usize,
+) -> String {
+    let mut width = 0;
+    let mut output = String::new();
+    let mut styled = false;
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            styled = true;
+            // 原样保留 CSI 序列：ESC [ 参数 结束字节
+            output.push(ch);
+            for ch in chars.by_ref() {
+                output.push(ch);
+                if ch.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        let ch_width = char_width(ch);
+        if width + ch_width > max_width {
+            if styled {
+                output.push_str("\x1b[0m");
+            }
+            break;
+        }
+        width += ch_width;
+        output.push(ch);
+    }
+    output
+}
+
+/// 跳过含 ANSI 颜色序列的字符串开头指定显示宽度的内容（颜色序列保留，
+/// 被切开的双宽字符以空格补齐）
+pub fn skip_styled_width(
+    text: &str,
+    skip: usize,
+) -> String {
+    let mut width = 0;
+    let mut output = String::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            output.push(ch);
+            for ch in chars.by_ref() {
+                output.push(ch);
+                if ch.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        if width >= skip {
+            output.push(ch);
+            continue;
+        }
+        width += char_width(ch);
+        if width > skip {
+            output.push_str(&" ".repeat(width - skip));
+        }
+    }
+    output
+}
+
+/// 含 ANSI 颜色序列的字符串的显示宽度（颜色序列不计宽度）
+pub fn styled_display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            chars
+                .by_ref()
+                .find(|ch| ch.is_ascii_alphabetic());
+            continue;
+        }
+        width += char_width(ch);
+    }
+    width
+}
+
+/// 将含 ANSI 颜色序列的字符串截断或右侧补空格到指定显示宽度
+pub fn fit_styled_to_width(
+    text: &str,
+    width: usize,
+) -> String {
+    let text = truncate_styled_to_width(text, width);
+    let padding =
+        width.saturating_sub(styled_display_width(&text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// 右侧补空格到指定显示宽度（左对齐）
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// 左侧补空格到指定显示宽度（右对齐）
+pub fn pad_left_to_width(
+    text: &str,
+    width: usize,
+) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", " ".repeat(padding), text)
+}
+
+/// 将多个条目按分隔符拼接并按显示宽度折行
+pub fn wrap_items(
+    items: &[String],
+    separator: &str,
+    max_width: usize,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for item in items {
+        let candidate = if current.is_empty() {
+            item.clone()
+        } else {
+            format!("{}{}{}", current, separator, item)
+        };
+        if !current.is_empty()
+            && display_width(&candidate) > max_width
+        {
+            lines.push(current);
+            current = truncate_to_width(item, max_width);
+        } else {
+            current =
+                truncate_to_width(&candidate, max_width);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// 按比例绘制水平条形图（使用 1/8 宽度方块，最大值占满 `width` 列）
+pub fn horizontal_bar(
+    value: u64,
+    max: u64,
+    width: usize,
+) -> String {
+    const PARTIAL: [char; 7] =
+        ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+    if max == 0 || width == 0 {
+        return String::new();
+    }
+    let eighths = (value as u128 * width as u128 * 8
+        / max as u128) as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(&partial) = (eighths % 8)
+        .checked_sub(1)
+        .and_then(|i| PARTIAL.get(i))
+    {
+        bar.push(partial);
+    }
+    bar
+}
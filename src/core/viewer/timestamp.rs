@@ -0,0 +1,279 @@
+//! 时间戳显示：按文件头的时区偏移换算为本地时间，按时间精度决定小数位数
+//!
+//! `timezone_offset` 视为相对 UTC 的秒数（东区为正，按有符号数解释），
+//! `timestamp_accuracy` 视为时间戳精度（纳秒），0 表示未知、显示全部 9 位。
+//!
+//! 完整时间的格式由 [`TimeFormat`] 选择：ISO 8601、Unix 秒、Unix 纳秒、
+//! 相对首个数据包的秒数，或 strftime 模式。
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset};
+
+use crate::core::pcap::parser::PcapFileHeader;
+
+/// 纳秒的最大小数位数
+const MAX_DIGITS: usize = 9;
+
+/// 完整时间的输出格式
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// `YYYY-MM-ddTHH:mm:ss.fff`（有时区偏移时带后缀）
+    #[default]
+    Iso,
+    /// Unix 秒，带小数（如 `1700000000.123456789`）
+    Epoch,
+    /// Unix 纳秒整数
+    EpochNs,
+    /// 相对首个数据包的秒数（如 `+12.500000000`）
+    Relative,
+    /// strftime 模式（如 `%H:%M:%S%.3f`），按显示时区换算
+    Pattern(String),
+}
+
+impl FromStr for TimeFormat {
+    type Err = String;
+
+    fn from_str(
+        s: &str,
+    ) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "iso" => return Ok(Self::Iso),
+            "epoch" => return Ok(Self::Epoch),
+            "epoch-ns" => return Ok(Self::EpochNs),
+            "relative" => return Ok(Self::Relative),
+            _ => {}
+        }
+        if !s.contains('%') {
+            return Err(format!(
+                "unknown time format `{}` (expected iso, epoch, epoch-ns, relative or a strftime pattern such as %H:%M:%S%.3f)",
+                s
+            ));
+        }
+        if StrftimeItems::new(s)
+            .any(|item| matches!(item, Item::Error))
+        {
+            return Err(format!(
+                "invalid strftime pattern `{}`",
+                s
+            ));
+        }
+        Ok(Self::Pattern(s.to_string()))
+    }
+}
+
+impl fmt::Display for TimeFormat {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Iso => f.write_str("iso"),
+            Self::Epoch => f.write_str("epoch"),
+            Self::EpochNs => f.write_str("epoch-ns"),
+            Self::Relative => f.write_str("relative"),
+            Self::Pattern(pattern) => f.write_str(pattern),
+        }
+    }
+}
+
+/// 时间戳显示方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampStyle {
+    /// 显示时使用的时区（None 为 UTC）
+    offset: Option<FixedOffset>,
+    /// 小数位数
+    digits: usize,
+    /// 完整时间的格式
+    format: TimeFormat,
+    /// 相对时间的起点（纳秒，通常为首个数据包的时间戳）
+    origin: i128,
+}
+
+impl Default for TimestampStyle {
+    /// UTC，ISO 格式，显示全部 9 位小数
+    fn default() -> Self {
+        Self {
+            offset: None,
+            digits: MAX_DIGITS,
+            format: TimeFormat::default(),
+            origin: 0,
+        }
+    }
+}
+
+impl TimestampStyle {
+    /// 按文件头选择显示方式，`utc` 时忽略时区偏移
+    pub fn from_header(
+        header: &PcapFileHeader,
+        utc: bool,
+    ) -> Self {
+        let offset = if utc {
+            None
+        } else {
+            FixedOffset::east_opt(
+                header.timezone_offset as i32,
+            )
+            .filter(|offset| offset.local_minus_utc() != 0)
+        };
+        Self {
+            offset,
+            digits: fraction_digits(
+                header.timestamp_accuracy,
+            ),
+            ..Self::default()
+        }
+    }
+
+    /// 使用指定的完整时间格式
+    pub fn with_format(
+        mut self,
+        format: TimeFormat,
+    ) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// 设置相对时间的起点（纳秒）
+    pub fn with_origin(mut self, origin: i128) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// 小数位数
+    pub fn digits(&self) -> usize {
+        self.digits
+    }
+
+    /// 按完整时间格式输出，ISO 格式为 `YYYY-MM-ddTHH:mm:ss.fff`
+    /// （有时区偏移时带 `+08:00` 后缀）；时间戳无效时为 None
+    pub fn format(
+        &self,
+        seconds: u32,
+        nanoseconds: u32,
+    ) -> Option<String> {
+        let nanos = seconds as i128 * 1_000_000_000
+            + nanoseconds as i128;
+        match &self.format {
+            TimeFormat::Iso => {}
+            TimeFormat::Epoch => {
+                DateTime::from_timestamp(
+                    seconds as i64,
+                    nanoseconds,
+                )?;
+                return Some(self.seconds(nanos, false));
+            }
+            TimeFormat::EpochNs => {
+                DateTime::from_timestamp(
+                    seconds as i64,
+                    nanoseconds,
+                )?;
+                return Some(nanos.to_string());
+            }
+            TimeFormat::Relative => {
+                return Some(
+                    self.seconds(nanos - self.origin, true),
+                );
+            }
+            TimeFormat::Pattern(pattern) => {
+                return self.render(
+                    seconds,
+                    nanoseconds,
+                    pattern,
+                    0,
+                );
+            }
+        }
+        let time = self.render(
+            seconds,
+            nanoseconds,
+            "%Y-%m-%dT%H:%M:%S",
+            self.digits,
+        )?;
+        Some(match self.offset {
+            Some(offset) => format!("{}{}", time, offset),
+            None => time,
+        })
+    }
+
+    /// 一天内的时间 `HH:mm:ss.fff`，小数位数不超过 `max_digits`
+    pub fn format_time_of_day(
+        &self,
+        seconds: u32,
+        nanoseconds: u32,
+        max_digits: usize,
+    ) -> Option<String> {
+        self.render(
+            seconds,
+            nanoseconds,
+            "%H:%M:%S",
+            self.digits.min(max_digits),
+        )
+    }
+
+    /// 纳秒数按小数位数输出为秒，`signed` 时正数带 `+`
+    fn seconds(&self, nanos: i128, signed: bool) -> String {
+        let sign = match (nanos < 0, signed) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => "",
+        };
+        let nanos = nanos.unsigned_abs();
+        let whole = nanos / 1_000_000_000;
+        if self.digits == 0 {
+            return format!("{}{}", sign, whole);
+        }
+        let fraction = (nanos % 1_000_000_000)
+            / 10u128.pow((MAX_DIGITS - self.digits) as u32);
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            whole,
+            fraction,
+            width = self.digits
+        )
+    }
+
+    fn render(
+        &self,
+        seconds: u32,
+        nanoseconds: u32,
+        pattern: &str,
+        digits: usize,
+    ) -> Option<String> {
+        let utc = DateTime::from_timestamp(
+            seconds as i64,
+            nanoseconds,
+        )?;
+        let base = match self.offset {
+            Some(offset) => utc
+                .with_timezone(&offset)
+                .format(pattern)
+                .to_string(),
+            None => utc.format(pattern).to_string(),
+        };
+        if digits == 0 {
+            return Some(base);
+        }
+        let fraction = nanoseconds
+            / 10u32.pow((MAX_DIGITS - digits) as u32);
+        Some(format!(
+            "{}.{:0width$}",
+            base,
+            fraction,
+            width = digits
+        ))
+    }
+}
+
+/// 按时间精度（纳秒）计算有意义的小数位数：
+/// 1 → 9 位，1000 → 6 位，1000000 → 3 位，0（未知）→ 9 位
+pub fn fraction_digits(accuracy: u32) -> usize {
+    match accuracy {
+        0 => MAX_DIGITS,
+        accuracy => MAX_DIGITS
+            .saturating_sub(accuracy.ilog10() as usize),
+    }
+}
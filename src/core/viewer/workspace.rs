@@ -0,0 +1,146 @@
+//! 工作区文件（`.pvw`）：一组数据文件及其查看状态与注释
+//!
+//! 本地路径以工作区文件所在目录为基准保存为相对路径，
+//! 便于连同数据文件一起交给他人继续排查。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::error::types::{PcapViewerError, Result};
+use crate::core::source;
+use crate::core::viewer::annotation::Annotations;
+use crate::core::viewer::session::ViewerState;
+
+/// 工作区文件扩展名
+pub const WORKSPACE_EXTENSION: &str = "pvw";
+
+/// 工作区文件格式版本
+const WORKSPACE_VERSION: u32 = 1;
+
+/// 工作区中的单个数据文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceFile {
+    pub path: PathBuf, // 数据文件路径或远程 URL
+    #[serde(flatten)]
+    pub state: ViewerState, // 位置、高亮与书签
+    #[serde(default)]
+    pub notes: Annotations, // 偏移注释
+}
+
+/// 工作区
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    #[serde(default)]
+    pub active: usize, // 当前查看的文件序号
+    pub files: Vec<WorkspaceFile>,
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub searches: BTreeMap<String, String>, // 命名的查找模式
+}
+
+/// 工作区文件内容
+#[derive(Debug, Serialize, Deserialize)]
+struct WorkspaceDocument {
+    version: u32,
+    #[serde(flatten)]
+    workspace: Workspace,
+}
+
+impl Workspace {
+    /// 路径是否为工作区文件（按扩展名判断）
+    pub fn is_workspace_path(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|ext| ext == WORKSPACE_EXTENSION)
+    }
+
+    /// 当前查看的文件
+    pub fn active_file(&self) -> Option<&WorkspaceFile> {
+        self.files
+            .get(self.active)
+            .or_else(|| self.files.first())
+    }
+
+    /// 读取工作区文件，相对路径解析为基于工作区目录的路径
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let document: WorkspaceDocument =
+            serde_json::from_str(&content).map_err(
+                |e| {
+                    PcapViewerError::InvalidFormat(format!(
+                        "Invalid workspace file {}: {}",
+                        path.display(),
+                        e
+                    ))
+                },
+            )?;
+        if document.version > WORKSPACE_VERSION {
+            return Err(PcapViewerError::InvalidFormat(
+                format!(
+                    "Unsupported workspace version: {}",
+                    document.version
+                ),
+            )
+            .into());
+        }
+
+        let mut workspace = document.workspace;
+        let base = base_dir(path);
+        for file in &mut workspace.files {
+            if file.path.is_relative()
+                && !source::is_remote(&file.path)
+            {
+                file.path = base.join(&file.path);
+            }
+        }
+        Ok(workspace)
+    }
+
+    /// 保存工作区，工作区目录下的文件保存为相对路径
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let base = fs::canonicalize(base_dir(path)).ok();
+        let mut workspace = self.clone();
+        for file in &mut workspace.files {
+            if source::is_remote(&file.path) {
+                continue;
+            }
+            let absolute = fs::canonicalize(&file.path)
+                .unwrap_or_else(|_| file.path.clone());
+            file.path = base
+                .as_ref()
+                .and_then(|base| {
+                    absolute.strip_prefix(base).ok()
+                })
+                .map_or(
+                    absolute.clone(),
+                    Path::to_path_buf,
+                );
+        }
+
+        let document = WorkspaceDocument {
+            version: WORKSPACE_VERSION,
+            workspace,
+        };
+        let content =
+            serde_json::to_string_pretty(&document)
+                .map_err(|e| {
+                    PcapViewerError::InvalidFormat(
+                        e.to_string(),
+                    )
+                })?;
+        fs::write(path, content + "\n")?;
+        Ok(())
+    }
+}
+
+/// 工作区文件所在目录（无父目录时为当前目录）
+fn base_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
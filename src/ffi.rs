@@ -0,0 +1,160 @@
+//! C ABI 接口
+//!
+//! 供 C/C++ 工具复用同一套解析逻辑，声明见 `include/pcap_custom.h`。
+//! 构建动态库：`cargo rustc --lib --release --features ffi --crate-type cdylib`
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+
+use crate::core::pcap::parser::PcapParser;
+
+/// C 侧可见的数据包信息（payload 在下一次读取或关闭前有效）
+#[repr(C)]
+pub struct PcapCustomPacket {
+    pub timestamp_seconds: u32,
+    pub timestamp_nanoseconds: u32,
+    pub packet_length: u32,
+    pub checksum: u32,
+    pub offset: u64,
+    pub payload: *const u8,
+    pub payload_length: u32,
+    /// 1 表示 CRC32 校验通过，0 表示不匹配
+    pub checksum_valid: c_int,
+}
+
+/// 打开的文件句柄
+pub struct PcapCustomReader {
+    data: Vec<u8>,
+    parser: PcapParser,
+    next_index: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> =
+        const { RefCell::new(None) };
+}
+
+/// 记录最近一次错误
+fn set_last_error(message: String) {
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR
+        .with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// 打开文件并建立数据包索引，失败时返回 NULL
+///
+/// # Safety
+///
+/// `path` 必须是以 NUL 结尾的有效 UTF-8 字符串指针。
+#[no_mangle]
+pub unsafe extern "C" fn pcap_custom_open(
+    path: *const c_char,
+) -> *mut PcapCustomReader {
+    if path.is_null() {
+        set_last_error("path is null".to_string());
+        return ptr::null_mut();
+    }
+
+    let path = match CStr::from_ptr(path).to_str() {
+        Ok(path) => path,
+        Err(e) => {
+            set_last_error(format!("invalid path: {}", e));
+            return ptr::null_mut();
+        }
+    };
+
+    let result = std::fs::read(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|data| {
+            let parser = PcapParser::builder()
+                .verify_crc(true)
+                .parse_bytes(&data)?;
+            Ok((data, parser))
+        });
+
+    match result {
+        Ok((data, parser)) => {
+            Box::into_raw(Box::new(PcapCustomReader {
+                data,
+                parser,
+                next_index: 0,
+            }))
+        }
+        Err(e) => {
+            set_last_error(e.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// 读取下一个数据包：返回 1 表示成功，0 表示已读完，-1 表示参数错误
+///
+/// # Safety
+///
+/// `reader` 必须来自 `pcap_custom_open` 且未关闭，`packet` 必须指向
+/// 可写的 `PcapCustomPacket`。
+#[no_mangle]
+pub unsafe extern "C" fn pcap_custom_next_packet(
+    reader: *mut PcapCustomReader,
+    packet: *mut PcapCustomPacket,
+) -> c_int {
+    if reader.is_null() || packet.is_null() {
+        set_last_error(
+            "reader or packet is null".to_string(),
+        );
+        return -1;
+    }
+
+    let reader = &mut *reader;
+    let Some(packet_ref) = reader
+        .parser
+        .packet_ref(&reader.data, reader.next_index)
+    else {
+        return 0;
+    };
+    reader.next_index += 1;
+
+    let header = packet_ref.header();
+    let payload = packet_ref.payload();
+    *packet = PcapCustomPacket {
+        timestamp_seconds: header.timestamp_seconds,
+        timestamp_nanoseconds: header.timestamp_nanoseconds,
+        packet_length: header.packet_length,
+        checksum: header.checksum,
+        offset: packet_ref.offset() as u64,
+        payload: payload.as_ptr(),
+        payload_length: payload.len() as u32,
+        checksum_valid: c_int::from(
+            packet_ref.checksum_valid().unwrap_or(false),
+        ),
+    };
+    1
+}
+
+/// 关闭句柄并释放内存（NULL 时无操作）
+///
+/// # Safety
+///
+/// `reader` 必须来自 `pcap_custom_open`，且只能关闭一次。
+#[no_mangle]
+pub unsafe extern "C" fn pcap_custom_close(
+    reader: *mut PcapCustomReader,
+) {
+    if !reader.is_null() {
+        drop(Box::from_raw(reader));
+    }
+}
+
+/// 获取当前线程最近一次错误信息（无错误时返回 NULL）
+///
+/// 返回的指针在下一次出错前有效。
+#[no_mangle]
+pub extern "C" fn pcap_custom_last_error() -> *const c_char
+{
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
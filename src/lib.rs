@@ -0,0 +1,28 @@
+//! 自定义 PCAP 格式解析库
+//!
+//! 提供文件头/数据包头的解析与写入能力，查看器二进制程序
+//! 与其他服务（如数据接入服务）共用同一套解析逻辑。
+
+pub mod app;
+pub mod core;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use crate::app::error::types::{
+    ParseError, PcapViewerError, Result,
+};
+#[cfg(feature = "async")]
+pub use crate::core::pcap::async_reader::AsyncPcapReader;
+pub use crate::core::pcap::builder::{
+    Endianness, ParserOptions, PcapParserBuilder,
+};
+pub use crate::core::pcap::packet_ref::{
+    OwnedPacket, PacketRef,
+};
+pub use crate::core::pcap::parser::{
+    DataPacket, DataPacketHeader, PcapFileHeader,
+    PcapParser,
+};
+pub use crate::core::pcap::writer::PcapWriter;
@@ -1,16 +1,16 @@
 //! PCAP 文件查看器主程序
 
-mod app;
 mod cli;
-mod core;
 
-use app::error::types::Result;
-use app::logging::setup::init_logging;
+use std::process::ExitCode;
 
-fn main() -> Result<()> {
-    // 初始化日志系统
-    init_logging();
-
-    // 运行命令行界面
-    cli::run_cli()
+fn main() -> ExitCode {
+    // 运行命令行界面，失败时按错误类型返回退出码
+    match cli::run_cli() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            cli::report_error(&e);
+            ExitCode::from(cli::exit::code_for(&e))
+        }
+    }
 }
@@ -0,0 +1,55 @@
+//! WASM 绑定接口
+//!
+//! 供浏览器前端复用解析逻辑。构建：
+//! `cargo build --lib --target wasm32-unknown-unknown --no-default-features --features wasm`
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::core::pcap::parser::{
+    DataPacketHeader, PcapFileHeader, PcapParser,
+};
+
+/// 数据包表中的一行
+#[derive(Serialize)]
+struct PacketRow {
+    index: usize,
+    offset: usize,
+    #[serde(flatten)]
+    header: DataPacketHeader,
+    checksum_valid: Option<bool>,
+}
+
+/// 解析结果
+#[derive(Serialize)]
+struct ParseOutput {
+    file_header: Option<PcapFileHeader>,
+    packets: Vec<PacketRow>,
+}
+
+/// 解析完整文件内容，返回 JSON 格式的文件头与数据包表
+#[wasm_bindgen(js_name = parsePackets)]
+pub fn parse_packets(
+    data: &[u8],
+) -> Result<String, JsValue> {
+    let parser = PcapParser::builder()
+        .verify_crc(true)
+        .parse_bytes(data)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let output = ParseOutput {
+        file_header: parser.file_header().cloned(),
+        packets: parser
+            .packet_refs(data)
+            .map(|packet| PacketRow {
+                index: packet.index(),
+                offset: packet.offset(),
+                header: *packet.header(),
+                checksum_valid: packet.checksum_valid(),
+            })
+            .collect(),
+    };
+
+    serde_json::to_string(&output)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}